@@ -0,0 +1,115 @@
+#![feature(test)]
+
+#[cfg(all(test, feature = "cache"))]
+mod benches {
+    extern crate test;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use serde_json::json;
+    use serenity::cache::Cache;
+    use serenity::model::event::GuildCreateEvent;
+    use serenity::model::id::GuildId;
+    use tokio_test::block_on;
+
+    use self::test::Bencher;
+
+    const GUILD_ID: GuildId = GuildId(1);
+
+    /// Creates the event of a guild with the given Id and 10,000 channels.
+    fn guild_create(guild_id: u64) -> GuildCreateEvent {
+        let channels = (2..10_002)
+            .map(|id| {
+                json!({
+                    "id": id.to_string(),
+                    "type": 0,
+                    "name": format!("channel-{}", id),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::from_value(json!({
+            "id": guild_id.to_string(),
+            "name": "guild",
+            "afk_timeout": 300,
+            "channels": channels,
+            "default_message_notifications": 0,
+            "emojis": [],
+            "explicit_content_filter": 0,
+            "features": [],
+            "joined_at": "2021-08-01T00:00:00Z",
+            "large": true,
+            "member_count": 0,
+            "members": [],
+            "mfa_level": 0,
+            "owner_id": "2",
+            "presences": [],
+            "region": "us-west",
+            "roles": [],
+            "verification_level": 0,
+            "voice_states": [],
+            "preferred_locale": "en-US",
+            "nsfw": false,
+            "nsfw_level": 0,
+            "system_channel_flags": 0,
+        }))
+        .unwrap()
+    }
+
+    /// Creates a cache holding a single guild with 10,000 channels.
+    fn cache() -> Cache {
+        let cache = Cache::default();
+        block_on(cache.update(&mut guild_create(GUILD_ID.0)));
+
+        cache
+    }
+
+    #[bench]
+    fn guild_clone(b: &mut Bencher) {
+        let cache = cache();
+
+        b.iter(|| block_on(async { cache.guild(GUILD_ID).await.unwrap().channels.len() }))
+    }
+
+    #[bench]
+    fn guild_ref(b: &mut Bencher) {
+        let cache = cache();
+
+        b.iter(|| block_on(async { cache.guild_ref(GUILD_ID).await.unwrap().channels.len() }))
+    }
+
+    #[bench]
+    fn guild_field(b: &mut Bencher) {
+        let cache = cache();
+
+        b.iter(|| {
+            block_on(async { cache.guild_field(GUILD_ID, |guild| guild.channels.len()).await })
+        })
+    }
+
+    #[bench]
+    fn guild_ref_contended(b: &mut Bencher) {
+        let cache = Arc::new(cache());
+        let done = Arc::new(AtomicBool::new(false));
+
+        // Keeps replacing another guild while the reads are measured.
+        let writer = {
+            let cache = Arc::clone(&cache);
+            let done = Arc::clone(&done);
+            let mut event = guild_create(GUILD_ID.0 + 1);
+
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    block_on(cache.update(&mut event));
+                }
+            })
+        };
+
+        b.iter(|| block_on(async { cache.guild_ref(GUILD_ID).await.unwrap().channels.len() }));
+
+        done.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+    }
+}
@@ -32,7 +32,7 @@ impl EventHandler for Handler {
         if msg.content == "!ping" {
             println!("Shard {}", ctx.shard_id);
 
-            if let Err(why) = msg.channel_id.say(&ctx.http, "Pong!").await {
+            if let Err(why) = msg.channel_id.say(ctx.http(), "Pong!").await {
                 println!("Error sending message: {:?}", why);
             }
         }
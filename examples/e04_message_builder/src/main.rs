@@ -34,7 +34,7 @@ impl EventHandler for Handler {
                 .push(" channel")
                 .build();
 
-            if let Err(why) = msg.channel_id.say(&context.http, &response).await {
+            if let Err(why) = msg.channel_id.say(context.http(), &response).await {
                 println!("Error sending message: {:?}", why);
             }
         }
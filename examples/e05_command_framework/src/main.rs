@@ -193,7 +193,7 @@ async fn dispatch_error(ctx: &Context, msg: &Message, error: DispatchError) {
         if info.is_first_try {
             let _ = msg
                 .channel_id
-                .say(&ctx.http, &format!("Try this again in {} seconds.", info.as_secs()))
+                .say(ctx.http(), &format!("Try this again in {} seconds.", info.as_secs()))
                 .await;
         }
     }
@@ -212,7 +212,7 @@ fn _dispatch_error_no_macro<'fut>(
             if info.is_first_try {
                 let _ = msg
                     .channel_id
-                    .say(&ctx.http, &format!("Try this again in {} seconds.", info.as_secs()))
+                    .say(ctx.http(), &format!("Try this again in {} seconds.", info.as_secs()))
                     .await;
             }
         };
@@ -336,7 +336,7 @@ async fn commands(ctx: &Context, msg: &Message) -> CommandResult {
         writeln!(contents, "- {name}: {amount}", name = k, amount = v)?;
     }
 
-    msg.channel_id.say(&ctx.http, &contents).await?;
+    msg.channel_id.say(ctx.http(), &contents).await?;
 
     Ok(())
 }
@@ -359,9 +359,9 @@ async fn say(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         ContentSafeOptions::default().clean_channel(false).clean_role(false)
     };
 
-    let content = content_safe(&ctx.cache, &args.rest(), &settings).await;
+    let content = content_safe(ctx, &args.rest(), &settings).await;
 
-    msg.channel_id.say(&ctx.http, &content).await?;
+    msg.channel_id.say(ctx.http(), &content).await?;
 
     Ok(())
 }
@@ -401,7 +401,7 @@ async fn owner_check(
 
 #[command]
 async fn some_long_command(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-    msg.channel_id.say(&ctx.http, &format!("Arguments: {:?}", args.rest())).await?;
+    msg.channel_id.say(ctx.http(), &format!("Arguments: {:?}", args.rest())).await?;
 
     Ok(())
 }
@@ -412,11 +412,11 @@ async fn some_long_command(ctx: &Context, msg: &Message, args: Args) -> CommandR
 async fn about_role(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let potential_role_name = args.rest();
 
-    if let Some(guild) = msg.guild(&ctx.cache).await {
+    if let Some(guild) = msg.guild(ctx).await {
         // `role_by_name()` allows us to attempt attaining a reference to a role
         // via its name.
         if let Some(role) = guild.role_by_name(potential_role_name) {
-            if let Err(why) = msg.channel_id.say(&ctx.http, &format!("Role-ID: {}", role.id)).await
+            if let Err(why) = msg.channel_id.say(ctx.http(), &format!("Role-ID: {}", role.id)).await
             {
                 println!("Error sending message: {:?}", why);
             }
@@ -426,7 +426,7 @@ async fn about_role(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     }
 
     msg.channel_id
-        .say(&ctx.http, format!("Could not find role named: {:?}", potential_role_name))
+        .say(ctx.http(), format!("Could not find role named: {:?}", potential_role_name))
         .await?;
 
     Ok(())
@@ -441,14 +441,14 @@ async fn multiply(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
     let res = first * second;
 
-    msg.channel_id.say(&ctx.http, &res.to_string()).await?;
+    msg.channel_id.say(ctx.http(), &res.to_string()).await?;
 
     Ok(())
 }
 
 #[command]
 async fn about(ctx: &Context, msg: &Message) -> CommandResult {
-    msg.channel_id.say(&ctx.http, "This is a small test-bot! : )").await?;
+    msg.channel_id.say(ctx.http(), "This is a small test-bot! : )").await?;
 
     Ok(())
 }
@@ -493,7 +493,7 @@ async fn latency(ctx: &Context, msg: &Message) -> CommandResult {
 #[only_in(guilds)]
 #[checks(Owner)]
 async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
-    msg.channel_id.say(&ctx.http, "Pong! : )").await?;
+    msg.channel_id.say(ctx.http(), "Pong! : )").await?;
 
     Ok(())
 }
@@ -506,7 +506,7 @@ async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
 // Allow only administrators to call this:
 #[required_permissions("ADMINISTRATOR")]
 async fn cat(ctx: &Context, msg: &Message) -> CommandResult {
-    msg.channel_id.say(&ctx.http, ":cat:").await?;
+    msg.channel_id.say(ctx.http(), ":cat:").await?;
 
     // We can return one ticket to the bucket undoing the ratelimit.
     Err(RevertBucket.into())
@@ -516,7 +516,7 @@ async fn cat(ctx: &Context, msg: &Message) -> CommandResult {
 #[description = "Sends an emoji with a dog."]
 #[bucket = "emoji"]
 async fn dog(ctx: &Context, msg: &Message) -> CommandResult {
-    msg.channel_id.say(&ctx.http, ":dog:").await?;
+    msg.channel_id.say(ctx.http(), ":dog:").await?;
 
     Ok(())
 }
@@ -529,7 +529,7 @@ async fn bird(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         format!(":bird: could not find animal named: `{}`.", args.rest())
     };
 
-    msg.channel_id.say(&ctx.http, say_content).await?;
+    msg.channel_id.say(ctx.http(), say_content).await?;
 
     Ok(())
 }
@@ -542,18 +542,18 @@ async fn am_i_admin(ctx: &Context, msg: &Message, _args: Args) -> CommandResult
     if let Some(member) = &msg.member {
         for role in &member.roles {
             if role
-                .to_role_cached(&ctx.cache)
+                .to_role_cached(ctx)
                 .await
                 .map_or(false, |r| r.has_permission(Permissions::ADMINISTRATOR))
             {
-                msg.channel_id.say(&ctx.http, "Yes, you are.").await?;
+                msg.channel_id.say(ctx.http(), "Yes, you are.").await?;
 
                 return Ok(());
             }
         }
     }
 
-    msg.channel_id.say(&ctx.http, "No, you are not.").await?;
+    msg.channel_id.say(ctx.http(), "No, you are not.").await?;
 
     Ok(())
 }
@@ -562,7 +562,7 @@ async fn am_i_admin(ctx: &Context, msg: &Message, _args: Args) -> CommandResult
 async fn slow_mode(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let say_content = if let Ok(slow_mode_rate_seconds) = args.single::<u64>() {
         if let Err(why) =
-            msg.channel_id.edit(&ctx.http, |c| c.slow_mode_rate(slow_mode_rate_seconds)).await
+            msg.channel_id.edit(ctx.http(), |c| c.slow_mode_rate(slow_mode_rate_seconds)).await
         {
             println!("Error setting channel's slow mode rate: {:?}", why);
 
@@ -570,14 +570,14 @@ async fn slow_mode(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         } else {
             format!("Successfully set slow mode rate to `{}` seconds.", slow_mode_rate_seconds)
         }
-    } else if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(&ctx.cache).await
+    } else if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(ctx).await
     {
         format!("Current slow mode rate is `{}` seconds.", channel.slow_mode_rate.unwrap_or(0))
     } else {
         "Failed to find channel in cache.".to_string()
     };
 
-    msg.channel_id.say(&ctx.http, say_content).await?;
+    msg.channel_id.say(ctx.http(), say_content).await?;
 
     Ok(())
 }
@@ -587,7 +587,7 @@ async fn slow_mode(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 #[command("upper")]
 #[sub_commands(sub)]
 async fn upper_command(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
-    msg.reply(&ctx.http, "This is the main function!").await?;
+    msg.reply(ctx.http(), "This is the main function!").await?;
 
     Ok(())
 }
@@ -597,7 +597,7 @@ async fn upper_command(ctx: &Context, msg: &Message, _args: Args) -> CommandResu
 #[aliases("sub-command", "secret")]
 #[description("This is `upper`'s sub-command.")]
 async fn sub(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
-    msg.reply(&ctx.http, "This is a sub function!").await?;
+    msg.reply(ctx.http(), "This is a sub function!").await?;
 
     Ok(())
 }
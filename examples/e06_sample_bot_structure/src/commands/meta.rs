@@ -4,7 +4,7 @@ use serenity::prelude::*;
 
 #[command]
 async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
-    msg.channel_id.say(&ctx.http, "Pong!").await?;
+    msg.channel_id.say(ctx.http(), "Pong!").await?;
 
     Ok(())
 }
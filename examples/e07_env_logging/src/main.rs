@@ -87,7 +87,7 @@ async fn main() {
 // if you wish to instrument commands, use it on the before function.
 #[command]
 async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
-    if let Err(why) = msg.channel_id.say(&ctx.http, "Pong! : )").await {
+    if let Err(why) = msg.channel_id.say(ctx.http(), "Pong! : )").await {
         error!("Error sending message: {:?}", why);
     }
 
@@ -19,7 +19,7 @@ impl EventHandler for Handler {
             // a title, description, three fields, and a footer.
             let msg = msg
                 .channel_id
-                .send_message(&ctx.http, |m| {
+                .send_message(ctx.http(), |m| {
                     m.content("Hello, World!");
                     m.embed(|e| {
                         e.title("This is a title");
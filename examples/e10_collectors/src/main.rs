@@ -144,7 +144,7 @@ async fn challenge(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
         .await;
 
     // Let's acquire borrow HTTP to send a message inside the `async move`.
-    let http = &ctx.http;
+    let http = ctx.http();
 
     // We want to process each message and get the length.
     // There are a couple of ways to do this. Folding the stream with `fold`
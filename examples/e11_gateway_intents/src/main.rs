@@ -3,7 +3,11 @@ use std::env;
 use serenity::{
     async_trait,
     client::bridge::gateway::GatewayIntents,
-    model::{channel::Message, event::PresenceUpdateEvent, gateway::Ready},
+    model::{
+        channel::Message,
+        event::PresenceUpdateEvent,
+        gateway::{Presence, Ready},
+    },
     prelude::*,
 };
 
@@ -18,7 +22,12 @@ impl EventHandler for Handler {
 
     // As the intents set in this example, this event shall never be dispatched.
     // Try it by changing your status.
-    async fn presence_update(&self, _ctx: Context, _new_data: PresenceUpdateEvent) {
+    async fn presence_update(
+        &self,
+        _ctx: Context,
+        _old_data: Option<Presence>,
+        _new_data: PresenceUpdateEvent,
+    ) {
         println!("Presence Update");
     }
 
@@ -26,7 +26,7 @@ struct Handler {
 impl EventHandler for Handler {
     async fn message(&self, ctx: Context, msg: Message) {
         if msg.content.starts_with("!ping") {
-            if let Err(why) = msg.channel_id.say(&ctx.http, "Pong!").await {
+            if let Err(why) = msg.channel_id.say(ctx.http(), "Pong!").await {
                 eprintln!("Error sending message: {:?}", why);
             }
         }
@@ -48,7 +48,7 @@ impl EventHandler for Handler {
             };
 
             if let Err(why) = command
-                .create_interaction_response(&ctx.http, |response| {
+                .create_interaction_response(ctx.http(), |response| {
                     response
                         .kind(InteractionResponseType::ChannelMessageWithSource)
                         .interaction_response_data(|message| message.content(content))
@@ -63,7 +63,7 @@ impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
 
-        let commands = ApplicationCommand::set_global_application_commands(&ctx.http, |commands| {
+        let commands = ApplicationCommand::set_global_application_commands(ctx.http(), |commands| {
             commands
                 .create_application_command(|command| {
                     command.name("ping").description("A ping command")
@@ -115,7 +115,7 @@ impl EventHandler for Handler {
         println!("I now have the following global slash commands: {:#?}", commands);
 
         let guild_command = GuildId(123456789)
-            .create_application_command(&ctx.http, |command| {
+            .create_application_command(ctx.http(), |command| {
                 command.name("wonderful_command").description("An amazing command")
             })
             .await;
@@ -1,8 +1,37 @@
 use std::collections::HashMap;
 
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
+
+use crate::model::channel::ChannelType;
+use crate::model::interactions::application_command::{
+    ApplicationCommandOptionType,
+    ApplicationIntegrationType,
+    InteractionContextType,
+};
+use crate::model::Permissions;
+use crate::utils;
+
+/// Sets the value of the `locale` in the localizations under the `key`.
+fn insert_localized(
+    map: &mut HashMap<&'static str, Value>,
+    key: &'static str,
+    locale: String,
+    value: String,
+) {
+    let localizations = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    let localizations = localizations.as_object_mut().expect("Must be an object");
+    localizations.insert(locale, Value::String(value));
+}
 
-use crate::{model::interactions::application_command::ApplicationCommandOptionType, utils};
+/// Converts pairs of locales and values into localizations.
+fn localizations(
+    locales: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+) -> Map<String, Value> {
+    locales
+        .into_iter()
+        .map(|(locale, value)| (locale.to_string(), Value::String(value.to_string())))
+        .collect()
+}
 
 /// A builder for creating a new [`ApplicationCommandOption`].
 ///
@@ -30,6 +59,14 @@ impl CreateApplicationCommandOption {
         self
     }
 
+    /// Sets the name of the option for the `locale`, e.g. `"de"`.
+    ///
+    /// **Note**: Must match the same requirements as [`Self::name`].
+    pub fn name_localized<L: ToString, D: ToString>(&mut self, locale: L, name: D) -> &mut Self {
+        insert_localized(&mut self.0, "name_localizations", locale.to_string(), name.to_string());
+        self
+    }
+
     /// Sets the description for the option.
     ///
     /// **Note**: Must be between 1 and 100 characters.
@@ -38,6 +75,19 @@ impl CreateApplicationCommandOption {
         self
     }
 
+    /// Sets the description of the option for the `locale`, e.g. `"de"`.
+    ///
+    /// **Note**: Must be between 1 and 100 characters.
+    pub fn description_localized<L: ToString, D: ToString>(
+        &mut self,
+        locale: L,
+        description: D,
+    ) -> &mut Self {
+        let description = description.to_string();
+        insert_localized(&mut self.0, "description_localizations", locale.to_string(), description);
+        self
+    }
+
     /// The first required option for the user to complete.
     ///
     /// **Note**: Only one option can be `default`.
@@ -87,6 +137,65 @@ impl CreateApplicationCommandOption {
         self.add_choice(choice)
     }
 
+    /// Adds an optional int-choice, with its name translated for the locales.
+    ///
+    /// The `locales` are pairs of a locale, e.g. `"de"`, and the name in it.
+    ///
+    /// **Note**: The same limits as for [`Self::add_int_choice`] apply.
+    pub fn add_int_choice_localized<D: ToString>(
+        &mut self,
+        name: D,
+        value: i32,
+        locales: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+    ) -> &mut Self {
+        let choice = json!({
+            "name": name.to_string(),
+            "value": value,
+            "name_localizations": localizations(locales),
+        });
+        self.add_choice(choice)
+    }
+
+    /// Adds an optional string-choice, with its name translated for the
+    /// locales.
+    ///
+    /// The `locales` are pairs of a locale, e.g. `"de"`, and the name in it.
+    ///
+    /// **Note**: The same limits as for [`Self::add_string_choice`] apply.
+    pub fn add_string_choice_localized<D: ToString, E: ToString>(
+        &mut self,
+        name: D,
+        value: E,
+        locales: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+    ) -> &mut Self {
+        let choice = json!({
+            "name": name.to_string(),
+            "value": value.to_string(),
+            "name_localizations": localizations(locales),
+        });
+        self.add_choice(choice)
+    }
+
+    /// Adds an optional number-choice, with its name translated for the
+    /// locales.
+    ///
+    /// The `locales` are pairs of a locale, e.g. `"de"`, and the name in it.
+    ///
+    /// **Note**: The same limits as for [`Self::add_number_choice`] apply.
+    pub fn add_number_choice_localized<D: ToString>(
+        &mut self,
+        name: D,
+        value: f64,
+        locales: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+    ) -> &mut Self {
+        let choice = json!({
+            "name": name.to_string(),
+            "value": value,
+            "name_localizations": localizations(locales),
+        });
+        self.add_choice(choice)
+    }
+
     fn add_choice(&mut self, value: Value) -> &mut Self {
         let choices = self.0.entry("choices").or_insert_with(|| Value::Array(Vec::new()));
         let choices_arr = choices.as_array_mut().expect("Must be an array");
@@ -95,6 +204,92 @@ impl CreateApplicationCommandOption {
         self
     }
 
+    /// Sets whether the user is suggested values while typing, which are
+    /// sent through autocomplete interactions.
+    ///
+    /// **Note**: Only for options of type [`String`], [`Integer`] and
+    /// [`Number`], and cannot be set along with choices.
+    ///
+    /// [`String`]: ApplicationCommandOptionType::String
+    /// [`Integer`]: ApplicationCommandOptionType::Integer
+    /// [`Number`]: ApplicationCommandOptionType::Number
+    pub fn set_autocomplete(&mut self, autocomplete: bool) -> &mut Self {
+        self.0.insert("autocomplete", Value::Bool(autocomplete));
+        self
+    }
+
+    /// Restricts the channels the user can pick to the given types.
+    ///
+    /// **Note**: Only for options of type [`Channel`].
+    ///
+    /// [`Channel`]: ApplicationCommandOptionType::Channel
+    pub fn channel_types(&mut self, channel_types: &[ChannelType]) -> &mut Self {
+        let channel_types = channel_types.iter().map(|kind| json!(*kind as u8)).collect();
+        self.0.insert("channel_types", Value::Array(channel_types));
+        self
+    }
+
+    /// Sets the minimum value the user can enter.
+    ///
+    /// **Note**: Only for options of type [`Integer`].
+    ///
+    /// [`Integer`]: ApplicationCommandOptionType::Integer
+    pub fn min_int_value(&mut self, value: i64) -> &mut Self {
+        self.0.insert("min_value", json!(value));
+        self
+    }
+
+    /// Sets the maximum value the user can enter.
+    ///
+    /// **Note**: Only for options of type [`Integer`].
+    ///
+    /// [`Integer`]: ApplicationCommandOptionType::Integer
+    pub fn max_int_value(&mut self, value: i64) -> &mut Self {
+        self.0.insert("max_value", json!(value));
+        self
+    }
+
+    /// Sets the minimum value the user can enter.
+    ///
+    /// **Note**: Only for options of type [`Number`].
+    ///
+    /// [`Number`]: ApplicationCommandOptionType::Number
+    pub fn min_number_value(&mut self, value: f64) -> &mut Self {
+        self.0.insert("min_value", json!(value));
+        self
+    }
+
+    /// Sets the maximum value the user can enter.
+    ///
+    /// **Note**: Only for options of type [`Number`].
+    ///
+    /// [`Number`]: ApplicationCommandOptionType::Number
+    pub fn max_number_value(&mut self, value: f64) -> &mut Self {
+        self.0.insert("max_value", json!(value));
+        self
+    }
+
+    /// Sets the minimum length of the text the user can enter.
+    ///
+    /// **Note**: Only for options of type [`String`]. Must be at most 6000.
+    ///
+    /// [`String`]: ApplicationCommandOptionType::String
+    pub fn min_length(&mut self, length: u16) -> &mut Self {
+        self.0.insert("min_length", json!(length));
+        self
+    }
+
+    /// Sets the maximum length of the text the user can enter.
+    ///
+    /// **Note**: Only for options of type [`String`]. Must be between 1 and
+    /// 6000.
+    ///
+    /// [`String`]: ApplicationCommandOptionType::String
+    pub fn max_length(&mut self, length: u16) -> &mut Self {
+        self.0.insert("max_length", json!(length));
+        self
+    }
+
     /// If the option is a [`SubCommandGroup`] or [`SubCommand`], nested options are its parameters.
     ///
     /// **Note**: A command can have up to 25 subcommand groups, or subcommands. A subcommand group can have up to 25 subcommands. A subcommand can have up to 25 options.
@@ -143,6 +338,54 @@ impl CreateApplicationCommand {
         self
     }
 
+    /// Specifies the name of the application command for the `locale`, e.g.
+    /// `"de"`.
+    ///
+    /// **Note**: Must match the same requirements as [`Self::name`].
+    pub fn name_localized<L: ToString, D: ToString>(&mut self, locale: L, name: D) -> &mut Self {
+        insert_localized(&mut self.0, "name_localizations", locale.to_string(), name.to_string());
+        self
+    }
+
+    /// Specifies the permissions a member needs to use the command by
+    /// default, which guild administrators can override.
+    ///
+    /// Pass [`Permissions::empty`] to only allow administrators by default.
+    pub fn default_member_permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.0.insert("default_member_permissions", Value::String(permissions.bits().to_string()));
+        self
+    }
+
+    /// Specifies whether the command is available in direct messages with
+    /// the bot.
+    ///
+    /// **Note**: Only for global commands. This defaults to `true`.
+    pub fn dm_permission(&mut self, dm_permission: bool) -> &mut Self {
+        self.0.insert("dm_permission", Value::Bool(dm_permission));
+        self
+    }
+
+    /// Specifies where the command can be used.
+    ///
+    /// **Note**: Only for global commands. This defaults to all contexts
+    /// allowed by the [`Self::integration_types`].
+    pub fn contexts(&mut self, contexts: &[InteractionContextType]) -> &mut Self {
+        let contexts = contexts.iter().map(|context| json!(*context as u8)).collect();
+        self.0.insert("contexts", Value::Array(contexts));
+        self
+    }
+
+    /// Specifies the ways the application can be installed for the command to
+    /// be available.
+    ///
+    /// **Note**: Only for global commands. This defaults to the installation
+    /// types set for the application.
+    pub fn integration_types(&mut self, types: &[ApplicationIntegrationType]) -> &mut Self {
+        let types = types.iter().map(|kind| json!(*kind as u8)).collect();
+        self.0.insert("integration_types", Value::Array(types));
+        self
+    }
+
     /// Specifies if the command should not be usable by default
     ///
     /// **Note**: Setting it to false will disable it for anyone,
@@ -161,6 +404,20 @@ impl CreateApplicationCommand {
         self
     }
 
+    /// Specifies the description of the application command for the
+    /// `locale`, e.g. `"de"`.
+    ///
+    /// **Note**: Must be between 1 and 100 characters long.
+    pub fn description_localized<L: ToString, D: ToString>(
+        &mut self,
+        locale: L,
+        description: D,
+    ) -> &mut Self {
+        let description = description.to_string();
+        insert_localized(&mut self.0, "description_localizations", locale.to_string(), description);
+        self
+    }
+
     /// Creates an application command option for the application command.
     ///
     /// **Note**: Application commands can have up to 25 options.
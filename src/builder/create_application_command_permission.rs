@@ -2,7 +2,11 @@ use std::collections::HashMap;
 
 use serde_json::Value;
 
-use crate::model::interactions::application_command::ApplicationCommandPermissionType;
+use crate::model::id::{RoleId, UserId};
+use crate::model::interactions::application_command::{
+    ApplicationCommandPermissionType,
+    CommandPermission,
+};
 use crate::utils;
 
 /// A builder for creating several [`ApplicationCommandPermission`].
@@ -210,3 +214,38 @@ impl CreateApplicationCommandPermissionData {
         self
     }
 }
+
+/// A builder for the [`CommandPermission`]s of a single application command,
+/// to be passed to [`Http::edit_application_command_permissions`].
+///
+/// The permissions replace all previously set overrides of the command.
+///
+/// [`Http::edit_application_command_permissions`]: crate::http::Http::edit_application_command_permissions
+#[derive(Clone, Debug, Default)]
+pub struct CreateApplicationCommandPermission(pub Vec<CommandPermission>);
+
+impl CreateApplicationCommandPermission {
+    /// Allows or denies the role to use the command.
+    pub fn role(&mut self, role_id: impl Into<RoleId>, permission: bool) -> &mut Self {
+        self.add_permission(CommandPermission::role(role_id, permission))
+    }
+
+    /// Allows or denies the user to use the command.
+    pub fn user(&mut self, user_id: impl Into<UserId>, permission: bool) -> &mut Self {
+        self.add_permission(CommandPermission::user(user_id, permission))
+    }
+
+    /// Adds a permission override for the command.
+    pub fn add_permission(&mut self, permission: CommandPermission) -> &mut Self {
+        self.0.push(permission);
+
+        self
+    }
+
+    /// Sets all the permission overrides for the command.
+    pub fn set_permissions(&mut self, permissions: Vec<CommandPermission>) -> &mut Self {
+        self.0 = permissions;
+
+        self
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
+use serde_json::json;
+
 use crate::internal::prelude::*;
-use crate::model::channel::ReactionType;
-use crate::model::interactions::message_component::ButtonStyle;
+use crate::model::channel::{ChannelType, ReactionType};
+use crate::model::id::{ChannelId, RoleId, UserId};
+use crate::model::interactions::message_component::{ActionRowComponent, ButtonStyle};
 use crate::utils;
 
 /// A builder for creating several [`ActionRow`]s.
@@ -97,6 +100,87 @@ impl CreateActionRow {
         self
     }
 
+    /// Creates a select menu of users.
+    pub fn create_user_select_menu<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateUserSelectMenu) -> &mut CreateUserSelectMenu,
+    {
+        let mut data = CreateUserSelectMenu::default();
+        f(&mut data);
+
+        self.add_user_select_menu(data);
+
+        self
+    }
+
+    /// Adds a select menu of users.
+    pub fn add_user_select_menu(&mut self, menu: CreateUserSelectMenu) -> &mut Self {
+        let components = self.0.entry("components").or_insert_with(|| Value::Array(Vec::new()));
+        let components_array = components.as_array_mut().expect("Must be an array");
+
+        components_array.push(menu.build());
+
+        self
+    }
+
+    /// Creates a select menu of roles.
+    pub fn create_role_select_menu<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateRoleSelectMenu) -> &mut CreateRoleSelectMenu,
+    {
+        let mut data = CreateRoleSelectMenu::default();
+        f(&mut data);
+
+        self.add_role_select_menu(data);
+
+        self
+    }
+
+    /// Adds a select menu of roles.
+    pub fn add_role_select_menu(&mut self, menu: CreateRoleSelectMenu) -> &mut Self {
+        let components = self.0.entry("components").or_insert_with(|| Value::Array(Vec::new()));
+        let components_array = components.as_array_mut().expect("Must be an array");
+
+        components_array.push(menu.build());
+
+        self
+    }
+
+    /// Creates a select menu of channels.
+    pub fn create_channel_select_menu<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateChannelSelectMenu) -> &mut CreateChannelSelectMenu,
+    {
+        let mut data = CreateChannelSelectMenu::default();
+        f(&mut data);
+
+        self.add_channel_select_menu(data);
+
+        self
+    }
+
+    /// Adds a select menu of channels.
+    pub fn add_channel_select_menu(&mut self, menu: CreateChannelSelectMenu) -> &mut Self {
+        let components = self.0.entry("components").or_insert_with(|| Value::Array(Vec::new()));
+        let components_array = components.as_array_mut().expect("Must be an array");
+
+        components_array.push(menu.build());
+
+        self
+    }
+
+    /// Adds an existing component, e.g. one of the components of a received
+    /// message, to send it again with modifications.
+    #[allow(clippy::unwrap_used)] // serializing a component should never error
+    pub fn add_component(&mut self, component: impl Into<ActionRowComponent>) -> &mut Self {
+        let components = self.0.entry("components").or_insert_with(|| Value::Array(Vec::new()));
+        let components_array = components.as_array_mut().expect("Must be an array");
+
+        components_array.push(serde_json::to_value(component.into()).unwrap());
+
+        self
+    }
+
     pub fn build(&mut self) -> Value {
         self.0.insert("type", Value::Number(serde_json::Number::from(1_u8)));
 
@@ -174,6 +258,42 @@ impl CreateButton {
     }
 }
 
+/// The most values a user can select in a select menu.
+const MAX_SELECT_VALUES: u64 = 25;
+
+fn set_min_values(map: &mut HashMap<&'static str, Value>, min: u64) {
+    assert!(min <= MAX_SELECT_VALUES, "min_values must be at most 25, got {}", min);
+
+    if let Some(max) = map.get("max_values").and_then(Value::as_u64) {
+        assert!(min <= max, "min_values ({}) must not exceed max_values ({})", min, max);
+    }
+
+    map.insert("min_values", Value::Number(Number::from(min)));
+}
+
+fn set_max_values(map: &mut HashMap<&'static str, Value>, max: u64) {
+    assert!((1..=MAX_SELECT_VALUES).contains(&max), "max_values must be from 1 to 25, got {}", max);
+
+    if let Some(min) = map.get("min_values").and_then(Value::as_u64) {
+        assert!(min <= max, "min_values ({}) must not exceed max_values ({})", min, max);
+    }
+
+    map.insert("max_values", Value::Number(Number::from(max)));
+}
+
+/// Builds the default values of a select menu of users, roles, or channels,
+/// which are the Ids along with the `kind` of what they identify.
+fn default_values(ids: impl IntoIterator<Item = u64>, kind: &str) -> Value {
+    let values = ids.into_iter().map(|id| {
+        json!({
+            "id": id.to_string(),
+            "type": kind,
+        })
+    });
+
+    Value::Array(values.collect())
+}
+
 /// A builder for creating a [`SelectMenu`].
 ///
 /// [`SelectMenu`]: crate::model::interactions::message_component::SelectMenu
@@ -193,15 +313,25 @@ impl CreateSelectMenu {
         self
     }
 
-    /// Sets the minimum values for the user to select.
+    /// Sets the minimum values for the user to select, from 0 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than 25, or than the maximum values if they
+    /// were already set.
     pub fn min_values(&mut self, min: u64) -> &mut Self {
-        self.0.insert("min_values", Value::Number(Number::from(min)));
+        set_min_values(&mut self.0, min);
         self
     }
 
-    /// Sets the maximum values for the user to select.
+    /// Sets the maximum values for the user to select, from 1 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is 0 or greater than 25, or less than the minimum values
+    /// if they were already set.
     pub fn max_values(&mut self, max: u64) -> &mut Self {
-        self.0.insert("max_values", Value::Number(Number::from(max)));
+        set_max_values(&mut self.0, max);
         self
     }
 
@@ -224,6 +354,179 @@ impl CreateSelectMenu {
     }
 }
 
+/// A builder for creating a select menu of users.
+#[derive(Clone, Debug, Default)]
+pub struct CreateUserSelectMenu(pub HashMap<&'static str, Value>);
+
+impl CreateUserSelectMenu {
+    /// The placeholder of the select menu.
+    pub fn placeholder<D: ToString>(&mut self, label: D) -> &mut Self {
+        self.0.insert("placeholder", Value::String(label.to_string()));
+        self
+    }
+
+    /// Sets the custom id of the select menu, a developer-defined identifier.
+    pub fn custom_id<D: ToString>(&mut self, id: D) -> &mut Self {
+        self.0.insert("custom_id", Value::String(id.to_string()));
+        self
+    }
+
+    /// Sets the minimum values for the user to select, from 0 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CreateSelectMenu::min_values`].
+    pub fn min_values(&mut self, min: u64) -> &mut Self {
+        set_min_values(&mut self.0, min);
+        self
+    }
+
+    /// Sets the maximum values for the user to select, from 1 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CreateSelectMenu::max_values`].
+    pub fn max_values(&mut self, max: u64) -> &mut Self {
+        set_max_values(&mut self.0, max);
+        self
+    }
+
+    /// Sets the users that are selected by default.
+    pub fn default_users<It>(&mut self, user_ids: It) -> &mut Self
+    where
+        It: IntoIterator,
+        It::Item: Into<UserId>,
+    {
+        let ids = user_ids.into_iter().map(|id| id.into().0);
+        self.0.insert("default_values", default_values(ids, "user"));
+        self
+    }
+
+    pub fn build(mut self) -> Value {
+        self.0.insert("type", Value::Number(serde_json::Number::from(5_u8)));
+
+        utils::hashmap_to_json_map(self.0.clone()).into()
+    }
+}
+
+/// A builder for creating a select menu of roles.
+#[derive(Clone, Debug, Default)]
+pub struct CreateRoleSelectMenu(pub HashMap<&'static str, Value>);
+
+impl CreateRoleSelectMenu {
+    /// The placeholder of the select menu.
+    pub fn placeholder<D: ToString>(&mut self, label: D) -> &mut Self {
+        self.0.insert("placeholder", Value::String(label.to_string()));
+        self
+    }
+
+    /// Sets the custom id of the select menu, a developer-defined identifier.
+    pub fn custom_id<D: ToString>(&mut self, id: D) -> &mut Self {
+        self.0.insert("custom_id", Value::String(id.to_string()));
+        self
+    }
+
+    /// Sets the minimum values for the user to select, from 0 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CreateSelectMenu::min_values`].
+    pub fn min_values(&mut self, min: u64) -> &mut Self {
+        set_min_values(&mut self.0, min);
+        self
+    }
+
+    /// Sets the maximum values for the user to select, from 1 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CreateSelectMenu::max_values`].
+    pub fn max_values(&mut self, max: u64) -> &mut Self {
+        set_max_values(&mut self.0, max);
+        self
+    }
+
+    /// Sets the roles that are selected by default.
+    pub fn default_roles<It>(&mut self, role_ids: It) -> &mut Self
+    where
+        It: IntoIterator,
+        It::Item: Into<RoleId>,
+    {
+        let ids = role_ids.into_iter().map(|id| id.into().0);
+        self.0.insert("default_values", default_values(ids, "role"));
+        self
+    }
+
+    pub fn build(mut self) -> Value {
+        self.0.insert("type", Value::Number(serde_json::Number::from(6_u8)));
+
+        utils::hashmap_to_json_map(self.0.clone()).into()
+    }
+}
+
+/// A builder for creating a select menu of channels.
+#[derive(Clone, Debug, Default)]
+pub struct CreateChannelSelectMenu(pub HashMap<&'static str, Value>);
+
+impl CreateChannelSelectMenu {
+    /// The placeholder of the select menu.
+    pub fn placeholder<D: ToString>(&mut self, label: D) -> &mut Self {
+        self.0.insert("placeholder", Value::String(label.to_string()));
+        self
+    }
+
+    /// Sets the custom id of the select menu, a developer-defined identifier.
+    pub fn custom_id<D: ToString>(&mut self, id: D) -> &mut Self {
+        self.0.insert("custom_id", Value::String(id.to_string()));
+        self
+    }
+
+    /// Sets the minimum values for the user to select, from 0 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CreateSelectMenu::min_values`].
+    pub fn min_values(&mut self, min: u64) -> &mut Self {
+        set_min_values(&mut self.0, min);
+        self
+    }
+
+    /// Sets the maximum values for the user to select, from 1 to 25.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CreateSelectMenu::max_values`].
+    pub fn max_values(&mut self, max: u64) -> &mut Self {
+        set_max_values(&mut self.0, max);
+        self
+    }
+
+    /// Sets the channels that are selected by default.
+    pub fn default_channels<It>(&mut self, channel_ids: It) -> &mut Self
+    where
+        It: IntoIterator,
+        It::Item: Into<ChannelId>,
+    {
+        let ids = channel_ids.into_iter().map(|id| id.into().0);
+        self.0.insert("default_values", default_values(ids, "channel"));
+        self
+    }
+
+    /// Restricts the channels that can be selected to those of the given
+    /// types.
+    pub fn channel_types(&mut self, channel_types: &[ChannelType]) -> &mut Self {
+        let types = channel_types.iter().map(|kind| Value::Number(Number::from(kind.num())));
+        self.0.insert("channel_types", Value::Array(types.collect()));
+        self
+    }
+
+    pub fn build(mut self) -> Value {
+        self.0.insert("type", Value::Number(serde_json::Number::from(8_u8)));
+
+        utils::hashmap_to_json_map(self.0.clone()).into()
+    }
+}
+
 /// A builder for creating several [`SelectMenuOption`].
 ///
 /// [`SelectMenuOption`]: crate::model::interactions::message_component::SelectMenuOption
@@ -325,3 +628,79 @@ impl CreateSelectMenuOption {
         self
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde_json::json;
+
+    use super::{CreateActionRow, CreateSelectMenu};
+    use crate::model::channel::ChannelType;
+    use crate::model::id::{ChannelId, UserId};
+    use crate::model::interactions::message_component::Button;
+
+    #[test]
+    fn test_add_component() {
+        let mut button: Button = serde_json::from_value(json!({
+            "type": 2,
+            "style": 1,
+            "label": "Old",
+            "custom_id": "vote"
+        }))
+        .unwrap();
+        button.label = Some("New".to_string());
+
+        let mut row = CreateActionRow::default();
+        row.create_button(|b| b.custom_id("other").label("Other"));
+        row.add_component(button);
+
+        let components = row.build()["components"].clone();
+        assert_eq!(components[0]["custom_id"], "other");
+        assert_eq!(components[1]["type"], 2);
+        assert_eq!(components[1]["style"], 1);
+        assert_eq!(components[1]["label"], "New");
+        assert_eq!(components[1]["custom_id"], "vote");
+    }
+
+    #[test]
+    fn test_select_menu_values() {
+        let mut menu = CreateSelectMenu::default();
+        menu.min_values(0).max_values(25);
+
+        assert_eq!(menu.0["min_values"], 0);
+        assert_eq!(menu.0["max_values"], 25);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_select_menu_min_above_max() {
+        CreateSelectMenu::default().max_values(2).min_values(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_select_menu_max_out_of_range() {
+        CreateSelectMenu::default().max_values(26);
+    }
+
+    #[test]
+    fn test_typed_select_menus() {
+        let mut row = CreateActionRow::default();
+        row.create_user_select_menu(|m| m.custom_id("users").default_users(vec![UserId(1)]));
+        row.create_role_select_menu(|m| m.custom_id("roles").max_values(2));
+        row.create_channel_select_menu(|m| {
+            m.custom_id("channels")
+                .default_channels(vec![ChannelId(3)])
+                .channel_types(&[ChannelType::Text, ChannelType::Voice])
+        });
+
+        let components = row.build()["components"].clone();
+        assert_eq!(components[0]["type"], 5);
+        assert_eq!(components[0]["default_values"], json!([{"id": "1", "type": "user"}]));
+        assert_eq!(components[1]["type"], 6);
+        assert_eq!(components[1]["max_values"], 2);
+        assert_eq!(components[2]["type"], 8);
+        assert_eq!(components[2]["default_values"], json!([{"id": "3", "type": "channel"}]));
+        assert_eq!(components[2]["channel_types"], json!([0, 2]));
+    }
+}
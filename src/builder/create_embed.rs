@@ -231,7 +231,7 @@ impl CreateEmbed {
     /// impl EventHandler for Handler {
     ///     async fn message(&self, context: Context, mut msg: Message) {
     ///         if msg.content == "~embed" {
-    ///             let _ = msg.channel_id.send_message(&context.http, |m| {
+    ///             let _ = msg.channel_id.send_message(context.http(), |m| {
     ///                 m.embed(|e| {
     ///                     e.title("hello").timestamp("2004-06-08T16:04:23")
     ///                 });
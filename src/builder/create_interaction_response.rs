@@ -131,6 +131,26 @@ impl CreateInteractionResponseData {
         self
     }
 
+    /// Sets or clears the [`EPHEMERAL`] flag, making the message visible
+    /// only to the user who invoked the interaction.
+    ///
+    /// Other flags set through [`Self::flags`] are left untouched.
+    ///
+    /// [`EPHEMERAL`]: InteractionApplicationCommandCallbackDataFlags::EPHEMERAL
+    pub fn ephemeral(&mut self, ephemeral: bool) -> &mut Self {
+        let ephemeral_bits = InteractionApplicationCommandCallbackDataFlags::EPHEMERAL.bits();
+        let mut bits = self.0.get("flags").and_then(Value::as_u64).unwrap_or(0);
+
+        if ephemeral {
+            bits |= ephemeral_bits;
+        } else {
+            bits &= !ephemeral_bits;
+        }
+
+        self.0.insert("flags", Value::Number(serde_json::Number::from(bits)));
+        self
+    }
+
     /// Sets the components of this message.
     #[cfg(feature = "unstable_discord_api")]
     pub fn components<F>(&mut self, f: F) -> &mut Self
@@ -144,3 +164,25 @@ impl CreateInteractionResponseData {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::Value;
+
+    use super::CreateInteractionResponseData;
+
+    #[test]
+    fn test_ephemeral() {
+        let mut data = CreateInteractionResponseData::default();
+
+        data.ephemeral(true);
+        assert_eq!(data.0.get("flags"), Some(&Value::from(64u64)));
+
+        data.0.insert("flags", Value::from(4u64));
+        data.ephemeral(true);
+        assert_eq!(data.0.get("flags"), Some(&Value::from(68u64)));
+
+        data.ephemeral(false);
+        assert_eq!(data.0.get("flags"), Some(&Value::from(4u64)));
+    }
+}
@@ -142,6 +142,26 @@ impl<'a> CreateInteractionResponseFollowup<'a> {
         self
     }
 
+    /// Sets or clears the [`EPHEMERAL`] flag, making the response visible
+    /// only to the user who invoked the interaction.
+    ///
+    /// Other flags set through [`Self::flags`] are left untouched.
+    ///
+    /// [`EPHEMERAL`]: InteractionApplicationCommandCallbackDataFlags::EPHEMERAL
+    pub fn ephemeral(&mut self, ephemeral: bool) -> &mut Self {
+        let ephemeral_bits = InteractionApplicationCommandCallbackDataFlags::EPHEMERAL.bits();
+        let mut bits = self.0.get("flags").and_then(Value::as_u64).unwrap_or(0);
+
+        if ephemeral {
+            bits |= ephemeral_bits;
+        } else {
+            bits &= !ephemeral_bits;
+        }
+
+        self.0.insert("flags", Value::Number(serde_json::Number::from(bits)));
+        self
+    }
+
     /// Sets the components of this message.
     #[cfg(feature = "unstable_discord_api")]
     pub fn components<F>(&mut self, f: F) -> &mut Self
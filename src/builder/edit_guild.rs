@@ -96,16 +96,22 @@ impl EditGuild {
 
     /// Set the features of the guild.
     ///
+    /// This replaces the whole list of features, so it should contain the
+    /// guild's current [`features`] along with the ones to enable. Only some
+    /// features, such as [`GuildFeature::Community`] and
+    /// [`GuildFeature::Discoverable`], can be toggled this way.
+    ///
     /// **Note**: Requires that the guild have the `DISCOVERABLE` feature enabled.
     /// You can check this through a guild's [`features`] list.
     ///
     /// [`features`]: crate::model::guild::Guild::features
-    pub fn features(&mut self, features: Vec<String>) -> &mut Self {
-        let mut values: Vec<Value> = vec![];
-
-        for value in features {
-            values.push(Value::String(value));
-        }
+    pub fn features<I, F>(&mut self, features: I) -> &mut Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<GuildFeature>,
+    {
+        let values =
+            features.into_iter().map(|f| Value::String(f.into().name().to_string())).collect();
 
         self.0.insert("features", Value::Array(values));
         self
@@ -80,6 +80,7 @@ pub use self::{
         CreateApplicationCommands,
     },
     create_application_command_permission::{
+        CreateApplicationCommandPermission,
         CreateApplicationCommandPermissions,
         CreateApplicationCommandPermissionsData,
         CreateApplicationCommandsPermissions,
@@ -87,10 +88,13 @@ pub use self::{
     create_components::{
         CreateActionRow,
         CreateButton,
+        CreateChannelSelectMenu,
         CreateComponents,
+        CreateRoleSelectMenu,
         CreateSelectMenu,
         CreateSelectMenuOption,
         CreateSelectMenuOptions,
+        CreateUserSelectMenu,
     },
     create_interaction_response::{CreateInteractionResponse, CreateInteractionResponseData},
     create_interaction_response_followup::CreateInteractionResponseFollowup,
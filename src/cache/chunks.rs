@@ -0,0 +1,57 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+use crate::model::id::GuildId;
+
+/// Tracks the member chunk requests sent for guilds by their nonce, to tell
+/// when all of them were answered.
+#[derive(Debug, Default)]
+pub(crate) struct MemberChunks {
+    pending: HashMap<String, PendingGuild>,
+    chunked: Vec<GuildId>,
+}
+
+#[derive(Debug)]
+struct PendingGuild {
+    guild_id: GuildId,
+    received: HashSet<u32>,
+}
+
+impl MemberChunks {
+    /// Starts waiting for the member chunks of a request.
+    pub(crate) fn expect(&mut self, guild_id: GuildId, nonce: String) {
+        self.pending.insert(nonce, PendingGuild {
+            guild_id,
+            received: HashSet::new(),
+        });
+    }
+
+    /// Marks a chunk of a request as received.
+    ///
+    /// Returns the guilds that were fully chunked since the last time, if no
+    /// request is pending anymore.
+    pub(crate) fn receive(
+        &mut self,
+        nonce: &str,
+        chunk_index: u32,
+        chunk_count: u32,
+    ) -> Option<Vec<GuildId>> {
+        let guild = self.pending.get_mut(nonce)?;
+        guild.received.insert(chunk_index);
+
+        if guild.received.len() < chunk_count as usize {
+            return None;
+        }
+
+        let guild = self.pending.remove(nonce)?;
+        self.chunked.push(guild.guild_id);
+
+        if self.pending.is_empty() {
+            Some(mem::take(&mut self.chunked))
+        } else {
+            None
+        }
+    }
+}
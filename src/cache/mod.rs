@@ -30,7 +30,7 @@
 //! [`Shard`]: crate::gateway::Shard
 //! [`http`]: crate::http
 
-use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use std::collections::{btree_map, hash_map::Entry, BTreeMap, HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::str::FromStr;
 
@@ -41,12 +41,22 @@ use tracing::instrument;
 use crate::model::prelude::*;
 
 mod cache_update;
+mod chunks;
+mod refs;
 mod settings;
+mod sharded;
+mod stats;
+mod temp;
 
 pub use self::cache_update::CacheUpdate;
+pub use self::refs::{CacheRef, CurrentUserRef, GuildChannelRef, GuildRef};
 pub use self::settings::Settings;
+pub use self::stats::CacheStats;
+use self::chunks::MemberChunks;
+use self::sharded::ShardedMap;
+use self::temp::TtlMap;
 
-type MessageCache = HashMap<ChannelId, HashMap<MessageId, Message>>;
+type MessageCache = HashMap<ChannelId, BTreeMap<MessageId, Message>>;
 
 #[async_trait]
 pub trait FromStrAndCache: Sized {
@@ -86,10 +96,11 @@ impl<F: FromStr> FromStrAndCache for F {
     }
 }
 
-/// Iterator given to the selector closure in [`Cache::channel_messages_field`].
+/// Iterator given to the selector closure in [`Cache::channel_messages_field`],
+/// yielding the messages in order of their Ids.
 // Wrapper around a specific iterator type to allow swapping out iterators on cache design changes
 #[derive(Clone, Debug)]
-pub struct MessageIterator<'a>(std::collections::hash_map::Values<'a, MessageId, Message>);
+pub struct MessageIterator<'a>(btree_map::Values<'a, MessageId, Message>);
 
 impl<'a> Iterator for MessageIterator<'a> {
     type Item = &'a Message;
@@ -121,12 +132,13 @@ pub struct Cache {
     /// When a [`Event::GuildDelete`] or [`Event::GuildUnavailable`] is
     /// received and processed by the cache, the relevant channels are also
     /// removed from this map.
-    pub(crate) channels: RwLock<HashMap<ChannelId, GuildChannel>>,
+    pub(crate) channels: ShardedMap<ChannelId, GuildChannel>,
     /// A map of channel categories.
     pub(crate) categories: RwLock<HashMap<ChannelId, ChannelCategory>>,
     /// A map of guilds with full data available. This includes data like
     /// [`Role`]s and [`Emoji`]s that are not available through the REST API.
-    pub(crate) guilds: RwLock<HashMap<GuildId, Guild>>,
+    pub(crate) guilds: ShardedMap<GuildId, Guild>,
+    /// The cached messages of each channel, ordered by their Ids.
     pub(crate) messages: RwLock<MessageCache>,
     /// A map of users' presences. This is updated in real-time. Note that
     /// status updates are often "eaten" by the gateway, and this should not
@@ -173,6 +185,17 @@ pub struct Cache {
     /// inserted into the cache. When a maximum number of messages are in a
     /// channel's cache, we can pop the front and remove that ID from the cache.
     pub(crate) message_queue: RwLock<HashMap<ChannelId, VecDeque<MessageId>>>,
+    /// Users seen in messages and interactions, kept for
+    /// [`Settings::time_to_live`] if [`Settings::cache_users`] is enabled.
+    pub(crate) temp_users: RwLock<TtlMap<UserId, User>>,
+    /// Members seen in messages and interactions, kept like
+    /// [`Self::temp_users`].
+    pub(crate) temp_members: RwLock<TtlMap<(GuildId, UserId), Member>>,
+    /// Threads seen in events, kept for [`Settings::time_to_live`] if
+    /// [`Settings::cache_channels`] is enabled.
+    pub(crate) temp_channels: RwLock<TtlMap<ChannelId, GuildChannel>>,
+    /// The member chunk requests whose chunks are still expected.
+    pub(crate) member_chunks: RwLock<MemberChunks>,
     /// The settings for the cache.
     settings: RwLock<Settings>,
 }
@@ -253,17 +276,96 @@ impl Cache {
     pub async fn unknown_members(&self) -> u64 {
         let mut total = 0;
 
-        for guild in self.guilds.read().await.values() {
-            let members = guild.members.len() as u64;
+        self.guilds
+            .for_each(|_, guild| {
+                let members = guild.members.len() as u64;
 
-            if guild.member_count > members {
-                total += guild.member_count - members;
-            }
-        }
+                if guild.member_count > members {
+                    total += guild.member_count - members;
+                }
+            })
+            .await;
 
         total
     }
 
+    /// Returns the number of cached members, summed across all guilds.
+    pub async fn member_count(&self) -> usize {
+        let mut count = 0;
+        self.guilds.for_each(|_, guild| count += guild.members.len()).await;
+
+        count
+    }
+
+    /// Returns the total number of cached members of the cached guilds, e.g.
+    /// for statistics like "watching X guilds with Y members".
+    ///
+    /// This is the same as [`Self::member_count`]. The guilds are read one at
+    /// a time, so no lock is held on several guilds at once. Sum
+    /// [`Guild::member_count`] instead to include the members that are not
+    /// cached.
+    #[inline]
+    pub async fn total_cached_member_count(&self) -> usize {
+        self.member_count().await
+    }
+
+    /// Returns the number of channels of the cached guilds, summed across all
+    /// guilds.
+    ///
+    /// This is the same as [`Self::guild_channel_count`].
+    #[inline]
+    pub async fn guild_channels_count(&self) -> usize {
+        self.guild_channel_count().await
+    }
+
+    /// Returns the number of cached messages, summed across all channels.
+    pub async fn message_count(&self) -> usize {
+        self.messages.read().await.values().map(BTreeMap::len).sum()
+    }
+
+    /// Returns counts of the cached data along with the configured limits,
+    /// e.g. to export them as metrics.
+    ///
+    /// Only read locks are taken, one collection at a time, so this does not
+    /// hold up cache updates for long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # async fn run() {
+    /// let cache = Cache::new();
+    /// let stats = cache.statistics().await;
+    ///
+    /// println!("{} guilds, {} members", stats.guilds, stats.members);
+    /// println!("roughly {} bytes", stats.approximate_size());
+    /// # }
+    /// ```
+    pub async fn statistics(&self) -> CacheStats {
+        let (mut guild_presences, mut voice_states) = (0, 0);
+        self.guilds
+            .for_each(|_, guild| {
+                guild_presences += guild.presences.len();
+                voice_states += guild.voice_states.len();
+            })
+            .await;
+
+        CacheStats {
+            guilds: self.guild_count().await,
+            unavailable_guilds: self.unavailable_guilds.read().await.len(),
+            channels: self.guild_channel_count().await,
+            categories: self.category_count().await,
+            private_channels: self.private_channels.read().await.len(),
+            users: self.user_count().await,
+            members: self.member_count().await,
+            messages: self.message_count().await,
+            presences: guild_presences + self.presences.read().await.len(),
+            voice_states,
+            max_messages: self.settings.read().await.max_messages,
+        }
+    }
+
     /// Fetches a vector of all [`PrivateChannel`] Ids that are
     /// stored in the cache.
     ///
@@ -319,8 +421,10 @@ impl Cache {
     /// [`Context`]: crate::client::Context
     /// [`Shard`]: crate::gateway::Shard
     pub async fn guilds(&self) -> Vec<GuildId> {
-        let chain = self.unavailable_guilds.read().await.clone().into_iter();
-        self.guilds.read().await.keys().cloned().chain(chain).collect()
+        let mut guilds = self.unavailable_guilds.read().await.iter().copied().collect::<Vec<_>>();
+        self.guilds.for_each(|guild_id, _| guilds.push(*guild_id)).await;
+
+        guilds
     }
 
     /// Retrieves a [`Channel`] from the cache based on the given Id.
@@ -338,7 +442,7 @@ impl Cache {
     }
 
     async fn _channel(&self, id: ChannelId) -> Option<Channel> {
-        if let Some(channel) = self.channels.read().await.get(&id) {
+        if let Some(channel) = self.channels.read(&id).await.get(&id) {
             let channel = channel.clone();
             return Some(Channel::Guild(channel));
         }
@@ -372,6 +476,34 @@ impl Cache {
         Some(selector(message_iter))
     }
 
+    /// Returns clones of the cached messages of a channel, ordered by their
+    /// Ids, i.e. from oldest to newest.
+    ///
+    /// Returns [`None`] if no messages of the channel were cached. To avoid
+    /// cloning all messages, use [`Self::channel_messages_field`] instead.
+    ///
+    /// # Examples
+    ///
+    /// Printing the 5 newest messages of a channel, for which message caching
+    /// has to be enabled via [`Settings::max_messages`]:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// # use serenity::model::id::ChannelId;
+    /// #
+    /// # async fn run() {
+    /// # let cache = Cache::default();
+    /// let messages = cache.channel_messages(ChannelId(7)).await.unwrap_or_default();
+    ///
+    /// for message in messages.iter().rev().take(5) {
+    ///     println!("{}: {}", message.author.name, message.content);
+    /// }
+    /// # }
+    /// ```
+    pub async fn channel_messages(&self, channel_id: impl Into<ChannelId>) -> Option<Vec<Message>> {
+        self.channel_messages_field(channel_id, |messages| messages.cloned().collect()).await
+    }
+
     /// Clones an entire guild from the cache based on the given `id`.
     ///
     /// In order to clone only a field of the guild, use [`Self::guild_field`].
@@ -401,7 +533,7 @@ impl Cache {
     }
 
     async fn _guild(&self, id: GuildId) -> Option<Guild> {
-        self.guilds.read().await.get(&id).cloned()
+        self.guilds.read(&id).await.get(&id).cloned()
     }
 
     /// This method allows to select a field of the guild instead of
@@ -436,7 +568,7 @@ impl Cache {
     where
         Fun: FnOnce(&Guild) -> Ret,
     {
-        let guilds = self.guilds.read().await;
+        let guilds = self.guilds.read(&id).await;
         let guild = guilds.get(&id)?;
 
         Some(field_accessor(guild))
@@ -444,7 +576,73 @@ impl Cache {
 
     /// Returns the number of cached guilds.
     pub async fn guild_count(&self) -> usize {
-        self.guilds.read().await.len()
+        self.guilds.len().await
+    }
+
+    /// Returns a reference to a guild in the cache, without cloning it like
+    /// [`Self::guild`] does.
+    ///
+    /// The reference holds a read lock on the part of the cached guilds that
+    /// the guild is stored in, so those guilds cannot be updated until it is
+    /// dropped. Do not hold it across an `.await` or while calling other
+    /// methods of the cache, which may deadlock; see [`CacheRef`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// # use serenity::model::id::GuildId;
+    /// #
+    /// # async fn run() {
+    /// # let cache = Cache::default();
+    /// let channel_count = match cache.guild_ref(GuildId(7)).await {
+    ///     Some(guild) => guild.channels.len(),
+    ///     None => 0,
+    /// };
+    /// # }
+    /// ```
+    ///
+    /// The reference cannot outlive the cache:
+    ///
+    /// ```rust,compile_fail
+    /// # use serenity::cache::Cache;
+    /// # use serenity::model::id::GuildId;
+    /// #
+    /// # async fn run() {
+    /// let guild = {
+    ///     let cache = Cache::default();
+    ///     cache.guild_ref(GuildId(7)).await
+    /// };
+    /// # }
+    /// ```
+    pub async fn guild_ref(&self, id: impl Into<GuildId>) -> Option<GuildRef<'_>> {
+        let id = id.into();
+
+        CacheRef::new(self.guilds.read(&id).await, &id)
+    }
+
+    /// Calls `f` with every cached guild, without cloning them.
+    ///
+    /// Like [`Self::guild_ref`], this holds read locks on the cached guilds,
+    /// one part of them at a time, while `f` runs, so `f` should not block or
+    /// call other methods of the cache. Guilds updated during the iteration
+    /// may be seen either before or after the update.
+    ///
+    /// # Examples
+    ///
+    /// Counting the members of all guilds:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # async fn run() {
+    /// # let cache = Cache::default();
+    /// let mut members = 0;
+    /// cache.guilds_iter(|guild| members += guild.member_count).await;
+    /// # }
+    /// ```
+    pub async fn guilds_iter(&self, mut f: impl FnMut(&Guild)) {
+        self.guilds.for_each(|_, guild| f(guild)).await;
     }
 
     /// Retrieves a reference to a [`Guild`]'s channel. Unlike [`Self::channel`],
@@ -498,7 +696,13 @@ impl Cache {
     }
 
     async fn _guild_channel(&self, id: ChannelId) -> Option<GuildChannel> {
-        self.channels.read().await.get(&id).cloned()
+        if let Some(channel) = self.channels.read(&id).await.get(&id) {
+            return Some(channel.clone());
+        }
+
+        let ttl = self.settings.read().await.time_to_live;
+
+        self.temp_channels.read().await.get(&id, ttl)
     }
 
     /// This method allows to only clone a field of the guild channel instead of
@@ -537,12 +741,37 @@ impl Cache {
     where
         Fun: FnOnce(&GuildChannel) -> Ret,
     {
-        let guild_channels = &self.channels.read().await;
+        let guild_channels = &self.channels.read(&id).await;
         let channel = guild_channels.get(&id)?;
 
         Some(field_selector(channel))
     }
 
+    /// Returns a reference to a guild channel in the cache, without cloning it
+    /// like [`Self::guild_channel`] does.
+    ///
+    /// Unlike [`Self::guild_channel`], this does not return the threads that
+    /// are only cached temporarily.
+    ///
+    /// The reference holds a read lock on the part of the cached guild channels
+    /// that the channel is stored in, so those channels cannot be updated until
+    /// it is dropped. Do not hold it across an `.await` or while calling other
+    /// methods of the cache, which may deadlock; see [`CacheRef`] for details.
+    pub async fn guild_channel_ref(&self, id: impl Into<ChannelId>) -> Option<GuildChannelRef<'_>> {
+        let id = id.into();
+
+        CacheRef::new(self.channels.read(&id).await, &id)
+    }
+
+    /// Calls `f` with every cached guild channel, without cloning them.
+    ///
+    /// Like [`Self::guild_channel_ref`], this holds read locks on the cached
+    /// guild channels, one part of them at a time, while `f` runs, so `f`
+    /// should not block or call other methods of the cache.
+    pub async fn guild_channels_iter(&self, mut f: impl FnMut(&GuildChannel)) {
+        self.channels.for_each(|_, channel| f(channel)).await;
+    }
+
     /// Retrieves a [`Guild`]'s member from the cache based on the guild's and
     /// user's given Ids.
     ///
@@ -607,10 +836,20 @@ impl Cache {
     }
 
     async fn _member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
-        match self.guilds.read().await.get(&guild_id) {
-            Some(guild) => guild.members.get(&user_id).cloned(),
-            None => None,
+        let member = self
+            .guilds
+            .read(&guild_id)
+            .await
+            .get(&guild_id)
+            .and_then(|guild| guild.members.get(&user_id).cloned());
+
+        if member.is_some() {
+            return member;
         }
+
+        let ttl = self.settings.read().await.time_to_live;
+
+        self.temp_members.read().await.get(&(guild_id, user_id), ttl)
     }
 
     /// This method allows to only clone a field of a member instead of
@@ -651,7 +890,7 @@ impl Cache {
     where
         Fun: FnOnce(&Member) -> Ret,
     {
-        let guilds = &self.guilds.read().await;
+        let guilds = &self.guilds.read(&guild_id).await;
         let guild = guilds.get(&guild_id)?;
         let member = guild.members.get(&user_id)?;
 
@@ -664,7 +903,7 @@ impl Cache {
     }
 
     async fn _guild_roles(&self, guild_id: GuildId) -> Option<HashMap<RoleId, Role>> {
-        self.guilds.read().await.get(&guild_id).map(|g| g.roles.clone())
+        self.guilds.read(&guild_id).await.get(&guild_id).map(|g| g.roles.clone())
     }
 
     /// This method clones and returns all unavailable guilds.
@@ -673,6 +912,22 @@ impl Cache {
         self.unavailable_guilds.read().await.clone()
     }
 
+    /// Returns the number of guilds that are unavailable, either because they
+    /// were unavailable in the [`Event::Ready`] and have not been received in a
+    /// [`Event::GuildCreate`] since, or because of an outage.
+    pub async fn unavailable_guild_count(&self) -> usize {
+        self.unavailable_guilds.read().await.len()
+    }
+
+    /// Whether the guild is unavailable.
+    ///
+    /// Unlike a guild that the current user left, which is no longer cached at
+    /// all, an unavailable guild is expected to be received in a
+    /// [`Event::GuildCreate`] once it becomes available.
+    pub async fn is_guild_unavailable(&self, id: impl Into<GuildId>) -> bool {
+        self.unavailable_guilds.read().await.contains(&id.into())
+    }
+
     /// This method returns all channels from a guild of with the given `guild_id`.
     #[inline]
     pub async fn guild_channels(
@@ -683,12 +938,53 @@ impl Cache {
     }
 
     async fn _guild_channels(&self, guild_id: GuildId) -> Option<HashMap<ChannelId, GuildChannel>> {
-        self.guilds.read().await.get(&guild_id).map(|g| g.channels.clone())
+        self.guilds.read(&guild_id).await.get(&guild_id).map(|g| g.channels.clone())
+    }
+
+    /// Finds a guild channel by its exact name, without the leading `#`,
+    /// across all cached guilds.
+    ///
+    /// **Note**: If channels of several guilds have the name, which of them is
+    /// returned is unspecified. To search a single guild, use
+    /// [`Self::guild_channel_by_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # async fn run() {
+    /// # let cache = Cache::default();
+    /// if let Some(channel) = cache.channel_by_name("bot-log").await {
+    ///     println!("Logging to {}", channel.id);
+    /// }
+    /// # }
+    /// ```
+    pub async fn channel_by_name(&self, name: &str) -> Option<GuildChannel> {
+        self.channels
+            .find_map(|_, channel| if channel.name == name { Some(channel.clone()) } else { None })
+            .await
+    }
+
+    /// Finds a channel of a guild by its exact name, without the leading `#`.
+    ///
+    /// If several channels of the guild have the name, which of them is
+    /// returned is unspecified.
+    pub async fn guild_channel_by_name(
+        &self,
+        guild_id: impl Into<GuildId>,
+        name: &str,
+    ) -> Option<GuildChannel> {
+        let guild_id = guild_id.into();
+        let guilds = self.guilds.read(&guild_id).await;
+        let guild = guilds.get(&guild_id)?;
+
+        guild.channels.values().find(|channel| channel.name == name).cloned()
     }
 
     /// Returns the number of guild channels in the cache.
     pub async fn guild_channel_count(&self) -> usize {
-        self.channels.read().await.len()
+        self.channels.len().await
     }
 
     /// Returns the number of shards.
@@ -697,6 +993,28 @@ impl Cache {
         *self.shard_count.read().await
     }
 
+    /// Returns the Ids of the cached guilds that the shard with the given Id
+    /// receives the events of, according to [`Self::shard_count`].
+    ///
+    /// Refer to [`utils::shard_id`] for how guilds are assigned to shards.
+    ///
+    /// [`utils::shard_id`]: crate::utils::shard_id
+    #[cfg(feature = "utils")]
+    pub async fn guilds_on_shard(&self, shard_id: u64) -> Vec<GuildId> {
+        let shard_count = self.shard_count().await;
+
+        let mut guilds = Vec::new();
+        self.guilds
+            .for_each(|guild_id, _| {
+                if crate::utils::shard_id(guild_id.0, shard_count) == shard_id {
+                    guilds.push(*guild_id);
+                }
+            })
+            .await;
+
+        guilds
+    }
+
     /// Retrieves a [`Channel`]'s message from the cache based on the channel's and
     /// message's given Ids.
     ///
@@ -819,7 +1137,9 @@ impl Cache {
     }
 
     async fn _role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role> {
-        self.guilds.read().await.get(&guild_id).and_then(|g| g.roles.get(&role_id)).cloned()
+        let guilds = self.guilds.read(&guild_id).await;
+
+        guilds.get(&guild_id).and_then(|g| g.roles.get(&role_id)).cloned()
     }
 
     /// Returns the settings.
@@ -847,6 +1167,92 @@ impl Cache {
         self.settings.write().await.max_messages = max;
     }
 
+    /// Starts tracking a member chunk request for a guild sent with the given
+    /// nonce, e.g. via [`Shard::chunk_guild`].
+    ///
+    /// Once the chunks of all tracked requests were received, updating the
+    /// cache with the last [`GuildMembersChunkEvent`] returns the guilds that
+    /// were chunked. This is done automatically for the guilds selected by
+    /// [`ClientBuilder::chunk_guilds_filter`].
+    ///
+    /// [`Shard::chunk_guild`]: crate::gateway::Shard::chunk_guild
+    /// [`ClientBuilder::chunk_guilds_filter`]: crate::client::ClientBuilder::chunk_guilds_filter
+    pub async fn expect_member_chunks(&self, guild_id: GuildId, nonce: impl Into<String>) {
+        self.member_chunks.write().await.expect(guild_id, nonce.into());
+    }
+
+    /// Temporarily caches a user, and their member if seen in a guild, if
+    /// [`Settings::cache_users`] is enabled.
+    pub(crate) async fn temp_cache_user(&self, user: &User, member: Option<&Member>) {
+        let settings = self.settings.read().await;
+
+        if !settings.cache_users {
+            return;
+        }
+
+        self.temp_users.write().await.insert(user.id, user.clone(), settings.time_to_live);
+
+        if let Some(member) = member {
+            self.temp_members.write().await.insert(
+                (member.guild_id, member.user.id),
+                member.clone(),
+                settings.time_to_live,
+            );
+        }
+    }
+
+    /// Temporarily caches a channel if [`Settings::cache_channels`] is
+    /// enabled.
+    pub(crate) async fn temp_cache_channel(&self, channel: &GuildChannel) {
+        let settings = self.settings.read().await;
+
+        if settings.cache_channels {
+            self.temp_channels.write().await.insert(
+                channel.id,
+                channel.clone(),
+                settings.time_to_live,
+            );
+        }
+    }
+
+    /// Removes a channel from the temporary cache, e.g. once it was deleted.
+    pub(crate) async fn remove_temp_channel(&self, channel_id: ChannelId) {
+        self.temp_channels.write().await.remove(&channel_id);
+    }
+
+    /// Removes the given messages of a channel from the message cache,
+    /// returning those that were cached.
+    ///
+    /// Messages that were never cached or already evicted are skipped.
+    pub(crate) async fn remove_messages(
+        &self,
+        channel_id: ChannelId,
+        message_ids: &[MessageId],
+    ) -> Vec<Message> {
+        let mut messages_map = self.messages.write().await;
+        let messages = match messages_map.get_mut(&channel_id) {
+            Some(messages) => messages,
+            None => return Vec::new(),
+        };
+
+        let removed: Vec<Message> =
+            message_ids.iter().filter_map(|id| messages.remove(id)).collect();
+
+        if !removed.is_empty() {
+            if let Some(queue) = self.message_queue.write().await.get_mut(&channel_id) {
+                queue.retain(|id| !removed.iter().any(|message| message.id == *id));
+            }
+        }
+
+        removed
+    }
+
+    /// Removes all cached messages of a channel.
+    pub(crate) async fn remove_channel_messages(&self, channel_id: ChannelId) {
+        self.messages.write().await.remove(&channel_id);
+        self.message_queue.write().await.remove(&channel_id);
+    }
+
     /// Retrieves a [`User`] from the cache's [`Self::users`] map, if it exists.
     ///
     /// The only advantage of this method is that you can pass in anything that
@@ -876,7 +1282,13 @@ impl Cache {
     }
 
     async fn _user(&self, user_id: UserId) -> Option<User> {
-        self.users.read().await.get(&user_id).cloned()
+        if let Some(user) = self.users.read().await.get(&user_id) {
+            return Some(user.clone());
+        }
+
+        let ttl = self.settings.read().await.time_to_live;
+
+        self.temp_users.read().await.get(&user_id, ttl)
     }
 
     /// Clones all users and returns them.
@@ -920,11 +1332,40 @@ impl Cache {
     }
 
     /// This method clones and returns the user used by the bot.
+    ///
+    /// The current user is populated once the [`Event::Ready`] is received.
+    /// Before that, this returns a default [`CurrentUser`] with an Id of `0`.
     #[inline]
     pub async fn current_user(&self) -> CurrentUser {
         self.user.read().await.clone()
     }
 
+    /// Returns a reference to the user used by the bot, without cloning it like
+    /// [`Self::current_user`] does.
+    ///
+    /// The current user is populated once the [`Event::Ready`] is received.
+    /// Before that, the reference points to a default [`CurrentUser`] with an
+    /// Id of `0`.
+    ///
+    /// The reference holds a read lock on the current user, so it cannot be
+    /// updated until the reference is dropped. Do not hold it across an
+    /// `.await` or while calling other methods of the cache; see [`CacheRef`]
+    /// for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # async fn run() {
+    /// # let cache = Cache::default();
+    /// let is_bot = cache.current_user_ref().await.bot;
+    /// # }
+    /// ```
+    pub async fn current_user_ref(&self) -> CurrentUserRef<'_> {
+        CurrentUserRef::new(self.user.read().await)
+    }
+
     /// This method returns the bot's ID.
     #[inline]
     pub async fn current_user_id(&self) -> UserId {
@@ -961,11 +1402,60 @@ impl Cache {
     ///
     /// Refer to the documentation for [`CacheUpdate`] for more information.
     ///
+    /// Events are applied the same way the client applies them as they are
+    /// received, so a standalone cache can be rebuilt by replaying stored
+    /// events in the order they were received.
+    ///
     /// # Examples
     ///
-    /// Refer to the [`CacheUpdate` examples].
+    /// Replaying a stored `GUILD_CREATE` payload into a standalone cache:
+    ///
+    /// ```rust,no_run
+    /// use serenity::cache::{Cache, Settings};
+    /// use serenity::model::event::GuildCreateEvent;
+    /// use serenity::model::id::ChannelId;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let stored = r#"{
+    ///     "id": "1",
+    ///     "name": "guild",
+    ///     "owner_id": "3",
+    ///     "joined_at": "2021-08-01T00:00:00+00:00",
+    ///     "channels": [{"id": "2", "type": 0, "name": "general"}],
+    ///     "afk_timeout": 0,
+    ///     "default_message_notifications": 0,
+    ///     "explicit_content_filter": 0,
+    ///     "mfa_level": 0,
+    ///     "verification_level": 0,
+    ///     "nsfw": false,
+    ///     "nsfw_level": 0,
+    ///     "system_channel_flags": 0,
+    ///     "large": false,
+    ///     "member_count": 0,
+    ///     "region": "",
+    ///     "preferred_locale": "en-US",
+    ///     "emojis": [],
+    ///     "features": [],
+    ///     "members": [],
+    ///     "presences": [],
+    ///     "roles": [],
+    ///     "voice_states": []
+    /// }"#;
+    ///
+    /// let cache = Cache::new_with_settings(Settings::new());
+    /// let mut event: GuildCreateEvent = serde_json::from_str(stored)?;
+    /// cache.update(&mut event).await;
+    ///
+    /// if let Some(channel) = cache.guild_channel(ChannelId(2)).await {
+    ///     println!("Replayed channel: {}", channel.name);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// For custom update implementations, refer to the [`CacheUpdate`
+    /// examples].
     ///
-    /// [`CacheUpdate`]: CacheUpdate
     /// [`CacheUpdate` examples]: CacheUpdate#examples
     #[instrument(skip(self, e))]
     pub async fn update<E: CacheUpdate>(&self, e: &mut E) -> Option<E::Output> {
@@ -982,14 +1472,28 @@ impl Cache {
             },
         }
     }
+
+    /// Drops the data of a received guild that the settings disable caching
+    /// of.
+    pub(crate) async fn strip_disabled_collections(&self, guild: &mut Guild) {
+        let settings = self.settings.read().await;
+
+        if !settings.cache_presences {
+            guild.presences = HashMap::new();
+        }
+
+        if !settings.cache_voice_states {
+            guild.voice_states = HashMap::new();
+        }
+    }
 }
 
 impl Default for Cache {
     fn default() -> Cache {
         Cache {
-            channels: RwLock::new(HashMap::default()),
+            channels: ShardedMap::default(),
             categories: RwLock::new(HashMap::default()),
-            guilds: RwLock::new(HashMap::default()),
+            guilds: ShardedMap::default(),
             messages: RwLock::new(HashMap::default()),
             presences: RwLock::new(HashMap::default()),
             private_channels: RwLock::new(HashMap::with_capacity(128)),
@@ -999,6 +1503,10 @@ impl Default for Cache {
             user: RwLock::new(CurrentUser::default()),
             users: RwLock::new(HashMap::default()),
             message_queue: RwLock::new(HashMap::default()),
+            temp_users: RwLock::new(TtlMap::default()),
+            temp_members: RwLock::new(TtlMap::default()),
+            temp_channels: RwLock::new(TtlMap::default()),
+            member_chunks: RwLock::new(MemberChunks::default()),
         }
     }
 }
@@ -1006,15 +1514,688 @@ impl Default for Cache {
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     use chrono::{DateTime, Utc};
     use serde_json::{Number, Value};
+    #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+    use tokio::time::delay_for as sleep;
+    #[cfg(feature = "tokio")]
+    use tokio::time::sleep;
 
     use crate::{
-        cache::{Cache, CacheUpdate, Settings},
+        cache::{Cache, CacheStats, CacheUpdate, Settings},
         model::prelude::*,
     };
 
+    #[allow(deprecated)]
+    fn guild(
+        id: u64,
+        channels: HashMap<ChannelId, GuildChannel>,
+        joined_at: DateTime<Utc>,
+    ) -> Guild {
+        Guild {
+            id: GuildId(id),
+            afk_channel_id: None,
+            afk_timeout: 0,
+            application_id: None,
+            default_message_notifications: DefaultMessageNotificationLevel::All,
+            emojis: HashMap::new(),
+            explicit_content_filter: ExplicitContentFilter::None,
+            features: vec![],
+            icon: None,
+            joined_at,
+            large: false,
+            member_count: 0,
+            members: HashMap::new(),
+            mfa_level: MfaLevel::None,
+            name: String::new(),
+            owner_id: UserId(3),
+            presences: HashMap::new(),
+            region: String::new(),
+            roles: HashMap::new(),
+            splash: None,
+            discovery_splash: None,
+            system_channel_id: None,
+            system_channel_flags: Default::default(),
+            rules_channel_id: None,
+            public_updates_channel_id: None,
+            verification_level: VerificationLevel::Low,
+            voice_states: HashMap::new(),
+            description: None,
+            premium_tier: PremiumTier::Tier0,
+            channels,
+            premium_subscription_count: 0,
+            banner: None,
+            vanity_url_code: Some("bruhmoment".to_string()),
+            preferred_locale: "en-US".to_string(),
+            welcome_screen: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            nsfw: false,
+            nsfw_level: NsfwLevel::Default,
+            max_video_channel_users: None,
+            max_presences: None,
+            max_members: None,
+            widget_enabled: Some(false),
+            widget_channel_id: None,
+            stage_instances: vec![],
+            threads: vec![],
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn message_create(id: u64) -> MessageCreateEvent {
+        let message = serde_json::json!({
+            "id": id.to_string(),
+            "channel_id": "2",
+            "author": {"id": "3", "username": "user", "discriminator": "0001", "avatar": null},
+            "content": "",
+            "timestamp": "2021-08-01T00:00:00+00:00",
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        });
+
+        MessageCreateEvent {
+            message: serde_json::from_value(message).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_channel_messages() {
+        let mut settings = Settings::new();
+        settings.max_messages(3);
+        let cache = Cache::new_with_settings(settings);
+
+        assert!(cache.channel_messages(ChannelId(2)).await.is_none());
+
+        let ids = |messages: Vec<Message>| messages.iter().map(|m| m.id.0).collect::<Vec<_>>();
+
+        // Messages delivered out of order are returned in Id order.
+        for id in &[3, 1, 2] {
+            cache.update(&mut message_create(*id)).await;
+        }
+        assert_eq!(ids(cache.channel_messages(ChannelId(2)).await.unwrap()), vec![1, 2, 3]);
+
+        // Eviction removes the message that was cached first, not the oldest.
+        let evicted = cache.update(&mut message_create(0)).await.unwrap();
+        assert_eq!(evicted.id, MessageId(3));
+        assert_eq!(ids(ChannelId(2).messages_cached(&cache).await.unwrap()), vec![0, 1, 2]);
+
+        cache.update(&mut message_create(4)).await;
+        assert_eq!(ids(cache.channel_messages(ChannelId(2)).await.unwrap()), vec![0, 2, 4]);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_cache_messages_eviction() {
+        let mut settings = Settings::new();
+        settings.max_messages(5);
+        let cache = Cache::new_with_settings(settings);
+
+        // Insert 10 messages more than the limit.
+        for id in 1..=15 {
+            let evicted = cache.update(&mut message_create(id)).await;
+
+            if id > 5 {
+                assert_eq!(evicted.unwrap().id, MessageId(id - 5));
+            } else {
+                assert!(evicted.is_none());
+            }
+        }
+
+        let cached_ids = |cache: &Cache| {
+            let cache = cache.messages.try_read().unwrap();
+            let mut ids = cache[&ChannelId(2)].keys().map(|id| id.0).collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids
+        };
+        assert_eq!(cached_ids(&cache), vec![11, 12, 13, 14, 15]);
+        assert!(cache.message(ChannelId(2), MessageId(10)).await.is_none());
+        assert_eq!(cache.message(ChannelId(2), MessageId(11)).await.unwrap().id, MessageId(11));
+
+        // Deleting an evicted message is a no-op.
+        let mut delete = MessageDeleteEvent {
+            guild_id: None,
+            channel_id: ChannelId(2),
+            message_id: MessageId(1),
+        };
+        assert!(cache.update(&mut delete).await.is_none());
+
+        // Deleting a cached message frees up its place.
+        delete.message_id = MessageId(12);
+        assert_eq!(cache.update(&mut delete).await.unwrap().id, MessageId(12));
+        assert!(cache.update(&mut message_create(16)).await.is_none());
+        assert_eq!(cached_ids(&cache), vec![11, 13, 14, 15, 16]);
+
+        // Updating an evicted message is a no-op as well.
+        let mut update = serde_json::from_value::<MessageUpdateEvent>(serde_json::json!({
+            "id": "3",
+            "channel_id": "2",
+            "content": "edited",
+        }))
+        .unwrap();
+        assert!(cache.update(&mut update).await.is_none());
+
+        let mut delete_bulk = MessageDeleteBulkEvent {
+            guild_id: None,
+            channel_id: ChannelId(2),
+            ids: vec![MessageId(2), MessageId(11), MessageId(13)],
+        };
+        assert_eq!(cache.update(&mut delete_bulk).await.unwrap().len(), 2);
+        assert_eq!(cached_ids(&cache), vec![14, 15, 16]);
+
+        // The oldest remaining message is evicted next.
+        for id in 17..=19 {
+            cache.update(&mut message_create(id)).await;
+        }
+        assert_eq!(cached_ids(&cache), vec![15, 16, 17, 18, 19]);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_statistics() {
+        let mut settings = Settings::new();
+        settings.max_messages(5);
+        let cache = Cache::new_with_settings(settings);
+
+        assert_eq!(cache.statistics().await, CacheStats {
+            max_messages: 5,
+            ..CacheStats::default()
+        });
+
+        let channel: GuildChannel = serde_json::from_value(serde_json::json!({
+            "id": "2",
+            "guild_id": "1",
+            "type": 0,
+            "name": "general",
+        }))
+        .unwrap();
+        let member: Member = serde_json::from_value(serde_json::json!({
+            "user": {"id": "3", "username": "user", "discriminator": "0001", "avatar": null},
+            "guild_id": "1",
+            "roles": [],
+            "joined_at": null,
+            "deaf": false,
+            "mute": false,
+        }))
+        .unwrap();
+
+        let mut channels = HashMap::new();
+        channels.insert(channel.id, channel);
+        let mut guild = guild(1, channels, "2021-08-01T00:00:00Z".parse().unwrap());
+        guild.member_count = 10;
+        guild.members.insert(member.user.id, member);
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+        cache
+            .update(&mut GuildUnavailableEvent {
+                guild_id: GuildId(4),
+            })
+            .await;
+
+        for id in 5..=6 {
+            let mut message = message_create(id);
+            message.message.guild_id = Some(GuildId(1));
+            cache.update(&mut message).await;
+        }
+
+        let presence: Presence = serde_json::from_value(serde_json::json!({
+            "user": {"id": "3"},
+            "status": "online",
+        }))
+        .unwrap();
+        cache
+            .update(&mut PresenceUpdateEvent {
+                guild_id: Some(GuildId(1)),
+                presence,
+            })
+            .await;
+
+        let stats = cache.statistics().await;
+        assert_eq!(stats, CacheStats {
+            guilds: 1,
+            unavailable_guilds: 1,
+            channels: 1,
+            categories: 0,
+            private_channels: 0,
+            users: 1,
+            members: 1,
+            messages: 2,
+            presences: 1,
+            voice_states: 0,
+            max_messages: 5,
+        });
+        assert!(stats.approximate_size() > 0);
+
+        assert_eq!(cache.guild_count().await, 1);
+        assert_eq!(cache.member_count().await, 1);
+        assert_eq!(cache.message_count().await, 2);
+        assert_eq!(cache.unknown_members().await, 9);
+        assert_eq!(cache.total_cached_member_count().await, 1);
+        assert_eq!(cache.guild_channels_count().await, 1);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_member_chunks() {
+        let chunk = |guild_id: u64, user_ids: &[u64], index: u32, count: u32| {
+            let members = user_ids
+                .iter()
+                .map(|id| {
+                    serde_json::json!({
+                        "user": {
+                            "id": id.to_string(),
+                            "username": "user",
+                            "discriminator": "0001",
+                            "avatar": null,
+                        },
+                        "roles": [],
+                        "joined_at": null,
+                        "deaf": false,
+                        "mute": false,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            serde_json::from_value::<GuildMembersChunkEvent>(serde_json::json!({
+                "guild_id": guild_id.to_string(),
+                "members": members,
+                "chunk_index": index,
+                "chunk_count": count,
+                "nonce": format!("chunk-{}", guild_id),
+            }))
+            .unwrap()
+        };
+
+        let cache = Cache::default();
+        let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+
+        for id in 1..=2 {
+            cache.guilds.insert(GuildId(id), guild(id, HashMap::new(), joined_at)).await;
+            cache.expect_member_chunks(GuildId(id), format!("chunk-{}", id)).await;
+        }
+
+        // Chunks of untracked requests are cached, but do not count.
+        let mut untracked = chunk(1, &[10], 0, 1);
+        untracked.nonce = None;
+        assert!(cache.update(&mut untracked).await.is_none());
+
+        assert!(cache.update(&mut chunk(1, &[11, 12], 1, 2)).await.is_none());
+        assert!(cache.update(&mut chunk(2, &[20], 0, 1)).await.is_none());
+        // A duplicate chunk does not complete the guild.
+        assert!(cache.update(&mut chunk(1, &[11, 12], 1, 2)).await.is_none());
+        assert_eq!(cache.guild(GuildId(1)).await.unwrap().members.len(), 3);
+
+        let mut chunked = cache.update(&mut chunk(1, &[13], 0, 2)).await.unwrap();
+        chunked.sort();
+        assert_eq!(chunked, vec![GuildId(1), GuildId(2)]);
+        assert_eq!(cache.guild(GuildId(1)).await.unwrap().members.len(), 4);
+        assert_eq!(cache.guild(GuildId(2)).await.unwrap().members.len(), 1);
+
+        // Guilds requested later are reported on their own.
+        cache.expect_member_chunks(GuildId(2), "chunk-2").await;
+        assert_eq!(cache.update(&mut chunk(2, &[21], 0, 1)).await.unwrap(), vec![GuildId(2)]);
+        assert_eq!(cache.guild(GuildId(2)).await.unwrap().members.len(), 2);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_disabled_collections() {
+        async fn run(settings: Settings) -> Cache {
+            let cache = Cache::new_with_settings(settings);
+            let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+
+            let mut guild = guild(1, HashMap::new(), joined_at);
+            let presence: Presence = serde_json::from_value(serde_json::json!({
+                "user": {"id": "3"},
+                "status": "online",
+            }))
+            .unwrap();
+            guild.presences.insert(presence.user_id, presence);
+            cache
+                .update(&mut GuildCreateEvent {
+                    guild,
+                })
+                .await;
+
+            for guild_id in &[Some(GuildId(1)), None] {
+                let presence: Presence = serde_json::from_value(serde_json::json!({
+                    "user": {
+                        "id": "4",
+                        "username": "user",
+                        "discriminator": "0002",
+                        "avatar": null,
+                    },
+                    "status": "idle",
+                }))
+                .unwrap();
+                cache
+                    .update(&mut PresenceUpdateEvent {
+                        guild_id: *guild_id,
+                        presence,
+                    })
+                    .await;
+            }
+
+            let mut voice_state: VoiceStateUpdateEvent =
+                serde_json::from_value(serde_json::json!({
+                    "channel_id": "2",
+                    "deaf": false,
+                    "guild_id": "1",
+                    "mute": false,
+                    "self_deaf": false,
+                    "self_mute": false,
+                    "self_video": false,
+                    "session_id": "session",
+                    "suppress": false,
+                    "user_id": "3",
+                }))
+                .unwrap();
+            cache.update(&mut voice_state).await;
+
+            let mut message = message_create(5);
+            message.message.guild_id = Some(GuildId(1));
+            cache.update(&mut message).await;
+
+            cache
+        }
+
+        let mut settings = Settings::new();
+        settings.max_messages(5);
+        let cache = run(settings.clone()).await;
+        let stats = cache.statistics().await;
+        assert_eq!((stats.presences, stats.voice_states, stats.messages), (3, 1, 1));
+
+        settings.cache_presences(false).cache_voice_states(false).cache_messages(false);
+        let cache = run(settings).await;
+        let stats = cache.statistics().await;
+        assert_eq!((stats.presences, stats.voice_states, stats.messages), (0, 0, 0));
+        assert_eq!(stats.guilds, 1);
+        assert!(cache.message(ChannelId(2), MessageId(5)).await.is_none());
+        // The user of the presence update is cached either way.
+        assert!(cache.user(UserId(4)).await.is_some());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_refs() {
+        let cache = Cache::default();
+        let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+
+        for id in 1..=2 {
+            cache.guilds.insert(GuildId(id), guild(id, HashMap::new(), joined_at)).await;
+        }
+
+        assert_eq!(cache.guild_ref(GuildId(1)).await.unwrap().id, GuildId(1));
+        assert!(cache.guild_ref(GuildId(3)).await.is_none());
+        assert!(cache.guild_channel_ref(ChannelId(1)).await.is_none());
+
+        let mut ids = Vec::new();
+        cache.guilds_iter(|guild| ids.push(guild.id)).await;
+        ids.sort();
+        assert_eq!(ids, vec![GuildId(1), GuildId(2)]);
+
+        // Updates go through once the reference is dropped.
+        drop(cache.guild_ref(GuildId(1)).await);
+        cache.guilds.remove(&GuildId(1)).await;
+        assert!(cache.guild_ref(GuildId(1)).await.is_none());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_unavailable_guilds() {
+        let cache = Cache::default();
+        let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+
+        for id in 1..=3 {
+            cache
+                .update(&mut GuildCreateEvent {
+                    guild: guild(id, HashMap::new(), joined_at),
+                })
+                .await;
+        }
+
+        // Guild 1 is left, guild 2 goes through an outage.
+        for (id, unavailable) in &[(1, false), (2, true)] {
+            let mut event = GuildDeleteEvent {
+                guild: serde_json::from_value(serde_json::json!({
+                    "id": id.to_string(),
+                    "unavailable": unavailable,
+                }))
+                .unwrap(),
+            };
+            assert!(cache.update(&mut event).await.is_some());
+        }
+
+        let mut event = GuildUnavailableEvent {
+            guild_id: GuildId(4),
+        };
+        cache.update(&mut event).await;
+
+        assert_eq!(cache.unavailable_guild_count().await, 2);
+        assert!(!cache.is_guild_unavailable(GuildId(1)).await);
+        assert!(cache.is_guild_unavailable(GuildId(2)).await);
+        assert!(!cache.is_guild_unavailable(GuildId(3)).await);
+        assert!(cache.is_guild_unavailable(GuildId(4)).await);
+
+        // The guild is available again once it is received.
+        cache
+            .update(&mut GuildCreateEvent {
+                guild: guild(2, HashMap::new(), joined_at),
+            })
+            .await;
+        assert!(!cache.is_guild_unavailable(GuildId(2)).await);
+        assert_eq!(cache.unavailable_guild_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_current_user_ref() {
+        let cache = Cache::default();
+        assert_eq!(cache.current_user_ref().await.id, UserId(0));
+
+        cache.user.write().await.id = UserId(1);
+        assert_eq!(cache.current_user_ref().await.id, UserId(1));
+        assert_eq!(cache.current_user().await.id, UserId(1));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_replay_events() {
+        let cache = Cache::new_with_settings(Settings::new());
+        let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+        let channel = |id: u64, name: &str| -> Channel {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "guild_id": "1",
+                "type": 0,
+                "name": name,
+            }))
+            .unwrap()
+        };
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild: guild(1, HashMap::new(), joined_at),
+            })
+            .await;
+        cache
+            .update(&mut ChannelCreateEvent {
+                channel: channel(2, "general"),
+            })
+            .await;
+        cache
+            .update(&mut ChannelCreateEvent {
+                channel: channel(3, "other"),
+            })
+            .await;
+        cache
+            .update(&mut ChannelUpdateEvent {
+                channel: channel(2, "renamed"),
+            })
+            .await;
+        cache
+            .update(&mut ChannelDeleteEvent {
+                channel: channel(3, "other"),
+            })
+            .await;
+
+        assert_eq!(cache.guild_channel(ChannelId(2)).await.unwrap().name, "renamed");
+        assert!(cache.guild_channel(ChannelId(3)).await.is_none());
+
+        let guild = cache.guild(GuildId(1)).await.unwrap();
+        assert_eq!(guild.channels.keys().collect::<Vec<_>>(), vec![&ChannelId(2)]);
+        assert_eq!(guild.channels[&ChannelId(2)].name, "renamed");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_concurrent_access() {
+        let cache = Arc::new(Cache::default());
+        let joined_at: DateTime<Utc> = "2021-08-01T00:00:00Z".parse().unwrap();
+
+        let writer = {
+            let cache = Arc::clone(&cache);
+
+            tokio::spawn(async move {
+                for id in 1..=100 {
+                    let mut event = GuildCreateEvent {
+                        guild: guild(id, HashMap::new(), joined_at),
+                    };
+
+                    cache.update(&mut event).await;
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        let readers = (0..4)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+
+                tokio::spawn(async move {
+                    for id in 1..=100 {
+                        // A guild is either not cached yet or cached as a whole.
+                        if let Some(guild) = cache.guild(GuildId(id)).await {
+                            assert_eq!(guild.id, GuildId(id));
+                        }
+
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        writer.await.unwrap();
+
+        for reader in readers {
+            reader.await.unwrap();
+        }
+
+        assert_eq!(cache.guild_count().await, 100);
+        assert_eq!(cache.guilds().await.len(), 100);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_channel_by_name() {
+        let cache = Cache::default();
+        let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+
+        for (guild_id, channel_id, name) in &[(1, 10, "bot-log"), (2, 20, "general")] {
+            let channel: GuildChannel = serde_json::from_value(serde_json::json!({
+                "id": channel_id.to_string(),
+                "guild_id": guild_id.to_string(),
+                "type": 0,
+                "name": name,
+            }))
+            .unwrap();
+            let mut channels = HashMap::new();
+            channels.insert(channel.id, channel);
+
+            cache
+                .update(&mut GuildCreateEvent {
+                    guild: guild(*guild_id, channels, joined_at),
+                })
+                .await;
+        }
+
+        assert_eq!(cache.channel_by_name("bot-log").await.unwrap().id, ChannelId(10));
+        assert!(cache.channel_by_name("#bot-log").await.is_none());
+        let channel = cache.guild_channel_by_name(GuildId(2), "general").await.unwrap();
+        assert_eq!(channel.id, ChannelId(20));
+        assert!(cache.guild_channel_by_name(GuildId(1), "general").await.is_none());
+    }
+
+    #[cfg(feature = "utils")]
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_guilds_on_shard() {
+        let cache = Cache::default();
+        let joined_at = "2021-08-01T00:00:00Z".parse().unwrap();
+
+        // Guild `n << 22` belongs to shard `n % shard_count`.
+        for n in 1..=5 {
+            let id = n << 22;
+            cache.guilds.insert(GuildId(id), guild(id, HashMap::new(), joined_at)).await;
+        }
+
+        assert_eq!(cache.guilds_on_shard(0).await.len(), 5);
+
+        *cache.shard_count.write().await = 3;
+        let mut guild_ids = cache.guilds_on_shard(1).await;
+        guild_ids.sort();
+        assert_eq!(guild_ids, vec![GuildId(1 << 22), GuildId(4 << 22)]);
+        assert!(cache.guilds_on_shard(3).await.is_empty());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_temp_cache_users() {
+        let mut event = message_create(1);
+        event.message.guild_id = Some(GuildId(1));
+        event.message.member =
+            Some(serde_json::from_value(serde_json::json!({"roles": [], "nick": "nick"})).unwrap());
+
+        // Nothing is cached temporarily by default.
+        let cache = Cache::default();
+        cache.update(&mut event).await;
+        assert!(cache.user(UserId(3)).await.is_none());
+
+        let mut settings = Settings::new();
+        settings.cache_users(true).time_to_live(Duration::from_millis(50));
+        let cache = Cache::new_with_settings(settings);
+
+        cache.update(&mut event).await;
+        assert_eq!(cache.user(UserId(3)).await.unwrap().name, "user");
+        let member = cache.member(GuildId(1), UserId(3)).await.unwrap();
+        assert_eq!(member.nick.as_deref(), Some("nick"));
+        assert_eq!(member.user.id, UserId(3));
+
+        // The permanent cache always wins.
+        let mut user = cache.user(UserId(3)).await.unwrap();
+        user.name = "permanent".to_string();
+        cache.users.write().await.insert(user.id, user);
+        assert_eq!(cache.user(UserId(3)).await.unwrap().name, "permanent");
+        cache.users.write().await.remove(&UserId(3));
+
+        sleep(Duration::from_millis(60)).await;
+        assert!(cache.user(UserId(3)).await.is_none());
+        assert!(cache.member(GuildId(1), UserId(3)).await.is_none());
+    }
+
     #[tokio::test]
     #[allow(clippy::unwrap_used)]
     async fn test_cache_messages() {
@@ -1134,61 +2315,139 @@ mod test {
             let mut channels = HashMap::new();
             channels.insert(ChannelId(2), guild_channel.clone());
 
-            #[allow(deprecated)]
             GuildCreateEvent {
-                guild: Guild {
-                    id: GuildId(1),
-                    afk_channel_id: None,
-                    afk_timeout: 0,
-                    application_id: None,
-                    default_message_notifications: DefaultMessageNotificationLevel::All,
-                    emojis: HashMap::new(),
-                    explicit_content_filter: ExplicitContentFilter::None,
-                    features: vec![],
-                    icon: None,
-                    joined_at: datetime,
-                    large: false,
-                    member_count: 0,
-                    members: HashMap::new(),
-                    mfa_level: MfaLevel::None,
-                    name: String::new(),
-                    owner_id: UserId(3),
-                    presences: HashMap::new(),
-                    region: String::new(),
-                    roles: HashMap::new(),
-                    splash: None,
-                    discovery_splash: None,
-                    system_channel_id: None,
-                    system_channel_flags: Default::default(),
-                    rules_channel_id: None,
-                    public_updates_channel_id: None,
-                    verification_level: VerificationLevel::Low,
-                    voice_states: HashMap::new(),
-                    description: None,
-                    premium_tier: PremiumTier::Tier0,
-                    channels,
-                    premium_subscription_count: 0,
-                    banner: None,
-                    vanity_url_code: Some("bruhmoment".to_string()),
-                    preferred_locale: "en-US".to_string(),
-                    welcome_screen: None,
-                    approximate_member_count: None,
-                    approximate_presence_count: None,
-                    nsfw: false,
-                    nsfw_level: NsfwLevel::Default,
-                    max_video_channel_users: None,
-                    max_presences: None,
-                    max_members: None,
-                    widget_enabled: Some(false),
-                    widget_channel_id: None,
-                    stage_instances: vec![],
-                    threads: vec![],
-                },
+                guild: guild(1, channels, datetime),
             }
         };
         assert!(cache.update(&mut guild_create).await.is_none());
         assert!(cache.update(&mut event).await.is_none());
 
+        // Updates return what they replaced.
+        let mut renamed_channel = guild_channel.clone();
+        renamed_channel.name = "renamed".to_string();
+        let mut channel_update = ChannelUpdateEvent {
+            channel: Channel::Guild(renamed_channel),
+        };
+        match cache.update(&mut channel_update).await {
+            Some(Channel::Guild(old)) => assert_eq!(old.name, ""),
+            other => panic!("unexpected channel: {:?}", other),
+        }
+        match cache.update(&mut channel_update).await {
+            Some(Channel::Guild(old)) => assert_eq!(old.name, "renamed"),
+            other => panic!("unexpected channel: {:?}", other),
+        }
+
+        let mut guild_update = GuildUpdateEvent {
+            guild: serde_json::from_value(serde_json::json!({
+                "id": "1",
+                "name": "renamed",
+                "afk_timeout": 0,
+                "default_message_notifications": 0,
+                "emojis": [],
+                "features": [],
+                "mfa_level": 0,
+                "owner_id": "3",
+                "region": "",
+                "roles": [],
+                "verification_level": 0,
+                "nsfw": false,
+                "nsfw_level": 0,
+                "system_channel_flags": 0,
+            }))
+            .unwrap(),
+        };
+        assert_eq!(cache.update(&mut guild_update).await.unwrap().name, "");
+        assert_eq!(cache.update(&mut guild_update).await.unwrap().name, "renamed");
+
+        let presence = |status: &str| -> Presence {
+            serde_json::from_value(serde_json::json!({
+                "user": {"id": "3"},
+                "status": status,
+            }))
+            .unwrap()
+        };
+        let mut presence_update = PresenceUpdateEvent {
+            guild_id: Some(GuildId(1)),
+            presence: presence("online"),
+        };
+        assert!(cache.update(&mut presence_update).await.is_none());
+        presence_update.presence = presence("idle");
+        assert_eq!(cache.update(&mut presence_update).await.unwrap().status, OnlineStatus::Online);
+
+        let role = |name: &str| -> Role {
+            serde_json::from_value(serde_json::json!({
+                "id": "4",
+                "guild_id": "1",
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "name": name,
+                "permissions": "0",
+                "position": 1,
+            }))
+            .unwrap()
+        };
+        let mut role_create = GuildRoleCreateEvent {
+            guild_id: GuildId(1),
+            role: role("old"),
+        };
+        cache.update(&mut role_create).await;
+        let mut role_update = GuildRoleUpdateEvent {
+            guild_id: GuildId(1),
+            role: role("new"),
+        };
+        assert_eq!(cache.update(&mut role_update).await.unwrap().name, "old");
+
+        let mut member_update = GuildMemberUpdateEvent {
+            guild_id: GuildId(1),
+            nick: Some("old".to_string()),
+            joined_at: datetime,
+            roles: vec![],
+            user: event.message.author.clone(),
+            premium_since: None,
+            pending: false,
+            deaf: false,
+            mute: false,
+            avatar: None,
+        };
+        assert!(cache.update(&mut member_update).await.is_none());
+        member_update.nick = Some("new".to_string());
+        let old = cache.update(&mut member_update).await.unwrap();
+        assert_eq!(old.nick.as_deref(), Some("old"));
+
+        let mut voice_state_update = VoiceStateUpdateEvent {
+            guild_id: Some(GuildId(1)),
+            voice_state: VoiceState {
+                channel_id: Some(ChannelId(10)),
+                deaf: false,
+                guild_id: Some(GuildId(1)),
+                member: None,
+                mute: false,
+                self_deaf: false,
+                self_mute: false,
+                self_stream: None,
+                self_video: false,
+                session_id: String::new(),
+                suppress: false,
+                token: None,
+                user_id: UserId(2),
+                request_to_speak_timestamp: None,
+            },
+        };
+        assert!(cache.update(&mut voice_state_update).await.is_none());
+        voice_state_update.voice_state.channel_id = Some(ChannelId(11));
+        let old = cache.update(&mut voice_state_update).await.unwrap();
+        assert_eq!(old.channel_id, Some(ChannelId(10)));
+
+        let mut message_update = serde_json::from_value::<MessageUpdateEvent>(serde_json::json!({
+            "id": "5",
+            "channel_id": "2",
+            "content": "edited",
+        }))
+        .unwrap();
+        assert_eq!(cache.update(&mut message_update).await.unwrap().content, "");
+        assert_eq!(cache.update(&mut message_update).await.unwrap().content, "edited");
+
         let mut guild_delete = GuildDeleteEvent {
             guild: GuildUnavailable {
                 id: GuildId(1),
@@ -0,0 +1,100 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    ops::Deref,
+};
+
+use tokio::sync::RwLockReadGuard;
+
+use crate::model::prelude::*;
+
+/// A reference to a value in the cache, which holds a read lock on the part of
+/// the map the value is cached in until it is dropped.
+///
+/// This avoids cloning the value, at the cost of blocking updates of the values
+/// sharing that part of the map while the reference is alive. Keep it
+/// short-lived: do not hold it across an `.await`, and do not call other
+/// [`Cache`] methods while holding it. The locks of the cache are fair, so a
+/// pending update blocks new reads of the same part, and such a read would wait
+/// for the update, which in turn waits for the reference to be dropped.
+///
+/// [`Cache`]: super::Cache
+#[must_use = "the reference blocks cache updates until it is dropped"]
+pub struct CacheRef<'a, V> {
+    value: RwLockReadGuard<'a, V>,
+}
+
+impl<'a, V> CacheRef<'a, V> {
+    /// Returns a reference to the value of the key, if the map contains it.
+    ///
+    /// The value is looked up once, the reference then keeps the lock of the
+    /// whole map.
+    pub(crate) fn new<K: Eq + Hash>(
+        map: RwLockReadGuard<'a, HashMap<K, V>>,
+        key: &K,
+    ) -> Option<Self> {
+        RwLockReadGuard::try_map(map, |map| map.get(key)).ok().map(|value| Self {
+            value,
+        })
+    }
+}
+
+impl<V> Deref for CacheRef<'_, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.value
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for CacheRef<'_, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// A reference to a cached [`Guild`], returned by [`Cache::guild_ref`].
+///
+/// [`Cache::guild_ref`]: super::Cache::guild_ref
+pub type GuildRef<'a> = CacheRef<'a, Guild>;
+
+/// A reference to a cached [`GuildChannel`], returned by
+/// [`Cache::guild_channel_ref`].
+///
+/// [`Cache::guild_channel_ref`]: super::Cache::guild_channel_ref
+pub type GuildChannelRef<'a> = CacheRef<'a, GuildChannel>;
+
+/// A reference to the cached [`CurrentUser`], returned by
+/// [`Cache::current_user_ref`].
+///
+/// Like a [`CacheRef`], it holds a read lock until it is dropped, which blocks
+/// updates of the current user.
+///
+/// [`Cache::current_user_ref`]: super::Cache::current_user_ref
+#[must_use = "the reference blocks cache updates until it is dropped"]
+pub struct CurrentUserRef<'a> {
+    user: RwLockReadGuard<'a, CurrentUser>,
+}
+
+impl<'a> CurrentUserRef<'a> {
+    pub(crate) fn new(user: RwLockReadGuard<'a, CurrentUser>) -> Self {
+        Self {
+            user,
+        }
+    }
+}
+
+impl Deref for CurrentUserRef<'_> {
+    type Target = CurrentUser;
+
+    fn deref(&self) -> &CurrentUser {
+        &self.user
+    }
+}
+
+impl fmt::Debug for CurrentUserRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Settings for the cache.
 ///
 /// # Examples
@@ -15,14 +17,74 @@
 pub struct Settings {
     /// The maximum number of messages to store in a channel's message cache.
     ///
+    /// Once a channel's cache is full, the message that was cached first is
+    /// evicted for every new message.
+    ///
     /// Defaults to 0.
     pub max_messages: usize,
+    /// How long users, members and channels are kept in the temporary cache.
+    ///
+    /// Defaults to 1 hour.
+    pub time_to_live: Duration,
+    /// Whether to temporarily cache the users and members seen in messages and
+    /// interactions, for when they are not in the cache otherwise, e.g.
+    /// because the `GUILD_MEMBERS` intent is disabled.
+    ///
+    /// They are kept for [`Self::time_to_live`], and returned by
+    /// [`Cache::user`] and [`Cache::member`] if the user or member is not
+    /// cached permanently.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Cache::user`]: super::Cache::user
+    /// [`Cache::member`]: super::Cache::member
+    pub cache_users: bool,
+    /// Whether to temporarily cache the threads seen in events, which are not
+    /// cached otherwise.
+    ///
+    /// They are kept for [`Self::time_to_live`], and returned by
+    /// [`Cache::guild_channel`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Cache::guild_channel`]: super::Cache::guild_channel
+    pub cache_channels: bool,
+    /// Whether to cache presences, both those of guild members and those
+    /// received outside of guilds.
+    ///
+    /// Disabling this saves memory for bots in many guilds. Presence events
+    /// are still dispatched, but the cache returns no previous presence for
+    /// them.
+    ///
+    /// Defaults to `true`.
+    pub cache_presences: bool,
+    /// Whether to cache the voice states of guilds.
+    ///
+    /// If disabled, [`Guild::voice_states`] of cached guilds is always empty.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// [`Guild::voice_states`]: crate::model::guild::Guild::voice_states
+    pub cache_voice_states: bool,
+    /// Whether to cache messages.
+    ///
+    /// Messages are only cached if this is enabled and [`Self::max_messages`]
+    /// is not 0.
+    ///
+    /// Defaults to `true`.
+    pub cache_messages: bool,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Settings {
             max_messages: usize::default(),
+            time_to_live: Duration::from_secs(60 * 60),
+            cache_users: false,
+            cache_channels: false,
+            cache_presences: true,
+            cache_voice_states: true,
+            cache_messages: true,
         }
     }
 }
@@ -55,4 +117,96 @@ impl Settings {
 
         self
     }
+
+    /// Sets how long users, members and channels are kept in the temporary
+    /// cache.
+    ///
+    /// Refer to [`time_to_live`] for more information.
+    ///
+    /// [`time_to_live`]: #structfield.time_to_live
+    pub fn time_to_live(&mut self, time_to_live: Duration) -> &mut Self {
+        self.time_to_live = time_to_live;
+
+        self
+    }
+
+    /// Sets whether to temporarily cache the users and members seen in
+    /// messages and interactions.
+    ///
+    /// Refer to [`cache_users`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Keep the users that sent a message for 10 minutes:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use serenity::cache::Settings;
+    ///
+    /// let mut settings = Settings::new();
+    /// settings.cache_users(true).time_to_live(Duration::from_secs(600));
+    /// ```
+    ///
+    /// [`cache_users`]: #structfield.cache_users
+    pub fn cache_users(&mut self, cache_users: bool) -> &mut Self {
+        self.cache_users = cache_users;
+
+        self
+    }
+
+    /// Sets whether to temporarily cache the threads seen in events.
+    ///
+    /// Refer to [`cache_channels`] for more information.
+    ///
+    /// [`cache_channels`]: #structfield.cache_channels
+    pub fn cache_channels(&mut self, cache_channels: bool) -> &mut Self {
+        self.cache_channels = cache_channels;
+
+        self
+    }
+
+    /// Sets whether to cache presences.
+    ///
+    /// Refer to [`cache_presences`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Only cache guilds, channels, roles and members:
+    ///
+    /// ```rust
+    /// use serenity::cache::Settings;
+    ///
+    /// let mut settings = Settings::new();
+    /// settings.cache_presences(false).cache_voice_states(false).cache_messages(false);
+    /// ```
+    ///
+    /// [`cache_presences`]: #structfield.cache_presences
+    pub fn cache_presences(&mut self, cache_presences: bool) -> &mut Self {
+        self.cache_presences = cache_presences;
+
+        self
+    }
+
+    /// Sets whether to cache the voice states of guilds.
+    ///
+    /// Refer to [`cache_voice_states`] for more information.
+    ///
+    /// [`cache_voice_states`]: #structfield.cache_voice_states
+    pub fn cache_voice_states(&mut self, cache_voice_states: bool) -> &mut Self {
+        self.cache_voice_states = cache_voice_states;
+
+        self
+    }
+
+    /// Sets whether to cache messages.
+    ///
+    /// Refer to [`cache_messages`] for more information.
+    ///
+    /// [`cache_messages`]: #structfield.cache_messages
+    pub fn cache_messages(&mut self, cache_messages: bool) -> &mut Self {
+        self.cache_messages = cache_messages;
+
+        self
+    }
 }
@@ -0,0 +1,112 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// The number of shards of a [`ShardedMap`].
+const SHARD_COUNT: usize = 16;
+
+/// A map split into shards that are locked independently, so that accessing a
+/// key only contends with accesses of keys in the same shard, e.g. reading a
+/// guild while another guild is inserted.
+///
+/// A key always belongs to the same shard, and the locks are fair, so the
+/// updates of a key are applied in the order their locks were requested.
+pub(crate) struct ShardedMap<K, V> {
+    hasher: RandomState,
+    shards: Box<[RwLock<HashMap<K, V>>]>,
+}
+
+impl<K: Eq + Hash, V> ShardedMap<K, V> {
+    fn shard_index(&self, key: &K) -> usize {
+        self.hasher.hash_one(key) as usize % self.shards.len()
+    }
+
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V>> {
+        &self.shards[self.shard_index(key)]
+    }
+
+    /// Locks the shard of the key for reading.
+    pub(crate) async fn read(&self, key: &K) -> RwLockReadGuard<'_, HashMap<K, V>> {
+        self.shard(key).read().await
+    }
+
+    /// Locks the shard of the key for writing.
+    pub(crate) async fn write(&self, key: &K) -> RwLockWriteGuard<'_, HashMap<K, V>> {
+        self.shard(key).write().await
+    }
+
+    pub(crate) async fn insert(&self, key: K, value: V) -> Option<V> {
+        self.write(&key).await.insert(key, value)
+    }
+
+    pub(crate) async fn remove(&self, key: &K) -> Option<V> {
+        self.write(key).await.remove(key)
+    }
+
+    /// Inserts many entries, locking every shard at most once.
+    pub(crate) async fn extend(&self, entries: impl IntoIterator<Item = (K, V)>) {
+        let mut by_shard: Vec<Vec<(K, V)>> = self.shards.iter().map(|_| Vec::new()).collect();
+
+        for (key, value) in entries {
+            let index = self.shard_index(&key);
+            by_shard[index].push((key, value));
+        }
+
+        for (shard, entries) in self.shards.iter().zip(by_shard) {
+            if !entries.is_empty() {
+                shard.write().await.extend(entries);
+            }
+        }
+    }
+
+    /// The number of entries, counted one shard at a time.
+    pub(crate) async fn len(&self) -> usize {
+        let mut len = 0;
+
+        for shard in self.shards.iter() {
+            len += shard.read().await.len();
+        }
+
+        len
+    }
+
+    /// Calls `f` with every entry, locking one shard at a time.
+    pub(crate) async fn for_each(&self, mut f: impl FnMut(&K, &V)) {
+        for shard in self.shards.iter() {
+            for (key, value) in shard.read().await.iter() {
+                f(key, value);
+            }
+        }
+    }
+
+    /// Returns the first value `f` returns for an entry, locking one shard at a
+    /// time.
+    pub(crate) async fn find_map<T>(&self, mut f: impl FnMut(&K, &V) -> Option<T>) -> Option<T> {
+        for shard in self.shards.iter() {
+            if let Some(found) = shard.read().await.iter().find_map(|(key, value)| f(key, value)) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
+}
+
+impl<K: Eq + Hash, V> Default for ShardedMap<K, V> {
+    fn default() -> Self {
+        Self {
+            hasher: RandomState::new(),
+            shards: (0..SHARD_COUNT).map(|_| RwLock::new(HashMap::new())).collect(),
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ShardedMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.shards.iter()).finish()
+    }
+}
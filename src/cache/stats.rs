@@ -0,0 +1,55 @@
+use std::mem::size_of;
+
+use crate::model::prelude::*;
+
+/// Counts of the data in the cache, returned by [`Cache::statistics`], e.g.
+/// to export them as metrics.
+///
+/// [`Cache::statistics`]: super::Cache::statistics
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CacheStats {
+    /// The number of available guilds.
+    pub guilds: usize,
+    /// The number of guilds that are unavailable, e.g. due to an outage.
+    pub unavailable_guilds: usize,
+    /// The number of guild channels.
+    pub channels: usize,
+    /// The number of channel categories.
+    pub categories: usize,
+    /// The number of private channels.
+    pub private_channels: usize,
+    /// The number of users.
+    pub users: usize,
+    /// The number of members, summed across all guilds.
+    pub members: usize,
+    /// The number of messages, summed across all channels.
+    pub messages: usize,
+    /// The number of presences, summed across all guilds along with those
+    /// received outside of guilds.
+    pub presences: usize,
+    /// The number of voice states, summed across all guilds.
+    pub voice_states: usize,
+    /// The configured maximum number of messages per channel.
+    pub max_messages: usize,
+}
+
+impl CacheStats {
+    /// Roughly estimates the memory used by the counted data, in bytes.
+    ///
+    /// This only accounts for the size of the values themselves, not for the
+    /// data they own on the heap, such as names and role lists, nor for the
+    /// overhead of the maps holding them. The actual usage is higher.
+    pub fn approximate_size(&self) -> usize {
+        self.guilds * size_of::<Guild>()
+            + self.unavailable_guilds * size_of::<GuildId>()
+            + self.channels * size_of::<GuildChannel>()
+            + self.categories * size_of::<ChannelCategory>()
+            + self.private_channels * size_of::<PrivateChannel>()
+            + self.users * size_of::<User>()
+            + self.members * size_of::<Member>()
+            + self.messages * size_of::<Message>()
+            + self.presences * size_of::<Presence>()
+            + self.voice_states * size_of::<VoiceState>()
+    }
+}
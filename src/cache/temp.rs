@@ -0,0 +1,86 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// A map of values that are only kept for a limited time after their
+/// insertion, for data that the cache only stores temporarily.
+///
+/// Expired entries are ignored by lookups right away, but only dropped by a
+/// sweep over the whole map, which runs on insertion at most once per time to
+/// live.
+#[derive(Debug)]
+pub(crate) struct TtlMap<K, V> {
+    entries: HashMap<K, (V, Instant)>,
+    last_sweep: Instant,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlMap<K, V> {
+    /// Inserts a value that expires after `ttl`, replacing the previous value
+    /// of the key.
+    pub(crate) fn insert(&mut self, key: K, value: V, ttl: Duration) {
+        let now = Instant::now();
+
+        if now.duration_since(self.last_sweep) >= ttl {
+            self.entries.retain(|_, (_, inserted_at)| now.duration_since(*inserted_at) < ttl);
+            self.last_sweep = now;
+        }
+
+        self.entries.insert(key, (value, now));
+    }
+
+    /// Returns the value of the key, unless it is older than `ttl`.
+    pub(crate) fn get(&self, key: &K, ttl: Duration) -> Option<V> {
+        self.entries
+            .get(key)
+            .filter(|(_, inserted_at)| inserted_at.elapsed() < ttl)
+            .map(|(value, _)| value.clone())
+    }
+
+    /// Removes the value of the key, expired or not.
+    pub(crate) fn remove(&mut self, key: &K) {
+        self.entries.remove(key);
+    }
+
+    /// The number of entries, including the expired ones that were not swept
+    /// yet.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V> Default for TtlMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            last_sweep: Instant::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{thread, time::Duration};
+
+    use super::TtlMap;
+
+    #[test]
+    fn test_expiry_and_sweep() {
+        let ttl = Duration::from_millis(20);
+        let mut map = TtlMap::default();
+
+        map.insert(1, "one", ttl);
+        assert_eq!(map.get(&1, ttl), Some("one"));
+
+        thread::sleep(ttl);
+        assert_eq!(map.get(&1, ttl), None);
+        assert_eq!(map.len(), 1);
+
+        // The next insertion sweeps the expired entry.
+        map.insert(2, "two", ttl);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&2, ttl), Some("two"));
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::ChunkGuildFilter;
+use crate::model::id::GuildId;
+
+/// The most member chunk requests a shard sends per [`PACING_WINDOW`].
+///
+/// The gateway closes connections sending more than 120 events per minute, so
+/// this leaves room for heartbeats, presence updates and manual requests.
+const MAX_REQUESTS_PER_WINDOW: usize = 100;
+const PACING_WINDOW: Duration = Duration::from_secs(60);
+
+/// Selects the guilds whose members each shard requests automatically after
+/// receiving them in a [`GuildCreateEvent`], set through
+/// [`ClientBuilder::chunk_guilds_filter`].
+///
+/// The requested members are inserted into the cache as their
+/// [`GuildMembersChunkEvent`]s arrive, and [`EventHandler::guilds_chunked`] is
+/// dispatched once all requested guilds are fully chunked.
+///
+/// This is unrelated to the [`ChunkGuildFilter`] passed to
+/// [`Shard::chunk_guild`], which selects the members of a single request.
+///
+/// [`GuildCreateEvent`]: crate::model::event::GuildCreateEvent
+/// [`GuildMembersChunkEvent`]: crate::model::event::GuildMembersChunkEvent
+/// [`ClientBuilder::chunk_guilds_filter`]: crate::client::ClientBuilder::chunk_guilds_filter
+/// [`EventHandler::guilds_chunked`]: crate::client::EventHandler::guilds_chunked
+/// [`Shard::chunk_guild`]: crate::gateway::Shard::chunk_guild
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum ChunkGuildsFilter {
+    /// Does not request the members of any guild.
+    None,
+    /// Requests all members of every guild. Requires the
+    /// [`GatewayIntents::GUILD_MEMBERS`] intent.
+    ///
+    /// [`GatewayIntents::GUILD_MEMBERS`]: super::GatewayIntents::GUILD_MEMBERS
+    All,
+    /// Requests the members of every guild whose username starts with the
+    /// query.
+    WithQuery(String),
+    /// Requests all members of the guilds the function returns `true` for.
+    /// Requires the [`GatewayIntents::GUILD_MEMBERS`] intent.
+    ///
+    /// [`GatewayIntents::GUILD_MEMBERS`]: super::GatewayIntents::GUILD_MEMBERS
+    Custom(Arc<dyn Fn(GuildId) -> bool + Send + Sync>),
+}
+
+impl ChunkGuildsFilter {
+    /// The members to request of the guild, if any.
+    fn members(&self, guild_id: GuildId) -> Option<ChunkGuildFilter> {
+        match self {
+            Self::None => None,
+            Self::All => Some(ChunkGuildFilter::None),
+            Self::WithQuery(query) => Some(ChunkGuildFilter::Query(query.clone())),
+            Self::Custom(filter) if filter(guild_id) => Some(ChunkGuildFilter::None),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+impl Default for ChunkGuildsFilter {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl fmt::Debug for ChunkGuildsFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::All => f.write_str("All"),
+            Self::WithQuery(query) => f.debug_tuple("WithQuery").field(query).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// The nonce of the automatic member chunk requests for a guild, which the
+/// cache uses to tell when the guild is fully chunked.
+pub(crate) fn chunk_nonce(guild_id: GuildId) -> String {
+    format!("chunk-{}", guild_id.0)
+}
+
+/// Queues the member chunk requests of a shard, pacing them to stay below the
+/// gateway's send limit.
+#[derive(Debug)]
+pub(crate) struct ChunkQueue {
+    filter: ChunkGuildsFilter,
+    queued: VecDeque<(GuildId, ChunkGuildFilter)>,
+    sent: VecDeque<Instant>,
+}
+
+impl ChunkQueue {
+    pub(crate) fn new(filter: ChunkGuildsFilter) -> Self {
+        Self {
+            filter,
+            queued: VecDeque::new(),
+            sent: VecDeque::new(),
+        }
+    }
+
+    /// Queues a request for the members of a received guild if the filter
+    /// selects it.
+    pub(crate) fn guild_create(&mut self, guild_id: GuildId) {
+        if self.queued.iter().any(|(queued, _)| *queued == guild_id) {
+            return;
+        }
+
+        if let Some(members) = self.filter.members(guild_id) {
+            self.queued.push_back((guild_id, members));
+        }
+    }
+
+    /// Takes the next queued request, unless sending it at `now` would exceed
+    /// the pacing limit.
+    pub(crate) fn next(&mut self, now: Instant) -> Option<(GuildId, ChunkGuildFilter)> {
+        while self
+            .sent
+            .front()
+            .map_or(false, |sent| now.saturating_duration_since(*sent) >= PACING_WINDOW)
+        {
+            self.sent.pop_front();
+        }
+
+        if self.sent.len() >= MAX_REQUESTS_PER_WINDOW {
+            return None;
+        }
+
+        let request = self.queued.pop_front()?;
+        self.sent.push_back(now);
+
+        Some(request)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::{sync::Arc, time::Instant};
+
+    use super::{ChunkGuildsFilter, ChunkQueue, MAX_REQUESTS_PER_WINDOW, PACING_WINDOW};
+    use crate::client::bridge::gateway::ChunkGuildFilter;
+    use crate::model::id::GuildId;
+
+    #[test]
+    fn test_filter() {
+        let mut queue = ChunkQueue::new(ChunkGuildsFilter::None);
+        queue.guild_create(GuildId(1));
+        assert!(queue.next(Instant::now()).is_none());
+
+        let even = ChunkGuildsFilter::Custom(Arc::new(|guild_id: GuildId| guild_id.0 % 2 == 0));
+        let mut queue = ChunkQueue::new(even);
+        queue.guild_create(GuildId(1));
+        queue.guild_create(GuildId(2));
+        queue.guild_create(GuildId(2));
+        assert!(matches!(queue.next(Instant::now()), Some((GuildId(2), ChunkGuildFilter::None))));
+        assert!(queue.next(Instant::now()).is_none());
+
+        let mut queue = ChunkQueue::new(ChunkGuildsFilter::WithQuery("a".to_string()));
+        queue.guild_create(GuildId(1));
+        match queue.next(Instant::now()) {
+            Some((GuildId(1), ChunkGuildFilter::Query(query))) => assert_eq!(query, "a"),
+            other => panic!("unexpected request: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pacing() {
+        let mut queue = ChunkQueue::new(ChunkGuildsFilter::All);
+
+        for id in 0..=MAX_REQUESTS_PER_WINDOW as u64 {
+            queue.guild_create(GuildId(id));
+        }
+
+        let start = Instant::now();
+
+        for _ in 0..MAX_REQUESTS_PER_WINDOW {
+            assert!(queue.next(start).is_some());
+        }
+
+        assert!(queue.next(start).is_none());
+        assert!(queue.next(start + PACING_WINDOW / 2).is_none());
+
+        let (guild_id, _) = queue.next(start + PACING_WINDOW).unwrap();
+        assert_eq!(guild_id, GuildId(MAX_REQUESTS_PER_WINDOW as u64));
+        assert!(queue.next(start + PACING_WINDOW).is_none());
+    }
+}
@@ -1,13 +1,25 @@
 //! A collection of events created by the client, not a part of the Discord API
 //! itself.
 
+use std::time::Duration;
+
 use super::ShardId;
 use crate::gateway::ConnectionStage;
 
-#[allow(clippy::enum_variant_names)]
+/// An event created by the client itself, given to
+/// [`RawEventHandler::raw_client_event`] before it is dispatched to the
+/// [`EventHandler`].
+///
+/// [`EventHandler`]: crate::client::EventHandler
+/// [`RawEventHandler::raw_client_event`]: crate::client::RawEventHandler::raw_client_event
 #[derive(Clone, Debug)]
-pub(crate) enum ClientEvent {
+#[non_exhaustive]
+pub enum ClientEvent {
+    /// A shard's connection stage was changed.
     ShardStageUpdate(ShardStageUpdateEvent),
+    /// Every shard of the client reached [`ConnectionStage::Connected`] for
+    /// the first time, giving the number of shards.
+    ShardsReady(u32),
 }
 
 /// An event denoting that a shard's connection stage was changed.
@@ -15,7 +27,14 @@ pub(crate) enum ClientEvent {
 /// # Examples
 ///
 /// This might happen when a shard changes from [`ConnectionStage::Identifying`]
-/// to [`ConnectionStage::Connected`].
+/// to [`ConnectionStage::Connected`], or from [`ConnectionStage::Connected`] to
+/// [`ConnectionStage::Resuming`] when it lost its connection.
+///
+/// Listen to it through [`EventHandler::shard_stage_update`] to monitor shards
+/// without polling the [`ShardManager`].
+///
+/// [`EventHandler::shard_stage_update`]: crate::client::EventHandler::shard_stage_update
+/// [`ShardManager`]: super::ShardManager
 #[derive(Clone, Debug)]
 pub struct ShardStageUpdateEvent {
     /// The new connection stage.
@@ -24,4 +43,7 @@ pub struct ShardStageUpdateEvent {
     pub old: ConnectionStage,
     /// The ID of the shard that had its connection stage change.
     pub shard_id: ShardId,
+    /// The latency between the last heartbeat and its acknowledgement, if
+    /// the shard has heartbeated since connecting.
+    pub latency: Option<Duration>,
 }
@@ -18,9 +18,10 @@ use serde::{
 ///
 /// # Privileged Intents
 ///
-/// The intents [`GatewayIntents::GUILD_PRESENCES`] and [`GatewayIntents::GUILD_MEMBERS`]
-/// are [Privileged Intents]. They need to be enabled in the
-/// *developer portal*.
+/// The intents [`GatewayIntents::GUILD_PRESENCES`], [`GatewayIntents::GUILD_MEMBERS`]
+/// and [`GatewayIntents::MESSAGE_CONTENT`] are [Privileged Intents]. They need
+/// to be enabled in the *developer portal*, see
+/// [`GatewayIntents::all_verified`].
 ///
 /// **Note**:
 /// Once the bot is in 100 guilds or more, [the bot must be verified] in
@@ -154,6 +155,15 @@ __impl_bitflags! {
         ///
         /// - TYPING_START
         DIRECT_MESSAGE_TYPING = 1 << 14;
+        /// Enables receiving the content, embeds, attachments and components
+        /// of messages that do not mention the bot or are not sent to it
+        /// directly.
+        ///
+        /// **Info**:
+        /// This intent is *privileged*.
+        /// In order to use it, you must head to your application in the
+        /// Developer Portal and enable the toggle for *Privileged Intents*.
+        MESSAGE_CONTENT = 1 << 15;
     }
 }
 
@@ -192,7 +202,36 @@ impl GatewayIntents {
     pub const fn privileged() -> GatewayIntents {
         // bitflags don't support const evaluation. Workaround.
         // See: https://github.com/bitflags/bitflags/issues/180
-        Self::from_bits_truncate(Self::GUILD_MEMBERS.bits() | Self::GUILD_PRESENCES.bits())
+        Self::from_bits_truncate(
+            Self::GUILD_MEMBERS.bits()
+                | Self::GUILD_PRESENCES.bits()
+                | Self::MESSAGE_CONTENT.bits(),
+        )
+    }
+
+    /// Gets all of the non-privileged intents, along with the privileged ones
+    /// in `enabled_privileged`.
+    ///
+    /// Pass the privileged intents you have enabled for the bot in the
+    /// developer portal, so that identifying does not fail with disallowed
+    /// intents. Any non-privileged intents in `enabled_privileged` are
+    /// ignored, as they are included anyway.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::client::bridge::gateway::GatewayIntents;
+    ///
+    /// let intents = GatewayIntents::all_verified(GatewayIntents::GUILD_MEMBERS);
+    ///
+    /// assert!(intents.guild_members());
+    /// assert!(intents.guilds());
+    /// assert!(!intents.guild_presences());
+    /// ```
+    pub const fn all_verified(enabled_privileged: GatewayIntents) -> GatewayIntents {
+        let privileged = enabled_privileged.bits() & Self::privileged().bits();
+
+        Self::from_bits_truncate(Self::non_privileged().bits() | privileged)
     }
 
     /// Checks if any of the included intents are privileged
@@ -200,7 +239,53 @@ impl GatewayIntents {
     /// [GUILD_MEMBERS]: #associatedconstant.GUILD_MEMBERS
     /// [GUILD_PRESENCES]: #associatedconstant.GUILD_PRESENCES
     pub fn is_privileged(self) -> bool {
-        self.guild_members() || self.guild_presences()
+        self.intersects(Self::privileged())
+    }
+
+    /// Gets the privileged intents that an application has enabled, as given
+    /// by the [`flags`] of its [`CurrentApplicationInfo`].
+    ///
+    /// An intent counts as enabled when either its full or its limited
+    /// application flag is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::client::bridge::gateway::GatewayIntents;
+    ///
+    /// // GATEWAY_GUILD_MEMBERS_LIMITED
+    /// let enabled = GatewayIntents::enabled_privileged(1 << 15);
+    ///
+    /// assert_eq!(enabled, GatewayIntents::GUILD_MEMBERS);
+    /// ```
+    ///
+    /// [`flags`]: crate::model::application::CurrentApplicationInfo::flags
+    /// [`CurrentApplicationInfo`]: crate::model::application::CurrentApplicationInfo
+    pub fn enabled_privileged(application_flags: u64) -> GatewayIntents {
+        const GATEWAY_PRESENCE: u64 = 0b11 << 12;
+        const GATEWAY_GUILD_MEMBERS: u64 = 0b11 << 14;
+        const GATEWAY_MESSAGE_CONTENT: u64 = 0b11 << 18;
+
+        let mut intents = GatewayIntents::empty();
+        intents.set(Self::GUILD_PRESENCES, application_flags & GATEWAY_PRESENCE != 0);
+        intents.set(Self::GUILD_MEMBERS, application_flags & GATEWAY_GUILD_MEMBERS != 0);
+        intents.set(Self::MESSAGE_CONTENT, application_flags & GATEWAY_MESSAGE_CONTENT != 0);
+
+        intents
+    }
+
+    /// Gets the privileged intents in this set that the application with the
+    /// given [`flags`] has not enabled.
+    ///
+    /// Identifying with any of these will make the gateway close the
+    /// connection with [`GatewayError::DisallowedGatewayIntents`]; building a
+    /// client with them fails with [`GatewayError::PrivilegedIntentsNotEnabled`].
+    ///
+    /// [`flags`]: crate::model::application::CurrentApplicationInfo::flags
+    /// [`GatewayError::DisallowedGatewayIntents`]: crate::gateway::GatewayError::DisallowedGatewayIntents
+    /// [`GatewayError::PrivilegedIntentsNotEnabled`]: crate::gateway::GatewayError::PrivilegedIntentsNotEnabled
+    pub fn missing_privileged(self, application_flags: u64) -> GatewayIntents {
+        self & Self::privileged() & !Self::enabled_privileged(application_flags)
     }
 
     /// Shorthand for checking that the set of intents contains the
@@ -314,4 +399,51 @@ impl GatewayIntents {
     pub fn direct_message_typing(self) -> bool {
         self.contains(Self::DIRECT_MESSAGE_TYPING)
     }
+
+    /// Shorthand for checking that the set of intents contains the
+    /// [MESSAGE_CONTENT] intent.
+    ///
+    /// [MESSAGE_CONTENT]: Self::MESSAGE_CONTENT
+    pub fn message_content(self) -> bool {
+        self.contains(Self::MESSAGE_CONTENT)
+    }
+}
+
+#[cfg(all(test, feature = "model"))]
+mod test {
+    use super::GatewayIntents;
+
+    #[test]
+    fn test_privileged_detection() {
+        let cases = [
+            (GatewayIntents::GUILDS, false),
+            (GatewayIntents::GUILD_MESSAGES | GatewayIntents::DIRECT_MESSAGES, false),
+            (GatewayIntents::GUILD_MEMBERS, true),
+            (GatewayIntents::GUILD_PRESENCES, true),
+            (GatewayIntents::MESSAGE_CONTENT, true),
+            (GatewayIntents::GUILDS | GatewayIntents::GUILD_PRESENCES, true),
+            (GatewayIntents::non_privileged(), false),
+            (GatewayIntents::privileged(), true),
+            (GatewayIntents::all(), true),
+            (GatewayIntents::empty(), false),
+        ];
+
+        for (intents, privileged) in &cases {
+            assert_eq!(intents.is_privileged(), *privileged, "{:?}", intents);
+        }
+    }
+
+    #[test]
+    fn test_missing_privileged() {
+        let intents = GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MEMBERS
+            | GatewayIntents::GUILD_PRESENCES;
+
+        assert_eq!(intents.missing_privileged(0), intents & GatewayIntents::privileged());
+        // GATEWAY_PRESENCE | GATEWAY_GUILD_MEMBERS_LIMITED
+        assert!(intents.missing_privileged(1 << 12 | 1 << 15).is_empty());
+        // GATEWAY_MESSAGE_CONTENT
+        assert_eq!(intents.missing_privileged(1 << 18), intents & GatewayIntents::privileged());
+        assert_eq!(intents.missing_privileged(1 << 14), GatewayIntents::GUILD_PRESENCES);
+    }
 }
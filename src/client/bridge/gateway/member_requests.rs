@@ -0,0 +1,279 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context as FutContext, Poll},
+    time::Duration,
+};
+
+use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
+use futures::stream::{Stream, StreamExt};
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, Delay as Sleep};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, Sleep};
+
+use super::ChunkGuildFilter;
+use crate::internal::prelude::*;
+use crate::model::event::GuildMembersChunkEvent;
+use crate::model::gateway::Presence;
+use crate::model::guild::Member;
+use crate::model::id::UserId;
+use crate::model::ModelError;
+
+/// The most users whose members can be requested by Id at once.
+const MAX_USER_IDS: usize = 100;
+/// How long to wait for a request to be answered by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+static NEXT_NONCE: AtomicU64 = AtomicU64::new(0);
+
+/// Generates a nonce for a member request, unique within the process.
+pub(crate) fn request_nonce() -> String {
+    format!("request-{}", NEXT_NONCE.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Checks that a member request does not ask for too many users by Id.
+pub(crate) fn validate_filter(filter: &ChunkGuildFilter) -> Result<()> {
+    match filter {
+        ChunkGuildFilter::UserIds(user_ids) if user_ids.len() > MAX_USER_IDS => {
+            Err(Error::Model(ModelError::TooManyUserIds(user_ids.len())))
+        },
+        _ => Ok(()),
+    }
+}
+
+/// The members received for a request made through
+/// [`ShardMessenger::request_guild_members`], as a [`Stream`].
+///
+/// The stream ends once the last [`GuildMembersChunkEvent`] of the request was
+/// received, or once its timeout elapses, which is 30 seconds by default.
+///
+/// [`ShardMessenger::request_guild_members`]: super::ShardMessenger::request_guild_members
+pub struct GuildMembersRequest {
+    receiver: Receiver<GuildMembersChunkEvent>,
+    members: VecDeque<Member>,
+    not_found: Vec<UserId>,
+    presences: Vec<Presence>,
+    timeout: Pin<Box<Sleep>>,
+    complete: bool,
+    done: bool,
+}
+
+impl GuildMembersRequest {
+    pub(crate) fn new() -> (Self, Sender<GuildMembersChunkEvent>) {
+        let (sender, receiver) = mpsc::unbounded();
+
+        let request = Self {
+            receiver,
+            members: VecDeque::new(),
+            not_found: Vec::new(),
+            presences: Vec::new(),
+            timeout: Box::pin(sleep(DEFAULT_TIMEOUT)),
+            complete: false,
+            done: false,
+        };
+
+        (request, sender)
+    }
+
+    /// Sets how long to wait for the request to be answered, from now on.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Box::pin(sleep(duration));
+
+        self
+    }
+
+    /// The Ids of the requested users that are not members of the guild,
+    /// among the chunks received so far.
+    pub fn not_found(&self) -> &[UserId] {
+        &self.not_found
+    }
+
+    /// The presences of the members received so far, if they were requested.
+    pub fn presences(&self) -> &[Presence] {
+        &self.presences
+    }
+
+    /// Whether the last chunk of the request was received, as opposed to the
+    /// request having timed out.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn receive(&mut self, chunk: GuildMembersChunkEvent) {
+        self.members.extend(chunk.members.into_iter().map(|(_, member)| member));
+        self.not_found.extend(chunk.not_found);
+        self.presences.extend(chunk.presences);
+
+        if chunk.chunk_index + 1 >= chunk.chunk_count {
+            self.complete = true;
+        }
+    }
+}
+
+impl Stream for GuildMembersRequest {
+    type Item = Member;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Member>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(member) = this.members.pop_front() {
+                return Poll::Ready(Some(member));
+            }
+
+            if this.complete || this.done {
+                return Poll::Ready(None);
+            }
+
+            match this.receiver.poll_next_unpin(ctx) {
+                Poll::Ready(Some(chunk)) => this.receive(chunk),
+                // The shard runner stopped.
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => {
+                    if this.timeout.as_mut().poll(ctx).is_pending() {
+                        return Poll::Pending;
+                    }
+
+                    this.done = true;
+                },
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for GuildMembersRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GuildMembersRequest")
+            .field("members", &self.members)
+            .field("not_found", &self.not_found)
+            .field("complete", &self.complete)
+            .finish()
+    }
+}
+
+/// Forwards the member chunks a shard receives to the pending requests they
+/// answer, by nonce.
+#[derive(Debug, Default)]
+pub(crate) struct MemberRequests {
+    pending: HashMap<String, Sender<GuildMembersChunkEvent>>,
+}
+
+impl MemberRequests {
+    pub(crate) fn insert(&mut self, nonce: String, sender: Sender<GuildMembersChunkEvent>) {
+        // Requests that were dropped or timed out are not answered anymore.
+        self.pending.retain(|_, sender| !sender.is_closed());
+        self.pending.insert(nonce, sender);
+    }
+
+    pub(crate) fn receive(&mut self, chunk: &GuildMembersChunkEvent) {
+        let nonce = match &chunk.nonce {
+            Some(nonce) => nonce,
+            None => return,
+        };
+
+        let sent = match self.pending.get(nonce) {
+            Some(sender) => sender.unbounded_send(chunk.clone()).is_ok(),
+            None => return,
+        };
+
+        if !sent || chunk.chunk_index + 1 >= chunk.chunk_count {
+            self.pending.remove(nonce);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use serde_json::json;
+
+    use super::{validate_filter, GuildMembersRequest, MemberRequests};
+    use crate::client::bridge::gateway::ChunkGuildFilter;
+    use crate::model::event::GuildMembersChunkEvent;
+    use crate::model::id::UserId;
+
+    fn chunk(nonce: &str, index: u32, count: u32, ids: &[u64]) -> GuildMembersChunkEvent {
+        let members = ids
+            .iter()
+            .map(|id| {
+                json!({
+                    "user": {
+                        "id": id.to_string(),
+                        "username": "user",
+                        "discriminator": "0001",
+                        "avatar": null,
+                    },
+                    "roles": [],
+                    "joined_at": "2021-08-01T00:00:00Z",
+                    "deaf": false,
+                    "mute": false,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::from_value(json!({
+            "guild_id": "1",
+            "members": members,
+            "chunk_index": index,
+            "chunk_count": count,
+            "nonce": nonce,
+            "not_found": if index == 0 { vec!["99"] } else { vec![] },
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_multiple_chunks() {
+        let mut requests = MemberRequests::default();
+        let (mut request, sender) = GuildMembersRequest::new();
+        requests.insert("request-a".to_string(), sender);
+
+        requests.receive(&chunk("request-a", 0, 2, &[10, 11]));
+        // Chunks answering other requests are not forwarded.
+        requests.receive(&chunk("request-b", 0, 1, &[20]));
+        requests.receive(&chunk("request-a", 1, 2, &[12]));
+        assert!(requests.pending.is_empty());
+
+        let mut ids = (&mut request).map(|member| member.user.id.0).collect::<Vec<_>>().await;
+        ids.sort_unstable();
+
+        assert_eq!(ids, vec![10, 11, 12]);
+        assert_eq!(request.not_found(), &[UserId(99)]);
+        assert!(request.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let mut requests = MemberRequests::default();
+        let (request, sender) = GuildMembersRequest::new();
+        let mut request = request.timeout(Duration::from_millis(10));
+        requests.insert("request-a".to_string(), sender);
+
+        requests.receive(&chunk("request-a", 0, 3, &[10]));
+
+        assert_eq!(request.next().await.unwrap().user.id, UserId(10));
+        assert!(request.next().await.is_none());
+        assert!(!request.is_complete());
+
+        // The timed out request is forgotten once another one is made.
+        drop(request);
+        let (_, sender) = GuildMembersRequest::new();
+        requests.insert("request-b".to_string(), sender);
+        assert_eq!(requests.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_filter() {
+        let ids = |count: u64| ChunkGuildFilter::UserIds((0..count).map(UserId).collect());
+
+        assert!(validate_filter(&ids(100)).is_ok());
+        assert!(validate_filter(&ids(101)).is_err());
+        assert!(validate_filter(&ChunkGuildFilter::Query("do".to_string())).is_ok());
+    }
+}
@@ -44,7 +44,10 @@
 
 pub mod event;
 
+mod chunk_guilds;
 mod intents;
+mod member_requests;
+mod reconnect;
 mod shard_manager;
 mod shard_manager_monitor;
 mod shard_messenger;
@@ -54,11 +57,15 @@ mod shard_runner_message;
 
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
-    time::Duration as StdDuration,
+    time::{Duration as StdDuration, Instant},
 };
 
+pub use self::chunk_guilds::ChunkGuildsFilter;
+pub(crate) use self::chunk_guilds::{chunk_nonce, ChunkQueue};
 pub use self::intents::GatewayIntents;
-pub use self::shard_manager::{ShardManager, ShardManagerOptions};
+pub use self::member_requests::GuildMembersRequest;
+pub use self::reconnect::{Backoff, ReconnectAttempts, ReconnectPolicy};
+pub use self::shard_manager::{ShardManager, ShardManagerOptions, ShutdownHook};
 pub use self::shard_manager_monitor::{ShardManagerError, ShardManagerMonitor};
 pub use self::shard_messenger::ShardMessenger;
 pub use self::shard_queuer::ShardQueuer;
@@ -83,7 +90,12 @@ pub enum ShardManagerMessage {
     /// Indicator that a [`ShardManagerMonitor`] should restart a shard.
     Restart(ShardId),
     /// An update from a shard runner,
-    ShardUpdate { id: ShardId, latency: Option<StdDuration>, stage: ConnectionStage },
+    ShardUpdate {
+        id: ShardId,
+        latency: Option<StdDuration>,
+        stage: ConnectionStage,
+        next_reconnect: Option<Instant>,
+    },
     /// Indicator that a [`ShardManagerMonitor`] should fully shutdown a shard
     /// without bringing it back up.
     Shutdown(ShardId, u16),
@@ -154,6 +166,10 @@ pub struct ShardRunnerInfo {
     pub runner_tx: ShardMessenger,
     /// The current connection stage of the shard.
     pub stage: ConnectionStage,
+    /// When the shard will next try to reconnect, if it lost its connection.
+    ///
+    /// Refer to [`ReconnectPolicy`] for how this is scheduled.
+    pub next_reconnect: Option<Instant>,
 }
 
 impl AsRef<ShardMessenger> for ShardRunnerInfo {
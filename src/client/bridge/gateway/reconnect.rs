@@ -0,0 +1,227 @@
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::gateway::ReconnectType;
+use crate::http::retry::jitter;
+
+/// An exponential backoff between the attempts of a shard to reconnect.
+///
+/// The first attempt waits for the base delay, and every following attempt
+/// waits `multiplier` times as long as the previous one, up to the maximum
+/// delay.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Backoff {
+    base_delay: Duration,
+    multiplier: u32,
+    max_delay: Duration,
+}
+
+impl Backoff {
+    /// Creates a backoff starting at `base_delay`, multiplied by `multiplier`
+    /// after every attempt, and never exceeding `max_delay`.
+    pub fn new(base_delay: Duration, multiplier: u32, max_delay: Duration) -> Self {
+        Self {
+            base_delay,
+            multiplier,
+            max_delay,
+        }
+    }
+
+    /// Returns how long to wait before the next attempt, after `attempts`
+    /// consecutive attempts failed.
+    pub fn delay(&self, attempts: u32) -> Duration {
+        self.base_delay
+            .checked_mul(self.multiplier.saturating_pow(attempts))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+/// Describes how long shards wait before trying to reconnect after they lost
+/// their connection.
+///
+/// Shards that can resume their session, e.g. after a dropped socket, follow
+/// the [`Self::resume`] backoff. Shards that have to identify again, e.g. after
+/// an invalidated session, follow the longer [`Self::reidentify`] backoff, as
+/// every identify counts towards the daily session start limit. Both backoffs
+/// start over once the shard is connected again.
+///
+/// # Examples
+///
+/// Retrying resumes after a second at first, up to a minute apart:
+///
+/// ```rust,no_run
+/// # use serenity::client::Client;
+/// # use serenity::client::bridge::gateway::{Backoff, ReconnectPolicy};
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// let backoff = Backoff::new(Duration::from_secs(1), 2, Duration::from_secs(60));
+/// let policy = ReconnectPolicy::new().resume(backoff);
+///
+/// let client = Client::builder("token").reconnect_policy(policy).await?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReconnectPolicy {
+    resume: Backoff,
+    reidentify: Backoff,
+    jitter: bool,
+}
+
+impl ReconnectPolicy {
+    /// Creates the default policy. Equivalent to [`Self::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the backoff between attempts to resume the session.
+    ///
+    /// Defaults to 250 milliseconds, doubling up to 30 seconds.
+    pub fn resume(mut self, backoff: Backoff) -> Self {
+        self.resume = backoff;
+
+        self
+    }
+
+    /// Sets the backoff between attempts to identify with a new session.
+    ///
+    /// Defaults to 2 seconds, doubling up to 5 minutes. This is in addition to
+    /// the identify ratelimit the [`ShardQueuer`] enforces.
+    ///
+    /// [`ShardQueuer`]: super::ShardQueuer
+    pub fn reidentify(mut self, backoff: Backoff) -> Self {
+        self.reidentify = backoff;
+
+        self
+    }
+
+    /// Sets whether the delays are randomly shortened by up to half, so that
+    /// shards that disconnected at the same time do not all reconnect at the
+    /// same time.
+    ///
+    /// This is enabled by default.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Returns how long to wait before the next reconnection of the given
+    /// type, after `attempts` consecutive attempts failed, without jitter.
+    pub fn delay(&self, reconnect: &ReconnectType, attempts: u32) -> Duration {
+        match reconnect {
+            ReconnectType::Resume => self.resume.delay(attempts),
+            ReconnectType::Reidentify => self.reidentify.delay(attempts),
+        }
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            resume: Backoff::new(Duration::from_millis(250), 2, Duration::from_secs(30)),
+            reidentify: Backoff::new(Duration::from_secs(2), 2, Duration::from_secs(5 * 60)),
+            jitter: true,
+        }
+    }
+}
+
+/// The consecutive reconnection attempts of a shard, which the [`ShardQueuer`]
+/// keeps across the runners of the shard, as identifying again replaces the
+/// runner.
+///
+/// [`ShardQueuer`]: super::ShardQueuer
+#[derive(Debug, Default)]
+pub struct ReconnectAttempts {
+    resume: AtomicU32,
+    reidentify: AtomicU32,
+}
+
+impl ReconnectAttempts {
+    /// Counts an attempt to reconnect, returning when it should happen.
+    pub(crate) fn next_attempt(
+        &self,
+        policy: &ReconnectPolicy,
+        reconnect: &ReconnectType,
+        now: Instant,
+    ) -> Instant {
+        let attempts = match reconnect {
+            ReconnectType::Resume => &self.resume,
+            ReconnectType::Reidentify => &self.reidentify,
+        };
+
+        let delay = policy.delay(reconnect, attempts.fetch_add(1, Ordering::Relaxed));
+
+        now + if policy.jitter { jitter(delay) } else { delay }
+    }
+
+    /// Starts both backoffs over, once the shard is connected again.
+    pub(crate) fn reset(&self) {
+        self.resume.store(0, Ordering::Relaxed);
+        self.reidentify.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{Backoff, ReconnectAttempts, ReconnectPolicy};
+    use crate::gateway::ReconnectType;
+
+    /// The delays of the next attempts of a kind, in milliseconds, at a fixed
+    /// point in time.
+    fn delays(
+        attempts: &ReconnectAttempts,
+        policy: &ReconnectPolicy,
+        kind: &ReconnectType,
+    ) -> Vec<u128> {
+        let now = Instant::now();
+
+        (0..6).map(|_| (attempts.next_attempt(policy, kind, now) - now).as_millis()).collect()
+    }
+
+    #[test]
+    fn test_resume_and_reidentify_schedules() {
+        let policy = ReconnectPolicy::new().jitter(false);
+        let attempts = ReconnectAttempts::default();
+
+        let resume = delays(&attempts, &policy, &ReconnectType::Resume);
+        assert_eq!(resume, vec![250, 500, 1000, 2000, 4000, 8000]);
+
+        // The schedules are independent of each other.
+        let reidentify = delays(&attempts, &policy, &ReconnectType::Reidentify);
+        assert_eq!(reidentify, vec![2000, 4000, 8000, 16000, 32000, 64000]);
+
+        attempts.reset();
+        assert_eq!(delays(&attempts, &policy, &ReconnectType::Resume)[0], 250);
+    }
+
+    #[test]
+    fn test_backoff_max_delay() {
+        let backoff = Backoff::new(Duration::from_secs(1), 3, Duration::from_secs(10));
+
+        assert_eq!(backoff.delay(0), Duration::from_secs(1));
+        assert_eq!(backoff.delay(2), Duration::from_secs(9));
+        assert_eq!(backoff.delay(3), Duration::from_secs(10));
+        // Overflowing delays are capped as well.
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_jitter() {
+        let policy = ReconnectPolicy::new();
+        let full = policy.delay(&ReconnectType::Reidentify, 0);
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            let attempts = ReconnectAttempts::default();
+            let delay = attempts.next_attempt(&policy, &ReconnectType::Reidentify, now) - now;
+
+            assert!(delay >= full / 2 && delay <= full, "{:?} is out of range", delay);
+        }
+    }
+}
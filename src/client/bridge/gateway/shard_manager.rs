@@ -1,29 +1,41 @@
 use std::{
     collections::{HashMap, VecDeque},
     sync::Arc,
+    time::Duration,
 };
 
 use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
 use futures::StreamExt;
 use tokio::sync::{Mutex, RwLock};
 use tokio::time::timeout;
-use tracing::{info, instrument, warn};
+use tracing::{debug, info, instrument, warn};
 use typemap_rev::TypeMap;
 
 use super::{
+    ChunkGuildsFilter,
     GatewayIntents,
+    ReconnectPolicy,
+    ShardClientMessage,
     ShardId,
     ShardManagerMessage,
     ShardManagerMonitor,
     ShardQueuer,
     ShardQueuerMessage,
     ShardRunnerInfo,
+    ShardRunnerMessage,
 };
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::{
+    ClientError,
+    DispatchTracker,
+    EventHandler,
+    RawEventHandler,
+    ShardsReadyTracker,
+};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
+use crate::gateway::{ConnectionStage, CurrentPresence, InterMessage, TransportCompression};
 use crate::internal::prelude::*;
 use crate::CacheAndHttp;
 
@@ -50,8 +62,12 @@ use crate::CacheAndHttp;
 /// # async fn run() -> Result<(), Box<dyn Error>> {
 /// #
 /// use tokio::sync::{Mutex, RwLock};
-/// use serenity::client::bridge::gateway::{ShardManager, ShardManagerOptions, GatewayIntents};
+/// use serenity::client::bridge::gateway::{
+///     ChunkGuildsFilter, GatewayIntents, ReconnectPolicy, ShardManager, ShardManagerOptions,
+/// };
 /// use serenity::client::{EventHandler, RawEventHandler};
+/// use serenity::gateway::TransportCompression;
+/// use serenity::model::user::OnlineStatus;
 /// use serenity::http::Http;
 /// use serenity::CacheAndHttp;
 /// use serenity::prelude::*;
@@ -75,6 +91,7 @@ use crate::CacheAndHttp;
 ///     data: &data,
 ///     event_handler: &Some(event_handler),
 ///     raw_event_handler: &None,
+///     raw_dispatch: false,
 ///     framework: &framework,
 ///     // the shard index to start initiating from
 ///     shard_index: 0,
@@ -87,6 +104,12 @@ use crate::CacheAndHttp;
 ///     ws_url: &gateway_url,
 ///     # cache_and_http: &cache_and_http,
 ///     intents: GatewayIntents::non_privileged(),
+///     chunk_guilds_filter: ChunkGuildsFilter::None,
+///     transport_compression: TransportCompression::default(),
+///     presence: (None, OnlineStatus::Online),
+///     // how many shards may identify at the same time
+///     max_concurrency: 1,
+///     reconnect_policy: ReconnectPolicy::default(),
 /// });
 /// #     Ok(())
 /// # }
@@ -110,6 +133,8 @@ pub struct ShardManager {
     shard_total: u64,
     shard_queuer: Sender<ShardQueuerMessage>,
     shard_shutdown: Receiver<ShardId>,
+    dispatches: Arc<DispatchTracker>,
+    shards_ready: Arc<ShardsReadyTracker>,
 }
 
 impl ShardManager {
@@ -126,9 +151,14 @@ impl ShardManager {
             data: Arc::clone(opt.data),
             event_handler: opt.event_handler.as_ref().map(|h| Arc::clone(h)),
             raw_event_handler: opt.raw_event_handler.as_ref().map(|rh| Arc::clone(rh)),
+            raw_dispatch: opt.raw_dispatch,
             #[cfg(feature = "framework")]
             framework: Arc::clone(opt.framework),
             last_start: None,
+            last_starts: HashMap::new(),
+            max_concurrency: opt.max_concurrency,
+            reconnect_policy: opt.reconnect_policy,
+            reconnect_attempts: HashMap::new(),
             manager_tx: thread_tx.clone(),
             queue: VecDeque::new(),
             runners: Arc::clone(&runners),
@@ -138,6 +168,9 @@ impl ShardManager {
             ws_url: Arc::clone(opt.ws_url),
             cache_and_http: Arc::clone(opt.cache_and_http),
             intents: opt.intents,
+            chunk_guilds_filter: opt.chunk_guilds_filter,
+            transport_compression: opt.transport_compression,
+            presence: opt.presence,
         };
 
         tokio::spawn(async move {
@@ -152,6 +185,8 @@ impl ShardManager {
             shard_total: opt.shard_total,
             shard_shutdown: shutdown_recv,
             runners,
+            dispatches: Arc::clone(&opt.cache_and_http.dispatches),
+            shards_ready: Arc::clone(&opt.cache_and_http.shards_ready),
         }));
 
         (Arc::clone(&manager), ShardManagerMonitor {
@@ -177,6 +212,7 @@ impl ShardManager {
     #[instrument(skip(self))]
     pub fn initialize(&mut self) -> Result<()> {
         let shard_to = self.shard_index + self.shard_init;
+        self.shards_ready.expect(self.shard_init);
 
         for shard_id in self.shard_index..shard_to {
             let shard_total = self.shard_total;
@@ -245,6 +281,57 @@ impl ShardManager {
         self.boot([shard_id, ShardId(shard_total)]);
     }
 
+    /// Restarts a single shard, e.g. one stuck in a bad state, without
+    /// disturbing the others.
+    ///
+    /// Unlike [`Self::restart`], this does not wait for the shard to shut down,
+    /// so it only needs a shared reference to the manager. The shard's runner
+    /// is told to shut down, which makes it close its connection and stop
+    /// without reconnecting, and its entry is replaced by a fresh runner queued
+    /// via the [`ShardQueuer`]. The new runner identifies with a new session,
+    /// respecting the identify ratelimit like any other queued shard. If the
+    /// shard is not running, it is started.
+    ///
+    /// # Examples
+    ///
+    /// Restarting shard ID 7:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Client;
+    /// use serenity::client::bridge::gateway::ShardId;
+    ///
+    /// # async fn run(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.shard_manager.lock().await.restart_shard(ShardId(7)).await?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::Shutdown`] if the manager was shut down, in which
+    /// case no runner is queued anymore.
+    #[instrument(skip(self))]
+    pub async fn restart_shard(&self, shard_id: ShardId) -> Result<()> {
+        info!("Restarting shard {}", shard_id);
+
+        if let Some(runner) = self.runners.lock().await.remove(&shard_id) {
+            // Unlike a close, which the runner would try to recover from by
+            // reconnecting, a shutdown stops it.
+            let msg = ShardClientMessage::Manager(ShardManagerMessage::Shutdown(shard_id, 4000));
+            let msg = InterMessage::Client(Box::new(msg));
+
+            // The runner may already have stopped on its own, which is fine as
+            // it is replaced either way.
+            if let Err(why) = runner.runner_tx.tx.unbounded_send(msg) {
+                debug!("Failed to send close to shard {}: {:?}", shard_id, why);
+            }
+        }
+
+        let msg = ShardQueuerMessage::Start(shard_id, ShardId(self.shard_total));
+
+        self.shard_queuer.unbounded_send(msg).map_err(|_| Error::Client(ClientError::Shutdown))
+    }
+
     /// Returns the [`ShardId`]s of the shards that have been instantiated and
     /// currently have a valid [`ShardRunner`].
     ///
@@ -254,6 +341,68 @@ impl ShardManager {
         self.runners.lock().await.keys().cloned().collect()
     }
 
+    /// Returns the [`ConnectionStage`]s of the shards that have been
+    /// instantiated, like [`Self::shards_instantiated`].
+    #[instrument(skip(self))]
+    pub async fn shard_stages(&self) -> HashMap<ShardId, ConnectionStage> {
+        let runners = self.runners.lock().await;
+
+        runners.iter().map(|(shard_id, runner)| (*shard_id, runner.stage)).collect()
+    }
+
+    /// Returns the latency of a shard, i.e. the time between its last
+    /// heartbeat and the acknowledgement of it.
+    ///
+    /// Returns [`None`] if the shard is not running or has not received an
+    /// acknowledgement yet.
+    pub async fn latency(&self, shard_id: ShardId) -> Option<Duration> {
+        self.runners.lock().await.get(&shard_id).and_then(|runner| runner.latency)
+    }
+
+    /// Returns the latencies of all running shards that have received a
+    /// heartbeat acknowledgement.
+    ///
+    /// Refer to [`Self::latency`] for more information.
+    pub async fn latencies(&self) -> HashMap<ShardId, Duration> {
+        self.runners
+            .lock()
+            .await
+            .iter()
+            .filter_map(|(shard_id, runner)| Some((*shard_id, runner.latency?)))
+            .collect()
+    }
+
+    /// Sends a message to the runners of all shards that have been
+    /// instantiated, e.g. to update the presence of every shard at once.
+    ///
+    /// A failure to reach one shard does not stop the message from being sent
+    /// to the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::BroadcastFailed`] with the ids of the shards
+    /// whose runners no longer receive messages.
+    #[instrument(skip(self))]
+    pub async fn broadcast(&self, msg: ShardRunnerMessage) -> Result<()> {
+        let mut failed = Vec::new();
+
+        for (shard_id, runner) in self.runners.lock().await.iter() {
+            if let Err(why) = runner.runner_tx.send_to_shard(msg.clone()) {
+                warn!("Failed to send a broadcast message to shard {}: {:?}", shard_id, why);
+
+                failed.push(shard_id.0);
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            failed.sort_unstable();
+
+            Err(Error::Client(ClientError::BroadcastFailed(failed)))
+        }
+    }
+
     /// Attempts to shut down the shard runner by Id.
     ///
     /// Returns a boolean indicating whether a shard runner was present. This is
@@ -271,17 +420,22 @@ impl ShardManager {
 
         let _ = self.shard_queuer.unbounded_send(ShardQueuerMessage::ShutdownShard(shard_id, code));
 
-        const TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(5);
-        match timeout(TIMEOUT, self.shard_shutdown.next()).await {
-            Ok(Some(shutdown_shard_id)) => {
-                if shutdown_shard_id != shard_id {
-                    warn!(
-                        "Failed to cleanly shutdown shard {}: Shutdown channel sent incorrect ID",
-                        shard_id,
-                    );
+        let shard_shutdown = &mut self.shard_shutdown;
+        let finished = async move {
+            while let Some(shutdown_shard_id) = shard_shutdown.next().await {
+                if shutdown_shard_id == shard_id {
+                    break;
                 }
-            },
-            Ok(None) => (),
+
+                // Shards shut down without waiting, e.g. by `restart_shard`,
+                // still report it.
+                debug!("Ignoring shutdown of shard {} awaiting {}", shutdown_shard_id, shard_id);
+            }
+        };
+
+        const TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+        match timeout(TIMEOUT, finished).await {
+            Ok(()) => (),
             Err(why) => {
                 warn!("Failed to cleanly shutdown shard {}, reached timeout: {:?}", shard_id, why,)
             },
@@ -318,6 +472,41 @@ impl ShardManager {
         let _ = self.monitor_tx.unbounded_send(ShardManagerMessage::ShutdownInitiated);
     }
 
+    /// Like [`Self::shutdown_all`], but gives the event handlers that are still
+    /// running up to `timeout` to finish first.
+    ///
+    /// No more events are dispatched once this is called, and events received
+    /// in the meantime are dropped. The shards are shut down with close code
+    /// 1000 afterwards, so their sessions are not resumed, and
+    /// [`Client::start`] returns.
+    ///
+    /// Returns whether the event handlers finished within the timeout. Use
+    /// a [`Self::shutdown_hook`] to wait for them regardless.
+    ///
+    /// [`Client::start`]: crate::Client::start
+    #[instrument(skip(self))]
+    pub async fn shutdown_gracefully(&mut self, timeout: Duration) -> bool {
+        info!("Waiting for event handlers to finish before shutting down");
+
+        self.dispatches.close();
+        let drained = tokio::time::timeout(timeout, self.dispatches.drained()).await.is_ok();
+
+        if !drained {
+            warn!("Event handlers still running after {:?}, shutting down anyway", timeout);
+        }
+
+        self.shutdown_all().await;
+
+        drained
+    }
+
+    /// Returns a hook to wait for a [graceful shutdown] to finish.
+    ///
+    /// [graceful shutdown]: Self::shutdown_gracefully
+    pub fn shutdown_hook(&self) -> ShutdownHook {
+        ShutdownHook(Arc::clone(&self.dispatches))
+    }
+
     #[instrument(skip(self))]
     fn boot(&mut self, shard_info: [ShardId; 2]) {
         info!("Telling shard queuer to start shard {}", shard_info[0]);
@@ -343,10 +532,30 @@ impl Drop for ShardManager {
     }
 }
 
+/// A hook to wait for a [graceful shutdown] of a [`ShardManager`] to finish,
+/// i.e. for the event handlers that were running to finish.
+///
+/// [graceful shutdown]: ShardManager::shutdown_gracefully
+#[derive(Clone, Debug)]
+pub struct ShutdownHook(Arc<DispatchTracker>);
+
+impl ShutdownHook {
+    /// Waits until the shard manager shuts down gracefully and the event
+    /// handlers that were running finished.
+    ///
+    /// This may be after [`ShardManager::shutdown_gracefully`] returned, if
+    /// the handlers did not finish within its timeout.
+    pub async fn wait(&self) {
+        self.0.drained().await;
+    }
+}
+
 pub struct ShardManagerOptions<'a> {
     pub data: &'a Arc<RwLock<TypeMap>>,
     pub event_handler: &'a Option<Arc<dyn EventHandler>>,
     pub raw_event_handler: &'a Option<Arc<dyn RawEventHandler>>,
+    /// Whether every payload is passed to [`EventHandler::raw_dispatch`].
+    pub raw_dispatch: bool,
     #[cfg(feature = "framework")]
     pub framework: &'a Arc<Box<dyn Framework + Send + Sync>>,
     pub shard_index: u64,
@@ -357,4 +566,251 @@ pub struct ShardManagerOptions<'a> {
     pub ws_url: &'a Arc<Mutex<String>>,
     pub cache_and_http: &'a Arc<CacheAndHttp>,
     pub intents: GatewayIntents,
+    pub chunk_guilds_filter: ChunkGuildsFilter,
+    pub transport_compression: TransportCompression,
+    pub presence: CurrentPresence,
+    /// How many shards may identify at the same time, as given by the
+    /// `max_concurrency` of the [`SessionStartLimit`].
+    ///
+    /// [`SessionStartLimit`]: crate::model::gateway::SessionStartLimit
+    pub max_concurrency: u64,
+    /// How long shards wait before trying to reconnect.
+    pub reconnect_policy: ReconnectPolicy,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
+    use tokio::sync::{Mutex, RwLock};
+    use tokio::time::{sleep, timeout};
+    use typemap_rev::TypeMap;
+
+    use super::ShardManager;
+    use crate::client::bridge::gateway::event::{ClientEvent, ShardStageUpdateEvent};
+    use crate::client::bridge::gateway::{
+        ShardClientMessage,
+        ShardId,
+        ShardManagerMessage,
+        ShardMessenger,
+        ShardQueuerMessage,
+        ShardRunnerInfo,
+    };
+    use crate::client::dispatch::{dispatch, DispatchEvent};
+    use crate::client::{Context, EventHandler};
+    #[cfg(feature = "framework")]
+    use crate::framework::Framework;
+    use crate::gateway::{ConnectionStage, InterMessage};
+    #[cfg(feature = "framework")]
+    use crate::model::channel::Message;
+    use crate::CacheAndHttp;
+
+    /// Creates a manager of 2 shards, along with the receiving end of its
+    /// queuer and the sending end of its shutdown channel, in place of a
+    /// running queuer and monitor.
+    fn manager() -> (ShardManager, Receiver<ShardQueuerMessage>, Sender<ShardId>) {
+        let (monitor_tx, _) = mpsc::unbounded();
+        let (queuer_tx, queuer_rx) = mpsc::unbounded();
+        let (shutdown_tx, shutdown_rx) = mpsc::unbounded();
+
+        let manager = ShardManager {
+            monitor_tx,
+            runners: Arc::new(Mutex::new(HashMap::new())),
+            shard_index: 0,
+            shard_init: 2,
+            shard_total: 2,
+            shard_queuer: queuer_tx,
+            shard_shutdown: shutdown_rx,
+            dispatches: Arc::default(),
+            shards_ready: Arc::default(),
+        };
+
+        (manager, queuer_rx, shutdown_tx)
+    }
+
+    /// Registers a runner of the shard, returning the receiving end of its
+    /// channel in place of the runner.
+    async fn add_runner(
+        manager: &ShardManager,
+        shard_id: ShardId,
+        stage: ConnectionStage,
+    ) -> Receiver<InterMessage> {
+        let (runner_tx, runner_rx) = mpsc::unbounded();
+        let runner = ShardRunnerInfo {
+            latency: None,
+            runner_tx: ShardMessenger::new(runner_tx),
+            stage,
+            next_reconnect: None,
+        };
+        manager.runners.lock().await.insert(shard_id, runner);
+
+        runner_rx
+    }
+
+    /// An event handler taking its time to handle shard stage updates.
+    struct SlowHandler {
+        delay: Duration,
+        handled: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventHandler for SlowHandler {
+        async fn shard_stage_update(&self, _: Context, _: ShardStageUpdateEvent) {
+            sleep(self.delay).await;
+
+            self.handled.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[cfg(feature = "framework")]
+    struct NoFramework;
+
+    #[cfg(feature = "framework")]
+    #[async_trait]
+    impl Framework for NoFramework {
+        async fn dispatch(&self, _: Context, _: Message) {}
+    }
+
+    /// Dispatches a shard stage update the way a shard runner would.
+    async fn dispatch_stage_update(cache_and_http: &Arc<CacheAndHttp>, handler: &Arc<SlowHandler>) {
+        let event = ShardStageUpdateEvent {
+            new: ConnectionStage::Connected,
+            old: ConnectionStage::Handshake,
+            shard_id: ShardId(0),
+            latency: None,
+        };
+        #[cfg(feature = "framework")]
+        let framework = Arc::new(Box::new(NoFramework) as Box<dyn Framework + Send + Sync>);
+        let (runner_tx, _) = mpsc::unbounded();
+
+        dispatch(
+            DispatchEvent::Client(ClientEvent::ShardStageUpdate(event)),
+            #[cfg(feature = "framework")]
+            &framework,
+            &Arc::new(RwLock::new(TypeMap::new())),
+            &Some(Arc::clone(handler) as Arc<dyn EventHandler>),
+            &None,
+            &runner_tx,
+            0,
+            None,
+            Arc::clone(cache_and_http),
+        )
+        .await;
+    }
+
+    /// Creates a manager tracking the dispatches of the returned cache and
+    /// http, and a handler taking `delay` to handle an event.
+    fn graceful_manager(
+        delay: Duration,
+    ) -> (ShardManager, Receiver<ShardQueuerMessage>, Arc<CacheAndHttp>, Arc<SlowHandler>) {
+        let (mut manager, queuer_rx, _) = manager();
+        let cache_and_http = Arc::new(CacheAndHttp::default());
+        manager.dispatches = Arc::clone(&cache_and_http.dispatches);
+
+        let handler = Arc::new(SlowHandler {
+            delay,
+            handled: AtomicUsize::new(0),
+        });
+
+        (manager, queuer_rx, cache_and_http, handler)
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_gracefully() {
+        let (mut manager, mut queuer_rx, cache_and_http, handler) =
+            graceful_manager(Duration::from_millis(100));
+        let _runner_rx = add_runner(&manager, ShardId(0), ConnectionStage::Connected).await;
+
+        dispatch_stage_update(&cache_and_http, &handler).await;
+        assert_eq!(handler.handled.load(Ordering::SeqCst), 0);
+
+        // The shard reported its shutdown before it was awaited.
+        manager.shard_shutdown = {
+            let (shutdown_tx, shutdown_rx) = mpsc::unbounded();
+            shutdown_tx.unbounded_send(ShardId(0)).unwrap();
+            shutdown_rx
+        };
+
+        assert!(manager.shutdown_gracefully(Duration::from_secs(5)).await);
+        assert_eq!(handler.handled.load(Ordering::SeqCst), 1);
+
+        let msg = queuer_rx.try_next().unwrap().unwrap();
+        assert!(matches!(msg, ShardQueuerMessage::ShutdownShard(ShardId(0), 1000)));
+
+        // Events received afterwards are not dispatched anymore.
+        dispatch_stage_update(&cache_and_http, &handler).await;
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(handler.handled.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_gracefully_timeout() {
+        let (mut manager, _queuer_rx, cache_and_http, handler) =
+            graceful_manager(Duration::from_millis(500));
+        let hook = manager.shutdown_hook();
+
+        dispatch_stage_update(&cache_and_http, &handler).await;
+
+        assert!(!manager.shutdown_gracefully(Duration::from_millis(50)).await);
+        assert_eq!(handler.handled.load(Ordering::SeqCst), 0);
+
+        // The hook still waits for the handler to finish.
+        timeout(Duration::from_secs(5), hook.wait()).await.unwrap();
+        assert_eq!(handler.handled.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restart_shard() {
+        let (manager, mut queuer_rx, _) = manager();
+        let mut runner_rx = add_runner(&manager, ShardId(1), ConnectionStage::Resuming).await;
+
+        manager.restart_shard(ShardId(1)).await.unwrap();
+
+        // The runner is shut down rather than closed, so it does not reconnect.
+        let msg = match runner_rx.try_next().unwrap().unwrap() {
+            InterMessage::Client(msg) => *msg,
+            msg => panic!("unexpected message: {:?}", msg),
+        };
+        assert!(matches!(
+            msg,
+            ShardClientMessage::Manager(ShardManagerMessage::Shutdown(ShardId(1), 4000))
+        ));
+        assert!(!manager.has(ShardId(1)).await);
+
+        // A new runner is queued to identify.
+        let msg = queuer_rx.try_next().unwrap().unwrap();
+        assert!(matches!(msg, ShardQueuerMessage::Start(ShardId(1), ShardId(2))));
+        assert!(queuer_rx.try_next().is_err());
+
+        drop(queuer_rx);
+        assert!(manager.restart_shard(ShardId(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        let (mut manager, mut queuer_rx, shutdown_tx) = manager();
+        let _runner_rx = add_runner(&manager, ShardId(0), ConnectionStage::Connected).await;
+        let _runner_rx = add_runner(&manager, ShardId(1), ConnectionStage::Connected).await;
+
+        // A shard restarted without waiting reported its shutdown late.
+        shutdown_tx.unbounded_send(ShardId(0)).unwrap();
+        shutdown_tx.unbounded_send(ShardId(1)).unwrap();
+
+        manager.shutdown(ShardId(1), 1000).await;
+
+        let msg = queuer_rx.try_next().unwrap().unwrap();
+        assert!(matches!(msg, ShardQueuerMessage::ShutdownShard(ShardId(1), 1000)));
+        // A deliberate shutdown does not queue the shard again.
+        assert!(queuer_rx.try_next().is_err());
+
+        let mut stages = HashMap::new();
+        stages.insert(ShardId(0), ConnectionStage::Connected);
+        assert_eq!(manager.shard_stages().await, stages);
+    }
 }
@@ -71,6 +71,7 @@ impl ShardManagerMonitor {
                     id,
                     latency,
                     stage,
+                    next_reconnect,
                 } => {
                     let manager = self.manager.lock().await;
                     let mut runners = manager.runners.lock().await;
@@ -78,6 +79,7 @@ impl ShardManagerMonitor {
                     if let Some(runner) = runners.get_mut(&id) {
                         runner.latency = latency;
                         runner.stage = stage;
+                        runner.next_reconnect = next_reconnect;
                     }
                 },
                 ShardManagerMessage::Shutdown(shard_id, code) => {
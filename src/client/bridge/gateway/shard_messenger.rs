@@ -1,7 +1,8 @@
 use async_tungstenite::tungstenite::Message;
 use futures::channel::mpsc::{TrySendError, UnboundedSender as Sender};
 
-use super::{ChunkGuildFilter, ShardClientMessage, ShardRunnerMessage};
+use super::member_requests::{request_nonce, validate_filter};
+use super::{ChunkGuildFilter, GuildMembersRequest, ShardClientMessage, ShardRunnerMessage};
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::collector::ComponentInteractionFilter;
 #[cfg(feature = "collector")]
@@ -108,6 +109,73 @@ impl ShardMessenger {
         });
     }
 
+    /// Requests the members of a guild, and returns them as a [`Stream`] once
+    /// the shard receives them.
+    ///
+    /// Unlike [`Self::chunk_guild`], the received [`GuildMembersChunkEvent`]s
+    /// are collected into the returned [`GuildMembersRequest`], which ends
+    /// after the last chunk answering this request. The chunks are still
+    /// dispatched to the event handler as usual.
+    ///
+    /// If `presences` is `true`, the presences of the members are requested
+    /// too, which requires the [`GatewayIntents::GUILD_PRESENCES`] intent.
+    ///
+    /// # Examples
+    ///
+    /// Fetching the members whose username starts with `"do"`:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::bridge::gateway::ShardMessenger;
+    /// # async fn run(shard: ShardMessenger) -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::StreamExt;
+    /// use serenity::client::bridge::gateway::ChunkGuildFilter;
+    /// use serenity::model::id::GuildId;
+    ///
+    /// let filter = ChunkGuildFilter::Query("do".to_owned());
+    /// let mut members =
+    ///     shard.request_guild_members(GuildId(81384788765712384), Some(20), filter, false)?;
+    ///
+    /// while let Some(member) = members.next().await {
+    ///     println!("{}", member.user.name);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::TooManyUserIds`] if more than 100 users are
+    /// requested by Id.
+    ///
+    /// [`ModelError::TooManyUserIds`]: crate::model::ModelError::TooManyUserIds
+    /// [`Stream`]: futures::stream::Stream
+    /// [`GuildMembersChunkEvent`]: crate::model::event::GuildMembersChunkEvent
+    /// [`GatewayIntents::GUILD_PRESENCES`]: super::GatewayIntents::GUILD_PRESENCES
+    pub fn request_guild_members(
+        &self,
+        guild_id: GuildId,
+        limit: Option<u16>,
+        filter: ChunkGuildFilter,
+        presences: bool,
+    ) -> crate::Result<GuildMembersRequest> {
+        validate_filter(&filter)?;
+
+        let (request, sender) = GuildMembersRequest::new();
+
+        // If the shard is gone, the request ends as soon as it is polled.
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RequestGuildMembers {
+            guild_id,
+            limit,
+            filter,
+            presences,
+            nonce: request_nonce(),
+            sender,
+        });
+
+        Ok(request)
+    }
+
     /// Sets the user's current activity, if any.
     ///
     /// Other presence settings are maintained.
@@ -217,6 +285,49 @@ impl ShardMessenger {
         let _ = self.send_to_shard(ShardRunnerMessage::SetStatus(online_status));
     }
 
+    /// Joins or moves to a voice channel of a guild, or leaves voice in the
+    /// guild if `channel_id` is `None`.
+    ///
+    /// This only updates the voice state of the current user, which is enough
+    /// to appear in a voice or stage channel; it does not connect to the voice
+    /// server to send or receive audio. The resulting [`VoiceStateUpdate`] and
+    /// [`VoiceServerUpdate`] events are dispatched to the event handler as
+    /// usual.
+    ///
+    /// **Note**: If a voice manager such as songbird is in use, use it to join
+    /// channels instead, as it keeps track of its own voice state.
+    ///
+    /// # Examples
+    ///
+    /// Joining a voice channel muted:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::bridge::gateway::ShardMessenger;
+    /// # fn run(shard: ShardMessenger) {
+    /// use serenity::model::id::{ChannelId, GuildId};
+    ///
+    /// shard.update_voice_state(GuildId(81384788765712384), Some(ChannelId(7)), true, false);
+    /// # }
+    /// ```
+    ///
+    /// [`VoiceStateUpdate`]: crate::model::event::Event::VoiceStateUpdate
+    /// [`VoiceServerUpdate`]: crate::model::event::Event::VoiceServerUpdate
+    pub fn update_voice_state(
+        &self,
+        guild_id: GuildId,
+        channel_id: Option<ChannelId>,
+        self_mute: bool,
+        self_deaf: bool,
+    ) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::UpdateVoiceState {
+            guild_id,
+            channel_id,
+            self_mute,
+            self_deaf,
+        });
+    }
+
     /// Shuts down the websocket by attempting to cleanly close the
     /// connection.
     pub fn shutdown_clean(&self) {
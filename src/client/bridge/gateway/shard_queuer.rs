@@ -17,7 +17,10 @@ use tracing::{debug, info, instrument, warn};
 use typemap_rev::TypeMap;
 
 use super::{
+    ChunkGuildsFilter,
     GatewayIntents,
+    ReconnectAttempts,
+    ReconnectPolicy,
     ShardClientMessage,
     ShardId,
     ShardManagerMessage,
@@ -33,7 +36,7 @@ use crate::client::{EventHandler, RawEventHandler};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
 use crate::gateway::ConnectionStage;
-use crate::gateway::{InterMessage, Shard};
+use crate::gateway::{CurrentPresence, InterMessage, Shard, TransportCompression};
 use crate::internal::prelude::*;
 use crate::CacheAndHttp;
 
@@ -45,6 +48,10 @@ const WAIT_BETWEEN_BOOTS_IN_SECONDS: u64 = 5;
 /// A shard queuer instance _should_ be run in its own thread, due to the
 /// blocking nature of the loop itself as well as a 5 second thread sleep
 /// between shard starts.
+///
+/// Shards are grouped into identify buckets by `shard_id % max_concurrency`;
+/// the 5 seconds only need to pass between starts of shards in the same
+/// bucket, so up to [`Self::max_concurrency`] shards may start at once.
 pub struct ShardQueuer {
     /// A copy of [`Client::data`] to be given to runners for contextual
     /// dispatching.
@@ -61,6 +68,8 @@ pub struct ShardQueuer {
     ///
     /// [`Client`]: crate::Client
     pub raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    /// Whether every payload is passed to [`EventHandler::raw_dispatch`].
+    pub raw_dispatch: bool,
     /// A copy of the framework
     #[cfg(feature = "framework")]
     pub framework: Arc<Box<dyn Framework + Send + Sync>>,
@@ -68,6 +77,18 @@ pub struct ShardQueuer {
     ///
     /// This is used to determine how long to wait between shard IDENTIFYs.
     pub last_start: Option<Instant>,
+    /// The instant that a shard of each identify bucket was last started,
+    /// keyed by bucket.
+    pub last_starts: HashMap<u64, Instant>,
+    /// How many shards may identify at the same time, as given by the
+    /// gateway's session start limit.
+    pub max_concurrency: u64,
+    /// How long shards wait before trying to reconnect.
+    pub reconnect_policy: ReconnectPolicy,
+    /// The consecutive reconnection attempts of each shard, keyed by shard
+    /// Id, kept here as the runner of a shard is replaced when it identifies
+    /// again.
+    pub reconnect_attempts: HashMap<u64, Arc<ReconnectAttempts>>,
     /// A copy of the sender channel to communicate with the
     /// [`ShardManagerMonitor`].
     ///
@@ -88,6 +109,12 @@ pub struct ShardQueuer {
     pub ws_url: Arc<Mutex<String>>,
     pub cache_and_http: Arc<CacheAndHttp>,
     pub intents: GatewayIntents,
+    /// The guilds whose members the shards request after receiving them.
+    pub chunk_guilds_filter: ChunkGuildsFilter,
+    /// How the gateway compresses the payloads sent to the shards.
+    pub transport_compression: TransportCompression,
+    /// The presence the shards identify with.
+    pub presence: CurrentPresence,
 }
 
 impl ShardQueuer {
@@ -98,7 +125,8 @@ impl ShardQueuer {
     ///
     /// If a [`ShardQueuerMessage::Start`] is received, this will:
     ///
-    /// 1. Check how much time has passed since the last shard was started
+    /// 1. Check how much time has passed since the last shard of the same
+    /// identify bucket was started
     /// 2. If the amount of time is less than the ratelimit, it will sleep until
     /// that time has passed
     /// 3. Start the shard by ID
@@ -142,30 +170,22 @@ impl ShardQueuer {
     }
 
     #[instrument(skip(self))]
-    async fn check_last_start(&mut self) {
-        let instant = match self.last_start {
-            Some(instant) => instant,
-            None => return,
-        };
-
-        // We must wait 5 seconds between IDENTIFYs to avoid session
-        // invalidations.
-        let duration = Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
-        let elapsed = instant.elapsed();
+    async fn check_last_start(&mut self, shard_id: u64) {
+        let bucket = identify_bucket(shard_id, self.max_concurrency);
+        let now = Instant::now();
+        let ready_at = identify_ready_at(&self.last_starts, bucket, now);
 
-        if elapsed >= duration {
-            return;
+        // We must wait 5 seconds between IDENTIFYs of the same bucket to
+        // avoid session invalidations.
+        if ready_at > now {
+            sleep(ready_at - now).await;
         }
-
-        let to_sleep = duration - elapsed;
-
-        sleep(to_sleep).await;
     }
 
     #[instrument(skip(self))]
     async fn checked_start(&mut self, id: u64, total: u64) {
         debug!("[Shard Queuer] Checked start for shard {} out of {}", id, total);
-        self.check_last_start().await;
+        self.check_last_start(id).await;
 
         if let Err(why) = self.start(id, total).await {
             warn!("[Shard Queuer] Err starting shard {}: {:?}", id, why);
@@ -174,25 +194,30 @@ impl ShardQueuer {
             self.queue.push_back((id, total));
         }
 
-        self.last_start = Some(Instant::now());
+        let now = Instant::now();
+        self.last_starts.insert(identify_bucket(id, self.max_concurrency), now);
+        self.last_start = Some(now);
     }
 
     #[instrument(skip(self))]
     async fn start(&mut self, shard_id: u64, shard_total: u64) -> Result<()> {
         let shard_info = [shard_id, shard_total];
 
-        let shard = Shard::new(
+        let mut shard = Shard::new_with_compression(
             Arc::clone(&self.ws_url),
             &self.cache_and_http.http.token,
             shard_info,
             self.intents,
+            self.transport_compression,
         )
         .await?;
+        shard.set_presence(self.presence.1, self.presence.0.clone());
 
         let mut runner = ShardRunner::new(ShardRunnerOptions {
             data: Arc::clone(&self.data),
             event_handler: self.event_handler.as_ref().map(|eh| Arc::clone(eh)),
             raw_event_handler: self.raw_event_handler.as_ref().map(|rh| Arc::clone(rh)),
+            raw_dispatch: self.raw_dispatch,
             #[cfg(feature = "framework")]
             framework: Arc::clone(&self.framework),
             manager_tx: self.manager_tx.clone(),
@@ -200,12 +225,16 @@ impl ShardQueuer {
             voice_manager: self.voice_manager.clone(),
             shard,
             cache_and_http: Arc::clone(&self.cache_and_http),
+            chunk_guilds_filter: self.chunk_guilds_filter.clone(),
+            reconnect_policy: self.reconnect_policy,
+            reconnect_attempts: Arc::clone(self.reconnect_attempts.entry(shard_id).or_default()),
         });
 
         let runner_info = ShardRunnerInfo {
             latency: None,
             runner_tx: ShardMessenger::new(runner.runner_tx()),
             stage: ConnectionStage::Disconnected,
+            next_reconnect: None,
         };
 
         tokio::spawn(async move {
@@ -263,3 +292,62 @@ impl ShardQueuer {
         }
     }
 }
+
+/// The identify bucket of a shard, given the maximum concurrency of the
+/// session start limit.
+fn identify_bucket(shard_id: u64, max_concurrency: u64) -> u64 {
+    shard_id % max_concurrency.max(1)
+}
+
+/// The instant at which a shard of the given bucket may identify.
+fn identify_ready_at(last_starts: &HashMap<u64, Instant>, bucket: u64, now: Instant) -> Instant {
+    let wait = Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
+
+    match last_starts.get(&bucket) {
+        Some(&last) if last + wait > now => last + wait,
+        _ => now,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use tokio::time::{Duration, Instant};
+
+    use super::{identify_bucket, identify_ready_at};
+
+    fn schedule(shards: u64, max_concurrency: u64) -> Vec<u64> {
+        let start = Instant::now();
+        let mut now = start;
+        let mut last_starts = HashMap::new();
+
+        (0..shards)
+            .map(|shard_id| {
+                let bucket = identify_bucket(shard_id, max_concurrency);
+                now = identify_ready_at(&last_starts, bucket, now);
+                last_starts.insert(bucket, now);
+
+                (now - start).as_secs()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identify_schedule() {
+        assert_eq!(schedule(16, 4), vec![0, 0, 0, 0, 5, 5, 5, 5, 10, 10, 10, 10, 15, 15, 15, 15]);
+        assert_eq!(schedule(4, 1), vec![0, 5, 10, 15]);
+        assert_eq!(schedule(3, 0), vec![0, 5, 10]);
+    }
+
+    #[test]
+    fn test_identify_ready_at_elapsed() {
+        let now = Instant::now();
+        let mut last_starts = HashMap::new();
+        last_starts.insert(0, now);
+
+        let later = now + Duration::from_secs(6);
+        assert_eq!(identify_ready_at(&last_starts, 0, later), later);
+        assert_eq!(identify_ready_at(&last_starts, 1, now), now);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, convert::TryFrom, sync::Arc, time::Instant};
 
 use async_tungstenite::tungstenite::{
     self,
@@ -8,15 +8,31 @@ use async_tungstenite::tungstenite::{
 use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
 use futures::{SinkExt, StreamExt};
 use serde::Deserialize;
+use serde_json::Value;
 use tokio::sync::RwLock;
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(feature = "tokio")]
+use tokio::time::sleep;
 use tracing::{debug, error, info, instrument, trace, warn};
 use typemap_rev::TypeMap;
 
 use super::event::{ClientEvent, ShardStageUpdateEvent};
-use super::{ShardClientMessage, ShardId, ShardManagerMessage, ShardRunnerMessage};
+use super::member_requests::MemberRequests;
+use super::{
+    chunk_nonce,
+    ChunkGuildsFilter,
+    ChunkQueue,
+    ReconnectAttempts,
+    ReconnectPolicy,
+    ShardClientMessage,
+    ShardId,
+    ShardManagerMessage,
+    ShardRunnerMessage,
+};
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::dispatch::{dispatch, DispatchEvent};
+use crate::client::dispatch::{context, dispatch, spawn_tracked, DispatchEvent};
 use crate::client::{EventHandler, RawEventHandler};
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::collector::ComponentInteractionFilter;
@@ -24,10 +40,19 @@ use crate::collector::ComponentInteractionFilter;
 use crate::collector::{EventFilter, LazyArc, LazyReactionAction, MessageFilter, ReactionFilter};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
-use crate::gateway::{GatewayError, InterMessage, ReconnectType, Shard, ShardAction};
+use crate::gateway::{
+    chunk_guild_payload,
+    voice_state_payload,
+    ConnectionStage,
+    GatewayError,
+    InterMessage,
+    ReconnectType,
+    Shard,
+    ShardAction,
+};
 use crate::internal::prelude::*;
-use crate::internal::ws_impl::{ReceiverExt, SenderExt};
-use crate::model::event::{Event, GatewayEvent};
+use crate::internal::ws_impl::{Payload, SenderExt};
+use crate::model::event::{Event, GatewayEvent, UnknownEvent};
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::model::interactions::{Interaction, InteractionType};
 use crate::CacheAndHttp;
@@ -37,6 +62,7 @@ pub struct ShardRunner {
     data: Arc<RwLock<TypeMap>>,
     event_handler: Option<Arc<dyn EventHandler>>,
     raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    raw_dispatch: bool,
     #[cfg(feature = "framework")]
     framework: Arc<Box<dyn Framework + Send + Sync>>,
     manager_tx: Sender<ShardManagerMessage>,
@@ -48,6 +74,11 @@ pub struct ShardRunner {
     #[cfg(feature = "voice")]
     voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync + 'static>>,
     cache_and_http: Arc<CacheAndHttp>,
+    chunk_queue: ChunkQueue,
+    member_requests: MemberRequests,
+    reconnect_policy: ReconnectPolicy,
+    reconnect_attempts: Arc<ReconnectAttempts>,
+    next_reconnect: Option<Instant>,
     #[cfg(feature = "collector")]
     event_filters: Vec<EventFilter>,
     #[cfg(feature = "collector")]
@@ -69,6 +100,7 @@ impl ShardRunner {
             data: opt.data,
             event_handler: opt.event_handler,
             raw_event_handler: opt.raw_event_handler,
+            raw_dispatch: opt.raw_dispatch,
             #[cfg(feature = "framework")]
             framework: opt.framework,
             manager_tx: opt.manager_tx,
@@ -76,6 +108,11 @@ impl ShardRunner {
             #[cfg(feature = "voice")]
             voice_manager: opt.voice_manager,
             cache_and_http: opt.cache_and_http,
+            chunk_queue: ChunkQueue::new(opt.chunk_guilds_filter),
+            member_requests: MemberRequests::default(),
+            reconnect_policy: opt.reconnect_policy,
+            reconnect_attempts: opt.reconnect_attempts,
+            next_reconnect: None,
             #[cfg(feature = "collector")]
             event_filters: Vec::new(),
             #[cfg(feature = "collector")]
@@ -120,6 +157,8 @@ impl ShardRunner {
                 return Ok(());
             }
 
+            self.send_chunk_requests().await;
+
             // check heartbeat
             if !self.shard.check_heartbeat().await {
                 warn!("[ShardRunner {:?}] Error heartbeating", self.shard.shard_info(),);
@@ -128,19 +167,39 @@ impl ShardRunner {
             }
 
             let pre = self.shard.stage();
+            let pre_latency = self.shard.latency();
             let (event, action, successful) = self.recv_event().await?;
             let post = self.shard.stage();
 
-            if post != pre {
+            if post != pre && post == ConnectionStage::Connected {
+                self.reconnect_attempts.reset();
+                self.next_reconnect = None;
+            }
+
+            // The latency changes with every acknowledged heartbeat.
+            if post != pre || self.shard.latency() != pre_latency {
                 self.update_manager();
+            }
 
+            if post != pre {
                 let e = ClientEvent::ShardStageUpdate(ShardStageUpdateEvent {
                     new: post,
                     old: pre,
                     shard_id: ShardId(self.shard.shard_info()[0]),
+                    latency: self.shard.latency(),
                 });
 
                 self.dispatch(DispatchEvent::Client(e)).await;
+
+                if post == ConnectionStage::Connected {
+                    let shard_id = self.shard.shard_info()[0];
+
+                    if let Some(total) = self.cache_and_http.shards_ready.connected(shard_id) {
+                        let e = ClientEvent::ShardsReady(total as u32);
+
+                        self.dispatch(DispatchEvent::Client(e)).await;
+                    }
+                }
             }
 
             match action {
@@ -158,7 +217,7 @@ impl ShardRunner {
                         match self.shard.reconnection_type() {
                             ReconnectType::Reidentify => return self.request_restart().await,
                             ReconnectType::Resume => {
-                                if let Err(why) = self.shard.resume().await {
+                                if let Err(why) = self.resume().await {
                                     warn!(
                                         "[ShardRunner {:?}] Resume failed, reidentifying: {:?}",
                                         self.shard.shard_info(),
@@ -180,6 +239,12 @@ impl ShardRunner {
                     self.handle_filters(&event);
                 }
 
+                match event {
+                    Event::GuildCreate(ref event) => self.chunk_queue.guild_create(event.guild.id),
+                    Event::GuildMembersChunk(ref event) => self.member_requests.receive(event),
+                    _ => {},
+                }
+
                 self.dispatch(DispatchEvent::Model(event)).await;
             }
 
@@ -269,7 +334,7 @@ impl ShardRunner {
     async fn action(&mut self, action: &ShardAction) -> Result<()> {
         match *action {
             ShardAction::Reconnect(ReconnectType::Reidentify) => self.request_restart().await,
-            ShardAction::Reconnect(ReconnectType::Resume) => self.shard.resume().await,
+            ShardAction::Reconnect(ReconnectType::Resume) => self.resume().await,
             ShardAction::Heartbeat => self.shard.heartbeat().await,
             ShardAction::Identify => self.shard.identify().await,
         }
@@ -343,11 +408,98 @@ impl ShardRunner {
             &self.raw_event_handler,
             &self.runner_tx,
             self.shard.shard_info()[0],
+            self.shard.latency(),
             Arc::clone(&self.cache_and_http),
         )
         .await;
     }
 
+    /// Passes a payload received over the gateway to
+    /// [`EventHandler::raw_dispatch`], waiting for it before the payload is
+    /// deserialized and dispatched.
+    #[instrument(skip(self, payload))]
+    async fn dispatch_raw(&self, payload: &Value) {
+        let event_handler = match &self.event_handler {
+            Some(event_handler) => Arc::clone(event_handler),
+            None => return,
+        };
+
+        let opcode = match payload.get("op").and_then(Value::as_u64).map(u8::try_from) {
+            Some(Ok(opcode)) => opcode,
+            _ => return,
+        };
+
+        // Shutting down gracefully, so no more events are dispatched.
+        let guard = match self.cache_and_http.dispatches.start() {
+            Some(guard) => guard,
+            None => return,
+        };
+
+        let ctx = context(
+            &self.data,
+            &self.runner_tx,
+            self.shard.shard_info()[0],
+            self.shard.latency(),
+            &self.cache_and_http.http,
+            #[cfg(feature = "cache")]
+            &self.cache_and_http.cache,
+        );
+
+        event_handler.raw_dispatch(ctx, opcode, payload).await;
+
+        drop(guard);
+    }
+
+    /// Dispatches a dispatch that failed to deserialize to the raw event
+    /// handler, as an [`Event::Unknown`] carrying the error.
+    fn dispatch_malformed(&self, unknown: UnknownEvent) {
+        let raw_event_handler = match &self.raw_event_handler {
+            Some(raw_event_handler) => Arc::clone(raw_event_handler),
+            None => return,
+        };
+
+        // Shutting down gracefully, so no more events are dispatched.
+        let guard = match self.cache_and_http.dispatches.start() {
+            Some(guard) => guard,
+            None => return,
+        };
+
+        let ctx = context(
+            &self.data,
+            &self.runner_tx,
+            self.shard.shard_info()[0],
+            self.shard.latency(),
+            &self.cache_and_http.http,
+            #[cfg(feature = "cache")]
+            &self.cache_and_http.cache,
+        );
+
+        spawn_tracked(&guard, async move {
+            raw_event_handler.raw_event(ctx, Event::Unknown(unknown)).await;
+        });
+    }
+
+    /// Sends as many of the queued member chunk requests as the pacing allows
+    /// for now, registering their nonces with the cache to track them.
+    #[instrument(skip(self))]
+    async fn send_chunk_requests(&mut self) {
+        while let Some((guild_id, filter)) = self.chunk_queue.next(Instant::now()) {
+            let nonce = chunk_nonce(guild_id);
+
+            #[cfg(feature = "cache")]
+            self.cache_and_http.cache.expect_member_chunks(guild_id, nonce.clone()).await;
+
+            if let Err(why) = self.shard.chunk_guild(guild_id, None, filter, Some(&nonce)).await {
+                warn!(
+                    "[ShardRunner {:?}] Failed to request members of {}: {:?}",
+                    self.shard.shard_info(),
+                    guild_id,
+                    why,
+                );
+            }
+        }
+    }
+
     // Handles a received value over the shard runner rx channel.
     //
     // Returns a boolean on whether the shard runner can continue.
@@ -403,6 +555,20 @@ impl ShardRunner {
                 }) => {
                     self.shard.chunk_guild(guild_id, limit, filter, nonce.as_deref()).await.is_ok()
                 },
+                ShardClientMessage::Runner(ShardRunnerMessage::RequestGuildMembers {
+                    guild_id,
+                    limit,
+                    filter,
+                    presences,
+                    nonce,
+                    sender,
+                }) => {
+                    let payload =
+                        chunk_guild_payload(guild_id, limit, filter, Some(&nonce), presences);
+                    self.member_requests.insert(nonce, sender);
+
+                    self.shard.client.send_json(&payload).await.is_ok()
+                },
                 ShardClientMessage::Runner(ShardRunnerMessage::Close(code, reason)) => {
                     let reason = reason.unwrap_or_else(String::new);
                     let close = CloseFrame {
@@ -442,6 +608,16 @@ impl ShardRunner {
 
                     self.shard.update_presence().await.is_ok()
                 },
+                ShardClientMessage::Runner(ShardRunnerMessage::UpdateVoiceState {
+                    guild_id,
+                    channel_id,
+                    self_mute,
+                    self_deaf,
+                }) => {
+                    let payload = voice_state_payload(guild_id, channel_id, self_mute, self_deaf);
+
+                    self.shard.client.send_json(&payload).await.is_ok()
+                },
                 #[cfg(feature = "collector")]
                 ShardClientMessage::Runner(ShardRunnerMessage::SetEventFilter(collector)) => {
                     self.event_filters.push(collector);
@@ -539,20 +715,66 @@ impl ShardRunner {
         Ok(true)
     }
 
+    /// Deserializes the event of a payload, after giving the payload to the
+    /// raw handlers that need it.
+    ///
+    /// Returns `None` if the event was malformed and given to the raw event
+    /// handler instead.
+    async fn deserialize_payload(&self, payload: Payload) -> Result<Option<GatewayEvent>> {
+        if !self.raw_dispatch && self.raw_event_handler.is_none() {
+            return payload.into_event().map(Some);
+        }
+
+        let value = payload.into_value()?;
+
+        if self.raw_dispatch {
+            self.dispatch_raw(&value).await;
+        }
+
+        if self.raw_event_handler.is_none() {
+            return Ok(Some(GatewayEvent::deserialize(value)?));
+        }
+
+        // The raw event handler is given dispatches that fail to deserialize
+        // as well, so the payload is kept until then.
+        match GatewayEvent::deserialize(&value) {
+            Ok(event) => Ok(Some(event)),
+            Err(why) => match UnknownEvent::from_dispatch(value) {
+                Some(mut unknown) => {
+                    warn!(
+                        "[ShardRunner {:?}] Failed to deserialize {} event: {:?}",
+                        self.shard.shard_info(),
+                        unknown.kind,
+                        why,
+                    );
+
+                    unknown.error = Some(Arc::new(why));
+                    self.dispatch_malformed(unknown);
+
+                    Ok(None)
+                },
+                None => Err(why.into()),
+            },
+        }
+    }
+
     /// Returns a received event, as well as whether reading the potentially
     /// present event was successful.
     #[instrument(skip(self))]
     async fn recv_event(&mut self) -> Result<(Option<Event>, Option<ShardAction>, bool)> {
-        let gw_event = match self.shard.client.recv_json().await {
-            Ok(Some(value)) => GatewayEvent::deserialize(value).map(Some).map_err(From::from),
+        let gw_event = match self.shard.recv_payload().await {
+            Ok(Some(payload)) => self.deserialize_payload(payload).await,
             Ok(None) => Ok(None),
-            Err(Error::Tungstenite(TungsteniteError::Io(_))) => {
+            // A corrupted compression stream can only be recovered from by
+            // connecting again.
+            Err(Error::Tungstenite(TungsteniteError::Io(_)))
+            | Err(Error::Gateway(GatewayError::DecompressionFailed)) => {
                 debug!("Attempting to auto-reconnect");
 
                 match self.shard.reconnection_type() {
                     ReconnectType::Reidentify => return Ok((None, None, false)),
                     ReconnectType::Resume => {
-                        if let Err(why) = self.shard.resume().await {
+                        if let Err(why) = self.resume().await {
                             warn!("Failed to resume: {:?}", why);
 
                             return Ok((None, None, false));
@@ -641,6 +863,8 @@ impl ShardRunner {
 
     #[instrument(skip(self))]
     async fn request_restart(&mut self) -> Result<()> {
+        // The restarted shard identifies again, so it waits longer.
+        self.wait_to_reconnect(ReconnectType::Reidentify).await;
         self.update_manager();
 
         debug!("[ShardRunner {:?}] Requesting restart", self.shard.shard_info(),);
@@ -666,8 +890,36 @@ impl ShardRunner {
             id: ShardId(self.shard.shard_info()[0]),
             latency: self.shard.latency(),
             stage: self.shard.stage(),
+            next_reconnect: self.next_reconnect,
         });
     }
+
+    /// Resumes the session of the shard once the [`ReconnectPolicy`] allows
+    /// it.
+    async fn resume(&mut self) -> Result<()> {
+        self.wait_to_reconnect(ReconnectType::Resume).await;
+
+        self.shard.resume().await
+    }
+
+    /// Waits until the next attempt to reconnect that the [`ReconnectPolicy`]
+    /// schedules, letting the manager know when that is.
+    async fn wait_to_reconnect(&mut self, reconnect: ReconnectType) {
+        let now = Instant::now();
+        let next = self.reconnect_attempts.next_attempt(&self.reconnect_policy, &reconnect, now);
+
+        debug!(
+            "[ShardRunner {:?}] Waiting {:?} to reconnect with {:?}",
+            self.shard.shard_info(),
+            next - now,
+            reconnect,
+        );
+
+        self.next_reconnect = Some(next);
+        self.update_manager();
+
+        sleep(next - now).await;
+    }
 }
 
 /// Options to be passed to [`ShardRunner::new`].
@@ -675,6 +927,8 @@ pub struct ShardRunnerOptions {
     pub data: Arc<RwLock<TypeMap>>,
     pub event_handler: Option<Arc<dyn EventHandler>>,
     pub raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    /// Whether every payload is passed to [`EventHandler::raw_dispatch`].
+    pub raw_dispatch: bool,
     #[cfg(feature = "framework")]
     pub framework: Arc<Box<dyn Framework + Send + Sync>>,
     pub manager_tx: Sender<ShardManagerMessage>,
@@ -682,4 +936,7 @@ pub struct ShardRunnerOptions {
     #[cfg(feature = "voice")]
     pub voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync>>,
     pub cache_and_http: Arc<CacheAndHttp>,
+    pub chunk_guilds_filter: ChunkGuildsFilter,
+    pub reconnect_policy: ReconnectPolicy,
+    pub reconnect_attempts: Arc<ReconnectAttempts>,
 }
@@ -1,12 +1,14 @@
 use async_tungstenite::tungstenite::Message;
+use futures::channel::mpsc::UnboundedSender as Sender;
 
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::collector::ComponentInteractionFilter;
 #[cfg(feature = "collector")]
 use crate::collector::{EventFilter, MessageFilter, ReactionFilter};
 use crate::model::{
+    event::GuildMembersChunkEvent,
     gateway::Activity,
-    id::{GuildId, UserId},
+    id::{ChannelId, GuildId, UserId},
     user::OnlineStatus,
 };
 
@@ -43,6 +45,22 @@ pub enum ShardRunnerMessage {
         /// [`GuildMembersChunkEvent`]: crate::model::event::GuildMembersChunkEvent
         nonce: Option<String>,
     },
+    /// Indicates that the client is to request the members of a guild, and
+    /// forward the [`GuildMembersChunkEvent`]s answering the request.
+    RequestGuildMembers {
+        /// The Id of the guild whose members to request.
+        guild_id: GuildId,
+        /// The maximum number of members to receive.
+        limit: Option<u16>,
+        /// A filter to apply to the returned members.
+        filter: ChunkGuildFilter,
+        /// Whether to request the presences of the members too.
+        presences: bool,
+        /// The nonce identifying the chunks answering the request.
+        nonce: String,
+        /// The sender to forward the chunks to.
+        sender: Sender<GuildMembersChunkEvent>,
+    },
     /// Indicates that the client is to close with the given status code and
     /// reason.
     ///
@@ -61,6 +79,17 @@ pub enum ShardRunnerMessage {
     SetPresence(OnlineStatus, Option<Activity>),
     /// Indicates that the client is to update the shard's presence's status.
     SetStatus(OnlineStatus),
+    /// Indicates that the client is to join, move to, or leave a voice channel.
+    UpdateVoiceState {
+        /// The Id of the guild of the voice channel.
+        guild_id: GuildId,
+        /// The Id of the voice channel to join, or `None` to leave voice.
+        channel_id: Option<ChannelId>,
+        /// Whether the current user is muted.
+        self_mute: bool,
+        /// Whether the current user is deafened.
+        self_deaf: bool,
+    },
     /// Sends a new filter for events to the shard.
     #[cfg(feature = "collector")]
     #[cfg_attr(docsrs, doc(cfg(feature = "collector")))]
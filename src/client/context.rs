@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::channel::mpsc::UnboundedSender as Sender;
 use tokio::sync::RwLock;
@@ -16,6 +17,7 @@ use crate::collector::{MessageFilter, ReactionFilter};
 use crate::gateway::InterMessage;
 use crate::http::Http;
 use crate::model::prelude::*;
+use crate::Result;
 
 /// The context is a general utility struct provided on event dispatches, which
 /// helps with dealing with the current "context" of the event dispatch.
@@ -42,18 +44,31 @@ pub struct Context {
     pub shard: ShardMessenger,
     /// The ID of the shard this context is related to.
     pub shard_id: u64,
+    /// The client's HTTP client.
+    ///
+    /// **Note**: Prefer [`Self::http`], as this field will be made private in
+    /// the next major version.
+    #[deprecated(note = "use `Context::http` instead, this field will be made private")]
     pub http: Arc<Http>,
+    /// The client's cache.
+    ///
+    /// **Note**: Prefer [`Self::cache`], as this field will be made private in
+    /// the next major version.
     #[cfg(feature = "cache")]
+    #[deprecated(note = "use `Context::cache` instead, this field will be made private")]
     pub cache: Arc<Cache>,
+    shard_latency: Option<Duration>,
 }
 
 impl Context {
     /// Create a new Context to be passed to an event handler.
     #[cfg(all(feature = "cache", feature = "gateway"))]
+    #[allow(deprecated)]
     pub(crate) fn new(
         data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
+        shard_latency: Option<Duration>,
         http: Arc<Http>,
         cache: Arc<Cache>,
     ) -> Context {
@@ -63,24 +78,29 @@ impl Context {
             data,
             http,
             cache,
+            shard_latency,
         }
     }
 
     #[cfg(all(not(feature = "cache"), not(feature = "gateway")))]
+    #[allow(deprecated)]
     pub fn easy(data: Arc<RwLock<TypeMap>>, shard_id: u64, http: Arc<Http>) -> Context {
         Context {
             shard_id,
             data,
             http,
+            shard_latency: None,
         }
     }
 
     /// Create a new Context to be passed to an event handler.
     #[cfg(all(not(feature = "cache"), feature = "gateway"))]
+    #[allow(deprecated)]
     pub(crate) fn new(
         data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
+        shard_latency: Option<Duration>,
         http: Arc<Http>,
     ) -> Context {
         Context {
@@ -88,9 +108,91 @@ impl Context {
             shard_id,
             data,
             http,
+            shard_latency,
         }
     }
 
+    /// Returns the client's HTTP client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # use serenity::model::channel::Message;
+    /// #
+    /// # async fn run(ctx: Context, msg: Message) {
+    /// let _ = ctx.http().get_message(msg.channel_id.0, msg.id.0).await;
+    /// # }
+    /// ```
+    #[inline]
+    #[allow(deprecated)]
+    pub fn http(&self) -> &Arc<Http> {
+        &self.http
+    }
+
+    /// Returns the client's cache.
+    ///
+    /// This always returns [`Some`] for now, but mirrors
+    /// [`CacheHttp::cache`] so that it may not in the future.
+    ///
+    /// [`CacheHttp::cache`]: crate::http::CacheHttp::cache
+    #[cfg(feature = "cache")]
+    #[inline]
+    #[allow(deprecated)]
+    pub fn cache(&self) -> Option<&Arc<Cache>> {
+        Some(&self.cache)
+    }
+
+    /// Returns the latency of the shard this context is related to, as of
+    /// when the event was dispatched.
+    ///
+    /// This is the time between the shard's last heartbeat and the
+    /// acknowledgement of it, or [`None`] if none was acknowledged yet. For
+    /// the latencies of all shards, use [`ShardManager::latencies`].
+    ///
+    /// [`ShardManager::latencies`]: super::bridge::gateway::ShardManager::latencies
+    #[inline]
+    pub fn shard_latency(&self) -> Option<Duration> {
+        self.shard_latency
+    }
+
+    /// Broadcasts that the current user is typing in a channel for the next 5
+    /// seconds.
+    ///
+    /// Refer to [`ChannelId::broadcast_typing`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Show that the bot is typing while it works on a reply:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # use serenity::model::channel::Message;
+    /// #
+    /// struct Handler;
+    ///
+    /// #[serenity::async_trait]
+    /// impl EventHandler for Handler {
+    ///     async fn message(&self, ctx: Context, msg: Message) {
+    ///         if msg.content == "!slow" {
+    ///             let _ = ctx.send_typing(msg.channel_id).await;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission to send
+    /// messages in the channel.
+    ///
+    /// [`ChannelId::broadcast_typing`]: crate::model::id::ChannelId::broadcast_typing
+    /// [`Error::Http`]: crate::error::Error::Http
+    #[inline]
+    pub async fn send_typing(&self, channel_id: impl Into<ChannelId>) -> Result<()> {
+        self.http().broadcast_typing(channel_id.into().0).await
+    }
+
     /// Sets the current user as being [`Online`]. This maintains the current
     /// activity.
     ///
@@ -415,23 +517,24 @@ impl Context {
 
 impl AsRef<Http> for Context {
     fn as_ref(&self) -> &Http {
-        &self.http
+        self.http()
     }
 }
 
 impl AsRef<Http> for Arc<Context> {
     fn as_ref(&self) -> &Http {
-        &self.http
+        self.http()
     }
 }
 
 impl AsRef<Arc<Http>> for Context {
     fn as_ref(&self) -> &Arc<Http> {
-        &self.http
+        self.http()
     }
 }
 
 #[cfg(feature = "cache")]
+#[allow(deprecated)]
 impl AsRef<Cache> for Context {
     fn as_ref(&self) -> &Cache {
         &self.cache
@@ -439,6 +542,7 @@ impl AsRef<Cache> for Context {
 }
 
 #[cfg(feature = "cache")]
+#[allow(deprecated)]
 impl AsRef<Cache> for Arc<Context> {
     fn as_ref(&self) -> &Cache {
         &*self.cache
@@ -446,6 +550,7 @@ impl AsRef<Cache> for Arc<Context> {
 }
 
 #[cfg(feature = "cache")]
+#[allow(deprecated)]
 impl AsRef<Arc<Cache>> for Context {
     fn as_ref(&self) -> &Arc<Cache> {
         &self.cache
@@ -1,12 +1,22 @@
 #[cfg(feature = "cache")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+#[cfg(feature = "cache")]
 use std::fmt;
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex, PoisonError};
+use std::time::Duration;
 
 use futures::{
-    channel::mpsc::UnboundedSender as Sender,
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender as Sender},
     future::{BoxFuture, FutureExt},
+    StreamExt,
 };
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+#[cfg(all(feature = "cache", feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(all(feature = "cache", feature = "tokio"))]
+use tokio::time::sleep;
 use tracing::instrument;
 use typemap_rev::TypeMap;
 
@@ -23,7 +33,7 @@ use crate::framework::Framework;
 use crate::gateway::InterMessage;
 use crate::http::Http;
 #[cfg(feature = "cache")]
-use crate::model::id::GuildId;
+use crate::model::{guild::GuildStatus, id::GuildId};
 use crate::model::{
     channel::{Channel, Message},
     event::Event,
@@ -46,25 +56,224 @@ async fn update<E>(_cache_and_http: &Arc<CacheAndHttp>, _event: &mut E) -> Optio
     None
 }
 
+/// Tracks the guilds each shard is still waiting for after becoming ready, so
+/// that [`EventHandler::cache_ready`] is dispatched exactly once per shard.
+#[cfg(feature = "cache")]
+#[derive(Debug, Default)]
+pub(crate) struct CacheReadyTracker {
+    shards: Mutex<HashMap<u64, ShardGuilds>>,
+    finished: Mutex<HashSet<u64>>,
+}
+
+#[cfg(feature = "cache")]
+#[derive(Debug, Default)]
+struct ShardGuilds {
+    pending: HashSet<GuildId>,
+    received: Vec<GuildId>,
+}
+
 #[cfg(feature = "cache")]
-fn context(
+impl CacheReadyTracker {
+    /// Starts waiting for the `guilds` of a shard that became ready.
+    ///
+    /// Returns the guilds to dispatch right away if the shard has none.
+    async fn ready(
+        &self,
+        shard_id: u64,
+        guilds: impl Iterator<Item = GuildId>,
+    ) -> Option<Vec<GuildId>> {
+        if self.finished.lock().await.contains(&shard_id) {
+            return None;
+        }
+
+        let pending = guilds.collect::<HashSet<_>>();
+
+        if pending.is_empty() {
+            self.finished.lock().await.insert(shard_id);
+
+            return Some(Vec::new());
+        }
+
+        self.shards.lock().await.insert(shard_id, ShardGuilds {
+            pending,
+            received: Vec::new(),
+        });
+
+        None
+    }
+
+    /// Marks a guild of a shard as received.
+    ///
+    /// Returns the guilds to dispatch if it was the last one the shard was
+    /// waiting for.
+    async fn guild_create(&self, shard_id: u64, guild_id: GuildId) -> Option<Vec<GuildId>> {
+        let mut shards = self.shards.lock().await;
+        let guilds = shards.get_mut(&shard_id)?;
+
+        if guilds.pending.remove(&guild_id) {
+            guilds.received.push(guild_id);
+        }
+
+        if !guilds.pending.is_empty() {
+            return None;
+        }
+
+        self.finished.lock().await.insert(shard_id);
+
+        shards.remove(&shard_id).map(|guilds| guilds.received)
+    }
+
+    /// Stops waiting for the guilds of a shard.
+    ///
+    /// Returns the guilds received so far if the shard was still waiting.
+    async fn timeout(&self, shard_id: u64) -> Option<Vec<GuildId>> {
+        let guilds = self.shards.lock().await.remove(&shard_id)?;
+
+        self.finished.lock().await.insert(shard_id);
+
+        Some(guilds.received)
+    }
+}
+
+/// Tracks the shards that connected so far, so that
+/// [`EventHandler::shards_ready`] is dispatched exactly once, when the last of
+/// them connects for the first time.
+#[derive(Debug, Default)]
+pub(crate) struct ShardsReadyTracker {
+    state: StdMutex<ShardsReady>,
+}
+
+#[derive(Debug, Default)]
+struct ShardsReady {
+    total: u64,
+    connected: HashSet<u64>,
+    dispatched: bool,
+}
+
+impl ShardsReadyTracker {
+    /// Sets the number of shards the client runs.
+    pub(crate) fn expect(&self, total: u64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        state.total = total;
+        state.connected.clear();
+    }
+
+    /// Marks a shard as connected.
+    ///
+    /// Returns the number of shards if it was the last one that had yet to
+    /// connect, and the event was not dispatched before.
+    pub(crate) fn connected(&self, shard_id: u64) -> Option<u64> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if state.dispatched {
+            return None;
+        }
+
+        state.connected.insert(shard_id);
+
+        if state.total == 0 || (state.connected.len() as u64) < state.total {
+            return None;
+        }
+
+        state.dispatched = true;
+
+        Some(state.total)
+    }
+}
+
+/// Tracks the dispatched events whose handlers are still running, so that
+/// shutting down gracefully can stop dispatching and wait for them.
+#[derive(Debug)]
+pub(crate) struct DispatchTracker {
+    // Every guard holds a sender, so the receiver ends once the tracker is
+    // closed and all guards are dropped. Nothing is ever sent.
+    tx: StdMutex<Option<Sender<()>>>,
+    rx: Mutex<UnboundedReceiver<()>>,
+}
+
+/// Keeps a [`DispatchTracker`] from being drained while it is alive.
+#[derive(Clone, Debug)]
+pub(crate) struct DispatchGuard {
+    _tx: Sender<()>,
+}
+
+impl Default for DispatchTracker {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::unbounded();
+
+        Self {
+            tx: StdMutex::new(Some(tx)),
+            rx: Mutex::new(rx),
+        }
+    }
+}
+
+impl DispatchTracker {
+    /// Starts tracking the dispatch of an event.
+    ///
+    /// Returns `None` if the tracker was closed, as the event must not be
+    /// dispatched then.
+    pub(crate) fn start(&self) -> Option<DispatchGuard> {
+        let tx = self.tx.lock().unwrap_or_else(PoisonError::into_inner);
+
+        tx.as_ref().map(|tx| DispatchGuard {
+            _tx: tx.clone(),
+        })
+    }
+
+    /// Stops dispatching events.
+    pub(crate) fn close(&self) {
+        self.tx.lock().unwrap_or_else(PoisonError::into_inner).take();
+    }
+
+    /// Waits until the tracker is closed and the handlers of all the events
+    /// dispatched before finished.
+    pub(crate) async fn drained(&self) {
+        self.rx.lock().await.next().await;
+    }
+}
+
+/// Spawns a task running an event handler, which keeps the tracker of the
+/// dispatch from being drained until it finishes.
+pub(crate) fn spawn_tracked(guard: &DispatchGuard, future: impl Future<Output = ()> + Send + 'static) {
+    let guard = guard.clone();
+
+    tokio::spawn(async move {
+        future.await;
+
+        drop(guard);
+    });
+}
+
+#[cfg(feature = "cache")]
+pub(crate) fn context(
     data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
+    shard_latency: Option<Duration>,
     http: &Arc<Http>,
     cache: &Arc<Cache>,
 ) -> Context {
-    Context::new(Arc::clone(data), runner_tx.clone(), shard_id, Arc::clone(http), Arc::clone(cache))
+    Context::new(
+        Arc::clone(data),
+        runner_tx.clone(),
+        shard_id,
+        shard_latency,
+        Arc::clone(http),
+        Arc::clone(cache),
+    )
 }
 
 #[cfg(not(feature = "cache"))]
-fn context(
+pub(crate) fn context(
     data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
+    shard_latency: Option<Duration>,
     http: &Arc<Http>,
 ) -> Context {
-    Context::new(Arc::clone(data), runner_tx.clone(), shard_id, Arc::clone(http))
+    Context::new(Arc::clone(data), runner_tx.clone(), shard_id, shard_latency, Arc::clone(http))
 }
 
 // Once we can use `Box` as part of a pattern, we will reconsider boxing.
@@ -123,9 +332,28 @@ impl DispatchEvent {
             },
             // Already handled by the framework check macro
             Self::Model(Event::MessageCreate(_)) => {},
+            Self::Model(Event::MessageDelete(ref mut event)) => {
+                update(cache_and_http, event).await;
+            },
+            Self::Model(Event::MessageDeleteBulk(ref mut event)) => {
+                update(cache_and_http, event).await;
+            },
             Self::Model(Event::MessageUpdate(ref mut event)) => {
                 update(cache_and_http, event).await;
             },
+            #[cfg(feature = "unstable_discord_api")]
+            Self::Model(Event::InteractionCreate(ref mut event)) => {
+                update(cache_and_http, event).await;
+            },
+            Self::Model(Event::ThreadCreate(ref mut event)) => {
+                update(cache_and_http, event).await;
+            },
+            Self::Model(Event::ThreadUpdate(ref mut event)) => {
+                update(cache_and_http, event).await;
+            },
+            Self::Model(Event::ThreadDelete(ref mut event)) => {
+                update(cache_and_http, event).await;
+            },
             Self::Model(Event::PresencesReplace(ref mut event)) => {
                 update(cache_and_http, event).await;
             },
@@ -156,9 +384,16 @@ pub(crate) fn dispatch<'rec>(
     raw_event_handler: &'rec Option<Arc<dyn RawEventHandler>>,
     runner_tx: &'rec Sender<InterMessage>,
     shard_id: u64,
+    shard_latency: Option<Duration>,
     cache_and_http: Arc<CacheAndHttp>,
 ) -> BoxFuture<'rec, ()> {
     async move {
+        // Shutting down gracefully, so no more events are dispatched.
+        let guard = match cache_and_http.dispatches.start() {
+            Some(guard) => guard,
+            None => return,
+        };
+
         match (event_handler, raw_event_handler) {
             (None, None) => {
                 event.update(&cache_and_http).await;
@@ -167,19 +402,21 @@ pub(crate) fn dispatch<'rec>(
                     #[cfg(feature = "framework")]
                     {
                         #[cfg(not(feature = "cache"))]
-                        let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+                        let context =
+                            context(data, runner_tx, shard_id, shard_latency, &cache_and_http.http);
                         #[cfg(feature = "cache")]
                         let context = context(
                             data,
                             runner_tx,
                             shard_id,
+                            shard_latency,
                             &cache_and_http.http,
                             &cache_and_http.cache,
                         );
 
                         let framework = Arc::clone(framework);
 
-                        tokio::spawn(async move {
+                        spawn_tracked(&guard, async move {
                             framework.dispatch(context, event.message).await;
                         });
                     }
@@ -190,12 +427,14 @@ pub(crate) fn dispatch<'rec>(
                     update(&cache_and_http, &mut event).await;
 
                     #[cfg(not(feature = "cache"))]
-                    let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+                    let context =
+                        context(data, runner_tx, shard_id, shard_latency, &cache_and_http.http);
                     #[cfg(feature = "cache")]
                     let context = context(
                         data,
                         runner_tx,
                         shard_id,
+                        shard_latency,
                         &cache_and_http.http,
                         &cache_and_http.cache,
                     );
@@ -203,63 +442,82 @@ pub(crate) fn dispatch<'rec>(
                     #[cfg(not(feature = "framework"))]
                     {
                         // Avoid cloning if there will be no framework dispatch.
-                        dispatch_message(context, event.message, h).await;
+                        dispatch_message(context, event.message, h, &guard).await;
                     }
 
                     #[cfg(feature = "framework")]
                     {
-                        dispatch_message(context.clone(), event.message.clone(), h).await;
+                        dispatch_message(context.clone(), event.message.clone(), h, &guard).await;
 
                         let framework = Arc::clone(framework);
 
-                        tokio::spawn(async move {
+                        spawn_tracked(&guard, async move {
                             framework.dispatch(context, event.message).await;
                         });
                     }
                 },
                 other => {
-                    handle_event(other, data, h, runner_tx, shard_id, cache_and_http).await;
+                    handle_event(
+                        other,
+                        data,
+                        h,
+                        runner_tx,
+                        shard_id,
+                        shard_latency,
+                        cache_and_http,
+                        &guard,
+                    )
+                    .await;
                 },
             },
             (None, Some(ref rh)) => {
                 event.update(&cache_and_http).await;
 
-                if let DispatchEvent::Model(event) = event {
-                    let event_handler = Arc::clone(rh);
+                let event_handler = Arc::clone(rh);
 
-                    #[cfg(not(feature = "cache"))]
-                    let context = context(data, runner_tx, shard_id, &cache_and_http.http);
-                    #[cfg(feature = "cache")]
-                    let context = context(
-                        data,
-                        runner_tx,
-                        shard_id,
-                        &cache_and_http.http,
-                        &cache_and_http.cache,
-                    );
+                #[cfg(not(feature = "cache"))]
+                let context =
+                    context(data, runner_tx, shard_id, shard_latency, &cache_and_http.http);
+                #[cfg(feature = "cache")]
+                let context = context(
+                    data,
+                    runner_tx,
+                    shard_id,
+                    shard_latency,
+                    &cache_and_http.http,
+                    &cache_and_http.cache,
+                );
+
+                let event = match event {
+                    DispatchEvent::Model(event) => event,
+                    DispatchEvent::Client(event) => {
+                        event_handler.raw_client_event(context, event).await;
+
+                        return;
+                    },
+                };
 
-                    #[cfg(not(feature = "framework"))]
-                    {
-                        // No clone needed, as there will be no framework dispatch.
-                        event_handler.raw_event(context, event).await;
-                    }
+                #[cfg(not(feature = "framework"))]
+                {
+                    // No clone needed, as there will be no framework dispatch.
+                    event_handler.raw_event(context, event).await;
+                }
 
-                    #[cfg(feature = "framework")]
-                    {
-                        if let Event::MessageCreate(ref msg_event) = event {
-                            // Must clone in order to dispatch the framework too.
-                            let message = msg_event.message.clone();
-                            event_handler.raw_event(context.clone(), event).await;
+                #[cfg(feature = "framework")]
+                {
+                    if let Event::MessageCreate(ref msg_event) = event {
+                        // Must clone in order to dispatch the framework too.
+                        let message = msg_event.message.clone();
+                        event_handler.raw_event(context.clone(), event).await;
 
-                            let framework = Arc::clone(framework);
+                        let framework = Arc::clone(framework);
 
-                            tokio::spawn(async move {
-                                framework.dispatch(context, message).await;
-                            });
-                        } else {
-                            // Avoid cloning if there will be no framework dispatch.
-                            event_handler.raw_event(context, event).await;
-                        }
+                        spawn_tracked(&guard, async move {
+                            framework.dispatch(context, message).await;
+                        });
+                    } else {
+                        // Avoid cloning if there will be no framework dispatch.
+                        event_handler.raw_event(context, event).await;
                     }
                 }
             },
@@ -267,13 +525,25 @@ pub(crate) fn dispatch<'rec>(
             // and passing no framework, as we dispatch once we are done right here.
             (Some(ref handler), Some(ref raw_handler)) => {
                 #[cfg(not(feature = "cache"))]
-                let context = context(data, runner_tx, shard_id, &cache_and_http.http);
-                #[cfg(feature = "cache")]
                 let context =
-                    context(data, runner_tx, shard_id, &cache_and_http.http, &cache_and_http.cache);
+                    context(data, runner_tx, shard_id, shard_latency, &cache_and_http.http);
+                #[cfg(feature = "cache")]
+                let context = context(
+                    data,
+                    runner_tx,
+                    shard_id,
+                    shard_latency,
+                    &cache_and_http.http,
+                    &cache_and_http.cache,
+                );
 
-                if let DispatchEvent::Model(ref event) = event {
-                    raw_handler.raw_event(context.clone(), event.clone()).await;
+                match event {
+                    DispatchEvent::Model(ref event) => {
+                        raw_handler.raw_event(context.clone(), event.clone()).await;
+                    },
+                    DispatchEvent::Client(ref event) => {
+                        raw_handler.raw_client_event(context.clone(), event.clone()).await;
+                    },
                 }
 
                 match event {
@@ -281,23 +551,38 @@ pub(crate) fn dispatch<'rec>(
                         #[cfg(not(feature = "framework"))]
                         {
                             // Avoid cloning if there will be no framework dispatch.
-                            dispatch_message(context, event.message, handler).await;
+                            dispatch_message(context, event.message, handler, &guard).await;
                         }
 
                         #[cfg(feature = "framework")]
                         {
-                            dispatch_message(context.clone(), event.message.clone(), handler).await;
+                            dispatch_message(
+                                context.clone(),
+                                event.message.clone(),
+                                handler,
+                                &guard,
+                            )
+                            .await;
 
                             let framework = Arc::clone(framework);
                             let message = event.message;
-                            tokio::spawn(async move {
+                            spawn_tracked(&guard, async move {
                                 framework.dispatch(context, message).await;
                             });
                         }
                     },
                     other => {
-                        handle_event(other, data, handler, runner_tx, shard_id, cache_and_http)
-                            .await
+                        handle_event(
+                            other,
+                            data,
+                            handler,
+                            runner_tx,
+                            shard_id,
+                            shard_latency,
+                            cache_and_http,
+                            &guard,
+                        )
+                        .await
                     },
                 }
             },
@@ -310,6 +595,7 @@ async fn dispatch_message(
     context: Context,
     mut message: Message,
     event_handler: &Arc<dyn EventHandler>,
+    guard: &DispatchGuard,
 ) {
     #[cfg(feature = "model")]
     {
@@ -318,48 +604,64 @@ async fn dispatch_message(
 
     let event_handler = Arc::clone(event_handler);
 
-    tokio::spawn(async move {
+    spawn_tracked(guard, async move {
         event_handler.message(context, message).await;
     });
 }
 // Once we can use `Box` as part of a pattern, we will reconsider boxing.
 #[allow(clippy::too_many_arguments)]
-#[instrument(skip(event, data, event_handler, cache_and_http))]
+#[instrument(skip(event, data, event_handler, cache_and_http, guard))]
 async fn handle_event(
     event: DispatchEvent,
     data: &Arc<RwLock<TypeMap>>,
     event_handler: &Arc<dyn EventHandler>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
+    shard_latency: Option<Duration>,
     cache_and_http: Arc<CacheAndHttp>,
+    guard: &DispatchGuard,
 ) {
     #[cfg(not(feature = "cache"))]
-    let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+    let context = context(data, runner_tx, shard_id, shard_latency, &cache_and_http.http);
     #[cfg(feature = "cache")]
-    let context = context(data, runner_tx, shard_id, &cache_and_http.http, &cache_and_http.cache);
+    let context = context(
+        data,
+        runner_tx,
+        shard_id,
+        shard_latency,
+        &cache_and_http.http,
+        &cache_and_http.cache,
+    );
 
     match event {
         DispatchEvent::Client(ClientEvent::ShardStageUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.shard_stage_update(context, event).await;
             });
         },
+        DispatchEvent::Client(ClientEvent::ShardsReady(total)) => {
+            let event_handler = Arc::clone(event_handler);
+
+            spawn_tracked(guard, async move {
+                event_handler.shards_ready(context, total).await;
+            });
+        },
         DispatchEvent::Model(Event::ChannelCreate(mut event)) => {
             update(&cache_and_http, &mut event).await;
             match event.channel {
                 Channel::Guild(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    tokio::spawn(async move {
+                    spawn_tracked(guard, async move {
                         event_handler.channel_create(context, &channel).await;
                     });
                 },
                 Channel::Category(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    tokio::spawn(async move {
+                    spawn_tracked(guard, async move {
                         event_handler.category_create(context, &channel).await;
                     });
                 },
@@ -375,14 +677,14 @@ async fn handle_event(
                 Channel::Guild(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    tokio::spawn(async move {
+                    spawn_tracked(guard, async move {
                         event_handler.channel_delete(context, &channel).await;
                     });
                 },
                 Channel::Category(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    tokio::spawn(async move {
+                    spawn_tracked(guard, async move {
                         event_handler.category_delete(context, &channel).await;
                     });
                 },
@@ -391,22 +693,21 @@ async fn handle_event(
         DispatchEvent::Model(Event::ChannelPinsUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.channel_pins_update(context, event).await;
             });
         },
         DispatchEvent::Model(Event::ChannelUpdate(mut event)) => {
+            #[cfg(feature = "cache")]
+            let old_channel = update(&cache_and_http, &mut event).await;
+            #[cfg(not(feature = "cache"))]
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
-                    let old_channel = cache_and_http.cache.as_ref().channel(event.channel.id()).await;
-                    update(&cache_and_http, &mut event).await;
-
                     event_handler.channel_update(context, old_channel, event.channel).await;
                 } else {
-                    update(&cache_and_http, &mut event).await;
-
                     event_handler.channel_update(context, event.channel).await;
                 }}
             });
@@ -414,14 +715,14 @@ async fn handle_event(
         DispatchEvent::Model(Event::GuildBanAdd(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_ban_addition(context, event.guild_id, event.user).await;
             });
         },
         DispatchEvent::Model(Event::GuildBanRemove(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_ban_removal(context, event.guild_id, event.user).await;
             });
         },
@@ -434,28 +735,22 @@ async fn handle_event(
 
             #[cfg(feature = "cache")]
             {
-                let context = context.clone();
+                let guilds =
+                    cache_and_http.cache_ready.guild_create(shard_id, event.guild.id).await;
 
-                if cache_and_http.cache.unavailable_guilds.read().await.is_empty() {
-                    let guild_amount = cache_and_http
-                        .cache
-                        .guilds
-                        .read()
-                        .await
-                        .iter()
-                        .map(|(&id, _)| id)
-                        .collect::<Vec<GuildId>>();
+                if let Some(guilds) = guilds {
+                    let context = context.clone();
                     let event_handler = Arc::clone(event_handler);
 
-                    tokio::spawn(async move {
-                        event_handler.cache_ready(context, guild_amount).await;
+                    spawn_tracked(guard, async move {
+                        event_handler.cache_ready(context, guilds).await;
                     });
                 }
             }
 
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.guild_create(context, event.guild, _is_new).await;
                 } else {
@@ -467,7 +762,7 @@ async fn handle_event(
             let _full = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.guild_delete(context, event.guild, _full).await;
                 } else {
@@ -479,14 +774,14 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_emojis_update(context, event.guild_id, event.emojis).await;
             });
         },
         DispatchEvent::Model(Event::GuildIntegrationsUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_integrations_update(context, event.guild_id).await;
             });
         },
@@ -495,7 +790,7 @@ async fn handle_event(
 
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_member_addition(context, event.guild_id, event.member).await;
             });
         },
@@ -503,7 +798,7 @@ async fn handle_event(
             let _member = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.guild_member_removal(context, event.guild_id, event.user, _member).await;
                 } else {
@@ -521,7 +816,7 @@ async fn handle_event(
 
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     if let Some(after) = _after {
                         event_handler.guild_member_update(context, _before, after).await;
@@ -532,10 +827,24 @@ async fn handle_event(
             });
         },
         DispatchEvent::Model(Event::GuildMembersChunk(mut event)) => {
+            #[cfg(feature = "cache")]
+            let chunked = update(&cache_and_http, &mut event).await;
+            #[cfg(not(feature = "cache"))]
             update(&cache_and_http, &mut event).await;
+
+            #[cfg(feature = "cache")]
+            if let Some(guilds) = chunked {
+                let context = context.clone();
+                let event_handler = Arc::clone(event_handler);
+
+                spawn_tracked(guard, async move {
+                    event_handler.guilds_chunked(context, guilds).await;
+                });
+            }
+
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_members_chunk(context, event).await;
             });
         },
@@ -543,7 +852,7 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_role_create(context, event.guild_id, event.role).await;
             });
         },
@@ -551,7 +860,7 @@ async fn handle_event(
             let _role = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.guild_role_delete(context, event.guild_id, event.role_id, _role).await;
                 } else {
@@ -563,7 +872,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.guild_role_update(context, event.guild_id, _before, event.role).await;
                 } else {
@@ -575,25 +884,21 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.guild_unavailable(context, event.guild_id).await;
             });
         },
         DispatchEvent::Model(Event::GuildUpdate(mut event)) => {
+            #[cfg(feature = "cache")]
+            let before = update(&cache_and_http, &mut event).await;
+            #[cfg(not(feature = "cache"))]
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
-                    let before = cache_and_http.cache
-                        .guild(&event.guild.id)
-                        .await;
-
-                    update(&cache_and_http, &mut event).await;
-
                     event_handler.guild_update(context, before, event.guild).await;
                 } else {
-                    update(&cache_and_http, &mut event).await;
-
                     event_handler.guild_update(context, event.guild).await;
                 }}
             });
@@ -601,32 +906,34 @@ async fn handle_event(
         DispatchEvent::Model(Event::InviteCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.invite_create(context, event).await;
             });
         },
         DispatchEvent::Model(Event::InviteDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.invite_delete(context, event).await;
             });
         },
         // Already handled by the framework check macro
         DispatchEvent::Model(Event::MessageCreate(_)) => {},
-        DispatchEvent::Model(Event::MessageDeleteBulk(event)) => {
+        DispatchEvent::Model(Event::MessageDeleteBulk(mut event)) => {
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler
                     .message_delete_bulk(context, event.channel_id, event.ids, event.guild_id)
                     .await;
             });
         },
-        DispatchEvent::Model(Event::MessageDelete(event)) => {
+        DispatchEvent::Model(Event::MessageDelete(mut event)) => {
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler
                     .message_delete(context, event.channel_id, event.message_id, event.guild_id)
                     .await;
@@ -636,7 +943,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     let _after = cache_and_http.cache.message(event.channel_id, event.id).await;
                     event_handler.message_update(context, _before, _after, event).await;
@@ -649,37 +956,44 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.presence_replace(context, event.presences).await;
             });
         },
         DispatchEvent::Model(Event::PresenceUpdate(mut event)) => {
+            #[cfg(feature = "cache")]
+            let old = update(&cache_and_http, &mut event).await;
+            #[cfg(not(feature = "cache"))]
             update(&cache_and_http, &mut event).await;
 
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
-                event_handler.presence_update(context, event).await;
+            spawn_tracked(guard, async move {
+                feature_cache! {{
+                    event_handler.presence_update(context, old, event).await;
+                } else {
+                    event_handler.presence_update(context, event).await;
+                }}
             });
         },
         DispatchEvent::Model(Event::ReactionAdd(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.reaction_add(context, event.reaction).await;
             });
         },
         DispatchEvent::Model(Event::ReactionRemove(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.reaction_remove(context, event.reaction).await;
             });
         },
         DispatchEvent::Model(Event::ReactionRemoveAll(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler
                     .reaction_remove_all(context, event.channel_id, event.message_id)
                     .await;
@@ -687,30 +1001,58 @@ async fn handle_event(
         },
         DispatchEvent::Model(Event::Ready(mut event)) => {
             update(&cache_and_http, &mut event).await;
+
+            #[cfg(feature = "cache")]
+            {
+                let guilds = event.ready.guilds.iter().map(GuildStatus::id);
+                let guilds = cache_and_http.cache_ready.ready(shard_id, guilds).await;
+
+                if let Some(guilds) = guilds {
+                    let context = context.clone();
+                    let event_handler = Arc::clone(event_handler);
+
+                    spawn_tracked(guard, async move {
+                        event_handler.cache_ready(context, guilds).await;
+                    });
+                } else if let Some(max_wait) = cache_and_http.cache_ready_max_wait {
+                    let context = context.clone();
+                    let event_handler = Arc::clone(event_handler);
+                    let cache_and_http = Arc::clone(&cache_and_http);
+
+                    spawn_tracked(guard, async move {
+                        sleep(max_wait).await;
+
+                        if let Some(guilds) = cache_and_http.cache_ready.timeout(shard_id).await {
+                            event_handler.cache_ready(context, guilds).await;
+                        }
+                    });
+                }
+            }
+
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.ready(context, event.ready).await;
             });
         },
         DispatchEvent::Model(Event::Resumed(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.resume(context, event).await;
             });
         },
         DispatchEvent::Model(Event::TypingStart(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.typing_start(context, event).await;
             });
         },
         DispatchEvent::Model(Event::Unknown(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.unknown(context, event.kind, event.value).await;
             });
         },
@@ -718,7 +1060,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.user_update(context, _before.expect("missing old user"), event.current_user).await;
                 } else {
@@ -729,7 +1071,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::VoiceServerUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.voice_server_update(context, event).await;
             });
         },
@@ -737,7 +1079,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 feature_cache! {{
                     event_handler.voice_state_update(context, event.guild_id, _before, event.voice_state).await;
                 } else {
@@ -748,15 +1090,16 @@ async fn handle_event(
         DispatchEvent::Model(Event::WebhookUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.webhook_update(context, event.guild_id, event.channel_id).await;
             });
         },
         #[cfg(feature = "unstable_discord_api")]
-        DispatchEvent::Model(Event::InteractionCreate(event)) => {
+        DispatchEvent::Model(Event::InteractionCreate(mut event)) => {
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.interaction_create(context, event.interaction).await;
             });
         },
@@ -764,7 +1107,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::IntegrationCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.integration_create(context, event.integration).await;
             });
         },
@@ -772,7 +1115,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::IntegrationUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.integration_update(context, event.integration).await;
             });
         },
@@ -780,7 +1123,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::IntegrationDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler
                     .integration_delete(context, event.id, event.guild_id, event.application_id)
                     .await;
@@ -790,7 +1133,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::ApplicationCommandCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.application_command_create(context, event.application_command).await;
             });
         },
@@ -798,7 +1141,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::ApplicationCommandUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.application_command_update(context, event.application_command).await;
             });
         },
@@ -806,72 +1149,133 @@ async fn handle_event(
         DispatchEvent::Model(Event::ApplicationCommandDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.application_command_delete(context, event.application_command).await;
             });
         },
         DispatchEvent::Model(Event::StageInstanceCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.stage_instance_create(context, event.stage_instance).await;
             });
         },
         DispatchEvent::Model(Event::StageInstanceUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.stage_instance_update(context, event.stage_instance).await;
             });
         },
         DispatchEvent::Model(Event::StageInstanceDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.stage_instance_delete(context, event.stage_instance).await;
             });
         },
-        DispatchEvent::Model(Event::ThreadCreate(event)) => {
+        DispatchEvent::Model(Event::ThreadCreate(mut event)) => {
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.thread_create(context, event.thread).await;
             });
         },
-        DispatchEvent::Model(Event::ThreadUpdate(event)) => {
+        DispatchEvent::Model(Event::ThreadUpdate(mut event)) => {
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.thread_update(context, event.thread).await;
             });
         },
-        DispatchEvent::Model(Event::ThreadDelete(event)) => {
+        DispatchEvent::Model(Event::ThreadDelete(mut event)) => {
+            update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.thread_delete(context, event.thread).await;
             });
         },
         DispatchEvent::Model(Event::ThreadListSync(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.thread_list_sync(context, event).await;
             });
         },
         DispatchEvent::Model(Event::ThreadMemberUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.thread_member_update(context, event.member).await;
             });
         },
         DispatchEvent::Model(Event::ThreadMembersUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            tokio::spawn(async move {
+            spawn_tracked(guard, async move {
                 event_handler.thread_members_update(context, event).await;
             });
         },
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    #[cfg(feature = "cache")]
+    use super::CacheReadyTracker;
+    use super::ShardsReadyTracker;
+    #[cfg(feature = "cache")]
+    use crate::model::id::GuildId;
+
+    #[cfg(feature = "cache")]
+    #[tokio::test]
+    async fn test_cache_ready_once_per_shard() {
+        let tracker = CacheReadyTracker::default();
+        let guilds = vec![GuildId(1), GuildId(2)];
+
+        assert!(tracker.ready(0, guilds.clone().into_iter()).await.is_none());
+        assert!(tracker.guild_create(0, GuildId(1)).await.is_none());
+        assert!(tracker.guild_create(1, GuildId(2)).await.is_none());
+        assert_eq!(tracker.guild_create(0, GuildId(2)).await.unwrap(), guilds);
+
+        // Neither guilds joined later nor another ready dispatch it again.
+        assert!(tracker.guild_create(0, GuildId(3)).await.is_none());
+        assert!(tracker.ready(0, guilds.into_iter()).await.is_none());
+        assert!(tracker.timeout(0).await.is_none());
+    }
+
+    #[cfg(feature = "cache")]
+    #[tokio::test]
+    async fn test_cache_ready_timeout() {
+        let tracker = CacheReadyTracker::default();
+
+        assert!(tracker.ready(0, vec![GuildId(1), GuildId(2)].into_iter()).await.is_none());
+        assert!(tracker.guild_create(0, GuildId(1)).await.is_none());
+        assert_eq!(tracker.timeout(0).await.unwrap(), vec![GuildId(1)]);
+        assert!(tracker.guild_create(0, GuildId(2)).await.is_none());
+
+        assert!(tracker.ready(1, Vec::new().into_iter()).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shards_ready_once() {
+        let tracker = ShardsReadyTracker::default();
+        tracker.expect(2);
+
+        assert!(tracker.connected(0).is_none());
+        // The shard reconnecting before the other one connected.
+        assert!(tracker.connected(0).is_none());
+        assert_eq!(tracker.connected(1), Some(2));
+
+        // Restarted shards connecting again do not dispatch it again.
+        assert!(tracker.connected(0).is_none());
+        assert!(tracker.connected(1).is_none());
+
+        tracker.expect(1);
+        assert!(tracker.connected(0).is_none());
+    }
+}
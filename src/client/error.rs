@@ -21,20 +21,63 @@ pub enum Error {
     ///
     /// [`validate_token`]: super::validate_token
     InvalidToken,
+    /// When a [`ClientBuilder`] was built without a token, or with an empty
+    /// one.
+    ///
+    /// [`ClientBuilder`]: super::ClientBuilder
+    MissingToken,
+    /// When a [`ClientBuilder`] was built with no [`GatewayIntents`] set.
+    ///
+    /// [`ClientBuilder`]: super::ClientBuilder
+    /// [`GatewayIntents`]: super::bridge::gateway::GatewayIntents
+    EmptyIntents,
     /// When a shard has completely failed to reboot after resume and/or
     /// reconnect attempts.
     ShardBootFailure,
     /// When all shards that the client is responsible for have shutdown with an
     /// error.
     Shutdown,
+    /// When a message broadcast via [`ShardManager::broadcast`] could not be
+    /// sent to some shards, whose ids are given.
+    ///
+    /// [`ShardManager::broadcast`]: super::bridge::gateway::ShardManager::broadcast
+    BroadcastFailed(Vec<u64>),
+    /// When starting the shards would use more sessions than are left in the
+    /// current [session start limit] period.
+    ///
+    /// [session start limit]: crate::model::gateway::SessionStartLimit
+    SessionStartLimitExhausted {
+        /// The number of shards that were to be started.
+        shards: u64,
+        /// The number of sessions that can still be started.
+        remaining: u64,
+        /// The number of milliseconds until the limit resets.
+        reset_after: u64,
+    },
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
             Error::InvalidToken => f.write_str("The provided token was invalid"),
+            Error::MissingToken => f.write_str("No token was provided to the client builder"),
+            Error::EmptyIntents => {
+                f.write_str("The client builder has no gateway intents set, at least one is required")
+            },
             Error::ShardBootFailure => f.write_str("Failed to (re-)boot a shard"),
             Error::Shutdown => f.write_str("The clients shards shutdown"),
+            Error::BroadcastFailed(shards) => {
+                write!(f, "Failed to broadcast a message to the shards {:?}", shards)
+            },
+            Error::SessionStartLimitExhausted {
+                shards,
+                remaining,
+                reset_after,
+            } => write!(
+                f,
+                "Cannot start {} shards with {} sessions left, the limit resets in {}ms",
+                shards, remaining, reset_after,
+            ),
         }
     }
 }
@@ -43,8 +86,16 @@ impl StdError for Error {
     fn description(&self) -> &str {
         match *self {
             Error::InvalidToken => "The provided token was invalid",
+            Error::MissingToken => "No token was provided to the client builder",
+            Error::EmptyIntents => {
+                "The client builder has no gateway intents set, at least one is required"
+            },
             Error::ShardBootFailure => "Failed to (re-)boot a shard",
             Error::Shutdown => "The clients shards shutdown",
+            Error::BroadcastFailed(_) => "Failed to broadcast a message to some shards",
+            Error::SessionStartLimitExhausted {
+                ..
+            } => "Not enough sessions are left to start the shards",
         }
     }
 }
@@ -6,23 +6,46 @@ use serde_json::Value;
 use super::context::Context;
 use crate::client::bridge::gateway::event::*;
 #[cfg(feature = "unstable_discord_api")]
-use crate::model::interactions::{application_command::ApplicationCommand, Interaction};
+use crate::model::interactions::{
+    application_command::{ApplicationCommand, ApplicationCommandInteraction},
+    message_component::MessageComponentInteraction,
+    Interaction,
+};
 use crate::model::prelude::*;
 
 /// The core trait for handling events by serenity.
 #[async_trait]
 pub trait EventHandler: Send + Sync {
-    /// Dispatched when the cache has received and inserted all data from
-    /// guilds.
+    /// Dispatched once per shard, when the cache has received and inserted the
+    /// data of all guilds the shard was sent in its [`Self::ready`] event.
     ///
     /// This process happens upon starting your bot and should be fairly quick.
     /// However, cache actions performed prior this event may fail as the data
     /// could be not inserted yet.
     ///
-    /// Provides the cached guilds' ids.
+    /// If some guilds never arrive, e.g. due to an outage, this is dispatched
+    /// once the time set through [`ClientBuilder::cache_ready_max_wait`] ran
+    /// out.
+    ///
+    /// Provides the ids of the shard's guilds that were cached.
+    ///
+    /// [`ClientBuilder::cache_ready_max_wait`]: super::ClientBuilder::cache_ready_max_wait
     #[cfg(feature = "cache")]
     async fn cache_ready(&self, _ctx: Context, _guilds: Vec<GuildId>) {}
 
+    /// Dispatched when the cache has received all member chunks requested for
+    /// the guilds selected by [`ClientBuilder::chunk_guilds_filter`], or via
+    /// [`Cache::expect_member_chunks`].
+    ///
+    /// Provides the ids of the guilds chunked since this was last dispatched.
+    /// It is dispatched again if more guilds are chunked later, e.g. after
+    /// joining one.
+    ///
+    /// [`ClientBuilder::chunk_guilds_filter`]: super::ClientBuilder::chunk_guilds_filter
+    /// [`Cache::expect_member_chunks`]: crate::cache::Cache::expect_member_chunks
+    #[cfg(feature = "cache")]
+    async fn guilds_chunked(&self, _ctx: Context, _guilds: Vec<GuildId>) {}
+
     /// Dispatched when a channel is created.
     ///
     /// Provides said channel's data.
@@ -346,12 +369,28 @@ pub trait EventHandler: Send + Sync {
     /// This event is legacy, and likely no longer sent by discord.
     async fn presence_replace(&self, _ctx: Context, _: Vec<Presence>) {}
 
+    /// Dispatched when a user's presence is updated (e.g off -> on).
+    ///
+    /// Provides the presence's old data (if available) and the new data.
+    ///
+    /// Note: This event will not trigger unless the "guild presences" privileged intent
+    /// is enabled on the bot application page.
+    #[cfg(feature = "cache")]
+    async fn presence_update(
+        &self,
+        _ctx: Context,
+        _old_if_available: Option<Presence>,
+        _new_data: PresenceUpdateEvent,
+    ) {
+    }
+
     /// Dispatched when a user's presence is updated (e.g off -> on).
     ///
     /// Provides the presence's new data.
     ///
     /// Note: This event will not trigger unless the "guild presences" privileged intent
     /// is enabled on the bot application page.
+    #[cfg(not(feature = "cache"))]
     async fn presence_update(&self, _ctx: Context, _new_data: PresenceUpdateEvent) {}
 
     /// Dispatched upon startup.
@@ -362,14 +401,37 @@ pub trait EventHandler: Send + Sync {
     /// Dispatched upon reconnection.
     async fn resume(&self, _ctx: Context, _: ResumedEvent) {}
 
-    /// Dispatched when a shard's connection stage is updated
+    /// Dispatched when a shard's connection stage is updated, e.g. when it
+    /// connects, disconnects or reconnects.
     ///
-    /// Provides the context of the shard and the event information about the update.
+    /// Provides the context of the shard and the event information about the
+    /// update, including the shard's latest latency.
     async fn shard_stage_update(&self, _ctx: Context, _: ShardStageUpdateEvent) {}
 
+    /// Dispatched once, when every shard of the client has connected for the
+    /// first time.
+    ///
+    /// Provides the number of shards. Shards connecting again later, e.g.
+    /// after being restarted, do not dispatch this again.
+    async fn shards_ready(&self, _ctx: Context, _total: u32) {}
+
     /// Dispatched when a user starts typing.
     async fn typing_start(&self, _ctx: Context, _: TypingStartEvent) {}
 
+    /// Dispatched for every payload received over the gateway, before it is
+    /// deserialized and dispatched to the other methods, if enabled through
+    /// [`ClientBuilder::raw_dispatch`].
+    ///
+    /// Provides the payload's opcode and its unparsed data, e.g. for logging
+    /// unexpected opcodes. The shard waits for this to return before handling
+    /// the payload, so spawn a task for anything taking longer.
+    ///
+    /// This is not named `raw_event` so as not to be confused with
+    /// [`RawEventHandler::raw_event`], which receives the deserialized events.
+    ///
+    /// [`ClientBuilder::raw_dispatch`]: crate::client::ClientBuilder::raw_dispatch
+    async fn raw_dispatch(&self, _ctx: Context, _opcode: u8, _payload: &Value) {}
+
     /// Dispatched when an unknown event was sent from discord.
     ///
     /// Provides the event's name and its unparsed data.
@@ -427,9 +489,49 @@ pub trait EventHandler: Send + Sync {
     /// Dispatched when an interaction is created (e.g a slash command was used or a button was clicked).
     ///
     /// Provides the created interaction.
+    ///
+    /// By default, this passes the interaction on to [`Self::application_command`]
+    /// or [`Self::message_component`] depending on its kind. Overriding this
+    /// method disables that.
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
-    async fn interaction_create(&self, _ctx: Context, _interaction: Interaction) {}
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        match interaction {
+            Interaction::ApplicationCommand(interaction) => {
+                self.application_command(ctx, interaction).await
+            },
+            Interaction::MessageComponent(interaction) => {
+                self.message_component(ctx, interaction).await
+            },
+            Interaction::Ping(_) => {},
+        }
+    }
+
+    /// Dispatched when an application command, e.g. a slash command, was used.
+    ///
+    /// Provides the interaction of the command.
+    ///
+    /// **Note**: This is dispatched by the default implementation of
+    /// [`Self::interaction_create`].
+    #[cfg(feature = "unstable_discord_api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
+    async fn application_command(
+        &self,
+        _ctx: Context,
+        _interaction: ApplicationCommandInteraction,
+    ) {
+    }
+
+    /// Dispatched when a message component, e.g. a button or a select menu,
+    /// was used.
+    ///
+    /// Provides the interaction of the component.
+    ///
+    /// **Note**: This is dispatched by the default implementation of
+    /// [`Self::interaction_create`].
+    #[cfg(feature = "unstable_discord_api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
+    async fn message_component(&self, _ctx: Context, _interaction: MessageComponentInteraction) {}
 
     /// Dispatched when a guild integration is created.
     ///
@@ -555,6 +657,17 @@ pub trait EventHandler: Send + Sync {
 /// This core trait for handling raw events
 #[async_trait]
 pub trait RawEventHandler: Send + Sync {
-    /// Dispatched when any event occurs
+    /// Dispatched when any event occurs, before the [`EventHandler`].
+    ///
+    /// Dispatches of unknown types are given as an [`Event::Unknown`], as
+    /// are dispatches whose payload failed to deserialize, along with the
+    /// [`UnknownEvent::error`]. To allow for the latter, every payload is
+    /// kept until it is deserialized while a raw event handler is set.
+    ///
+    /// [`UnknownEvent::error`]: crate::model::event::UnknownEvent::error
     async fn raw_event(&self, _ctx: Context, _ev: Event) {}
+
+    /// Dispatched when an event created by the client itself occurs, such as
+    /// a shard changing its connection stage, before the [`EventHandler`].
+    async fn raw_client_event(&self, _ctx: Context, _ev: ClientEvent) {}
 }
@@ -44,7 +44,9 @@ use typemap_rev::{TypeMap, TypeMapKey};
 
 #[cfg(feature = "gateway")]
 use self::bridge::gateway::{
+    ChunkGuildsFilter,
     GatewayIntents,
+    ReconnectPolicy,
     ShardManager,
     ShardManagerError,
     ShardManagerMonitor,
@@ -52,6 +54,10 @@ use self::bridge::gateway::{
 };
 #[cfg(feature = "voice")]
 use self::bridge::voice::VoiceGatewayManager;
+#[cfg(all(feature = "cache", feature = "gateway"))]
+pub(crate) use self::dispatch::CacheReadyTracker;
+#[cfg(feature = "gateway")]
+pub(crate) use self::dispatch::{DispatchTracker, ShardsReadyTracker};
 pub use self::{context::Context, error::Error as ClientError};
 #[cfg(feature = "gateway")]
 pub use self::{
@@ -59,7 +65,7 @@ pub use self::{
     extras::Extras,
 };
 #[cfg(feature = "gateway")]
-use super::gateway::GatewayError;
+use super::gateway::{CurrentPresence, GatewayError, TransportCompression};
 #[cfg(feature = "cache")]
 pub use crate::cache::Cache;
 #[cfg(feature = "cache")]
@@ -70,6 +76,11 @@ use crate::http::Http;
 use crate::internal::prelude::*;
 #[cfg(feature = "unstable_discord_api")]
 use crate::model::id::ApplicationId;
+#[cfg(feature = "gateway")]
+use crate::model::{
+    gateway::{Activity, BotGateway, SessionStartLimit},
+    user::OnlineStatus,
+};
 use crate::model::id::UserId;
 pub use crate::CacheAndHttp;
 
@@ -84,11 +95,17 @@ pub struct ClientBuilder<'a> {
     http: Option<Http>,
     fut: Option<BoxFuture<'a, Result<Client>>>,
     intents: GatewayIntents,
+    chunk_guilds_filter: ChunkGuildsFilter,
+    transport_compression: TransportCompression,
+    presence: CurrentPresence,
+    reconnect_policy: ReconnectPolicy,
     #[cfg(feature = "unstable_discord_api")]
     application_id: Option<ApplicationId>,
     #[cfg(feature = "cache")]
     timeout: Option<Duration>,
     #[cfg(feature = "cache")]
+    cache_ready_max_wait: Option<Duration>,
+    #[cfg(feature = "cache")]
     cache_settings: Option<CacheSettings>,
     #[cfg(feature = "framework")]
     framework: Option<Arc<Box<dyn Framework + Send + Sync + 'static>>>,
@@ -96,6 +113,7 @@ pub struct ClientBuilder<'a> {
     voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync + 'static>>,
     event_handler: Option<Arc<dyn EventHandler>>,
     raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    raw_dispatch: bool,
 }
 
 #[cfg(feature = "gateway")]
@@ -107,11 +125,17 @@ impl<'a> ClientBuilder<'a> {
             http: None,
             fut: None,
             intents: GatewayIntents::non_privileged(),
+            chunk_guilds_filter: ChunkGuildsFilter::None,
+            transport_compression: TransportCompression::default(),
+            presence: (None, OnlineStatus::Online),
+            reconnect_policy: ReconnectPolicy::default(),
             #[cfg(feature = "unstable_discord_api")]
             application_id: None,
             #[cfg(feature = "cache")]
             timeout: None,
             #[cfg(feature = "cache")]
+            cache_ready_max_wait: None,
+            #[cfg(feature = "cache")]
             cache_settings: Some(CacheSettings::new()),
             #[cfg(feature = "framework")]
             framework: None,
@@ -119,6 +143,7 @@ impl<'a> ClientBuilder<'a> {
             voice_manager: None,
             event_handler: None,
             raw_event_handler: None,
+            raw_dispatch: false,
         }
     }
 
@@ -214,6 +239,19 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
+    /// Sets how long to wait at most for the guilds of a shard to arrive after
+    /// it became ready. Once the time ran out, [`EventHandler::cache_ready`] is
+    /// dispatched for the shard with the guilds received so far.
+    ///
+    /// By default, this waits until all guilds arrived, which may never
+    /// happen while some of them are unavailable due to an outage.
+    #[cfg(feature = "cache")]
+    pub fn cache_ready_max_wait(mut self, max_wait: Duration) -> Self {
+        self.cache_ready_max_wait = Some(max_wait);
+
+        self
+    }
+
     /// Sets the settings of the cache.
     /// Refer to [`Settings`] for more information.
     ///
@@ -308,23 +346,90 @@ impl<'a> ClientBuilder<'a> {
     ///
     /// # Privileged Intents
     ///
-    /// The intents [`GatewayIntents::GUILD_PRESENCES`] and [`GatewayIntents::GUILD_MEMBERS`]
-    /// are *privileged*.
+    /// The intents [`GatewayIntents::GUILD_PRESENCES`], [`GatewayIntents::GUILD_MEMBERS`]
+    /// and [`GatewayIntents::MESSAGE_CONTENT`] are *privileged*.
     /// [Privileged intents] need to be enabled in the *developer portal*.
     /// Once the bot is in 100 guilds or more, [the bot must be verified] in
     /// order to use privileged intents.
     ///
+    /// When privileged intents are given and the `model` feature is enabled,
+    /// building the client fetches the [current application info] and fails
+    /// with [`GatewayError::PrivilegedIntentsNotEnabled`], naming the missing
+    /// intents, if any of them are not enabled.
+    ///
     /// [gateway intent]: https://discord.com/developers/docs/topics/gateway#privileged-intents
     /// [Privileged intents]: https://discord.com/developers/docs/topics/gateway#privileged-intents
     /// [the bot must be verified]: https://support.discord.com/hc/en-us/articles/360040720412-Bot-Verification-and-Data-Whitelisting
     /// [`GatewayIntents::GUILD_PRESENCES`]: crate::client::bridge::gateway::GatewayIntents::GUILD_PRESENCES
     /// [`GatewayIntents::GUILD_MEMBERS`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MEMBERS
+    /// [`GatewayIntents::MESSAGE_CONTENT`]: crate::client::bridge::gateway::GatewayIntents::MESSAGE_CONTENT
+    /// [current application info]: crate::http::client::Http::get_current_application_info
     pub fn intents(mut self, intents: GatewayIntents) -> Self {
         self.intents = intents;
 
         self
     }
 
+    /// Sets the guilds whose members the shards request after receiving them,
+    /// in order to cache all members of large guilds too.
+    ///
+    /// The requests are paced to stay below the gateway's send limit, so
+    /// chunking many guilds takes a while.
+    /// [`EventHandler::guilds_chunked`] is dispatched once all requested
+    /// guilds are fully chunked.
+    ///
+    /// By default, no guild is chunked.
+    pub fn chunk_guilds_filter(mut self, filter: ChunkGuildsFilter) -> Self {
+        self.chunk_guilds_filter = filter;
+
+        self
+    }
+
+    /// Sets how the gateway compresses the payloads sent to the shards.
+    ///
+    /// [`TransportCompression::ZlibStream`] compresses the whole connection,
+    /// which saves bandwidth for bots in many guilds at the cost of keeping a
+    /// decompression context for each shard.
+    ///
+    /// By default, only large payloads are compressed, one by one.
+    pub fn transport_compression(mut self, compression: TransportCompression) -> Self {
+        self.transport_compression = compression;
+
+        self
+    }
+
+    /// Sets how long the shards wait before trying to reconnect after they
+    /// lost their connection, e.g. during a Discord outage.
+    ///
+    /// Refer to [`ReconnectPolicy`] for the defaults.
+    pub fn reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+
+        self
+    }
+
+    /// Sets the activity the shards start with, sent when identifying.
+    ///
+    /// It can be changed later through [`Context::set_activity`] or
+    /// [`Context::set_presence`].
+    pub fn activity(mut self, activity: Activity) -> Self {
+        self.presence.0 = Some(activity);
+
+        self
+    }
+
+    /// Sets the online status the shards start with, sent when identifying.
+    ///
+    /// [`OnlineStatus::Offline`] is not a valid status, so it is converted to
+    /// [`OnlineStatus::Invisible`].
+    ///
+    /// By default, this is [`OnlineStatus::Online`].
+    pub fn status(mut self, status: OnlineStatus) -> Self {
+        self.presence.1 = status;
+
+        self
+    }
+
     /// Sets an event handler with multiple methods for each possible event.
     pub fn event_handler<H: EventHandler + 'static>(mut self, event_handler: H) -> Self {
         self.event_handler = Some(Arc::new(event_handler));
@@ -339,6 +444,72 @@ impl<'a> ClientBuilder<'a> {
 
         self
     }
+
+    /// Sets whether every payload received over the gateway is passed to
+    /// [`EventHandler::raw_dispatch`] before it is deserialized.
+    ///
+    /// This is disabled by default, as the shards then have to decode every
+    /// payload into a [`Value`] and wait for the handler before deserializing
+    /// it.
+    ///
+    /// [`Value`]: serde_json::Value
+    pub fn raw_dispatch(mut self, raw_dispatch: bool) -> Self {
+        self.raw_dispatch = raw_dispatch;
+
+        self
+    }
+
+    /// Checks that the builder has everything required to connect to the
+    /// gateway.
+    fn validate(&self) -> Result<()> {
+        // The token is prefixed with `Bot ` even if it was blank.
+        let has_token = self
+            .token
+            .as_ref()
+            .map_or(false, |token| !token.trim_start_matches("Bot ").trim().is_empty());
+
+        if !has_token {
+            return Err(Error::Client(ClientError::MissingToken));
+        }
+
+        if self.intents.is_empty() {
+            return Err(Error::Client(ClientError::EmptyIntents));
+        }
+
+        #[cfg(feature = "model")]
+        if let Some(activity) = &self.presence.0 {
+            activity.check_stream_url()?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds the [`Client`], the same as awaiting the builder.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::MissingToken`] if no token was set, and
+    /// [`ClientError::EmptyIntents`] if the intents are empty. If the `model`
+    /// feature is enabled, also returns [`ModelError::InvalidStreamUrl`] if
+    /// the activity is streaming without a Twitch or YouTube URL.
+    ///
+    /// Otherwise returns [`Error::Http`] if the gateway URL could not be
+    /// retrieved.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    /// [`ModelError::InvalidStreamUrl`]: crate::model::ModelError::InvalidStreamUrl
+    pub async fn build(self) -> Result<Client> {
+        self.await
+    }
+}
+
+/// Creates a builder without a token, which must be set via
+/// [`ClientBuilder::token`] before building the client.
+#[cfg(feature = "gateway")]
+impl<'a> Default for ClientBuilder<'a> {
+    fn default() -> Self {
+        Self::_new()
+    }
 }
 
 #[cfg(feature = "gateway")]
@@ -349,6 +520,10 @@ impl<'a> Future for ClientBuilder<'a> {
     #[instrument(skip(self))]
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
+            if let Err(why) = self.validate() {
+                return Poll::Ready(Err(why));
+            }
+
             let data = Arc::new(RwLock::new(self.data.take().unwrap()));
             #[cfg(feature = "framework")]
             let framework = self.framework.take()
@@ -356,7 +531,12 @@ impl<'a> Future for ClientBuilder<'a> {
                 If you don't want to use the command framework, disable default features and specify all features you want to use.");
             let event_handler = self.event_handler.take();
             let raw_event_handler = self.raw_event_handler.take();
+            let raw_dispatch = self.raw_dispatch;
             let intents = self.intents;
+            let chunk_guilds_filter = self.chunk_guilds_filter.clone();
+            let transport_compression = self.transport_compression;
+            let reconnect_policy = self.reconnect_policy;
+            let presence = self.presence.clone();
             let http = Arc::new(self.http.take().unwrap());
 
             #[cfg(feature = "unstable_discord_api")]
@@ -372,17 +552,42 @@ impl<'a> Future for ClientBuilder<'a> {
                 cache: Arc::new(Cache::new_with_settings(self.cache_settings.take().unwrap())),
                 #[cfg(feature = "cache")]
                 update_cache_timeout: self.timeout.take(),
+                #[cfg(feature = "cache")]
+                cache_ready_max_wait: self.cache_ready_max_wait.take(),
+                #[cfg(feature = "cache")]
+                cache_ready: Arc::default(),
+                dispatches: Arc::default(),
+                shards_ready: Arc::default(),
                 http: Arc::clone(&http),
             });
 
             self.fut = Some(Box::pin(async move {
-                let url = Arc::new(Mutex::new(http.get_gateway().await?.url));
+                #[cfg(feature = "model")]
+                if intents.is_privileged() {
+                    let info = http.get_current_application_info().await?;
+
+                    if let Some(flags) = info.flags {
+                        let missing = intents.missing_privileged(flags);
+
+                        if !missing.is_empty() {
+                            error!("Privileged intents are not enabled: {:?}", missing);
+
+                            return Err(Error::Gateway(
+                                GatewayError::PrivilegedIntentsNotEnabled(missing),
+                            ));
+                        }
+                    }
+                }
+
+                let bot_gateway = http.get_bot_gateway().await?;
+                let url = Arc::new(Mutex::new(bot_gateway.url.clone()));
 
                 let (shard_manager, shard_manager_worker) = {
                     ShardManager::new(ShardManagerOptions {
                         data: &data,
                         event_handler: &event_handler,
                         raw_event_handler: &raw_event_handler,
+                        raw_dispatch,
                         #[cfg(feature = "framework")]
                         framework: &framework,
                         shard_index: 0,
@@ -393,12 +598,18 @@ impl<'a> Future for ClientBuilder<'a> {
                         ws_url: &url,
                         cache_and_http: &cache_and_http,
                         intents,
+                        chunk_guilds_filter,
+                        transport_compression,
+                        presence,
+                        max_concurrency: bot_gateway.session_start_limit.max_concurrency,
+                        reconnect_policy,
                     })
                     .await
                 };
 
                 Ok(Client {
                     ws_uri: url,
+                    bot_gateway,
                     data,
                     shard_manager,
                     shard_manager_worker,
@@ -622,6 +833,42 @@ pub struct Client {
     /// #     Ok(())
     /// # }
     /// ```
+    ///
+    /// Shutting down after one minute, giving the event handlers that are
+    /// still running up to ten seconds to finish:
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use serenity::client::{Client, EventHandler};
+    /// use std::time::Duration;
+    ///
+    /// struct Handler;
+    ///
+    /// impl EventHandler for Handler { }
+    ///
+    /// let token = std::env::var("DISCORD_TOKEN")?;
+    /// let mut client = Client::builder(&token).event_handler(Handler).await?;
+    ///
+    /// let shard_manager = client.shard_manager.clone();
+    /// let hook = shard_manager.lock().await.shutdown_hook();
+    ///
+    /// tokio::spawn(async move {
+    ///     tokio::time::sleep(Duration::from_secs(60)).await;
+    ///
+    ///     let timeout = Duration::from_secs(10);
+    ///
+    ///     if !shard_manager.lock().await.shutdown_gracefully(timeout).await {
+    ///         println!("Some event handlers are still running");
+    ///     }
+    /// });
+    ///
+    /// println!("Client shutdown: {:?}", client.start().await);
+    ///
+    /// // Wait for the event handlers that outlasted the timeout, if any.
+    /// hook.wait().await;
+    /// #     Ok(())
+    /// # }
+    /// ```
     pub shard_manager: Arc<Mutex<ShardManager>>,
     shard_manager_worker: ShardManagerMonitor,
     /// The voice manager for the client.
@@ -641,6 +888,8 @@ pub struct Client {
     /// A container for an optional cache and HTTP client.
     /// It also contains the cache update timeout.
     pub cache_and_http: Arc<CacheAndHttp>,
+    /// The gateway information fetched when the client was built.
+    bot_gateway: BotGateway,
 }
 
 impl Client {
@@ -648,6 +897,15 @@ impl Client {
         ClientBuilder::new(token)
     }
 
+    /// The session start limit as of when the client was built.
+    ///
+    /// Every shard started uses one of the [`SessionStartLimit::remaining`]
+    /// sessions, and up to [`SessionStartLimit::max_concurrency`] shards are
+    /// started at the same time.
+    pub fn session_start_limit(&self) -> &SessionStartLimit {
+        &self.bot_gateway.session_start_limit
+    }
+
     /// Establish the connection and start listening for events.
     ///
     /// This will start receiving events in a loop and start dispatching the
@@ -735,9 +993,9 @@ impl Client {
     #[instrument(skip(self))]
     pub async fn start_autosharded(&mut self) -> Result<()> {
         let (x, y) = {
-            let res = self.cache_and_http.http.get_bot_gateway().await?;
+            let shards = self.bot_gateway.shards;
 
-            (res.shards as u64 - 1, res.shards as u64)
+            (shards - 1, shards)
         };
 
         self.start_connection([0, x, y]).await
@@ -932,6 +1190,19 @@ impl Client {
             let mut manager = self.shard_manager.lock().await;
 
             let init = shard_data[1] - shard_data[0] + 1;
+            let limit = &self.bot_gateway.session_start_limit;
+
+            info!("{} of {} session starts remaining", limit.remaining, limit.total);
+
+            if init > limit.remaining {
+                error!("Not enough session starts left to start {} shards", init);
+
+                return Err(Error::Client(ClientError::SessionStartLimitExhausted {
+                    shards: init,
+                    remaining: limit.remaining,
+                    reset_after: limit.reset_after,
+                }));
+            }
 
             manager.set_shards(shard_data[0], init, shard_data[2]).await;
 
@@ -1042,3 +1313,30 @@ pub fn parse_token(token: impl AsRef<str>) -> Option<TokenComponents> {
         creation_time: timestamp,
     })
 }
+
+#[cfg(all(test, feature = "gateway"))]
+mod test {
+    use super::{Activity, ClientBuilder, ClientError, GatewayIntents};
+    #[cfg(feature = "model")]
+    use crate::model::ModelError;
+    use crate::Error;
+
+    #[tokio::test]
+    async fn test_builder_validation() {
+        let missing = ClientBuilder::default().build().await;
+        assert!(matches!(missing, Err(Error::Client(ClientError::MissingToken))));
+
+        let empty = ClientBuilder::new("  ").build().await;
+        assert!(matches!(empty, Err(Error::Client(ClientError::MissingToken))));
+
+        let no_intents = ClientBuilder::new("token").intents(GatewayIntents::empty()).build().await;
+        assert!(matches!(no_intents, Err(Error::Client(ClientError::EmptyIntents))));
+
+        #[cfg(feature = "model")]
+        {
+            let activity = Activity::streaming("Coding", "https://example.com/stream");
+            let bad_stream = ClientBuilder::new("token").activity(activity).build().await;
+            assert!(matches!(bad_stream, Err(Error::Model(ModelError::InvalidStreamUrl(_)))));
+        }
+    }
+}
@@ -1,11 +1,58 @@
 //! A set of constants used by the library.
 
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, TimeZone, Utc};
+
 /// The maximum length of the textual size of an embed.
 pub const EMBED_MAX_LENGTH: usize = 6000;
 
 /// The maximum number of embeds in a message.
 pub const EMBED_MAX_COUNT: usize = 10;
 
+/// The Discord epoch - the first second of 2015 - in milliseconds since the
+/// Unix epoch. The timestamps encoded in snowflakes count from this point.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::constants::DISCORD_EPOCH;
+///
+/// assert_eq!(Utc.timestamp_millis(DISCORD_EPOCH as i64).timestamp(), 1420070400);
+/// ```
+pub const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// Returns the [`DISCORD_EPOCH`] as a date and time.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::constants::discord_epoch;
+///
+/// assert_eq!(discord_epoch().to_rfc3339(), "2015-01-01T00:00:00+00:00");
+/// ```
+pub fn discord_epoch() -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(DISCORD_EPOCH as i64)
+        .single()
+        .expect("the Discord epoch is a valid timestamp")
+}
+
+/// The base URL of the Discord API, which the API version and the paths of
+/// [`Route`]s are appended to.
+///
+/// [`Route`]: crate::http::routing::Route
+pub const API_BASE_URL: &str = "https://discord.com";
+
+/// The version of the Discord API used by default.
+pub const API_VERSION: u8 = 9;
+
+/// The versions of the Discord API that can be used, see
+/// [`HttpBuilder::api_version`].
+///
+/// [`HttpBuilder::api_version`]: crate::http::HttpBuilder::api_version
+pub const SUPPORTED_API_VERSIONS: RangeInclusive<u8> = 8..=10;
+
 /// The gateway version used by the library. The gateway URI is retrieved via
 /// the REST API.
 pub const GATEWAY_VERSION: u8 = 9;
@@ -169,3 +216,14 @@ pub mod close_codes {
     /// Disallowed gateway intents have been provided.
     pub const DISALLOWED_GATEWAY_INTENTS: u16 = 4014;
 }
+
+#[cfg(test)]
+mod test {
+    use super::discord_epoch;
+
+    #[test]
+    fn test_discord_epoch() {
+        assert_eq!(discord_epoch().timestamp(), 1420070400);
+        assert_eq!(discord_epoch().timestamp_subsec_millis(), 0);
+    }
+}
@@ -116,6 +116,26 @@ pub enum Error {
     Tungstenite(TungsteniteError),
 }
 
+impl Error {
+    /// Returns true when the error is caused by an HTTP request failing because
+    /// the current user lacks the required permissions.
+    ///
+    /// See [`HttpError::is_missing_permissions`].
+    #[cfg(feature = "http")]
+    pub fn is_missing_permissions(&self) -> bool {
+        matches!(self, Error::Http(e) if e.is_missing_permissions())
+    }
+
+    /// Returns true when the error is caused by an HTTP request targeting a
+    /// message that does not exist.
+    ///
+    /// See [`HttpError::is_unknown_message`].
+    #[cfg(feature = "http")]
+    pub fn is_unknown_message(&self) -> bool {
+        matches!(self, Error::Http(e) if e.is_unknown_message())
+    }
+}
+
 impl From<FormatError> for Error {
     fn from(e: FormatError) -> Error {
         Error::Format(e)
@@ -188,7 +208,7 @@ impl From<InvalidHeaderValue> for Error {
 #[cfg(feature = "http")]
 impl From<ReqwestError> for Error {
     fn from(e: ReqwestError) -> Error {
-        HttpError::Request(e).into()
+        HttpError::from(e).into()
     }
 }
 
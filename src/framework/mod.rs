@@ -39,14 +39,14 @@
 //!
 //! #[command]
 //! async fn about(ctx: &Context, msg: &Message) -> CommandResult {
-//!     msg.channel_id.say(&ctx.http, "A simple test bot").await?;
+//!     msg.channel_id.say(ctx.http(), "A simple test bot").await?;
 //!
 //!     Ok(())
 //! }
 //!
 //! #[command]
 //! async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
-//!     msg.channel_id.say(&ctx.http, "pong!").await?;
+//!     msg.channel_id.say(ctx.http(), "pong!").await?;
 //!
 //!     Ok(())
 //! }
@@ -268,7 +268,7 @@ impl Configuration {
     ///
     /// #[command]
     /// async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
-    ///     msg.channel_id.say(&ctx.http, "Pong!").await?;
+    ///     msg.channel_id.say(ctx.http(), "Pong!").await?;
     ///     Ok(())
     /// }
     ///
@@ -1312,7 +1312,7 @@ pub async fn with_embeds(
             ref suggestions,
         } => {
             send_suggestion_embed(
-                &ctx.http,
+                ctx.http(),
                 msg.channel_id,
                 help_description,
                 suggestions,
@@ -1324,7 +1324,7 @@ pub async fn with_embeds(
             help_error_message,
         } => {
             send_error_embed(
-                &ctx.http,
+                ctx.http(),
                 msg.channel_id,
                 help_error_message,
                 help_options.embed_error_colour,
@@ -1336,7 +1336,7 @@ pub async fn with_embeds(
             ref groups,
         } => {
             send_grouped_commands_embed(
-                &ctx.http,
+                ctx.http(),
                 help_options,
                 msg.channel_id,
                 help_description,
@@ -1349,7 +1349,7 @@ pub async fn with_embeds(
             ref command,
         } => {
             send_single_command_embed(
-                &ctx.http,
+                ctx.http(),
                 help_options,
                 msg.channel_id,
                 command,
@@ -215,6 +215,7 @@ impl StandardFramework {
             || (self.config.ignore_webhooks && msg.webhook_id.is_some())
     }
 
+    #[allow(deprecated)]
     async fn should_fail<'a>(
         &'a self,
         ctx: &'a Context,
@@ -343,14 +344,14 @@ impl StandardFramework {
     /// // For information regarding this macro, learn more about it in its documentation in `command_attr`.
     /// #[command]
     /// async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
-    ///     msg.channel_id.say(&ctx.http, "pong!").await?;
+    ///     msg.channel_id.say(ctx.http(), "pong!").await?;
     ///
     ///     Ok(())
     /// }
     ///
     /// #[command]
     /// async fn pong(ctx: &Context, msg: &Message) -> CommandResult {
-    ///     msg.channel_id.say(&ctx.http, "ping!").await?;
+    ///     msg.channel_id.say(ctx.http(), "ping!").await?;
     ///
     ///     Ok(())
     /// }
@@ -26,6 +26,7 @@ use uwl::Stream;
 // the author does possess them. To avoid defaulting to permissions of everyone, we fetch
 // the member from HTTP if it is missing in the guild's members list.
 #[cfg(feature = "cache")]
+#[allow(deprecated)]
 async fn permissions_in(
     ctx: &Context,
     guild_id: GuildId,
@@ -241,6 +242,7 @@ pub async fn prefix<'a>(
 }
 
 /// Checked per valid group or command in the message.
+#[allow(deprecated)]
 async fn check_discrepancy(
     #[allow(unused_variables)] ctx: &Context,
     msg: &Message,
@@ -269,7 +271,7 @@ async fn check_discrepancy(
             {
                 Some(Some(member)) => member,
                 // Member not found.
-                Some(None) => match ctx.http.get_member(guild_id.0, msg.author.id.0).await {
+                Some(None) => match ctx.http().get_member(guild_id.0, msg.author.id.0).await {
                     Ok(member) => member,
                     Err(_) => return Ok(()),
                 },
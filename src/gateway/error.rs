@@ -5,6 +5,8 @@ use std::{
 
 use async_tungstenite::tungstenite::protocol::CloseFrame;
 
+use crate::client::bridge::gateway::GatewayIntents;
+
 /// An error that occurred while attempting to deal with the gateway.
 ///
 /// Note that - from a user standpoint - there should be no situation in which
@@ -50,11 +52,25 @@ pub enum Error {
     ReconnectFailure,
     /// When undocumented gateway intents are provided.
     InvalidGatewayIntents,
-    /// When disallowed gatewax intents are provided.
+    /// When the payloads received over a connection with
+    /// [`TransportCompression::ZlibStream`] could not be decompressed.
+    ///
+    /// [`TransportCompression::ZlibStream`]: super::TransportCompression::ZlibStream
+    DecompressionFailed,
+    /// When a payload could not be decoded from Erlang's External Term Format.
+    #[cfg(feature = "etf")]
+    InvalidEtf(String),
+    /// When disallowed gateway intents are provided.
     ///
     /// If an connection has been established but priviliged gateway intents
     /// were provided without enabling them prior.
     DisallowedGatewayIntents,
+    /// When the client was built with privileged gateway intents that are not
+    /// enabled for the application.
+    ///
+    /// Holds the privileged intents that are not enabled. Unlike
+    /// [`Self::DisallowedGatewayIntents`], this is returned before connecting.
+    PrivilegedIntentsNotEnabled(GatewayIntents),
 }
 
 impl Display for Error {
@@ -73,9 +89,15 @@ impl Display for Error {
             Error::OverloadedShard => f.write_str("Shard has too many guilds"),
             Error::ReconnectFailure => f.write_str("Failed to Reconnect"),
             Error::InvalidGatewayIntents => f.write_str("Invalid gateway intents were provided"),
+            Error::DecompressionFailed => f.write_str("Failed to decompress a payload"),
+            #[cfg(feature = "etf")]
+            Error::InvalidEtf(why) => write!(f, "Invalid ETF payload: {}", why),
             Error::DisallowedGatewayIntents => {
                 f.write_str("Disallowed gateway intents were provided")
             },
+            Error::PrivilegedIntentsNotEnabled(intents) => {
+                write!(f, "Privileged gateway intents are not enabled: {:?}", intents)
+            },
         }
     }
 }
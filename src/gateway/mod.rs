@@ -60,6 +60,8 @@ pub use self::{
     ws_client_ext::WebSocketGatewayClientExt,
 };
 #[cfg(feature = "client")]
+pub(crate) use self::ws_client_ext::{chunk_guild_payload, voice_state_payload};
+#[cfg(feature = "client")]
 use crate::client::bridge::gateway::ShardClientMessage;
 use crate::model::{gateway::Activity, user::OnlineStatus};
 
@@ -148,6 +150,27 @@ impl Display for ConnectionStage {
     }
 }
 
+/// How the gateway compresses the payloads it sends to a [`Shard`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum TransportCompression {
+    /// Large payloads are compressed one by one, and the others are sent as
+    /// text.
+    ///
+    /// This is the default.
+    None,
+    /// All payloads are compressed as a single zlib stream per connection,
+    /// which compresses better as it is not reset between payloads, e.g.
+    /// for the burst of guilds after identifying.
+    ZlibStream,
+}
+
+impl Default for TransportCompression {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
 /// A message to be passed around within the library.
 ///
 /// As a user you usually don't need to worry about this, but when working with
@@ -17,6 +17,7 @@ use super::{
     GatewayError,
     ReconnectType,
     ShardAction,
+    TransportCompression,
     WebSocketGatewayClientExt,
     WsStream,
 };
@@ -27,6 +28,7 @@ use crate::internal::prelude::*;
 use crate::internal::ws_impl::create_native_tls_client;
 #[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
 use crate::internal::ws_impl::create_rustls_client;
+use crate::internal::ws_impl::{convert_ws_message, recv_ws_message, Payload, ZlibStreamInflater};
 use crate::model::{
     event::{Event, GatewayEvent},
     gateway::Activity,
@@ -68,6 +70,7 @@ use crate::model::{
 /// [module docs]: crate::gateway#sharding
 pub struct Shard {
     pub client: WsStream,
+    compression: TransportCompression,
     current_presence: CurrentPresence,
     /// A tuple of:
     ///
@@ -79,11 +82,19 @@ pub struct Shard {
     /// [`latency`]: fn@Self::latency
     heartbeat_instants: (Option<Instant>, Option<Instant>),
     heartbeat_interval: Option<u64>,
+    /// The latency measured by the last acknowledged heartbeat.
+    latency: Option<StdDuration>,
+    /// The decompression context of the connection, if it uses
+    /// [`TransportCompression::ZlibStream`].
+    inflater: ZlibStreamInflater,
     /// This is used by the heartbeater to determine whether the last
     /// heartbeat was sent without an acknowledgement, and whether to reconnect.
     // This _must_ be set to `true` in `Shard::handle_event`'s
     // `Ok(GatewayEvent::HeartbeatAck)` arm.
     last_heartbeat_acknowledged: bool,
+    /// The gateway URL given by the READY of the current session, to connect
+    /// to when resuming it.
+    resume_ws_url: Option<String>,
     seq: u64,
     session_id: Option<String>,
     shard_info: [u64; 2],
@@ -140,9 +151,36 @@ impl Shard {
         token: &str,
         shard_info: [u64; 2],
         intents: GatewayIntents,
+    ) -> Result<Shard> {
+        Self::new_with_compression(
+            ws_url,
+            token,
+            shard_info,
+            intents,
+            TransportCompression::default(),
+        )
+        .await
+    }
+
+    /// Instantiates a new instance of a Shard using the given transport
+    /// compression, bypassing the client.
+    ///
+    /// Refer to [`Self::new`] for more information.
+    ///
+    /// # Errors
+    /// On Error, will return either [`Error::Gateway`], [`Error::Tungstenite`]
+    /// or a Rustls/native TLS error.
+    ///
+    /// [`Error::Gateway`]: crate::Error::Gateway
+    pub async fn new_with_compression(
+        ws_url: Arc<Mutex<String>>,
+        token: &str,
+        shard_info: [u64; 2],
+        intents: GatewayIntents,
+        compression: TransportCompression,
     ) -> Result<Shard> {
         let url = ws_url.lock().await.clone();
-        let client = connect(&url).await?;
+        let client = connect(&url, compression).await?;
 
         let current_presence = (None, OnlineStatus::Online);
         let heartbeat_instants = (None, None);
@@ -155,10 +193,14 @@ impl Shard {
         Ok(Shard {
             shutdown: false,
             client,
+            compression,
             current_presence,
             heartbeat_instants,
             heartbeat_interval,
+            latency: None,
+            inflater: ZlibStreamInflater::new(),
             last_heartbeat_acknowledged,
+            resume_ws_url: None,
             seq,
             stage,
             started: Instant::now(),
@@ -170,6 +212,12 @@ impl Shard {
         })
     }
 
+    /// Retrieves the transport compression used by the shard.
+    #[inline]
+    pub fn transport_compression(&self) -> TransportCompression {
+        self.compression
+    }
+
     /// Retrieves the current presence of the shard.
     #[inline]
     pub fn current_presence(&self) -> &CurrentPresence {
@@ -337,6 +385,7 @@ impl Shard {
                 debug!("[Shard {:?}] Received Ready", self.shard_info);
 
                 self.session_id = Some(ready.ready.session_id.clone());
+                self.resume_ws_url = ready.ready.resume_gateway_url.clone();
                 self.stage = ConnectionStage::Connected;
             },
             Event::Resumed(_) => {
@@ -399,16 +448,12 @@ impl Shard {
             },
             Some(close_codes::NOT_AUTHENTICATED) => {
                 warn!("[Shard {:?}] Sent no authentication.", self.shard_info);
-
-                return Err(Error::Gateway(GatewayError::NoAuthentication));
             },
             Some(close_codes::AUTHENTICATION_FAILED) => {
                 error!(
                     "[Shard {:?}] Sent invalid authentication, please check the token.",
                     self.shard_info
                 );
-
-                return Err(Error::Gateway(GatewayError::InvalidAuthentication));
             },
             Some(close_codes::ALREADY_AUTHENTICATED) => {
                 warn!("[Shard {:?}] Already authenticated.", self.shard_info);
@@ -423,31 +468,24 @@ impl Shard {
             },
             Some(close_codes::INVALID_SHARD) => {
                 warn!("[Shard {:?}] Sent invalid shard data.", self.shard_info);
-
-                return Err(Error::Gateway(GatewayError::InvalidShardData));
             },
             Some(close_codes::SHARDING_REQUIRED) => {
                 error!("[Shard {:?}] Shard has too many guilds.", self.shard_info);
-
-                return Err(Error::Gateway(GatewayError::OverloadedShard));
             },
             Some(4006) | Some(close_codes::SESSION_TIMEOUT) => {
                 info!("[Shard {:?}] Invalid session.", self.shard_info);
 
                 self.session_id = None;
+                self.resume_ws_url = None;
             },
             Some(close_codes::INVALID_GATEWAY_INTENTS) => {
                 error!("[Shard {:?}] Invalid gateway intents have been provided.", self.shard_info);
-
-                return Err(Error::Gateway(GatewayError::InvalidGatewayIntents));
             },
             Some(close_codes::DISALLOWED_GATEWAY_INTENTS) => {
                 error!(
                     "[Shard {:?}] Disallowed gateway intents have been provided.",
                     self.shard_info
                 );
-
-                return Err(Error::Gateway(GatewayError::DisallowedGatewayIntents));
             },
             Some(other) if !clean => {
                 warn!(
@@ -460,6 +498,10 @@ impl Shard {
             _ => {},
         }
 
+        if let Some(why) = num.and_then(|code| close_code_error(code)) {
+            return Err(Error::Gateway(why));
+        }
+
         let resume = num
             .map(|x| x != close_codes::AUTHENTICATION_FAILED && self.session_id.is_some())
             .unwrap_or(true);
@@ -506,7 +548,17 @@ impl Shard {
             },
             Ok(GatewayEvent::Heartbeat(s)) => Ok(Some(self.handle_heartbeat_event(s))),
             Ok(GatewayEvent::HeartbeatAck) => {
-                self.heartbeat_instants.1 = Some(Instant::now());
+                let now = Instant::now();
+
+                // Only the acknowledgement of a sent heartbeat measures the
+                // latency.
+                if !self.last_heartbeat_acknowledged {
+                    if let Some(latency) = heartbeat_latency(self.heartbeat_instants.0, now) {
+                        self.latency = Some(latency);
+                    }
+                }
+
+                self.heartbeat_instants.1 = Some(now);
                 self.last_heartbeat_acknowledged = true;
 
                 trace!("[Shard {:?}] Received heartbeat ack", self.shard_info);
@@ -611,18 +663,16 @@ impl Shard {
         }
     }
 
-    /// Calculates the heartbeat latency between the shard and the gateway.
-    // Shamelessly stolen from brayzure's commit in eris:
-    // <https://github.com/abalabahaha/eris/commit/0ce296ae9a542bcec0edf1c999ee2d9986bed5a6>
+    /// Returns the heartbeat latency between the shard and the gateway, i.e.
+    /// the time between sending the last acknowledged heartbeat and receiving
+    /// its acknowledgement.
+    ///
+    /// This is `None` until the first heartbeat is acknowledged, and is updated
+    /// with every acknowledgement after that. While a heartbeat awaits its
+    /// acknowledgement, the previous latency is returned.
     #[instrument(skip(self))]
     pub fn latency(&self) -> Option<StdDuration> {
-        if let (Some(sent), Some(received)) = self.heartbeat_instants {
-            if received > sent {
-                return Some(received - sent);
-            }
-        }
-
-        None
+        self.latency
     }
 
     /// Performs a deterministic reconnect.
@@ -731,7 +781,18 @@ impl Shard {
     /// - the `stage` to [`ConnectionStage::Identifying`]
     #[instrument(skip(self))]
     pub async fn identify(&mut self) -> Result<()> {
-        self.client.send_identify(&self.shard_info, &self.token, self.intents).await?;
+        // Payloads must not be compressed again when using `zlib-stream`.
+        let compress = self.compression == TransportCompression::None;
+
+        self.client
+            .send_identify_with_presence(
+                &self.shard_info,
+                &self.token,
+                self.intents,
+                compress,
+                &self.current_presence,
+            )
+            .await?;
 
         self.heartbeat_instants.0 = Some(Instant::now());
         self.stage = ConnectionStage::Identifying;
@@ -743,6 +804,9 @@ impl Shard {
     ///
     /// This will set the stage of the shard before and after instantiation of
     /// the client.
+    ///
+    /// If the shard has a session to resume, the client connects to the
+    /// gateway URL given for resuming it, if any.
     #[instrument(skip(self))]
     pub async fn initialize(&mut self) -> Result<WsStream> {
         debug!("[Shard {:?}] Initializing.", self.shard_info);
@@ -757,19 +821,47 @@ impl Shard {
         // accurate when a Hello is received.
         self.stage = ConnectionStage::Connecting;
         self.started = Instant::now();
-        let url = &self.ws_url.lock().await.clone();
-        let client = connect(url).await?;
+        let ws_url = self.ws_url.lock().await.clone();
+        let url =
+            connection_url(&self.reconnection_type(), &ws_url, self.resume_ws_url.as_deref());
+        let client = connect(url, self.compression).await?;
+        self.inflater.reset();
         self.stage = ConnectionStage::Handshake;
 
         Ok(client)
     }
 
+    /// Receives the next payload from the gateway, decompressing it if needed.
+    ///
+    /// Returns `None` if no payload was received within half a second, or if
+    /// only a part of a compressed payload was received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GatewayError::DecompressionFailed`] if the payloads could not
+    /// be decompressed, in which case the shard must reconnect.
+    pub async fn recv_json(&mut self) -> Result<Option<Value>> {
+        self.recv_payload().await?.map(Payload::into_value).transpose()
+    }
+
+    /// Receives the next payload from the gateway as [`Self::recv_json`] does,
+    /// without decoding it into a [`Value`] if it does not have to be.
+    pub(crate) async fn recv_payload(&mut self) -> Result<Option<Payload>> {
+        let message = recv_ws_message(&mut self.client).await?;
+
+        match self.compression {
+            TransportCompression::None => convert_ws_message(message),
+            TransportCompression::ZlibStream => self.inflater.convert(message),
+        }
+    }
+
     #[instrument(skip(self))]
     pub async fn reset(&mut self) {
         self.heartbeat_instants = (Some(Instant::now()), None);
         self.heartbeat_interval = None;
         self.last_heartbeat_acknowledged = true;
         self.session_id = None;
+        self.resume_ws_url = None;
         self.stage = ConnectionStage::Disconnected;
         self.seq = 0;
     }
@@ -806,23 +898,129 @@ impl Shard {
 }
 
 #[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
-async fn connect(base_url: &str) -> Result<WsStream> {
-    let url = build_gateway_url(base_url)?;
+async fn connect(base_url: &str, compression: TransportCompression) -> Result<WsStream> {
+    let url = build_gateway_url(base_url, compression)?;
 
     Ok(create_rustls_client(url).await?)
 }
 
 #[cfg(feature = "native_tls_backend_marker")]
-async fn connect(base_url: &str) -> Result<WsStream> {
-    let url = build_gateway_url(base_url)?;
+async fn connect(base_url: &str, compression: TransportCompression) -> Result<WsStream> {
+    let url = build_gateway_url(base_url, compression)?;
 
     Ok(create_native_tls_client(url).await?)
 }
 
-fn build_gateway_url(base: &str) -> Result<Url> {
-    Url::parse(&format!("{}?v={}", base, constants::GATEWAY_VERSION)).map_err(|why| {
+fn build_gateway_url(base: &str, compression: TransportCompression) -> Result<Url> {
+    let compress = match compression {
+        TransportCompression::None => "",
+        TransportCompression::ZlibStream => "&compress=zlib-stream",
+    };
+
+    let encoding = if cfg!(feature = "etf") { "etf" } else { "json" };
+    let url =
+        format!("{}?v={}&encoding={}{}", base, constants::GATEWAY_VERSION, encoding, compress);
+
+    Url::parse(&url).map_err(|why| {
         warn!("Error building gateway URL with base `{}`: {:?}", base, why);
 
         Error::Gateway(GatewayError::BuildingUrl)
     })
 }
+
+/// Picks the gateway URL to connect to for a reconnection of the given type.
+///
+/// Resuming uses the URL given for it by the READY payload, falling back to
+/// the regular gateway URL if the payload did not include one, while fresh
+/// identifies always use the regular one.
+fn connection_url<'a>(
+    reconnect: &ReconnectType,
+    ws_url: &'a str,
+    resume_ws_url: Option<&'a str>,
+) -> &'a str {
+    match (reconnect, resume_ws_url) {
+        (ReconnectType::Resume, Some(resume_ws_url)) => resume_ws_url,
+        _ => ws_url,
+    }
+}
+
+/// Calculates the latency of a heartbeat sent at `sent` and acknowledged at
+/// `acknowledged`.
+// Shamelessly stolen from brayzure's commit in eris:
+// <https://github.com/abalabahaha/eris/commit/0ce296ae9a542bcec0edf1c999ee2d9986bed5a6>
+fn heartbeat_latency(sent: Option<Instant>, acknowledged: Instant) -> Option<StdDuration> {
+    sent.and_then(|sent| acknowledged.checked_duration_since(sent))
+}
+
+/// Maps a close code sent by the gateway to the error it represents, if the
+/// shard should not try to reconnect after it.
+fn close_code_error(code: u16) -> Option<GatewayError> {
+    Some(match code {
+        close_codes::NOT_AUTHENTICATED => GatewayError::NoAuthentication,
+        close_codes::AUTHENTICATION_FAILED => GatewayError::InvalidAuthentication,
+        close_codes::INVALID_SHARD => GatewayError::InvalidShardData,
+        close_codes::SHARDING_REQUIRED => GatewayError::OverloadedShard,
+        close_codes::INVALID_GATEWAY_INTENTS => GatewayError::InvalidGatewayIntents,
+        close_codes::DISALLOWED_GATEWAY_INTENTS => GatewayError::DisallowedGatewayIntents,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{close_code_error, connection_url, heartbeat_latency};
+    use crate::constants::close_codes;
+    use crate::gateway::{GatewayError, ReconnectType};
+
+    #[test]
+    fn test_heartbeat_latency() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        // Nothing can be measured before a heartbeat is sent.
+        assert_eq!(heartbeat_latency(None, at(100)), None);
+
+        assert_eq!(heartbeat_latency(Some(at(0)), at(40)), Some(Duration::from_millis(40)));
+        assert_eq!(heartbeat_latency(Some(at(40)), at(40)), Some(Duration::from_millis(0)));
+        // An acknowledgement cannot precede the heartbeat.
+        assert_eq!(heartbeat_latency(Some(at(50)), at(40)), None);
+    }
+
+    #[test]
+    fn test_connection_url() {
+        let ws_url = "wss://gateway.discord.gg";
+        let resume_ws_url = Some("wss://gateway-us-east1-b.discord.gg");
+
+        assert_eq!(
+            connection_url(&ReconnectType::Resume, ws_url, resume_ws_url),
+            "wss://gateway-us-east1-b.discord.gg"
+        );
+        // Older READY payloads don't include a URL to resume with.
+        assert_eq!(connection_url(&ReconnectType::Resume, ws_url, None), ws_url);
+        // An invalidated session must be identified anew on the regular URL.
+        assert_eq!(connection_url(&ReconnectType::Reidentify, ws_url, resume_ws_url), ws_url);
+    }
+
+    #[test]
+    fn test_close_code_error() {
+        assert!(matches!(
+            close_code_error(close_codes::AUTHENTICATION_FAILED),
+            Some(GatewayError::InvalidAuthentication)
+        ));
+        assert!(matches!(
+            close_code_error(close_codes::INVALID_GATEWAY_INTENTS),
+            Some(GatewayError::InvalidGatewayIntents)
+        ));
+        assert!(matches!(
+            close_code_error(close_codes::DISALLOWED_GATEWAY_INTENTS),
+            Some(GatewayError::DisallowedGatewayIntents)
+        ));
+
+        // Codes after which the shard reconnects are not errors.
+        assert!(close_code_error(close_codes::SESSION_TIMEOUT).is_none());
+        assert!(close_code_error(close_codes::RATE_LIMITED).is_none());
+        assert!(close_code_error(1000).is_none());
+    }
+}
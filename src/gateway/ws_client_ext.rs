@@ -1,5 +1,5 @@
 use std::env::consts;
-use std::time::SystemTime;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use serde_json::json;
@@ -11,7 +11,8 @@ use crate::constants::{self, OpCode};
 use crate::gateway::{CurrentPresence, WsStream};
 use crate::internal::prelude::*;
 use crate::internal::ws_impl::SenderExt;
-use crate::model::id::GuildId;
+use crate::model::id::{ChannelId, GuildId};
+use crate::model::user::OnlineStatus;
 
 #[async_trait]
 pub trait WebSocketGatewayClientExt {
@@ -33,6 +34,29 @@ pub trait WebSocketGatewayClientExt {
         intents: GatewayIntents,
     ) -> Result<()>;
 
+    /// Identifies, requesting large payloads to be compressed one by one if
+    /// `compress` is `true`.
+    ///
+    /// This must be `false` when the whole connection is already compressed.
+    async fn send_identify_with_compress(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        intents: GatewayIntents,
+        compress: bool,
+    ) -> Result<()>;
+
+    /// Like [`Self::send_identify_with_compress`], but also sets the presence
+    /// the shard starts with.
+    async fn send_identify_with_presence(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        intents: GatewayIntents,
+        compress: bool,
+        presence: &CurrentPresence,
+    ) -> Result<()>;
+
     async fn send_presence_update(
         &mut self,
         shard_info: &[u64; 2],
@@ -61,23 +85,7 @@ impl WebSocketGatewayClientExt for WsStream {
     ) -> Result<()> {
         debug!("[Shard {:?}] Requesting member chunks", shard_info);
 
-        let mut payload = json!({
-            "op": OpCode::GetGuildMembers.num(),
-            "d": {
-                "guild_id": guild_id.as_ref().0.to_string(),
-                "limit": limit.unwrap_or(0),
-                "nonce": nonce.unwrap_or(""),
-            },
-        });
-
-        match filter {
-            ChunkGuildFilter::None => payload["d"]["query"] = json!(""),
-            ChunkGuildFilter::Query(query) => payload["d"]["query"] = json!(query),
-            ChunkGuildFilter::UserIds(user_ids) => {
-                let ids = user_ids.iter().map(|x| x.0).collect::<Vec<u64>>();
-                payload["d"]["user_ids"] = json!(ids)
-            },
-        };
+        let payload = chunk_guild_payload(guild_id, limit, filter, nonce, false);
 
         self.send_json(&payload).await.map_err(From::from)
     }
@@ -100,26 +108,38 @@ impl WebSocketGatewayClientExt for WsStream {
         shard_info: &[u64; 2],
         token: &str,
         intents: GatewayIntents,
+    ) -> Result<()> {
+        self.send_identify_with_compress(shard_info, token, intents, true).await
+    }
+
+    #[instrument(skip(self, token))]
+    async fn send_identify_with_compress(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        intents: GatewayIntents,
+        compress: bool,
+    ) -> Result<()> {
+        let presence = (None, OnlineStatus::Online);
+
+        self.send_identify_with_presence(shard_info, token, intents, compress, &presence).await
+    }
+
+    #[instrument(skip(self, token))]
+    async fn send_identify_with_presence(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        intents: GatewayIntents,
+        compress: bool,
+        presence: &CurrentPresence,
     ) -> Result<()> {
         debug!("[Shard {:?}] Identifying", shard_info);
 
-        self.send_json(&json!({
-            "op": OpCode::Identify.num(),
-            "d": {
-                "compress": true,
-                "large_threshold": constants::LARGE_THRESHOLD,
-                "shard": shard_info,
-                "token": token,
-                "intents": intents,
-                "v": constants::GATEWAY_VERSION,
-                "properties": {
-                    "$browser": "serenity",
-                    "$device": "serenity",
-                    "$os": consts::OS,
-                },
-            },
-        }))
-        .await
+        let payload =
+            identify_payload(shard_info, token, intents, compress, presence, SystemTime::now());
+
+        self.send_json(&payload).await
     }
 
     #[instrument(skip(self))]
@@ -128,23 +148,11 @@ impl WebSocketGatewayClientExt for WsStream {
         shard_info: &[u64; 2],
         current_presence: &CurrentPresence,
     ) -> Result<()> {
-        let &(ref activity, ref status) = current_presence;
-        let now = SystemTime::now();
-
         debug!("[Shard {:?}] Sending presence update", shard_info);
 
         self.send_json(&json!({
             "op": OpCode::StatusUpdate.num(),
-            "d": {
-                "afk": false,
-                "since": now,
-                "status": status.name(),
-                "game": activity.as_ref().map(|x| json!({
-                    "name": x.name,
-                    "type": x.kind,
-                    "url": x.url,
-                })),
-            },
+            "d": presence_payload(current_presence, SystemTime::now()),
         }))
         .await
     }
@@ -171,3 +179,216 @@ impl WebSocketGatewayClientExt for WsStream {
         .map_err(From::from)
     }
 }
+
+/// Builds a request for the members of a guild, which also requests their
+/// presences if `presences` is `true`.
+pub(crate) fn chunk_guild_payload(
+    guild_id: GuildId,
+    limit: Option<u16>,
+    filter: ChunkGuildFilter,
+    nonce: Option<&str>,
+    presences: bool,
+) -> Value {
+    let mut payload = json!({
+        "op": OpCode::GetGuildMembers.num(),
+        "d": {
+            "guild_id": guild_id.as_ref().0.to_string(),
+            "limit": limit.unwrap_or(0),
+            "nonce": nonce.unwrap_or(""),
+        },
+    });
+
+    match filter {
+        ChunkGuildFilter::None => payload["d"]["query"] = json!(""),
+        ChunkGuildFilter::Query(query) => payload["d"]["query"] = json!(query),
+        ChunkGuildFilter::UserIds(user_ids) => {
+            let ids = user_ids.iter().map(|x| x.0).collect::<Vec<u64>>();
+            payload["d"]["user_ids"] = json!(ids)
+        },
+    };
+
+    if presences {
+        payload["d"]["presences"] = json!(true);
+    }
+
+    payload
+}
+
+/// Builds a voice state update, which joins the channel, moves to it, or
+/// disconnects from voice in the guild if `channel_id` is `None`.
+pub(crate) fn voice_state_payload(
+    guild_id: GuildId,
+    channel_id: Option<ChannelId>,
+    self_mute: bool,
+    self_deaf: bool,
+) -> Value {
+    json!({
+        "op": OpCode::VoiceStateUpdate.num(),
+        "d": {
+            "guild_id": guild_id.0.to_string(),
+            "channel_id": channel_id.map(|id| id.0.to_string()),
+            "self_mute": self_mute,
+            "self_deaf": self_deaf,
+        },
+    })
+}
+
+fn identify_payload(
+    shard_info: &[u64; 2],
+    token: &str,
+    intents: GatewayIntents,
+    compress: bool,
+    presence: &CurrentPresence,
+    now: SystemTime,
+) -> Value {
+    json!({
+        "op": OpCode::Identify.num(),
+        "d": {
+            "compress": compress,
+            "large_threshold": constants::LARGE_THRESHOLD,
+            "shard": shard_info,
+            "token": token,
+            "intents": intents,
+            "v": constants::GATEWAY_VERSION,
+            "presence": presence_payload(presence, now),
+            "properties": {
+                "$browser": "serenity",
+                "$device": "serenity",
+                "$os": consts::OS,
+            },
+        },
+    })
+}
+
+/// Builds the presence object sent in an identify or a presence update.
+///
+/// `now` is only sent if the status is [`OnlineStatus::Idle`], as the time
+/// since which the user is idle.
+fn presence_payload(presence: &CurrentPresence, now: SystemTime) -> Value {
+    let &(ref activity, ref status) = presence;
+    let since = match status {
+        OnlineStatus::Idle => now.duration_since(UNIX_EPOCH).ok().map(|d| d.as_millis() as u64),
+        _ => None,
+    };
+
+    let activities = activity.iter().map(|activity| {
+        let mut value = json!({
+            "name": activity.name,
+            "type": activity.kind,
+        });
+
+        if let Some(url) = &activity.url {
+            value["url"] = json!(url);
+        }
+
+        if let Some(details) = &activity.details {
+            value["details"] = json!(details);
+        }
+
+        if let Some(state) = &activity.state {
+            value["state"] = json!(state);
+        }
+
+        value
+    });
+
+    json!({
+        "afk": false,
+        "since": since,
+        "status": status.name(),
+        "activities": activities.collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde_json::json;
+
+    use super::{identify_payload, presence_payload, voice_state_payload};
+    use crate::client::bridge::gateway::GatewayIntents;
+    use crate::model::gateway::Activity;
+    use crate::model::id::{ChannelId, GuildId};
+    use crate::model::user::OnlineStatus;
+
+    #[test]
+    fn test_identify_payload_presence() {
+        let presence = (Some(Activity::watching("the logs")), OnlineStatus::DoNotDisturb);
+        let payload = identify_payload(
+            &[0, 1],
+            "token",
+            GatewayIntents::GUILDS,
+            false,
+            &presence,
+            SystemTime::now(),
+        );
+
+        assert_eq!(payload["op"], 2);
+        assert_eq!(payload["d"]["compress"], false);
+        assert_eq!(payload["d"]["intents"], 1);
+        assert_eq!(
+            payload["d"]["presence"],
+            json!({
+                "afk": false,
+                "since": null,
+                "status": "dnd",
+                "activities": [{"name": "the logs", "type": 3}],
+            })
+        );
+    }
+
+    #[test]
+    fn test_presence_update_payload() {
+        let now = UNIX_EPOCH + Duration::from_secs(2);
+        let activity = Activity::streaming("Coding", "https://twitch.tv/serenity")
+            .with_details("Writing tests");
+
+        assert_eq!(
+            presence_payload(&(Some(activity), OnlineStatus::Idle), now),
+            json!({
+                "afk": false,
+                "since": 2000,
+                "status": "idle",
+                "activities": [{
+                    "name": "Coding",
+                    "type": 1,
+                    "url": "https://twitch.tv/serenity",
+                    "details": "Writing tests",
+                }],
+            })
+        );
+
+        assert_eq!(
+            presence_payload(&(Some(Activity::custom("Away")), OnlineStatus::Online), now),
+            json!({
+                "afk": false,
+                "since": null,
+                "status": "online",
+                "activities": [{"name": "Custom Status", "type": 4, "state": "Away"}],
+            })
+        );
+        assert_eq!(presence_payload(&(None, OnlineStatus::Online), now)["activities"], json!([]));
+    }
+
+    #[test]
+    fn test_voice_state_payload() {
+        assert_eq!(
+            voice_state_payload(GuildId(1), Some(ChannelId(2)), false, true),
+            json!({
+                "op": 4,
+                "d": {
+                    "guild_id": "1",
+                    "channel_id": "2",
+                    "self_mute": false,
+                    "self_deaf": true,
+                },
+            })
+        );
+
+        // A null channel disconnects from voice.
+        let payload = voice_state_payload(GuildId(1), None, false, false);
+        assert!(payload["d"]["channel_id"].is_null());
+        assert!(payload["d"].as_object().unwrap().contains_key("channel_id"));
+    }
+}
@@ -7,9 +7,11 @@ use std::{
     str::FromStr,
     sync::Arc,
     task::{Context as FutContext, Poll},
+    time::Duration,
 };
 
 use bytes::buf::Buf;
+use chrono::{DateTime, Utc};
 use futures::future::BoxFuture;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use reqwest::{
@@ -17,28 +19,40 @@ use reqwest::{
     StatusCode,
     Url,
 };
-use reqwest::{multipart::Part, Client, ClientBuilder, Response as ReqwestResponse};
+use reqwest::{
+    multipart::{Form, Part},
+    Client,
+    ClientBuilder,
+    RequestBuilder as ReqwestRequestBuilder,
+    Response as ReqwestResponse,
+};
 use serde::de::DeserializeOwned;
 use serde_json::json;
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(feature = "tokio")]
+use tokio::time::sleep;
 use tokio::{fs::File, io::AsyncReadExt};
 use tracing::{debug, instrument, trace};
 
 use super::{
+    error::ErrorResponse,
     ratelimiting::{RatelimitedRequest, Ratelimiter},
-    request::Request,
-    routing::RouteInfo,
+    request::{route_url, Request},
+    retry::RetryPolicy,
+    routing::{path, RouteInfo},
     typing::Typing,
     AttachmentType,
     GuildPagination,
     HttpError,
 };
 use crate::constants;
-use crate::http::routing::Route;
 use crate::internal::prelude::*;
 #[cfg(feature = "unstable_discord_api")]
 use crate::model::interactions::application_command::{
     ApplicationCommand,
     ApplicationCommandPermission,
+    CommandPermission,
 };
 use crate::model::prelude::*;
 
@@ -62,10 +76,28 @@ use crate::model::prelude::*;
 ///     .expect("Error creating Http");
 /// # }
 /// ```
+///
+/// ## Global ratelimit
+///
+/// Unless disabled, the ratelimiter pre-emptively limits the requests across
+/// all routes to Discord's default global ratelimit of
+/// [`DEFAULT_GLOBAL_LIMIT`] requests per second. Bots that were granted a
+/// higher limit should raise it through [`Self::ratelimiter_global_limit`],
+/// and bots whose requests go through a ratelimiting proxy may turn it off
+/// through [`Self::ratelimiter_global_limit_disabled`].
+///
+/// [`DEFAULT_GLOBAL_LIMIT`]: super::ratelimiting::DEFAULT_GLOBAL_LIMIT
 pub struct HttpBuilder<'a> {
     client: Option<Arc<Client>>,
     ratelimiter: Option<Ratelimiter>,
     ratelimiter_disabled: Option<bool>,
+    ratelimiter_global_limit: Option<u32>,
+    ratelimiter_global_limit_disabled: bool,
+    retry_policy: Option<RetryPolicy>,
+    request_timeout: Option<Duration>,
+    upload_timeout: Option<Option<Duration>>,
+    base_url: Option<String>,
+    api_version: Option<u8>,
     token: Option<String>,
     proxy: Option<Url>,
     fut: Option<BoxFuture<'a, Result<Http>>>,
@@ -79,6 +111,13 @@ impl<'a> HttpBuilder<'a> {
             client: None,
             ratelimiter: None,
             ratelimiter_disabled: Some(false),
+            ratelimiter_global_limit: None,
+            ratelimiter_global_limit_disabled: false,
+            retry_policy: None,
+            request_timeout: None,
+            upload_timeout: None,
+            base_url: None,
+            api_version: None,
             token: None,
             proxy: None,
             fut: None,
@@ -144,6 +183,102 @@ impl<'a> HttpBuilder<'a> {
         self
     }
 
+    /// Sets the number of requests per second the ratelimiter allows across
+    /// all routes. If this is not used, Discord's default of
+    /// [`DEFAULT_GLOBAL_LIMIT`] is used.
+    ///
+    /// Only set this if Discord granted your bot a higher global ratelimit.
+    ///
+    /// [`DEFAULT_GLOBAL_LIMIT`]: super::ratelimiting::DEFAULT_GLOBAL_LIMIT
+    pub fn ratelimiter_global_limit(mut self, global_limit: u32) -> Self {
+        self.ratelimiter_global_limit = Some(global_limit);
+
+        self
+    }
+
+    /// Sets whether or not the ratelimiter pre-emptively limits the number of
+    /// requests per second across all routes. Per-route ratelimits are still
+    /// respected.
+    ///
+    /// This is mainly intended for when requests go through a proxy that
+    /// enforces the global ratelimit on its own, see [`Self::proxy`].
+    pub fn ratelimiter_global_limit_disabled(mut self, global_limit_disabled: bool) -> Self {
+        self.ratelimiter_global_limit_disabled = global_limit_disabled;
+
+        self
+    }
+
+    /// Sets the policy for retrying requests that failed due to a ratelimit or
+    /// a server error. If one isn't provided, requests are not retried beyond
+    /// what the ratelimiter does on its own.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+
+        self
+    }
+
+    /// Sets how long a request may take at most, from sending it until its
+    /// response body has been read. A request exceeding this fails with
+    /// [`HttpError::RequestTimeout`].
+    ///
+    /// If this is not used, requests never time out. Single requests may
+    /// override this through [`Http::request_with_timeout`].
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+
+        self
+    }
+
+    /// Sets how long a file upload may take at most, as uploads of large files
+    /// can take much longer than other requests. Pass [`None`] to let uploads
+    /// run for as long as they take.
+    ///
+    /// If this is not used, uploads use the [`Self::request_timeout`].
+    pub fn upload_timeout(mut self, upload_timeout: Option<Duration>) -> Self {
+        self.upload_timeout = Some(upload_timeout);
+
+        self
+    }
+
+    /// Sets the base URL of the API that requests are sent to, e.g. a
+    /// compatibility proxy. If this is not used, the [`API_BASE_URL`] is used.
+    ///
+    /// The base URL should be in the form of the protocol and hostname, e.g.
+    /// `https://discord.com`; the API version and the path of the route are
+    /// appended to it. CDN URLs are not affected.
+    ///
+    /// [`API_BASE_URL`]: crate::constants::API_BASE_URL
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Result<Self> {
+        let base_url = base_url.into();
+        Url::from_str(&base_url).map_err(HttpError::Url)?;
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+
+        Ok(self)
+    }
+
+    /// Sets the version of the API to use. If this is not used, the
+    /// [`API_VERSION`] is used.
+    ///
+    /// **Note**: The models of the library are made for the default version,
+    /// other versions may add or change fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HttpError::UnsupportedApiVersion`] if the version is not
+    /// within the [`SUPPORTED_API_VERSIONS`].
+    ///
+    /// [`API_VERSION`]: crate::constants::API_VERSION
+    /// [`SUPPORTED_API_VERSIONS`]: crate::constants::SUPPORTED_API_VERSIONS
+    pub fn api_version(mut self, api_version: u8) -> Result<Self> {
+        if !constants::SUPPORTED_API_VERSIONS.contains(&api_version) {
+            return Err(HttpError::UnsupportedApiVersion(api_version).into());
+        }
+
+        self.api_version = Some(api_version);
+
+        Ok(self)
+    }
+
     /// Sets the proxy that Discord HTTP API requests will be passed to. This is
     /// mainly intended for something like [`twilight-http-proxy`] where
     /// multiple processes can make API requests while sharing a single
@@ -187,12 +322,24 @@ impl<'a> Future for HttpBuilder<'a> {
                 Arc::new(builder.build().expect("Cannot build reqwest::Client"))
             });
 
-            let ratelimiter = self.ratelimiter.take().unwrap_or_else(|| {
+            let mut ratelimiter = self.ratelimiter.take().unwrap_or_else(|| {
                 let client = Arc::clone(&client);
                 Ratelimiter::new(client, token.to_string())
             });
 
+            if self.ratelimiter_global_limit_disabled {
+                ratelimiter.set_global_limit(None);
+            } else if let Some(global_limit) = self.ratelimiter_global_limit {
+                ratelimiter.set_global_limit(Some(global_limit));
+            }
+
             let ratelimiter_disabled = self.ratelimiter_disabled.take().unwrap();
+            let retry_policy = self.retry_policy.take().unwrap_or_default();
+            let request_timeout = self.request_timeout.take();
+            let upload_timeout = self.upload_timeout.take().unwrap_or(request_timeout);
+            let base_url =
+                self.base_url.take().unwrap_or_else(|| constants::API_BASE_URL.to_string());
+            let api_version = self.api_version.take().unwrap_or(constants::API_VERSION);
             let proxy = self.proxy.take();
 
             self.fut = Some(Box::pin(async move {
@@ -200,6 +347,11 @@ impl<'a> Future for HttpBuilder<'a> {
                     client,
                     ratelimiter,
                     ratelimiter_disabled,
+                    retry_policy,
+                    request_timeout,
+                    upload_timeout,
+                    base_url,
+                    api_version,
                     proxy,
                     token,
                     #[cfg(feature = "unstable_discord_api")]
@@ -215,12 +367,23 @@ impl<'a> Future for HttpBuilder<'a> {
 /// **Note**: For all member functions that return a [`Result`], the
 /// Error kind will be either [`Error::Http`] or [`Error::Json`].
 ///
+/// Requests are pre-emptively limited to Discord's default global ratelimit of
+/// [`DEFAULT_GLOBAL_LIMIT`] requests per second across all routes, see
+/// [`Ratelimiter::global_limit`]. Use [`HttpBuilder::ratelimiter_global_limit`]
+/// to raise it, or [`Ratelimiter::set_global_limit`] to change it later.
+///
 /// [`Error::Http`]: crate::error::Error::Http
 /// [`Error::Json`]: crate::error::Error::Json
+/// [`DEFAULT_GLOBAL_LIMIT`]: super::ratelimiting::DEFAULT_GLOBAL_LIMIT
 pub struct Http {
     pub(crate) client: Arc<Client>,
     pub ratelimiter: Ratelimiter,
     pub ratelimiter_disabled: bool,
+    pub retry_policy: RetryPolicy,
+    pub request_timeout: Option<Duration>,
+    pub upload_timeout: Option<Duration>,
+    pub base_url: String,
+    pub api_version: u8,
     pub proxy: Option<Url>,
     pub token: String,
     #[cfg(feature = "unstable_discord_api")]
@@ -233,6 +396,11 @@ impl fmt::Debug for Http {
             .field("client", &self.client)
             .field("ratelimiter", &self.ratelimiter)
             .field("ratelimiter_disabled", &self.ratelimiter_disabled)
+            .field("retry_policy", &self.retry_policy)
+            .field("request_timeout", &self.request_timeout)
+            .field("upload_timeout", &self.upload_timeout)
+            .field("base_url", &self.base_url)
+            .field("api_version", &self.api_version)
             .field("proxy", &self.proxy)
             .finish()
     }
@@ -246,6 +414,11 @@ impl Http {
             client,
             ratelimiter: Ratelimiter::new(client2, token.to_string()),
             ratelimiter_disabled: false,
+            retry_policy: RetryPolicy::default(),
+            request_timeout: None,
+            upload_timeout: None,
+            base_url: constants::API_BASE_URL.to_string(),
+            api_version: constants::API_VERSION,
             proxy: None,
             token: token.to_string(),
             #[cfg(feature = "unstable_discord_api")]
@@ -288,6 +461,13 @@ impl Http {
         base
     }
 
+    /// The ratelimiter of the client, e.g. to check the configured
+    /// [`Ratelimiter::global_limit`].
+    #[inline]
+    pub fn ratelimiter(&self) -> &Ratelimiter {
+        &self.ratelimiter
+    }
+
     /// Adds a single [`Role`] to a [`Member`] in a [`Guild`].
     ///
     /// **Note**: Requires the [Manage Roles] permission and respect of role
@@ -313,6 +493,9 @@ impl Http {
     /// Passing a `delete_message_days` of `0` is equivalent to not removing any
     /// messages. Up to `7` days' worth of messages may be deleted.
     ///
+    /// A non-empty `reason` is sent as the `X-Audit-Log-Reason` header, and
+    /// shows up in the guild's audit log.
+    ///
     /// **Note**: Requires that you have the [Ban Members] permission.
     ///
     /// [Ban Members]: Permissions::BAN_MEMBERS
@@ -323,12 +506,16 @@ impl Http {
         delete_message_days: u8,
         reason: &str,
     ) -> Result<()> {
+        let body = serde_json::to_vec(&json!({
+            "delete_message_days": delete_message_days,
+        }))?;
+
         self.wind(204, Request {
-            body: None,
-            headers: None,
+            body: Some(&body),
+            headers: audit_log_reason(reason)?,
             route: RouteInfo::GuildBanUser {
-                delete_message_days: Some(delete_message_days),
-                reason: Some(&utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string()),
+                delete_message_days: None,
+                reason: None,
                 guild_id,
                 user_id,
             },
@@ -1305,6 +1492,41 @@ impl Http {
         .await
     }
 
+    /// Replaces the permission overrides of a command in a guild, restricting
+    /// which roles and users can use it.
+    ///
+    /// Unlike [`Self::edit_guild_application_command_permissions`], this takes
+    /// the id of the application the command belongs to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the command does not exist, or if the
+    /// bearer token lacks the permission to edit its permissions.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    #[cfg(feature = "unstable_discord_api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
+    pub async fn edit_application_command_permissions(
+        &self,
+        application_id: ApplicationId,
+        guild_id: GuildId,
+        command_id: CommandId,
+        permissions: Vec<CommandPermission>,
+    ) -> Result<ApplicationCommandPermission> {
+        let body = serde_json::to_vec(&json!({ "permissions": permissions }))?;
+
+        self.fire(Request {
+            body: Some(&body),
+            headers: None,
+            route: RouteInfo::EditGuildApplicationCommandPermission {
+                application_id: application_id.0,
+                guild_id: guild_id.0,
+                command_id: command_id.0,
+            },
+        })
+        .await
+    }
+
     /// Edits the positions of a guild's channels.
     pub async fn edit_guild_channel_positions(&self, guild_id: u64, value: &Value) -> Result<()> {
         let body = serde_json::to_vec(value)?;
@@ -1838,75 +2060,20 @@ impl Http {
     where
         T: Into<AttachmentType<'a>>,
     {
-        let mut multipart = reqwest::multipart::Form::new();
-
-        for (file_num, file) in files.into_iter().enumerate() {
-            match file.into() {
-                AttachmentType::Bytes {
-                    data,
-                    filename,
-                } => {
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(data.into_owned()).file_name(filename),
-                    );
-                },
-                AttachmentType::File {
-                    file,
-                    filename,
-                } => {
-                    let mut buf = Vec::new();
-                    file.try_clone().await?.read_to_end(&mut buf).await?;
-
-                    multipart =
-                        multipart.part(file_num.to_string(), Part::stream(buf).file_name(filename));
-                },
-                AttachmentType::Path(path) => {
-                    let filename =
-                        path.file_name().map(|filename| filename.to_string_lossy().into_owned());
-                    let mut file = File::open(path).await?;
-                    let mut buf = vec![];
-                    file.read_to_end(&mut buf).await?;
-
-                    let part = match filename {
-                        Some(filename) => Part::bytes(buf).file_name(filename),
-                        None => Part::bytes(buf),
-                    };
-
-                    multipart = multipart.part(file_num.to_string(), part);
-                },
-                AttachmentType::Image(url) => {
-                    let url = Url::parse(url).map_err(|_| Error::Url(url.to_string()))?;
-                    let filename = url
-                        .path_segments()
-                        .and_then(|segments| segments.last().map(ToString::to_string))
-                        .ok_or_else(|| Error::Url(url.to_string()))?;
-                    let response = self.client.get(url).send().await?;
-                    let mut bytes = response.bytes().await?;
-                    let mut picture: Vec<u8> = vec![0; bytes.len()];
-                    bytes.copy_to_slice(&mut picture[..]);
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(picture).file_name(filename.to_string()),
-                    );
-                },
-            }
-        }
-
-        multipart = multipart.text("payload_json", serde_json::to_string(&map)?);
+        let files = self.read_files(files).await?;
+        let payload_json = serde_json::to_string(&map)?;
 
+        let path = path::webhook_with_token_optioned(webhook_id, token, wait);
+        let url = route_url(self.request_base_url(), self.api_version, &path);
         let response = self
-            .client
-            .post(&Route::webhook_with_token_optioned(webhook_id, token, wait))
-            .multipart(multipart)
-            .header(CONTENT_TYPE, HeaderValue::from_static("multipart/form-data"))
-            .send()
+            .upload(&files, &payload_json, |multipart| {
+                self.client
+                    .post(&url)
+                    .multipart(multipart)
+                    .header(CONTENT_TYPE, HeaderValue::from_static("multipart/form-data"))
+            })
             .await?;
 
-        if !response.status().is_success() {
-            return Err(HttpError::from_response(response).await.into());
-        }
-
         response.json::<Message>().await.map(Some).map_err(From::from)
     }
 
@@ -2052,11 +2219,26 @@ impl Http {
         .await
     }
 
+    /// Gets all active threads from a guild.
+    pub async fn get_guild_active_threads(&self, guild_id: u64) -> Result<ThreadsData> {
+        self.fire(Request {
+            body: None,
+            headers: None,
+            route: RouteInfo::GetGuildActiveThreads {
+                guild_id,
+            },
+        })
+        .await
+    }
+
     /// Gets all archived public threads from a channel.
+    ///
+    /// The `before` cursor is the archive timestamp of a thread, as returned
+    /// in its [`ThreadMetadata::archive_timestamp`].
     pub async fn get_channel_archived_public_threads(
         &self,
         channel_id: u64,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     ) -> Result<ThreadsData> {
         self.fire(Request {
@@ -2072,10 +2254,13 @@ impl Http {
     }
 
     /// Gets all archived private threads from a channel.
+    ///
+    /// The `before` cursor is the archive timestamp of a thread, as returned
+    /// in its [`ThreadMetadata::archive_timestamp`].
     pub async fn get_channel_archived_private_threads(
         &self,
         channel_id: u64,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     ) -> Result<ThreadsData> {
         self.fire(Request {
@@ -2091,6 +2276,9 @@ impl Http {
     }
 
     /// Gets all archived private threads joined from a channel.
+    ///
+    /// Unlike the other archived thread listings, the `before` cursor is the
+    /// Id of a thread.
     pub async fn get_channel_joined_archived_private_threads(
         &self,
         channel_id: u64,
@@ -2760,6 +2948,40 @@ impl Http {
         .await
     }
 
+    /// Gets the users that voted for an answer of a poll.
+    ///
+    /// The `answer_id` is the poll's own id of the answer, starting at `1`,
+    /// not a snowflake. Pass the id of the last user received as `after` to
+    /// get the next page. Discord returns `25` users at most by default, and
+    /// at most `100` users per request.
+    pub async fn get_poll_answer_voters(
+        &self,
+        channel_id: u64,
+        message_id: u64,
+        answer_id: u8,
+        after: Option<u64>,
+        limit: Option<u8>,
+    ) -> Result<Vec<User>> {
+        let mut map: BTreeMap<String, Value> = self
+            .fire(Request {
+                body: None,
+                headers: None,
+                route: RouteInfo::GetPollAnswerVoters {
+                    after,
+                    answer_id,
+                    channel_id,
+                    limit,
+                    message_id,
+                },
+            })
+            .await?;
+
+        match map.remove("users") {
+            Some(v) => serde_json::from_value::<Vec<User>>(v).map_err(From::from),
+            None => Ok(vec![]),
+        }
+    }
+
     /// Gets user Ids based on their reaction to a message. This endpoint is dumb.
     pub async fn get_reaction_users(
         &self,
@@ -2940,6 +3162,9 @@ impl Http {
     }
 
     /// Kicks a member from a guild with a provided reason.
+    ///
+    /// A non-empty `reason` is sent as the `X-Audit-Log-Reason` header, and
+    /// shows up in the guild's audit log.
     pub async fn kick_member_with_reason(
         &self,
         guild_id: u64,
@@ -2948,11 +3173,11 @@ impl Http {
     ) -> Result<()> {
         self.wind(204, Request {
             body: None,
-            headers: None,
+            headers: audit_log_reason(reason)?,
             route: RouteInfo::KickMember {
                 guild_id,
                 user_id,
-                reason: &utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string(),
+                reason: "",
             },
         })
         .await
@@ -2986,88 +3211,27 @@ impl Http {
     where
         T: Into<AttachmentType<'a>>,
     {
-        let uri = api!("/channels/{}/messages", channel_id);
-        let mut url = match Url::parse(&uri) {
+        let path = path::channel_messages(channel_id, None);
+        let uri = route_url(self.request_base_url(), self.api_version, &path);
+        let url = match Url::parse(&uri) {
             Ok(url) => url,
             Err(_) => return Err(Error::Url(uri)),
         };
 
-        if let Some(proxy) = &self.proxy {
-            url.set_host(proxy.host_str()).map_err(HttpError::Url)?;
-            url.set_scheme(proxy.scheme()).map_err(|_| HttpError::InvalidScheme)?;
-            url.set_port(proxy.port()).map_err(|_| HttpError::InvalidPort)?;
-        }
-
-        let mut multipart = reqwest::multipart::Form::new();
-
-        for (file_num, file) in files.into_iter().enumerate() {
-            match file.into() {
-                AttachmentType::Bytes {
-                    data,
-                    filename,
-                } => {
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(data.into_owned()).file_name(filename),
-                    );
-                },
-                AttachmentType::File {
-                    file,
-                    filename,
-                } => {
-                    let mut buf = Vec::new();
-                    file.try_clone().await?.read_to_end(&mut buf).await?;
-
-                    multipart =
-                        multipart.part(file_num.to_string(), Part::stream(buf).file_name(filename));
-                },
-                AttachmentType::Path(path) => {
-                    let filename =
-                        path.file_name().map(|filename| filename.to_string_lossy().into_owned());
-                    let mut file = File::open(path).await?;
-                    let mut buf = vec![];
-                    file.read_to_end(&mut buf).await?;
-
-                    let part = match filename {
-                        Some(filename) => Part::bytes(buf).file_name(filename),
-                        None => Part::bytes(buf),
-                    };
-
-                    multipart = multipart.part(file_num.to_string(), part);
-                },
-                AttachmentType::Image(url) => {
-                    let url = Url::parse(url).map_err(|_| Error::Url(url.to_string()))?;
-                    let filename = url
-                        .path_segments()
-                        .and_then(|segments| segments.last().map(ToString::to_string))
-                        .ok_or_else(|| Error::Url(url.to_string()))?;
-                    let response = self.client.get(url).send().await?;
-                    let mut bytes = response.bytes().await?;
-                    let mut picture: Vec<u8> = vec![0; bytes.len()];
-                    bytes.copy_to_slice(&mut picture[..]);
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(picture).file_name(filename.to_string()),
-                    );
-                },
-            }
-        }
-
-        multipart = multipart.text("payload_json", serde_json::to_string(&map)?);
+        let files = self.read_files(files).await?;
+        let payload_json = serde_json::to_string(&map)?;
 
+        let token = HeaderValue::from_str(&self.token)?;
         let response = self
-            .client
-            .post(url)
-            .header(AUTHORIZATION, HeaderValue::from_str(&self.token)?)
-            .header(USER_AGENT, HeaderValue::from_static(constants::USER_AGENT))
-            .multipart(multipart)
-            .send()
+            .upload(&files, &payload_json, |multipart| {
+                self.client
+                    .post(url.clone())
+                    .header(AUTHORIZATION, token.clone())
+                    .header(USER_AGENT, HeaderValue::from_static(constants::USER_AGENT))
+                    .multipart(multipart)
+            })
             .await?;
 
-        if !response.status().is_success() {
-            return Err(HttpError::from_response(response).await.into());
-        }
-
         response.json::<Message>().await.map_err(From::from)
     }
 
@@ -3301,7 +3465,27 @@ impl Http {
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
     pub async fn fire<T: DeserializeOwned>(&self, req: Request<'_>) -> Result<T> {
-        let response = self.request(req).await?;
+        self.fire_with_timeout(req, self.request_timeout).await
+    }
+
+    /// Fires off a request like [`Self::fire`], but with the given `timeout`
+    /// instead of [`Self::request_timeout`].
+    ///
+    /// Pass [`None`] to let the request run for as long as it takes, e.g. for
+    /// large uploads.
+    ///
+    /// # Errors
+    ///
+    /// If there is an error, it will be either [`Error::Http`] or [`Error::Json`].
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    /// [`Error::Json`]: crate::error::Error::Json
+    pub async fn fire_with_timeout<T: DeserializeOwned>(
+        &self,
+        req: Request<'_>,
+        timeout: Option<Duration>,
+    ) -> Result<T> {
+        let response = self.request_with_timeout(req, timeout).await?;
 
         response.json::<T>().await.map_err(From::from)
     }
@@ -3341,20 +3525,197 @@ impl Http {
     /// #     Ok(())
     /// # }
     /// ```
-    #[instrument]
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HttpError::RetriesExhausted`] if the request was retried
+    /// according to the [`Self::retry_policy`] and still failed.
+    ///
+    /// Returns an [`HttpError::RequestTimeout`] if the request took longer
+    /// than [`Self::request_timeout`].
     pub async fn request(&self, req: Request<'_>) -> Result<ReqwestResponse> {
-        let response = if self.ratelimiter_disabled {
-            let request = req.build(&self.client, &self.token, self.proxy.as_ref())?.build()?;
-            self.client.execute(request).await?
-        } else {
-            let ratelimiting_req = RatelimitedRequest::from(req);
-            self.ratelimiter.perform(ratelimiting_req).await?
-        };
+        self.request_with_timeout(req, self.request_timeout).await
+    }
 
-        if response.status().is_success() {
-            Ok(response)
-        } else {
-            Err(Error::Http(Box::new(HttpError::from_response(response).await)))
+    /// Performs a request like [`Self::request`], but with the given `timeout`
+    /// instead of [`Self::request_timeout`].
+    ///
+    /// Pass [`None`] to let the request run for as long as it takes, e.g. for
+    /// large uploads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HttpError::RetriesExhausted`] if the request was retried
+    /// according to the [`Self::retry_policy`] and still failed.
+    ///
+    /// Returns an [`HttpError::RequestTimeout`] if the request took longer
+    /// than the `timeout`.
+    #[instrument]
+    pub async fn request_with_timeout(
+        &self,
+        req: Request<'_>,
+        timeout: Option<Duration>,
+    ) -> Result<ReqwestResponse> {
+        let base_url = self.request_base_url();
+        let mut attempts = 0u16;
+
+        loop {
+            attempts += 1;
+
+            let response = if self.ratelimiter_disabled {
+                let mut builder = req.build_with_base_url(
+                    &self.client,
+                    &self.token,
+                    base_url,
+                    self.api_version,
+                )?;
+
+                if let Some(timeout) = timeout {
+                    builder = builder.timeout(timeout);
+                }
+
+                self.client.execute(builder.build()?).await?
+            } else {
+                let ratelimiting_req = RatelimitedRequest::from(req.clone())
+                    .timeout(timeout)
+                    .base_url(base_url)
+                    .api_version(self.api_version);
+                self.ratelimiter.perform(ratelimiting_req).await?
+            };
+
+            if response.status().is_success() {
+                return Ok(response);
+            }
+
+            match self.retry_policy.delay(response.status(), response.headers(), attempts) {
+                Some(delay) => {
+                    debug!("Retrying request after {}: attempt {}", response.status(), attempts);
+
+                    sleep(delay).await;
+                },
+                None => return Err(retry_error(response, attempts).await),
+            }
+        }
+    }
+
+    /// The base URL that requests are sent to: the [`Self::proxy`] if set,
+    /// otherwise the [`Self::base_url`].
+    fn request_base_url(&self) -> &str {
+        self.proxy.as_ref().map_or(self.base_url.as_str(), Url::as_str)
+    }
+
+    /// Reads the files to upload into memory, so that a multipart form can be
+    /// built from them for every attempt of an upload.
+    async fn read_files<'a, T, It: IntoIterator<Item = T>>(
+        &self,
+        files: It,
+    ) -> Result<Vec<(Vec<u8>, Option<String>)>>
+    where
+        T: Into<AttachmentType<'a>>,
+    {
+        let mut read = Vec::new();
+
+        for file in files {
+            match file.into() {
+                AttachmentType::Bytes {
+                    data,
+                    filename,
+                } => {
+                    read.push((data.into_owned(), Some(filename)));
+                },
+                AttachmentType::File {
+                    file,
+                    filename,
+                } => {
+                    let mut buf = Vec::new();
+                    file.try_clone().await?.read_to_end(&mut buf).await?;
+
+                    read.push((buf, Some(filename)));
+                },
+                AttachmentType::Path(path) => {
+                    let filename =
+                        path.file_name().map(|filename| filename.to_string_lossy().into_owned());
+                    let mut file = File::open(path).await?;
+                    let mut buf = vec![];
+                    file.read_to_end(&mut buf).await?;
+
+                    read.push((buf, filename));
+                },
+                AttachmentType::Image(url) => {
+                    let url = Url::parse(url).map_err(|_| Error::Url(url.to_string()))?;
+                    let filename = url
+                        .path_segments()
+                        .and_then(|segments| segments.last().map(ToString::to_string))
+                        .ok_or_else(|| Error::Url(url.to_string()))?;
+                    let response = self.client.get(url).send().await?;
+                    let mut bytes = response.bytes().await?;
+                    let mut picture: Vec<u8> = vec![0; bytes.len()];
+                    bytes.copy_to_slice(&mut picture[..]);
+
+                    read.push((picture, Some(filename)));
+                },
+            }
+        }
+
+        Ok(read)
+    }
+
+    /// Performs a multipart upload of the `files` along with the
+    /// `payload_json`, using the request returned by `request` for the form.
+    ///
+    /// As uploads are not idempotent, they are only retried if
+    /// [`RetryPolicy::retry_multipart`] has been enabled. Each attempt is
+    /// limited by the [`Self::upload_timeout`].
+    async fn upload(
+        &self,
+        files: &[(Vec<u8>, Option<String>)],
+        payload_json: &str,
+        request: impl Fn(Form) -> ReqwestRequestBuilder,
+    ) -> Result<ReqwestResponse> {
+        let mut attempts = 0u16;
+
+        loop {
+            attempts += 1;
+
+            let mut multipart = Form::new();
+
+            for (file_num, (data, filename)) in files.iter().enumerate() {
+                let part = match filename {
+                    Some(filename) => Part::bytes(data.clone()).file_name(filename.clone()),
+                    None => Part::bytes(data.clone()),
+                };
+
+                multipart = multipart.part(file_num.to_string(), part);
+            }
+
+            multipart = multipart.text("payload_json", payload_json.to_string());
+
+            let mut builder = request(multipart);
+
+            if let Some(timeout) = self.upload_timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            let response = builder.send().await?;
+
+            if response.status().is_success() {
+                return Ok(response);
+            }
+
+            let delay = if self.retry_policy.retries_multipart() {
+                self.retry_policy.delay(response.status(), response.headers(), attempts)
+            } else {
+                None
+            };
+
+            match delay {
+                Some(delay) => {
+                    debug!("Retrying upload after {}: attempt {}", response.status(), attempts);
+
+                    sleep(delay).await;
+                },
+                None => return Err(retry_error(response, attempts).await),
+            }
         }
     }
 
@@ -3377,6 +3738,38 @@ impl Http {
     }
 }
 
+/// Creates the headers carrying the audit log `reason` for a request, if one is
+/// given.
+fn audit_log_reason(reason: &str) -> Result<Option<Headers>> {
+    if reason.is_empty() {
+        return Ok(None);
+    }
+
+    let encoded = utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string();
+
+    let mut headers = Headers::with_capacity(1);
+    headers.insert("x-audit-log-reason", HeaderValue::from_str(&encoded)?);
+
+    Ok(Some(headers))
+}
+
+/// Creates the error for an unsuccessful response, noting the number of
+/// attempts if the request was retried.
+async fn retry_error(response: ReqwestResponse, attempts: u16) -> Error {
+    let response = ErrorResponse::from_response(response).await;
+
+    let error = if attempts > 1 {
+        HttpError::RetriesExhausted {
+            attempts,
+            response,
+        }
+    } else {
+        HttpError::UnsuccessfulRequest(response)
+    };
+
+    Error::Http(Box::new(error))
+}
+
 #[cfg(not(feature = "native_tls_backend_marker"))]
 fn configure_client_backend(builder: ClientBuilder) -> ClientBuilder {
     builder.use_rustls_tls()
@@ -3403,6 +3796,11 @@ impl Default for Http {
             client,
             ratelimiter: Ratelimiter::new(client2, ""),
             ratelimiter_disabled: false,
+            retry_policy: RetryPolicy::default(),
+            request_timeout: None,
+            upload_timeout: None,
+            base_url: constants::API_BASE_URL.to_string(),
+            api_version: constants::API_VERSION,
             proxy: None,
             token: "".to_string(),
             #[cfg(feature = "unstable_discord_api")]
@@ -3410,3 +3808,190 @@ impl Default for Http {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::Duration;
+
+    use reqwest::Client;
+
+    use super::{Http, HttpBuilder};
+    use crate::constants;
+    use crate::http::mock::{response, MockServer};
+    use crate::http::request::{Request, RequestBuilder};
+    use crate::http::routing::{Route, RouteInfo};
+    use crate::http::HttpError;
+    use crate::internal::prelude::JsonMap;
+    use crate::Error;
+
+    fn get_bans() -> Request<'static> {
+        RequestBuilder::new(RouteInfo::GetBans {
+            guild_id: 1,
+        })
+        .build()
+    }
+
+    /// Creates an [`Http`] with a timeout of 50ms, and a server that takes
+    /// 300ms to respond.
+    async fn slow_server() -> (MockServer, Http) {
+        let server = MockServer::with_delay(
+            vec![response("200 OK", &[], "[]")],
+            Duration::from_millis(300),
+        );
+        let http = server.builder().request_timeout(Duration::from_millis(50)).await.unwrap();
+
+        (server, http)
+    }
+
+    #[tokio::test]
+    async fn test_default_timeout() {
+        let (_server, http) = slow_server().await;
+
+        match http.request(get_bans()).await.unwrap_err() {
+            Error::Http(why) => assert!(why.is_timeout(), "unexpected error: {:?}", why),
+            why => panic!("unexpected error: {:?}", why),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_override() {
+        let (_server, http) = slow_server().await;
+
+        http.request_with_timeout(get_bans(), Some(Duration::from_secs(5))).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_timeout_disabled() {
+        let (_server, http) = slow_server().await;
+
+        http.request_with_timeout(get_bans(), None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upload_timeout() {
+        let (_server, http) = slow_server().await;
+        let files = vec![(&b"data"[..], "file.txt")];
+
+        match http.send_files(1, files, JsonMap::new()).await.unwrap_err() {
+            Error::Http(why) => assert!(why.is_timeout(), "unexpected error: {:?}", why),
+            why => panic!("unexpected error: {:?}", why),
+        }
+
+        let server = MockServer::with_delay(
+            vec![response("200 OK", &[], "{}")],
+            Duration::from_millis(300),
+        );
+        let http = server
+            .builder()
+            .request_timeout(Duration::from_millis(50))
+            .upload_timeout(None)
+            .await
+            .unwrap();
+        let files = vec![(&b"data"[..], "file.txt")];
+
+        // The empty body is not a message, but the upload itself did not time out.
+        match http.send_files(1, files, JsonMap::new()).await.unwrap_err() {
+            Error::Http(why) => assert!(!why.is_timeout(), "unexpected error: {:?}", why),
+            why => panic!("unexpected error: {:?}", why),
+        }
+    }
+
+    #[test]
+    fn test_route_under_custom_base() {
+        let request = get_bans();
+        let base_url = "http://localhost:3000";
+        let built = request.build_with_base_url(&Client::new(), "token", base_url, 10).unwrap();
+
+        assert_eq!(
+            built.build().unwrap().url().as_str(),
+            "http://localhost:3000/api/v10/guilds/1/bans"
+        );
+    }
+
+    #[test]
+    fn test_default_route_urls() {
+        let api_url = format!("{}/api/v{}", constants::API_BASE_URL, constants::API_VERSION);
+
+        // Routes are built relative to the API root.
+        assert_eq!(get_bans().route_ref().deconstruct().2, "/guilds/1/bans");
+        assert_eq!(Route::guild_bans(1), format!("{}/guilds/1/bans", api_url));
+        assert_eq!(Route::gateway(), format!("{}/gateway", api_url));
+
+        let proxy = "http://localhost:3000".parse().unwrap();
+        let built = get_bans().build(&Client::new(), "token", Some(&proxy)).unwrap();
+        let url = format!("http://localhost:3000/api/v{}/guilds/1/bans", constants::API_VERSION);
+        assert_eq!(built.build().unwrap().url().as_str(), url);
+    }
+
+    #[test]
+    fn test_unsupported_api_version() {
+        match HttpBuilder::new("token").api_version(42) {
+            Err(Error::Http(why)) => assert!(matches!(*why, HttpError::UnsupportedApiVersion(42))),
+            Err(why) => panic!("unexpected error: {:?}", why),
+            Ok(_) => panic!("unsupported API version was accepted"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_base_url_and_version() {
+        let server = MockServer::new(vec![response("200 OK", &[], "[]")]);
+        let builder = HttpBuilder::new("token").base_url(server.addr()).unwrap();
+        #[cfg(feature = "unstable_discord_api")]
+        let builder = builder.application_id(1);
+        let http = builder.api_version(10).unwrap().await.unwrap();
+
+        http.request(get_bans()).await.unwrap();
+
+        assert!(server.next_request().starts_with("GET /api/v10/guilds/1/bans "));
+
+        // Ratelimits are still tracked by route, regardless of the base URL.
+        let routes = http.ratelimiter.routes();
+        assert!(routes.read().await.contains_key(&Route::GuildsIdBans(1)));
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    #[tokio::test]
+    async fn test_edit_application_command_permissions() {
+        use serde_json::json;
+
+        use crate::builder::CreateApplicationCommandPermission;
+        use crate::model::id::{ApplicationId, CommandId, GuildId, RoleId, UserId};
+
+        let body = r#"{"id":"3","application_id":"1","guild_id":"2","permissions":[
+            {"id":"4","type":1,"permission":true},{"id":"5","type":2,"permission":false}]}"#;
+        let server = MockServer::new(vec![response("200 OK", &[], body)]);
+        let http = HttpBuilder::new("token")
+            .base_url(server.addr())
+            .unwrap()
+            .application_id(1)
+            .await
+            .unwrap();
+
+        let mut permissions = CreateApplicationCommandPermission::default();
+        permissions.role(RoleId(4), true).user(UserId(5), false);
+
+        assert_eq!(
+            serde_json::to_value(&permissions.0).unwrap(),
+            json!([
+                {"id": 4, "type": 1, "permission": true},
+                {"id": 5, "type": 2, "permission": false},
+            ])
+        );
+
+        let edited = http
+            .edit_application_command_permissions(
+                ApplicationId(1),
+                GuildId(2),
+                CommandId(3),
+                permissions.0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(edited.id, CommandId(3));
+        assert_eq!(edited.permissions[1].id.to_user_id(), UserId(5));
+
+        let request = server.next_request();
+        assert!(request.starts_with("PUT /api/v9/applications/1/guilds/2/commands/3/permissions "));
+    }
+}
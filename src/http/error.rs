@@ -5,16 +5,148 @@ use std::{
 
 use reqwest::{header::InvalidHeaderValue, Error as ReqwestError, Response, StatusCode, Url};
 use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Serialize, Serializer};
 use url::ParseError as UrlError;
 
 use crate::http::utils::deserialize_errors;
 use crate::internal::prelude::{JsonMap, StdResult};
 
+macro_rules! json_error_codes {
+    ($($(#[$attr:meta])* $variant:ident = $value:literal,)*) => {
+        /// A [JSON error code] returned by Discord alongside an unsuccessful
+        /// response.
+        ///
+        /// Codes that are not known to the library are kept in
+        /// [`Self::Unknown`].
+        ///
+        /// [JSON error code]: https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        #[non_exhaustive]
+        pub enum JsonErrorCode {
+            $($(#[$attr])* $variant,)*
+            /// A code that is not known to the library.
+            Unknown(u32),
+        }
+
+        impl JsonErrorCode {
+            /// Returns the numeric value of the code.
+            pub fn num(self) -> u32 {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(code) => code,
+                }
+            }
+        }
+
+        impl From<u32> for JsonErrorCode {
+            fn from(code: u32) -> Self {
+                match code {
+                    $($value => Self::$variant,)*
+                    code => Self::Unknown(code),
+                }
+            }
+        }
+    };
+}
+
+json_error_codes! {
+    /// General error, such as a malformed request body.
+    ///
+    /// This is also used when the error response could not be decoded.
+    General = 0,
+    /// Unknown account.
+    UnknownAccount = 10001,
+    /// Unknown application.
+    UnknownApplication = 10002,
+    /// Unknown channel.
+    UnknownChannel = 10003,
+    /// Unknown guild.
+    UnknownGuild = 10004,
+    /// Unknown integration.
+    UnknownIntegration = 10005,
+    /// Unknown invite.
+    UnknownInvite = 10006,
+    /// Unknown member.
+    UnknownMember = 10007,
+    /// Unknown message.
+    UnknownMessage = 10008,
+    /// Unknown permission overwrite.
+    UnknownPermissionOverwrite = 10009,
+    /// Unknown role.
+    UnknownRole = 10011,
+    /// Unknown user.
+    UnknownUser = 10013,
+    /// Unknown emoji.
+    UnknownEmoji = 10014,
+    /// Unknown webhook.
+    UnknownWebhook = 10015,
+    /// Unknown ban.
+    UnknownBan = 10026,
+    /// Unknown interaction.
+    UnknownInteraction = 10062,
+    /// Bots cannot use this endpoint.
+    BotsCannotUseEndpoint = 20001,
+    /// Only bots can use this endpoint.
+    OnlyBotsCanUseEndpoint = 20002,
+    /// Maximum number of guilds reached.
+    MaximumGuildsReached = 30001,
+    /// Maximum number of pins reached for the channel.
+    MaximumPinsReached = 30003,
+    /// Maximum number of guild roles reached.
+    MaximumRolesReached = 30005,
+    /// Maximum number of reactions reached.
+    MaximumReactionsReached = 30010,
+    /// Maximum number of guild channels reached.
+    MaximumChannelsReached = 30013,
+    /// Unauthorized. Provide a valid token and try again.
+    Unauthorized = 40001,
+    /// Request entity too large.
+    RequestEntityTooLarge = 40005,
+    /// Interaction has already been acknowledged.
+    InteractionAlreadyAcknowledged = 40060,
+    /// Missing access.
+    MissingAccess = 50001,
+    /// Invalid account type.
+    InvalidAccountType = 50002,
+    /// Cannot execute action on a DM channel.
+    CannotExecuteOnDmChannel = 50003,
+    /// Cannot edit a message authored by another user.
+    CannotEditMessageByOtherUser = 50005,
+    /// Cannot send an empty message.
+    CannotSendEmptyMessage = 50006,
+    /// Cannot send messages to this user.
+    CannotSendMessagesToUser = 50007,
+    /// Missing permissions.
+    MissingPermissions = 50013,
+    /// Invalid authentication token provided.
+    InvalidAuthenticationToken = 50014,
+    /// Provided too few or too many messages to delete.
+    InvalidBulkDeleteCount = 50016,
+    /// A message provided was too old to bulk delete.
+    MessageTooOldToBulkDelete = 50034,
+    /// Invalid form body or invalid `Content-Type` provided.
+    InvalidFormBody = 50035,
+    /// Reaction was blocked.
+    ReactionBlocked = 90001,
+}
+
+impl Serialize for JsonErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_u32(self.num())
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        u32::deserialize(deserializer).map(Self::from)
+    }
+}
+
 #[derive(Clone, Serialize, PartialEq, Debug)]
 #[non_exhaustive]
 pub struct DiscordJsonError {
     /// The error code.
-    pub code: isize,
+    pub code: JsonErrorCode,
     /// The error message.
     pub message: String,
     /// The full explained errors with their path in the request
@@ -29,7 +161,7 @@ impl<'de> Deserialize<'de> for DiscordJsonError {
         let code = map
             .remove("code")
             .ok_or_else(|| DeError::custom("expected code"))
-            .and_then(isize::deserialize)
+            .and_then(JsonErrorCode::deserialize)
             .map_err(DeError::custom)?;
 
         let message = map
@@ -61,7 +193,8 @@ pub struct DiscordJsonSingleError {
     pub code: String,
     /// The error message.
     pub message: String,
-    /// The path to the error in the request body itself, dot separated.
+    /// The path to the error in the request body itself, as a JSON pointer,
+    /// e.g. `/embed/fields/0/name`.
     pub path: String,
 }
 
@@ -80,7 +213,7 @@ impl ErrorResponse {
             status_code: r.status(),
             url: r.url().clone(),
             error: r.json().await.unwrap_or_else(|_| DiscordJsonError {
-                code: -1,
+                code: JsonErrorCode::General,
                 message:
                     "[Serenity] Could not decode json when receiving error response from discord!"
                         .to_string(),
@@ -111,6 +244,26 @@ pub enum Error {
     InvalidScheme,
     /// When using a proxy with an invalid port.
     InvalidPort,
+    /// When a request did not complete within its timeout, see
+    /// [`HttpBuilder::request_timeout`].
+    ///
+    /// [`HttpBuilder::request_timeout`]: super::HttpBuilder::request_timeout
+    RequestTimeout(ReqwestError),
+    /// When a request was still unsuccessful after being retried as
+    /// configured by the [`RetryPolicy`].
+    ///
+    /// [`RetryPolicy`]: super::RetryPolicy
+    RetriesExhausted {
+        /// The total number of attempts made, including the first one.
+        attempts: u16,
+        /// The response to the last attempt.
+        response: ErrorResponse,
+    },
+    /// When setting an API version the library does not support, see
+    /// [`SUPPORTED_API_VERSIONS`].
+    ///
+    /// [`SUPPORTED_API_VERSIONS`]: crate::constants::SUPPORTED_API_VERSIONS
+    UnsupportedApiVersion(u8),
 }
 
 impl Error {
@@ -122,7 +275,7 @@ impl Error {
 
     /// Returns true when the error is caused by an unsuccessful request
     pub fn is_unsuccessful_request(&self) -> bool {
-        matches!(self, Self::UnsuccessfulRequest(_))
+        matches!(self, Self::UnsuccessfulRequest(_) | Self::RetriesExhausted { .. })
     }
 
     /// Returns true when the error is caused by the url containing invalid input
@@ -135,13 +288,44 @@ impl Error {
         matches!(self, Self::InvalidHeader(_))
     }
 
+    /// Returns true when the error is caused by a request timing out
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::RequestTimeout(_))
+    }
+
     /// Returns the status code if the error is an unsuccessful request
     pub fn status_code(&self) -> Option<StatusCode> {
         match self {
             Self::UnsuccessfulRequest(res) => Some(res.status_code),
+            Self::RetriesExhausted {
+                response, ..
+            } => Some(response.status_code),
+            _ => None,
+        }
+    }
+
+    /// Returns the JSON error code if the error is an unsuccessful request.
+    pub fn json_error_code(&self) -> Option<JsonErrorCode> {
+        match self {
+            Self::UnsuccessfulRequest(res) => Some(res.error.code),
+            Self::RetriesExhausted {
+                response, ..
+            } => Some(response.error.code),
             _ => None,
         }
     }
+
+    /// Returns true when the error is caused by the requested message not
+    /// existing.
+    pub fn is_unknown_message(&self) -> bool {
+        self.json_error_code() == Some(JsonErrorCode::UnknownMessage)
+    }
+
+    /// Returns true when the error is caused by the current user lacking the
+    /// permissions required for the request.
+    pub fn is_missing_permissions(&self) -> bool {
+        self.json_error_code() == Some(JsonErrorCode::MissingPermissions)
+    }
 }
 
 impl From<ErrorResponse> for Error {
@@ -152,7 +336,11 @@ impl From<ErrorResponse> for Error {
 
 impl From<ReqwestError> for Error {
     fn from(error: ReqwestError) -> Error {
-        Error::Request(error)
+        if error.is_timeout() {
+            Error::RequestTimeout(error)
+        } else {
+            Error::Request(error)
+        }
     }
 }
 
@@ -171,22 +359,14 @@ impl From<InvalidHeaderValue> for Error {
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Error::UnsuccessfulRequest(e) => {
-                f.write_str(&e.error.message)?;
-
-                // Put Discord's human readable error explanations in parantheses
-                let mut errors_iter = e.error.errors.iter();
-                if let Some(error) = errors_iter.next() {
-                    f.write_str(" (")?;
-                    f.write_str(&error.message)?;
-                    for error in errors_iter {
-                        f.write_str(", ")?;
-                        f.write_str(&error.message)?;
-                    }
-                    f.write_str(")")?;
-                }
-
-                Ok(())
+            Error::UnsuccessfulRequest(e) => fmt_error_response(e, f),
+            Error::RetriesExhausted {
+                attempts,
+                response,
+            } => {
+                fmt_error_response(response, f)?;
+
+                write!(f, " [after {} attempts]", attempts)
             },
             Error::RateLimitI64F64 => f.write_str("Error decoding a header into an i64 or f64"),
             Error::RateLimitUtf8 => f.write_str("Error decoding a header from UTF-8"),
@@ -195,15 +375,35 @@ impl Display for Error {
             Error::Request(_) => f.write_str("Error while sending HTTP request."),
             Error::InvalidScheme => f.write_str("Invalid Url scheme."),
             Error::InvalidPort => f.write_str("Invalid port."),
+            Error::RequestTimeout(_) => f.write_str("HTTP request timed out."),
+            Error::UnsupportedApiVersion(v) => write!(f, "Unsupported API version {}.", v),
+        }
+    }
+}
+
+fn fmt_error_response(e: &ErrorResponse, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str(&e.error.message)?;
+
+    // Put Discord's human readable error explanations in parantheses
+    let mut errors_iter = e.error.errors.iter();
+    if let Some(error) = errors_iter.next() {
+        f.write_str(" (")?;
+        f.write_str(&error.message)?;
+        for error in errors_iter {
+            f.write_str(", ")?;
+            f.write_str(&error.message)?;
         }
+        f.write_str(")")?;
     }
+
+    Ok(())
 }
 
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
             Error::Url(inner) => Some(inner),
-            Error::Request(inner) => Some(inner),
+            Error::Request(inner) | Error::RequestTimeout(inner) => Some(inner),
             _ => None,
         }
     }
@@ -220,7 +420,7 @@ mod test {
     #[tokio::test]
     async fn test_error_response_into() {
         let error = DiscordJsonError {
-            code: 43121215,
+            code: JsonErrorCode::Unknown(43121215),
             message: String::from("This is a Ferris error"),
             errors: vec![],
         };
@@ -242,4 +442,55 @@ mod test {
 
         assert_eq!(error_response, known);
     }
+
+    #[test]
+    fn test_json_error_code() {
+        assert_eq!(JsonErrorCode::from(10008), JsonErrorCode::UnknownMessage);
+        assert_eq!(JsonErrorCode::from(12345), JsonErrorCode::Unknown(12345));
+        assert_eq!(JsonErrorCode::MissingPermissions.num(), 50013);
+    }
+
+    #[test]
+    fn test_nested_validation_errors() {
+        let body = r#"{
+            "code": 50035,
+            "message": "Invalid Form Body",
+            "errors": {
+                "embed": {
+                    "fields": {
+                        "0": {
+                            "name": {
+                                "_errors": [{
+                                    "code": "BASE_TYPE_REQUIRED",
+                                    "message": "This field is required"
+                                }]
+                            }
+                        }
+                    },
+                    "title": {
+                        "_errors": [{
+                            "code": "BASE_TYPE_MAX_LENGTH",
+                            "message": "Must be 256 or fewer in length."
+                        }]
+                    }
+                }
+            }
+        }"#;
+
+        let error: DiscordJsonError = serde_json::from_str(body).unwrap();
+
+        assert_eq!(error.code, JsonErrorCode::InvalidFormBody);
+        assert_eq!(error.errors, vec![
+            DiscordJsonSingleError {
+                code: "BASE_TYPE_REQUIRED".to_string(),
+                message: "This field is required".to_string(),
+                path: "/embed/fields/0/name".to_string(),
+            },
+            DiscordJsonSingleError {
+                code: "BASE_TYPE_MAX_LENGTH".to_string(),
+                message: "Must be 256 or fewer in length.".to_string(),
+                path: "/embed/title".to_string(),
+            },
+        ]);
+    }
 }
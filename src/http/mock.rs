@@ -0,0 +1,94 @@
+//! A minimal HTTP server for testing requests against canned responses.
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use super::{Http, HttpBuilder};
+
+/// Serves the given raw responses in order, one per connection.
+pub(crate) struct MockServer {
+    addr: String,
+    requests: Receiver<String>,
+}
+
+impl MockServer {
+    pub(crate) fn new(responses: Vec<String>) -> Self {
+        Self::with_delay(responses, Duration::default())
+    }
+
+    /// Like [`Self::new`], but waits for `delay` before sending each response.
+    #[allow(clippy::unwrap_used)]
+    pub(crate) fn with_delay(responses: Vec<String>, delay: Duration) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0; 8192];
+                let len = stream.read(&mut buf).unwrap();
+                let _ = tx.send(String::from_utf8_lossy(&buf[..len]).into_owned());
+                thread::sleep(delay);
+                // The client may have given up on the request in the meantime.
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self {
+            addr,
+            requests,
+        }
+    }
+
+    /// The address to use as a proxy for [`Http`].
+    pub(crate) fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Creates an [`Http`] sending its requests to this server.
+    #[allow(clippy::unwrap_used)]
+    pub(crate) async fn http(&self) -> Http {
+        self.builder().await.unwrap()
+    }
+
+    /// Creates an [`HttpBuilder`] sending its requests to this server, for
+    /// further configuration.
+    #[allow(clippy::unwrap_used)]
+    pub(crate) fn builder(&self) -> HttpBuilder<'static> {
+        let builder =
+            HttpBuilder::new("token").proxy(self.addr.clone()).unwrap().ratelimiter_disabled(true);
+        #[cfg(feature = "unstable_discord_api")]
+        let builder = builder.application_id(1);
+
+        builder
+    }
+
+    /// Returns the next raw request received by the server, head and body.
+    #[allow(clippy::unwrap_used)]
+    pub(crate) fn next_request(&self) -> String {
+        self.requests.recv().unwrap()
+    }
+}
+
+/// Builds a raw response with the given status line and JSON `body`.
+pub(crate) fn response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+    let mut response = format!("HTTP/1.1 {}\r\n", status);
+
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    response.push_str(&format!(
+        "Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body,
+    ));
+
+    response
+}
@@ -14,7 +14,9 @@
 //! The former require a [`Client`] to have logged in, while the latter may be
 //! made regardless of any other usage of the library.
 //!
-//! If a request spuriously fails, it will be retried once.
+//! If a request spuriously fails, it will be retried once. Further retries of
+//! ratelimited requests and server errors can be configured through a
+//! [`RetryPolicy`].
 //!
 //! Note that you may want to perform requests through a [model]s'
 //! instance methods where possible, as they each offer different
@@ -25,8 +27,11 @@
 
 pub mod client;
 pub mod error;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod ratelimiting;
 pub mod request;
+pub mod retry;
 pub mod routing;
 pub mod typing;
 pub mod utils;
@@ -43,7 +48,9 @@ use tokio::fs::File;
 
 pub use self::client::*;
 pub use self::error::Error as HttpError;
+pub use self::error::JsonErrorCode;
 use self::request::Request;
+pub use self::retry::RetryPolicy;
 pub use self::typing::*;
 #[cfg(feature = "cache")]
 use crate::cache::Cache;
@@ -56,15 +63,16 @@ use crate::CacheAndHttp;
 /// This trait will be required by functions that need [`Http`] and can
 /// optionally use a [`Cache`] to potentially avoid REST-requests.
 ///
-/// The types [`Context`], [`Cache`], and [`Http`] implement this trait
-/// and thus passing these to functions expecting `impl CacheHttp` is possible.
+/// The types [`Context`], [`CacheAndHttp`], [`Http`] and `Arc<Http>`
+/// implement this trait and thus passing these to functions expecting
+/// `impl CacheHttp` is possible. [`Http`] and `Arc<Http>` provide no cache.
 ///
 /// In a situation where you have the `cache`-feature enabled but you do not
 /// pass a cache, the function will behave as if no `cache`-feature is active.
 ///
 /// If you are calling a function that expects `impl CacheHttp` as argument
 /// and you wish to utilise the `cache`-feature but you got no access to a
-/// [`Context`], you can pass a tuple of `(CacheRwLock, Http)`.
+/// [`Context`], you can pass a tuple of `(&Arc<Cache>, &Http)`.
 pub trait CacheHttp: Send + Sync {
     fn http(&self) -> &Http;
 
@@ -90,11 +98,11 @@ where
 #[cfg(feature = "client")]
 impl CacheHttp for Context {
     fn http(&self) -> &Http {
-        &self.http
+        Context::http(self)
     }
     #[cfg(feature = "cache")]
     fn cache(&self) -> Option<&Arc<Cache>> {
-        Some(&self.cache)
+        Context::cache(self)
     }
 }
 
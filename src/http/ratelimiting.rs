@@ -46,7 +46,7 @@ use std::{
     i64,
     str::{self, FromStr},
     sync::Arc,
-    time::SystemTime,
+    time::{Instant, SystemTime},
 };
 
 use reqwest::{header::HeaderMap, StatusCode};
@@ -61,6 +61,7 @@ use tracing::{debug, instrument};
 
 pub use super::routing::Route;
 use super::{HttpError, Request};
+use crate::constants;
 use crate::internal::prelude::*;
 
 /// Ratelimiter for requests to the Discord API.
@@ -76,15 +77,22 @@ use crate::internal::prelude::*;
 /// Occasionally for very high traffic bots, a global ratelimit may be reached
 /// which blocks all future requests until the global ratelimit is over,
 /// regardless of route. The value of this global ratelimit is never given
-/// through the API, so it can't be pre-emptively ratelimited. This only affects
-/// the largest of bots.
+/// through the API, so it is pre-emptively ratelimited using the
+/// [`global_limit`], which defaults to Discord's standard limit of
+/// [`DEFAULT_GLOBAL_LIMIT`] requests per second. Large bots that were granted
+/// a higher limit should raise it through [`Self::set_global_limit`].
+/// Interaction routes are not bound by the global ratelimit, and so are never
+/// held back by it.
 ///
+/// [`global_limit`]: Self::global_limit
 /// [`limit`]: Ratelimit::limit
 /// [`remaining`]: Ratelimit::remaining
 /// [`reset`]: Ratelimit::reset
 pub struct Ratelimiter {
     client: Arc<Client>,
     global: Arc<Mutex<()>>,
+    global_limit: Option<u32>,
+    global_bucket: Mutex<GlobalRatelimit>,
     // When futures is implemented, make tasks clear out their respective entry
     // when the 'reset' passes.
     routes: Arc<RwLock<HashMap<Route, Arc<Mutex<Ratelimit>>>>>,
@@ -96,6 +104,7 @@ impl fmt::Debug for Ratelimiter {
         f.debug_struct("Ratelimiter")
             .field("client", &self.client)
             .field("global", &self.global)
+            .field("global_limit", &self.global_limit)
             .field("routes", &self.routes)
             .finish()
     }
@@ -115,11 +124,51 @@ impl Ratelimiter {
         Self {
             client,
             global: Default::default(),
+            global_limit: Some(DEFAULT_GLOBAL_LIMIT),
+            global_bucket: Default::default(),
             routes: Default::default(),
             token,
         }
     }
 
+    /// The number of requests per second that are allowed across all routes
+    /// before requests are pre-emptively delayed, or [`None`] if pre-emptive
+    /// global ratelimiting is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// #
+    /// # let http = Http::default();
+    /// if let Some(limit) = http.ratelimiter().global_limit() {
+    ///     println!("Sending at most {} requests per second", limit);
+    /// }
+    /// ```
+    #[inline]
+    pub fn global_limit(&self) -> Option<u32> {
+        self.global_limit
+    }
+
+    /// Sets the number of requests per second allowed across all routes.
+    ///
+    /// Pass [`None`] to disable pre-emptive global ratelimiting entirely, for
+    /// example when requests are sent through a proxy that ratelimits on its
+    /// own.
+    pub fn set_global_limit(&mut self, global_limit: Option<u32>) {
+        self.global_limit = global_limit;
+    }
+
+    /// Waits for a ticket of the global ratelimit, if the `route` is subject to
+    /// it.
+    async fn global_pre_hook(&self, route: &Route) {
+        if let Some(limit) = self.global_limit {
+            if !route.is_global_exempt() {
+                self.global_bucket.lock().await.pre_hook(limit).await;
+            }
+        }
+    }
+
     /// The routes mutex is a HashMap of each [`Route`] and their respective
     /// ratelimit information.
     ///
@@ -160,6 +209,9 @@ impl Ratelimiter {
     pub async fn perform(&self, req: RatelimitedRequest<'_>) -> Result<Response> {
         let RatelimitedRequest {
             req,
+            timeout,
+            base_url,
+            api_version,
         } = req;
 
         loop {
@@ -188,9 +240,16 @@ impl Ratelimiter {
             let bucket = Arc::clone(self.routes.write().await.entry(route).or_default());
 
             bucket.lock().await.pre_hook(&route).await;
+            self.global_pre_hook(&route).await;
+
+            let mut builder =
+                req.build_with_base_url(&self.client, &self.token, base_url, api_version)?;
+
+            if let Some(timeout) = timeout {
+                builder = builder.timeout(timeout);
+            }
 
-            let request = req.build(&self.client, &self.token, None)?.build()?;
-            let response = self.client.execute(request).await?;
+            let response = self.client.execute(builder.build()?).await?;
 
             // Check if the request got ratelimited by checking for status 429,
             // and if so, sleep for the value of the header 'retry-after' -
@@ -235,6 +294,45 @@ impl Ratelimiter {
     }
 }
 
+/// The number of requests per second Discord allows across all routes, unless
+/// a bot was granted a higher limit.
+pub const DEFAULT_GLOBAL_LIMIT: u32 = 50;
+
+/// Bookkeeping for the pre-emptive global ratelimit, which allows a number of
+/// requests per second regardless of route.
+#[derive(Debug, Default)]
+struct GlobalRatelimit {
+    /// The number of requests remaining in the current second.
+    remaining: u32,
+    /// The time when the current second is over.
+    reset: Option<Instant>,
+}
+
+impl GlobalRatelimit {
+    async fn pre_hook(&mut self, limit: u32) {
+        let now = Instant::now();
+
+        match self.reset {
+            Some(reset) if reset > now => {
+                if self.remaining == 0 {
+                    debug!("Pre-emptive global ratelimit for {}ms", (reset - now).as_millis());
+
+                    sleep(reset - now).await;
+
+                    self.remaining = limit;
+                    self.reset = Some(Instant::now() + Duration::from_secs(1));
+                }
+            },
+            _ => {
+                self.remaining = limit;
+                self.reset = Some(now + Duration::from_secs(1));
+            },
+        }
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+}
+
 /// A set of data containing information about the ratelimits for a particular
 /// [`Route`], which is stored in [`Http`].
 ///
@@ -380,17 +478,56 @@ impl Default for Ratelimit {
 #[derive(Debug)]
 pub struct RatelimitedRequest<'a> {
     req: Request<'a>,
+    timeout: Option<Duration>,
+    base_url: &'a str,
+    api_version: u8,
+}
+
+impl<'a> RatelimitedRequest<'a> {
+    /// Sets how long the request may take at most, excluding the time spent
+    /// waiting for ratelimits.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    /// Sets the base URL that the request is sent to, e.g.
+    /// `https://discord.com`. Defaults to the [`API_BASE_URL`].
+    ///
+    /// The ratelimits are tracked per [`Route`], so they are the same
+    /// regardless of the base URL.
+    ///
+    /// [`API_BASE_URL`]: crate::constants::API_BASE_URL
+    pub fn base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+
+        self
+    }
+
+    /// Sets the version of the API that the request is sent to. Defaults to
+    /// the [`API_VERSION`].
+    ///
+    /// [`API_VERSION`]: crate::constants::API_VERSION
+    pub fn api_version(mut self, api_version: u8) -> Self {
+        self.api_version = api_version;
+
+        self
+    }
 }
 
 impl<'a> From<Request<'a>> for RatelimitedRequest<'a> {
     fn from(req: Request<'a>) -> Self {
         Self {
             req,
+            timeout: None,
+            base_url: constants::API_BASE_URL,
+            api_version: constants::API_VERSION,
         }
     }
 }
 
-fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<T>> {
+pub(super) fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<T>> {
     let header = match headers.get(header) {
         Some(v) => v,
         None => return Ok(None),
@@ -406,12 +543,19 @@ fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<
 
 #[cfg(test)]
 mod tests {
-    use std::{error::Error as StdError, result::Result as StdResult};
+    use std::{
+        error::Error as StdError,
+        result::Result as StdResult,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
 
     use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 
     use super::parse_header;
-    use crate::{error::Error, http::HttpError};
+    use crate::http::mock::{response, MockServer};
+    use crate::error::Error;
+    use crate::http::{Http, HttpError};
 
     type Result<T> = StdResult<T, Box<dyn StdError>>;
 
@@ -470,4 +614,63 @@ mod tests {
         assert!(is_err!("x-bad-num", HttpError::RateLimitI64F64));
         assert!(is_err!("x-bad-unicode", HttpError::RateLimitUtf8));
     }
+
+    /// Creates a server answering `count` requests, and an [`Http`] sending
+    /// them to it through the ratelimiter with the given global limit.
+    #[allow(clippy::unwrap_used)]
+    async fn limited_server(count: usize, global_limit: u32) -> (MockServer, Http) {
+        let server = MockServer::new(vec![response("204 No Content", &[], ""); count]);
+        let http = server
+            .builder()
+            .ratelimiter_disabled(false)
+            .ratelimiter_global_limit(global_limit)
+            .await
+            .unwrap();
+
+        (server, http)
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_global_limit_pacing() {
+        // Twice the default limit, which would take 3 seconds for 200 requests.
+        let (_server, http) = limited_server(200, 100).await;
+        assert_eq!(http.ratelimiter().global_limit(), Some(100));
+        let http = Arc::new(http);
+
+        let start = Instant::now();
+        let tasks = (0..200)
+            .map(|id| {
+                let http = Arc::clone(&http);
+
+                tokio::spawn(async move { http.unpin_message(id, 1).await })
+            })
+            .collect::<Vec<_>>();
+
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        // The first 100 requests go through immediately and the other 100 once
+        // the second is over.
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(900), "too fast: {:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(2), "too slow: {:?}", elapsed);
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_global_limit_exempt() {
+        let (_server, http) = limited_server(10, 1).await;
+        let map = serde_json::json!({});
+
+        let start = Instant::now();
+
+        for id in 0..10 {
+            http.create_interaction_response(id, "token", &map).await.unwrap();
+        }
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
 }
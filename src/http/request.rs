@@ -1,5 +1,3 @@
-use std::borrow::Cow;
-
 use reqwest::{
     header::{
         HeaderMap as Headers,
@@ -77,12 +75,40 @@ impl<'a> Request<'a> {
         }
     }
 
+    /// Builds the request, sending it to the `proxy` if given, otherwise to
+    /// the default API URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HttpError::Url`] if the resulting URL is invalid, or an
+    /// [`HttpError::InvalidHeader`] if the token is not a valid header value.
     #[instrument(skip(token))]
     pub fn build(
         &'a self,
         client: &Client,
         token: &str,
         proxy: Option<&Url>,
+    ) -> Result<ReqwestRequestBuilder, HttpError> {
+        let base_url = proxy.map_or(constants::API_BASE_URL, Url::as_str);
+
+        self.build_with_base_url(client, token, base_url, constants::API_VERSION)
+    }
+
+    /// Builds the request, sending it to the `base_url` with the
+    /// `api_version`, e.g. `https://discord.com` and `10`, instead of the
+    /// default ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HttpError::Url`] if the resulting URL is invalid, or an
+    /// [`HttpError::InvalidHeader`] if the token is not a valid header value.
+    #[instrument(skip(token))]
+    pub fn build_with_base_url(
+        &'a self,
+        client: &Client,
+        token: &str,
+        base_url: &str,
+        api_version: u8,
     ) -> Result<ReqwestRequestBuilder, HttpError> {
         let Request {
             body,
@@ -90,13 +116,9 @@ impl<'a> Request<'a> {
             route: ref route_info,
         } = *self;
 
-        let (method, _, mut path) = route_info.deconstruct();
-
-        if let Some(proxy) = proxy {
-            path = Cow::Owned(path.to_mut().replace("https://discord.com/", proxy.as_str()));
-        }
-
-        let mut builder = client.request(method.reqwest_method(), Url::parse(&path)?);
+        let (method, _, path) = route_info.deconstruct();
+        let url = Url::parse(&route_url(base_url, api_version, &path))?;
+        let mut builder = client.request(method.reqwest_method(), url);
 
         if let Some(bytes) = body {
             builder = builder.body(Vec::from(bytes));
@@ -150,3 +172,14 @@ impl<'a> Request<'a> {
         &mut self.route
     }
 }
+
+/// Returns the URL of the `path` of a route under the `base_url` and the
+/// `api_version`. Routes outside of the API, such as the status page, are
+/// absolute and kept as they are.
+pub(super) fn route_url(base_url: &str, api_version: u8, path: &str) -> String {
+    if path.starts_with('/') {
+        format!("{}/api/v{}{}", base_url.trim_end_matches('/'), api_version, path)
+    } else {
+        path.to_string()
+    }
+}
@@ -0,0 +1,279 @@
+//! Configuration for automatically retrying requests that failed due to a
+//! ratelimit or a server error.
+//!
+//! By default no request is retried beyond what the [`Ratelimiter`] already
+//! does on its own. Use [`HttpBuilder::retry_policy`] to opt into retries.
+//!
+//! [`Ratelimiter`]: super::ratelimiting::Ratelimiter
+//! [`HttpBuilder::retry_policy`]: super::HttpBuilder::retry_policy
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{header::HeaderMap, StatusCode};
+
+use super::ratelimiting::parse_header;
+
+/// Describes when and how often a failed request is retried.
+///
+/// # Examples
+///
+/// Retry up to three times on both 429s and 5xx responses:
+///
+/// ```rust
+/// # use serenity::http::{HttpBuilder, RetryPolicy};
+/// # async fn run() {
+/// let policy = RetryPolicy::new().max_retries(3).retry_server_errors(true);
+///
+/// let http = HttpBuilder::new("token").retry_policy(policy).await.expect("Error creating Http");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u8,
+    retry_server_errors: bool,
+    retry_ratelimited: bool,
+    retry_multipart: bool,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries. Equivalent to [`Self::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many times a request is retried at most before giving up.
+    ///
+    /// A value of `0` disables retrying entirely.
+    pub fn max_retries(mut self, max_retries: u8) -> Self {
+        self.max_retries = max_retries;
+
+        self
+    }
+
+    /// Sets whether responses with a 5xx status code are retried, using an
+    /// exponential backoff with jitter between attempts.
+    ///
+    /// This is disabled by default.
+    pub fn retry_server_errors(mut self, retry_server_errors: bool) -> Self {
+        self.retry_server_errors = retry_server_errors;
+
+        self
+    }
+
+    /// Sets whether responses with a 429 status code are retried after
+    /// sleeping for the duration of their `retry-after` header.
+    ///
+    /// This is enabled by default, but has no effect unless
+    /// [`Self::max_retries`] is set.
+    pub fn retry_ratelimited(mut self, retry_ratelimited: bool) -> Self {
+        self.retry_ratelimited = retry_ratelimited;
+
+        self
+    }
+
+    /// Sets whether file uploads are retried as well.
+    ///
+    /// Uploads are not idempotent - if Discord processed the upload before
+    /// failing, a retry will post the files twice - so this is disabled by
+    /// default.
+    pub fn retry_multipart(mut self, retry_multipart: bool) -> Self {
+        self.retry_multipart = retry_multipart;
+
+        self
+    }
+
+    /// Sets the delay before the first retry of a server error. Every
+    /// following retry doubles this delay.
+    ///
+    /// Defaults to 500 milliseconds.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+
+        self
+    }
+
+    /// Sets the longest delay before a retry. The exponential backoff of server
+    /// errors stops growing at this delay, and ratelimits whose `retry-after`
+    /// exceeds it are not retried but returned as errors.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+
+        self
+    }
+
+    /// Whether file uploads may be retried.
+    #[inline]
+    pub fn retries_multipart(&self) -> bool {
+        self.retry_multipart
+    }
+
+    /// Returns how long to wait before retrying a request that failed with the
+    /// given `status` and `headers` after `attempts` attempts, or [`None`] if
+    /// it should not be retried.
+    pub(crate) fn delay(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        attempts: u16,
+    ) -> Option<Duration> {
+        if attempts > u16::from(self.max_retries) {
+            return None;
+        }
+
+        if status == StatusCode::TOO_MANY_REQUESTS && self.retry_ratelimited {
+            // Negative, NaN or overly long values are ignored, rather than
+            // making `Duration::from_secs_f64` panic.
+            return parse_header::<f64>(headers, "retry-after")
+                .ok()
+                .flatten()
+                .filter(|secs| *secs >= 0.0 && *secs <= self.max_delay.as_secs_f64())
+                .map(Duration::from_secs_f64);
+        }
+
+        if status.is_server_error() && self.retry_server_errors {
+            let factor = 2u32.saturating_pow(u32::from(attempts.saturating_sub(1)));
+            let backoff = self
+                .base_delay
+                .checked_mul(factor)
+                .map_or(self.max_delay, |backoff| backoff.min(self.max_delay));
+
+            return Some(jitter(backoff));
+        }
+
+        None
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            retry_server_errors: false,
+            retry_ratelimited: true,
+            retry_multipart: false,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Picks a delay between half of and the full `backoff`, so that clients that
+/// failed at the same time do not all retry at the same time.
+pub(crate) fn jitter(backoff: Duration) -> Duration {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+    let half = backoff / 2;
+
+    half + half.mul_f64(f64::from(nanos % 1000) / 1000.0)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::Duration;
+
+    use reqwest::{header::HeaderMap, StatusCode};
+
+    use super::RetryPolicy;
+    use crate::http::mock::{response, MockServer};
+    use crate::http::HttpError;
+    use crate::Error;
+
+    fn ok() -> String {
+        response("200 OK", &[], "[]")
+    }
+
+    fn ratelimited() -> String {
+        response("429 Too Many Requests", &[("Retry-After", "0.01")], "{}")
+    }
+
+    fn server_error() -> String {
+        response("500 Internal Server Error", &[], "{}")
+    }
+
+    async fn get_bans(server: &MockServer, policy: RetryPolicy) -> crate::Result<()> {
+        let http = server.builder().retry_policy(policy).await?;
+
+        http.get_bans(1).await.map(|_| ())
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new().max_retries(2).base_delay(Duration::from_millis(100));
+        let headers = HeaderMap::new();
+
+        assert!(policy.delay(StatusCode::INTERNAL_SERVER_ERROR, &headers, 1).is_none());
+
+        let policy = policy.retry_server_errors(true);
+        let delay = policy.delay(StatusCode::BAD_GATEWAY, &headers, 2).unwrap();
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        assert!(policy.delay(StatusCode::BAD_GATEWAY, &headers, 3).is_none());
+        assert!(policy.delay(StatusCode::NOT_FOUND, &headers, 1).is_none());
+    }
+
+    #[test]
+    fn test_delay_bounds() {
+        let policy = RetryPolicy::new()
+            .max_retries(255)
+            .retry_server_errors(true)
+            .max_delay(Duration::from_secs(10));
+        let mut headers = HeaderMap::new();
+
+        let delay = policy.delay(StatusCode::BAD_GATEWAY, &headers, 255).unwrap();
+        assert!(delay >= Duration::from_secs(5) && delay <= Duration::from_secs(10));
+        assert!(policy.delay(StatusCode::BAD_GATEWAY, &headers, 256).is_none());
+
+        for retry_after in &["-1", "NaN", "inf", "11"] {
+            headers.insert("retry-after", retry_after.parse().unwrap());
+            assert!(policy.delay(StatusCode::TOO_MANY_REQUESTS, &headers, 1).is_none());
+        }
+
+        headers.insert("retry-after", "2.5".parse().unwrap());
+        let delay = policy.delay(StatusCode::TOO_MANY_REQUESTS, &headers, 1).unwrap();
+        assert_eq!(delay, Duration::from_millis(2500));
+    }
+
+    #[tokio::test]
+    async fn test_ratelimited_then_ok() {
+        let server = MockServer::new(vec![ratelimited(), ok()]);
+
+        get_bans(&server, RetryPolicy::new().max_retries(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_then_ok() {
+        let server = MockServer::new(vec![server_error(), server_error(), ok()]);
+        let policy = RetryPolicy::new()
+            .max_retries(2)
+            .retry_server_errors(true)
+            .base_delay(Duration::from_millis(1));
+
+        get_bans(&server, policy).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted() {
+        let server = MockServer::new(vec![server_error(), server_error(), server_error()]);
+        let policy = RetryPolicy::new()
+            .max_retries(2)
+            .retry_server_errors(true)
+            .base_delay(Duration::from_millis(1));
+
+        match get_bans(&server, policy).await.unwrap_err() {
+            Error::Http(why) => match *why {
+                HttpError::RetriesExhausted {
+                    attempts,
+                    response,
+                } => {
+                    assert_eq!(attempts, 3);
+                    assert_eq!(response.status_code.as_u16(), 500);
+                },
+                why => panic!("unexpected error: {:?}", why),
+            },
+            why => panic!("unexpected error: {:?}", why),
+        }
+    }
+}
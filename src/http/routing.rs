@@ -1,16 +1,27 @@
+pub(super) mod path;
+
 use std::{
     borrow::Cow,
-    fmt::{Display, Write},
+    fmt::Display,
 };
 
-use super::LightMethod;
+use chrono::{DateTime, Utc};
+
+use super::{request::route_url, LightMethod};
 use crate::constants;
 
 /// A representation of all routes registered within the library. These are safe
 /// and memory-efficient representations of each path that functions exist for
 /// in the [`http`] module.
 ///
+/// The functions of this type return the URLs of the routes under the default
+/// API URL. Requests are built from the paths of the routes instead, relative
+/// to the API root, so that they can be sent to the [`HttpBuilder::base_url`]
+/// with the [`HttpBuilder::api_version`].
+///
 /// [`http`]: crate::http
+/// [`HttpBuilder::base_url`]: super::HttpBuilder::base_url
+/// [`HttpBuilder::api_version`]: super::HttpBuilder::api_version
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
 pub enum Route {
@@ -92,6 +103,13 @@ pub enum Route {
     ///
     /// [`ChannelId`]: crate::model::id::ChannelId
     ChannelsIdPinsMessageId(u64),
+    /// Route for the `/channels/:channel_id/polls/:message_id/answers/:answer_id`
+    /// path.
+    ///
+    /// The data is the relevant [`ChannelId`].
+    ///
+    /// [`ChannelId`]: crate::model::id::ChannelId
+    ChannelsIdPollsMessageIdAnswersAnswerId(u64),
     /// Route for the `/channels/:channel_id/message/:message_id/crosspost` path.
     ///
     /// The data is the relevant [`ChannelId`].
@@ -308,6 +326,12 @@ pub enum Route {
     ///
     /// [`GuildId`]: crate::model::id::GuildId
     GuildsIdRolesId(u64),
+    /// Route for the `/guilds/:guild_id/threads/active` path.
+    ///
+    /// The data is the relevant [`GuildId`].
+    ///
+    /// [`GuildId`]: crate::model::id::GuildId
+    GuildsIdThreadsActive(u64),
     /// Route for the `/guilds/:guild_id/vanity-url` path.
     ///
     /// The data is the relevant [`GuildId`].
@@ -447,20 +471,33 @@ pub enum Route {
 }
 
 impl Route {
+    /// Whether requests to the route are exempt from the global ratelimit.
+    ///
+    /// This is the case for interaction routes, which only have their own
+    /// per-route limits.
+    pub(crate) fn is_global_exempt(&self) -> bool {
+        match self {
+            #[cfg(feature = "unstable_discord_api")]
+            Route::InteractionsId(_) | Route::WebhooksApplicationId(_) => true,
+            Route::None => true,
+            _ => false,
+        }
+    }
+
     pub fn channel(channel_id: u64) -> String {
-        format!(api!("/channels/{}"), channel_id)
+        default_url(&path::channel(channel_id))
     }
 
     pub fn channel_invites(channel_id: u64) -> String {
-        format!(api!("/channels/{}/invites"), channel_id)
+        default_url(&path::channel_invites(channel_id))
     }
 
     pub fn channel_message(channel_id: u64, message_id: u64) -> String {
-        format!(api!("/channels/{}/messages/{}"), channel_id, message_id)
+        default_url(&path::channel_message(channel_id, message_id))
     }
 
     pub fn channel_message_crosspost(channel_id: u64, message_id: u64) -> String {
-        format!(api!("/channels/{}/messages/{}/crosspost"), channel_id, message_id)
+        default_url(&path::channel_message_crosspost(channel_id, message_id))
     }
 
     pub fn channel_message_reaction<D, T>(
@@ -473,10 +510,7 @@ impl Route {
         D: Display,
         T: Display,
     {
-        format!(
-            api!("/channels/{}/messages/{}/reactions/{}/{}"),
-            channel_id, message_id, reaction_type, user_id,
-        )
+        default_url(&path::channel_message_reaction(channel_id, message_id, user_id, reaction_type))
     }
 
     pub fn channel_message_reaction_emoji<T>(
@@ -487,17 +521,13 @@ impl Route {
     where
         T: Display,
     {
-        format!(
-            api!("/channels/{}/messages/{}/reactions/{}"),
-            channel_id, message_id, reaction_type,
-        )
+        default_url(&path::channel_message_reaction_emoji(channel_id, message_id, reaction_type))
     }
 
     pub fn channel_message_reactions(channel_id: u64, message_id: u64) -> String {
-        api!("/channels/{}/messages/{}/reactions", channel_id, message_id)
+        default_url(&path::channel_message_reactions(channel_id, message_id))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn channel_message_reactions_list(
         channel_id: u64,
         message_id: u64,
@@ -505,125 +535,97 @@ impl Route {
         limit: u8,
         after: Option<u64>,
     ) -> String {
-        let mut uri = format!(
-            api!("/channels/{}/messages/{}/reactions/{}?limit={}"),
-            channel_id, message_id, reaction, limit,
-        );
-
-        if let Some(after) = after {
-            let _ = write!(uri, "&after={}", after);
-        }
-
-        uri
+        default_url(&path::channel_message_reactions_list(
+            channel_id, message_id, reaction, limit, after,
+        ))
     }
 
     pub fn channel_messages(channel_id: u64, query: Option<&str>) -> String {
-        format!(api!("/channels/{}/messages{}"), channel_id, query.unwrap_or(""),)
+        default_url(&path::channel_messages(channel_id, query))
     }
 
     pub fn channel_messages_bulk_delete(channel_id: u64) -> String {
-        format!(api!("/channels/{}/messages/bulk-delete"), channel_id)
+        default_url(&path::channel_messages_bulk_delete(channel_id))
     }
 
     pub fn channel_permission(channel_id: u64, target_id: u64) -> String {
-        format!(api!("/channels/{}/permissions/{}"), channel_id, target_id)
+        default_url(&path::channel_permission(channel_id, target_id))
     }
 
     pub fn channel_pin(channel_id: u64, message_id: u64) -> String {
-        format!(api!("/channels/{}/pins/{}"), channel_id, message_id)
+        default_url(&path::channel_pin(channel_id, message_id))
     }
 
     pub fn channel_pins(channel_id: u64) -> String {
-        format!(api!("/channels/{}/pins"), channel_id)
+        default_url(&path::channel_pins(channel_id))
+    }
+
+    pub fn channel_poll_answer_voters(
+        channel_id: u64,
+        message_id: u64,
+        answer_id: u8,
+        after: Option<u64>,
+        limit: Option<u8>,
+    ) -> String {
+        default_url(&path::channel_poll_answer_voters(
+            channel_id, message_id, answer_id, after, limit,
+        ))
     }
 
     pub fn channel_typing(channel_id: u64) -> String {
-        format!(api!("/channels/{}/typing"), channel_id)
+        default_url(&path::channel_typing(channel_id))
     }
 
     pub fn channel_webhooks(channel_id: u64) -> String {
-        format!(api!("/channels/{}/webhooks"), channel_id)
+        default_url(&path::channel_webhooks(channel_id))
     }
 
     pub fn channel_public_threads(channel_id: u64, message_id: u64) -> String {
-        format!(api!("/channels/{}/messages/{}/threads"), channel_id, message_id)
+        default_url(&path::channel_public_threads(channel_id, message_id))
     }
 
     pub fn channel_private_threads(channel_id: u64) -> String {
-        format!(api!("/channels/{}/threads"), channel_id)
+        default_url(&path::channel_private_threads(channel_id))
     }
 
     pub fn channel_thread_member(channel_id: u64, user_id: u64) -> String {
-        format!(api!("/channels/{}/thread-members/{}"), channel_id, user_id)
+        default_url(&path::channel_thread_member(channel_id, user_id))
     }
 
     pub fn channel_thread_member_me(channel_id: u64) -> String {
-        format!(api!("/channels/{}/thread-members/@me"), channel_id)
+        default_url(&path::channel_thread_member_me(channel_id))
     }
 
     pub fn channel_thread_members(channel_id: u64) -> String {
-        format!(api!("/channels/{}/thread-members"), channel_id)
+        default_url(&path::channel_thread_members(channel_id))
     }
 
     pub fn channel_active_threads(channel_id: u64) -> String {
-        format!(api!("/channels/{}/threads/active"), channel_id)
+        default_url(&path::channel_active_threads(channel_id))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn channel_archived_public_threads(
         channel_id: u64,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     ) -> String {
-        let mut s = format!(api!("/channels/{}/threads/archived/public"), channel_id);
-
-        if let Some(id) = before {
-            let _ = write!(s, "&before={}", id);
-        }
-
-        if let Some(limit) = limit {
-            let _ = write!(s, "&limit={}", limit);
-        }
-
-        s
+        default_url(&path::channel_archived_public_threads(channel_id, before, limit))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn channel_archived_private_threads(
         channel_id: u64,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     ) -> String {
-        let mut s = format!(api!("/channels/{}/threads/archived/private"), channel_id);
-
-        if let Some(id) = before {
-            let _ = write!(s, "&before={}", id);
-        }
-
-        if let Some(limit) = limit {
-            let _ = write!(s, "&limit={}", limit);
-        }
-
-        s
+        default_url(&path::channel_archived_private_threads(channel_id, before, limit))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn channel_joined_private_threads(
         channel_id: u64,
         before: Option<u64>,
         limit: Option<u64>,
     ) -> String {
-        let mut s = format!(api!("/channels/{}/users/@me/threads/archived/private"), channel_id);
-
-        if let Some(id) = before {
-            let _ = write!(s, "&before={}", id);
-        }
-
-        if let Some(limit) = limit {
-            let _ = write!(s, "&limit={}", limit);
-        }
-
-        s
+        default_url(&path::channel_joined_private_threads(channel_id, before, limit))
     }
 
     pub fn gateway() -> &'static str {
@@ -635,14 +637,13 @@ impl Route {
     }
 
     pub fn guild(guild_id: u64) -> String {
-        format!(api!("/guilds/{}"), guild_id)
+        default_url(&path::guild(guild_id))
     }
 
     pub fn guild_with_counts(guild_id: u64) -> String {
-        format!(api!("/guilds/{}?with_counts=true"), guild_id)
+        default_url(&path::guild_with_counts(guild_id))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn guild_audit_logs(
         guild_id: u64,
         action_type: Option<u8>,
@@ -650,29 +651,11 @@ impl Route {
         before: Option<u64>,
         limit: Option<u8>,
     ) -> String {
-        let mut s = format!(api!("/guilds/{}/audit-logs?"), guild_id,);
-
-        if let Some(action_type) = action_type {
-            let _ = write!(s, "&action_type={}", action_type);
-        }
-
-        if let Some(before) = before {
-            let _ = write!(s, "&before={}", before);
-        }
-
-        if let Some(limit) = limit {
-            let _ = write!(s, "&limit={}", limit);
-        }
-
-        if let Some(user_id) = user_id {
-            let _ = write!(s, "&user_id={}", user_id);
-        }
-
-        s
+        default_url(&path::guild_audit_logs(guild_id, action_type, user_id, before, limit))
     }
 
     pub fn guild_ban(guild_id: u64, user_id: u64) -> String {
-        format!(api!("/guilds/{}/bans/{}"), guild_id, user_id)
+        default_url(&path::guild_ban(guild_id, user_id))
     }
 
     pub fn guild_ban_optioned(
@@ -681,136 +664,119 @@ impl Route {
         delete_message_days: u8,
         reason: &str,
     ) -> String {
-        format!(
-            api!("/guilds/{}/bans/{}?delete_message_days={}&reason={}"),
-            guild_id, user_id, delete_message_days, reason,
-        )
+        default_url(&path::guild_ban_optioned(guild_id, user_id, delete_message_days, reason))
     }
 
     pub fn guild_kick_optioned(guild_id: u64, user_id: u64, reason: &str) -> String {
-        format!(api!("/guilds/{}/members/{}?reason={}"), guild_id, user_id, reason,)
+        default_url(&path::guild_kick_optioned(guild_id, user_id, reason))
     }
 
     pub fn guild_bans(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/bans"), guild_id)
+        default_url(&path::guild_bans(guild_id))
     }
 
     pub fn guild_channels(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/channels"), guild_id)
+        default_url(&path::guild_channels(guild_id))
     }
 
     pub fn guild_widget(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/widget"), guild_id)
+        default_url(&path::guild_widget(guild_id))
     }
 
     pub fn guild_preview(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/preview"), guild_id)
+        default_url(&path::guild_preview(guild_id))
     }
 
     pub fn guild_emojis(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/emojis"), guild_id)
+        default_url(&path::guild_emojis(guild_id))
     }
 
     pub fn guild_emoji(guild_id: u64, emoji_id: u64) -> String {
-        format!(api!("/guilds/{}/emojis/{}"), guild_id, emoji_id)
+        default_url(&path::guild_emoji(guild_id, emoji_id))
     }
 
     pub fn guild_integration(guild_id: u64, integration_id: u64) -> String {
-        format!(api!("/guilds/{}/integrations/{}"), guild_id, integration_id)
+        default_url(&path::guild_integration(guild_id, integration_id))
     }
 
     pub fn guild_integration_sync(guild_id: u64, integration_id: u64) -> String {
-        format!(api!("/guilds/{}/integrations/{}/sync"), guild_id, integration_id,)
+        default_url(&path::guild_integration_sync(guild_id, integration_id))
     }
 
     pub fn guild_integrations(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/integrations"), guild_id)
+        default_url(&path::guild_integrations(guild_id))
     }
 
     pub fn guild_invites(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/invites"), guild_id)
+        default_url(&path::guild_invites(guild_id))
     }
 
     pub fn guild_member(guild_id: u64, user_id: u64) -> String {
-        format!(api!("/guilds/{}/members/{}"), guild_id, user_id)
+        default_url(&path::guild_member(guild_id, user_id))
     }
 
     pub fn guild_member_role(guild_id: u64, user_id: u64, role_id: u64) -> String {
-        format!(api!("/guilds/{}/members/{}/roles/{}"), guild_id, user_id, role_id,)
+        default_url(&path::guild_member_role(guild_id, user_id, role_id))
     }
 
     pub fn guild_members(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/members"), guild_id)
+        default_url(&path::guild_members(guild_id))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn guild_members_search(guild_id: u64, query: &str, limit: Option<u64>) -> String {
-        let mut s = format!(api!("/guilds/{}/members/search?"), guild_id);
-
-        let _ = write!(s, "&query={}", query);
-
-        let _ = write!(s, "&limit={}", limit.unwrap_or(constants::MEMBER_FETCH_LIMIT));
-
-        s
+        default_url(&path::guild_members_search(guild_id, query, limit))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn guild_members_optioned(guild_id: u64, after: Option<u64>, limit: Option<u64>) -> String {
-        let mut s = format!(api!("/guilds/{}/members?"), guild_id);
-
-        if let Some(after) = after {
-            let _ = write!(s, "&after={}", after);
-            // should not error, ignoring
-        }
-
-        let _ = write!(s, "&limit={}", limit.unwrap_or(constants::MEMBER_FETCH_LIMIT));
-        // should not error, ignoring
-
-        s
+        default_url(&path::guild_members_optioned(guild_id, after, limit))
     }
 
     pub fn guild_member_me(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/members/@me"), guild_id)
+        default_url(&path::guild_member_me(guild_id))
     }
 
     pub fn guild_nickname(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/members/@me/nick"), guild_id)
+        default_url(&path::guild_nickname(guild_id))
     }
 
     pub fn guild_prune(guild_id: u64, days: u64) -> String {
-        format!(api!("/guilds/{}/prune?days={}"), guild_id, days)
+        default_url(&path::guild_prune(guild_id, days))
     }
 
     pub fn guild_regions(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/regions"), guild_id)
+        default_url(&path::guild_regions(guild_id))
     }
 
     pub fn guild_role(guild_id: u64, role_id: u64) -> String {
-        format!(api!("/guilds/{}/roles/{}"), guild_id, role_id)
+        default_url(&path::guild_role(guild_id, role_id))
     }
 
     pub fn guild_roles(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/roles"), guild_id)
+        default_url(&path::guild_roles(guild_id))
+    }
+
+    pub fn guild_active_threads(guild_id: u64) -> String {
+        default_url(&path::guild_active_threads(guild_id))
     }
 
     pub fn guild_vanity_url(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/vanity-url"), guild_id)
+        default_url(&path::guild_vanity_url(guild_id))
     }
 
     pub fn guild_voice_states(guild_id: u64, user_id: u64) -> String {
-        format!(api!("/guilds/{}/voice-states/{}"), guild_id, user_id)
+        default_url(&path::guild_voice_states(guild_id, user_id))
     }
 
     pub fn guild_voice_states_me(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/voice-states/@me"), guild_id)
+        default_url(&path::guild_voice_states_me(guild_id))
     }
 
     pub fn guild_webhooks(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/webhooks"), guild_id)
+        default_url(&path::guild_webhooks(guild_id))
     }
 
     pub fn guild_welcome_screen(guild_id: u64) -> String {
-        format!(api!("/guilds/{}/welcome-screen"), guild_id)
+        default_url(&path::guild_welcome_screen(guild_id))
     }
 
     pub fn guilds() -> &'static str {
@@ -818,11 +784,11 @@ impl Route {
     }
 
     pub fn invite(code: &str) -> String {
-        format!(api!("/invites/{}"), code)
+        default_url(&path::invite(code))
     }
 
     pub fn invite_optioned(code: &str, stats: bool) -> String {
-        format!(api!("/invites/{}?with_counts={}"), code, stats)
+        default_url(&path::invite_optioned(code, stats))
     }
 
     pub fn oauth2_application_current() -> &'static str {
@@ -846,7 +812,7 @@ impl Route {
     }
 
     pub fn user<D: Display>(target: D) -> String {
-        format!(api!("/users/{}"), target)
+        default_url(&path::user(target))
     }
 
     pub fn user_me_connections() -> &'static str {
@@ -854,37 +820,24 @@ impl Route {
     }
 
     pub fn user_dm_channels<D: Display>(target: D) -> String {
-        format!(api!("/users/{}/channels"), target)
+        default_url(&path::user_dm_channels(target))
     }
 
     pub fn user_guild<D: Display>(target: D, guild_id: u64) -> String {
-        format!(api!("/users/{}/guilds/{}"), target, guild_id)
+        default_url(&path::user_guild(target, guild_id))
     }
 
     pub fn user_guilds<D: Display>(target: D) -> String {
-        format!(api!("/users/{}/guilds"), target)
+        default_url(&path::user_guilds(target))
     }
 
-    #[allow(clippy::let_underscore_must_use)]
     pub fn user_guilds_optioned<D: Display>(
         target: D,
         after: Option<u64>,
         before: Option<u64>,
         limit: u64,
     ) -> String {
-        let mut s = format!(api!("/users/{}/guilds?limit={}&"), target, limit);
-
-        if let Some(after) = after {
-            let _ = write!(s, "&after={}", after);
-            // should not error, ignoring
-        }
-
-        if let Some(before) = before {
-            let _ = write!(s, "&before={}", before);
-            // should not error, ignoring
-        }
-
-        s
+        default_url(&path::user_guilds_optioned(target, after, before, limit))
     }
 
     pub fn voice_regions() -> &'static str {
@@ -892,28 +845,28 @@ impl Route {
     }
 
     pub fn webhook(webhook_id: u64) -> String {
-        format!(api!("/webhooks/{}"), webhook_id)
+        default_url(&path::webhook(webhook_id))
     }
 
     pub fn webhook_with_token<D>(webhook_id: u64, token: D) -> String
     where
         D: Display,
     {
-        format!(api!("/webhooks/{}/{}"), webhook_id, token)
+        default_url(&path::webhook_with_token(webhook_id, token))
     }
 
     pub fn webhook_with_token_optioned<D>(webhook_id: u64, token: D, wait: bool) -> String
     where
         D: Display,
     {
-        format!(api!("/webhooks/{}/{}?wait={}"), webhook_id, token, wait)
+        default_url(&path::webhook_with_token_optioned(webhook_id, token, wait))
     }
 
     pub fn webhook_message<D>(webhook_id: u64, token: D, message_id: u64) -> String
     where
         D: Display,
     {
-        format!(api!("/webhooks/{}/{}/messages/{}"), webhook_id, token, message_id)
+        default_url(&path::webhook_message(webhook_id, token, message_id))
     }
 
     #[cfg(feature = "unstable_discord_api")]
@@ -922,7 +875,7 @@ impl Route {
         application_id: u64,
         token: D,
     ) -> String {
-        format!(api!("/webhooks/{}/{}/messages/@original"), application_id, token)
+        default_url(&path::webhook_original_interaction_response(application_id, token))
     }
 
     #[cfg(feature = "unstable_discord_api")]
@@ -932,31 +885,31 @@ impl Route {
         token: D,
         message_id: u64,
     ) -> String {
-        format!(api!("/webhooks/{}/{}/messages/{}"), application_id, token, message_id)
+        default_url(&path::webhook_followup_message(application_id, token, message_id))
     }
 
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
     pub fn webhook_followup_messages<D: Display>(application_id: u64, token: D) -> String {
-        format!(api!("/webhooks/{}/{}"), application_id, token)
+        default_url(&path::webhook_followup_messages(application_id, token))
     }
 
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
     pub fn interaction_response<D: Display>(application_id: u64, token: D) -> String {
-        format!(api!("/interactions/{}/{}/callback"), application_id, token)
+        default_url(&path::interaction_response(application_id, token))
     }
 
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
     pub fn application_command(application_id: u64, command_id: u64) -> String {
-        format!(api!("/applications/{}/commands/{}"), application_id, command_id)
+        default_url(&path::application_command(application_id, command_id))
     }
 
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
     pub fn application_commands(application_id: u64) -> String {
-        format!(api!("/applications/{}/commands"), application_id)
+        default_url(&path::application_commands(application_id))
     }
 
     #[cfg(feature = "unstable_discord_api")]
@@ -966,10 +919,7 @@ impl Route {
         guild_id: u64,
         command_id: u64,
     ) -> String {
-        format!(
-            api!("/applications/{}/guilds/{}/commands/{}"),
-            application_id, guild_id, command_id
-        )
+        default_url(&path::application_guild_command(application_id, guild_id, command_id))
     }
 
     #[cfg(feature = "unstable_discord_api")]
@@ -979,30 +929,31 @@ impl Route {
         guild_id: u64,
         command_id: u64,
     ) -> String {
-        format!(
-            api!("/applications/{}/guilds/{}/commands/{}/permissions"),
-            application_id, guild_id, command_id
-        )
+        default_url(&path::application_guild_command_permissions(
+            application_id,
+            guild_id,
+            command_id,
+        ))
     }
 
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
     pub fn application_guild_commands(application_id: u64, guild_id: u64) -> String {
-        format!(api!("/applications/{}/guilds/{}/commands"), application_id, guild_id)
+        default_url(&path::application_guild_commands(application_id, guild_id))
     }
 
     #[cfg(feature = "unstable_discord_api")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
     pub fn application_guild_commands_permissions(application_id: u64, guild_id: u64) -> String {
-        format!(api!("/applications/{}/guilds/{}/commands/permissions"), application_id, guild_id)
+        default_url(&path::application_guild_commands_permissions(application_id, guild_id))
     }
 
     pub fn stage_instances() -> String {
-        api!("/stage-instances").to_string()
+        default_url(&path::stage_instances())
     }
 
     pub fn stage_instance(channel_id: u64) -> String {
-        format!(api!("/stage-instances/{}"), channel_id)
+        default_url(&path::stage_instance(channel_id))
     }
 }
 
@@ -1352,12 +1303,12 @@ pub enum RouteInfo<'a> {
     },
     GetChannelArchivedPublicThreads {
         channel_id: u64,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     },
     GetChannelArchivedPrivateThreads {
         channel_id: u64,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     },
     GetChannelJoinedPrivateArchivedThreads {
@@ -1448,6 +1399,9 @@ pub enum RouteInfo<'a> {
     GetGuildRoles {
         guild_id: u64,
     },
+    GetGuildActiveThreads {
+        guild_id: u64,
+    },
     GetGuildVanityUrl {
         guild_id: u64,
     },
@@ -1478,6 +1432,13 @@ pub enum RouteInfo<'a> {
     GetPins {
         channel_id: u64,
     },
+    GetPollAnswerVoters {
+        after: Option<u64>,
+        answer_id: u8,
+        channel_id: u64,
+        limit: Option<u8>,
+        message_id: u64,
+    },
     GetReactionUsers {
         after: Option<u64>,
         channel_id: u64,
@@ -1547,6 +1508,11 @@ pub enum RouteInfo<'a> {
 }
 
 impl<'a> RouteInfo<'a> {
+    /// Returns the method, ratelimiting bucket and path of the route.
+    ///
+    /// The path is relative to the API root, e.g. `/channels/7`, except for
+    /// routes outside of the API, such as those of the status page, which are
+    /// absolute URLs.
     pub fn deconstruct(&self) -> (LightMethod, Route, Cow<'_, str>) {
         match *self {
             RouteInfo::AddMemberRole {
@@ -1556,40 +1522,46 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::GuildsIdMembersIdRolesId(guild_id),
-                Cow::from(Route::guild_member_role(guild_id, user_id, role_id)),
+                Cow::from(path::guild_member_role(guild_id, user_id, role_id)),
             ),
             RouteInfo::GuildBanUser {
                 guild_id,
                 delete_message_days,
                 reason,
                 user_id,
-            } => (
-                // TODO
-                LightMethod::Put,
-                Route::GuildsIdBansUserId(guild_id),
-                Cow::from(Route::guild_ban_optioned(
-                    guild_id,
-                    user_id,
-                    delete_message_days.unwrap_or(0),
-                    reason.unwrap_or(""),
-                )),
-            ),
+            } => {
+                let mut query = Vec::new();
+
+                if let Some(delete_message_days) = delete_message_days {
+                    query.push(format!("delete_message_days={}", delete_message_days));
+                }
+
+                if let Some(reason) = reason {
+                    query.push(format!("reason={}", reason));
+                }
+
+                (
+                    LightMethod::Put,
+                    Route::GuildsIdBansUserId(guild_id),
+                    Cow::from(with_query(path::guild_ban(guild_id, user_id), &query)),
+                )
+            },
             RouteInfo::BroadcastTyping {
                 channel_id,
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdTyping(channel_id),
-                Cow::from(Route::channel_typing(channel_id)),
+                Cow::from(path::channel_typing(channel_id)),
             ),
             RouteInfo::CreateChannel {
                 guild_id,
             } => (
                 LightMethod::Post,
                 Route::GuildsIdChannels(guild_id),
-                Cow::from(Route::guild_channels(guild_id)),
+                Cow::from(path::guild_channels(guild_id)),
             ),
             RouteInfo::CreateStageInstance => {
-                (LightMethod::Post, Route::StageInstances, Cow::from(Route::stage_instances()))
+                (LightMethod::Post, Route::StageInstances, Cow::from(path::stage_instances()))
             },
             RouteInfo::CreatePublicThread {
                 channel_id,
@@ -1597,21 +1569,21 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdMessagesIdThreads(channel_id),
-                Cow::from(Route::channel_public_threads(channel_id, message_id)),
+                Cow::from(path::channel_public_threads(channel_id, message_id)),
             ),
             RouteInfo::CreatePrivateThread {
                 channel_id,
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdThreads(channel_id),
-                Cow::from(Route::channel_private_threads(channel_id)),
+                Cow::from(path::channel_private_threads(channel_id)),
             ),
             RouteInfo::CreateEmoji {
                 guild_id,
             } => (
                 LightMethod::Post,
                 Route::GuildsIdEmojis(guild_id),
-                Cow::from(Route::guild_emojis(guild_id)),
+                Cow::from(path::guild_emojis(guild_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::CreateFollowupMessage {
@@ -1620,7 +1592,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::WebhooksId(application_id),
-                Cow::from(Route::webhook_followup_messages(application_id, interaction_token)),
+                Cow::from(path::webhook_followup_messages(application_id, interaction_token)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::CreateGlobalApplicationCommand {
@@ -1628,7 +1600,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::ApplicationsIdCommands(application_id),
-                Cow::from(Route::application_commands(application_id)),
+                Cow::from(path::application_commands(application_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::CreateGlobalApplicationCommands {
@@ -1636,10 +1608,10 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ApplicationsIdCommands(application_id),
-                Cow::from(Route::application_commands(application_id)),
+                Cow::from(path::application_commands(application_id)),
             ),
             RouteInfo::CreateGuild => {
-                (LightMethod::Post, Route::Guilds, Cow::from(Route::guilds()))
+                (LightMethod::Post, Route::Guilds, Cow::from(path::guilds()))
             },
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::CreateGuildApplicationCommand {
@@ -1648,7 +1620,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::ApplicationsIdGuildsIdCommands(application_id),
-                Cow::from(Route::application_guild_commands(application_id, guild_id)),
+                Cow::from(path::application_guild_commands(application_id, guild_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::CreateGuildApplicationCommands {
@@ -1657,7 +1629,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ApplicationsIdGuildsIdCommands(application_id),
-                Cow::from(Route::application_guild_commands(application_id, guild_id)),
+                Cow::from(path::application_guild_commands(application_id, guild_id)),
             ),
             RouteInfo::CreateGuildIntegration {
                 guild_id,
@@ -1665,7 +1637,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::GuildsIdIntegrationsId(guild_id),
-                Cow::from(Route::guild_integration(guild_id, integration_id)),
+                Cow::from(path::guild_integration(guild_id, integration_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::CreateInteractionResponse {
@@ -1674,21 +1646,21 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::InteractionsId(interaction_id),
-                Cow::from(Route::interaction_response(interaction_id, interaction_token)),
+                Cow::from(path::interaction_response(interaction_id, interaction_token)),
             ),
             RouteInfo::CreateInvite {
                 channel_id,
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdInvites(channel_id),
-                Cow::from(Route::channel_invites(channel_id)),
+                Cow::from(path::channel_invites(channel_id)),
             ),
             RouteInfo::CreateMessage {
                 channel_id,
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdMessages(channel_id),
-                Cow::from(Route::channel_messages(channel_id, None)),
+                Cow::from(path::channel_messages(channel_id, None)),
             ),
             RouteInfo::CreatePermission {
                 channel_id,
@@ -1696,12 +1668,12 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ChannelsIdPermissionsOverwriteId(channel_id),
-                Cow::from(Route::channel_permission(channel_id, target_id)),
+                Cow::from(path::channel_permission(channel_id, target_id)),
             ),
             RouteInfo::CreatePrivateChannel => (
                 LightMethod::Post,
                 Route::UsersMeChannels,
-                Cow::from(Route::user_dm_channels("@me")),
+                Cow::from(path::user_dm_channels("@me")),
             ),
             RouteInfo::CreateReaction {
                 channel_id,
@@ -1710,14 +1682,14 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ChannelsIdMessagesIdReactionsUserIdType(channel_id),
-                Cow::from(Route::channel_message_reaction(channel_id, message_id, "@me", reaction)),
+                Cow::from(path::channel_message_reaction(channel_id, message_id, "@me", reaction)),
             ),
             RouteInfo::CreateRole {
                 guild_id,
             } => (
                 LightMethod::Post,
                 Route::GuildsIdRoles(guild_id),
-                Cow::from(Route::guild_roles(guild_id)),
+                Cow::from(path::guild_roles(guild_id)),
             ),
             RouteInfo::CrosspostMessage {
                 channel_id,
@@ -1725,28 +1697,28 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdCrosspostsMessageId(channel_id),
-                Cow::from(Route::channel_message_crosspost(channel_id, message_id)),
+                Cow::from(path::channel_message_crosspost(channel_id, message_id)),
             ),
             RouteInfo::CreateWebhook {
                 channel_id,
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdWebhooks(channel_id),
-                Cow::from(Route::channel_webhooks(channel_id)),
+                Cow::from(path::channel_webhooks(channel_id)),
             ),
             RouteInfo::DeleteChannel {
                 channel_id,
             } => (
                 LightMethod::Delete,
                 Route::ChannelsId(channel_id),
-                Cow::from(Route::channel(channel_id)),
+                Cow::from(path::channel(channel_id)),
             ),
             RouteInfo::DeleteStageInstance {
                 channel_id,
             } => (
                 LightMethod::Delete,
                 Route::StageInstancesChannelId(channel_id),
-                Cow::from(Route::stage_instance(channel_id)),
+                Cow::from(path::stage_instance(channel_id)),
             ),
             RouteInfo::DeleteEmoji {
                 emoji_id,
@@ -1754,7 +1726,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::GuildsIdEmojisId(guild_id),
-                Cow::from(Route::guild_emoji(guild_id, emoji_id)),
+                Cow::from(path::guild_emoji(guild_id, emoji_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::DeleteFollowupMessage {
@@ -1764,7 +1736,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::WebhooksApplicationId(application_id),
-                Cow::from(Route::webhook_followup_message(
+                Cow::from(path::webhook_followup_message(
                     application_id,
                     interaction_token,
                     message_id,
@@ -1777,12 +1749,12 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ApplicationsIdCommandsId(application_id),
-                Cow::from(Route::application_command(application_id, command_id)),
+                Cow::from(path::application_command(application_id, command_id)),
             ),
             RouteInfo::DeleteGuild {
                 guild_id,
             } => {
-                (LightMethod::Delete, Route::GuildsId(guild_id), Cow::from(Route::guild(guild_id)))
+                (LightMethod::Delete, Route::GuildsId(guild_id), Cow::from(path::guild(guild_id)))
             },
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::DeleteGuildApplicationCommand {
@@ -1792,7 +1764,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ApplicationsIdGuildsIdCommandsId(application_id),
-                Cow::from(Route::application_guild_command(application_id, guild_id, command_id)),
+                Cow::from(path::application_guild_command(application_id, guild_id, command_id)),
             ),
             RouteInfo::DeleteGuildIntegration {
                 guild_id,
@@ -1800,18 +1772,18 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::GuildsIdIntegrationsId(guild_id),
-                Cow::from(Route::guild_integration(guild_id, integration_id)),
+                Cow::from(path::guild_integration(guild_id, integration_id)),
             ),
             RouteInfo::DeleteInvite {
                 code,
-            } => (LightMethod::Delete, Route::InvitesCode, Cow::from(Route::invite(code))),
+            } => (LightMethod::Delete, Route::InvitesCode, Cow::from(path::invite(code))),
             RouteInfo::DeleteMessageReactions {
                 channel_id,
                 message_id,
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdMessagesIdReactions(channel_id),
-                Cow::from(Route::channel_message_reactions(channel_id, message_id)),
+                Cow::from(path::channel_message_reactions(channel_id, message_id)),
             ),
             RouteInfo::DeleteMessageReactionEmoji {
                 channel_id,
@@ -1820,7 +1792,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdMessagesIdReactions(channel_id),
-                Cow::from(Route::channel_message_reaction_emoji(channel_id, message_id, reaction)),
+                Cow::from(path::channel_message_reaction_emoji(channel_id, message_id, reaction)),
             ),
             RouteInfo::DeleteMessage {
                 channel_id,
@@ -1828,14 +1800,14 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdMessagesId(LightMethod::Delete, message_id),
-                Cow::from(Route::channel_message(channel_id, message_id)),
+                Cow::from(path::channel_message(channel_id, message_id)),
             ),
             RouteInfo::DeleteMessages {
                 channel_id,
             } => (
                 LightMethod::Post,
                 Route::ChannelsIdMessagesBulkDelete(channel_id),
-                Cow::from(Route::channel_messages_bulk_delete(channel_id)),
+                Cow::from(path::channel_messages_bulk_delete(channel_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::DeleteOriginalInteractionResponse {
@@ -1844,7 +1816,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::WebhooksApplicationId(application_id),
-                Cow::from(Route::webhook_original_interaction_response(
+                Cow::from(path::webhook_original_interaction_response(
                     application_id,
                     interaction_token,
                 )),
@@ -1855,7 +1827,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdPermissionsOverwriteId(channel_id),
-                Cow::from(Route::channel_permission(channel_id, target_id)),
+                Cow::from(path::channel_permission(channel_id, target_id)),
             ),
             RouteInfo::DeleteReaction {
                 channel_id,
@@ -1865,7 +1837,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdMessagesIdReactionsUserIdType(channel_id),
-                Cow::from(Route::channel_message_reaction(channel_id, message_id, user, reaction)),
+                Cow::from(path::channel_message_reaction(channel_id, message_id, user, reaction)),
             ),
             RouteInfo::DeleteRole {
                 guild_id,
@@ -1873,14 +1845,14 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::GuildsIdRolesId(guild_id),
-                Cow::from(Route::guild_role(guild_id, role_id)),
+                Cow::from(path::guild_role(guild_id, role_id)),
             ),
             RouteInfo::DeleteWebhook {
                 webhook_id,
             } => (
                 LightMethod::Delete,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook(webhook_id)),
+                Cow::from(path::webhook(webhook_id)),
             ),
             RouteInfo::DeleteWebhookWithToken {
                 token,
@@ -1888,7 +1860,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook_with_token(webhook_id, token)),
+                Cow::from(path::webhook_with_token(webhook_id, token)),
             ),
             RouteInfo::DeleteWebhookMessage {
                 token,
@@ -1897,21 +1869,21 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::WebhooksIdMessagesId(webhook_id),
-                Cow::from(Route::webhook_message(webhook_id, token, message_id)),
+                Cow::from(path::webhook_message(webhook_id, token, message_id)),
             ),
             RouteInfo::EditChannel {
                 channel_id,
             } => (
                 LightMethod::Patch,
                 Route::ChannelsId(channel_id),
-                Cow::from(Route::channel(channel_id)),
+                Cow::from(path::channel(channel_id)),
             ),
             RouteInfo::EditStageInstance {
                 channel_id,
             } => (
                 LightMethod::Patch,
                 Route::StageInstancesChannelId(channel_id),
-                Cow::from(Route::stage_instance(channel_id)),
+                Cow::from(path::stage_instance(channel_id)),
             ),
             RouteInfo::EditEmoji {
                 emoji_id,
@@ -1919,7 +1891,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdEmojisId(guild_id),
-                Cow::from(Route::guild_emoji(guild_id, emoji_id)),
+                Cow::from(path::guild_emoji(guild_id, emoji_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::EditFollowupMessage {
@@ -1929,7 +1901,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::WebhooksApplicationId(application_id),
-                Cow::from(Route::webhook_followup_message(
+                Cow::from(path::webhook_followup_message(
                     application_id,
                     interaction_token,
                     message_id,
@@ -1942,11 +1914,11 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::ApplicationsIdCommandsId(application_id),
-                Cow::from(Route::application_command(application_id, command_id)),
+                Cow::from(path::application_command(application_id, command_id)),
             ),
             RouteInfo::EditGuild {
                 guild_id,
-            } => (LightMethod::Patch, Route::GuildsId(guild_id), Cow::from(Route::guild(guild_id))),
+            } => (LightMethod::Patch, Route::GuildsId(guild_id), Cow::from(path::guild(guild_id))),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::EditGuildApplicationCommand {
                 application_id,
@@ -1955,7 +1927,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::ApplicationsIdGuildsIdCommandsId(application_id),
-                Cow::from(Route::application_guild_command(application_id, guild_id, command_id)),
+                Cow::from(path::application_guild_command(application_id, guild_id, command_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::EditGuildApplicationCommandPermission {
@@ -1965,7 +1937,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ApplicationsIdGuildsIdCommandIdPermissions(application_id),
-                Cow::from(Route::application_guild_command_permissions(
+                Cow::from(path::application_guild_command_permissions(
                     application_id,
                     guild_id,
                     command_id,
@@ -1978,28 +1950,28 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ApplicationsIdGuildsIdCommandsPermissions(application_id),
-                Cow::from(Route::application_guild_commands_permissions(application_id, guild_id)),
+                Cow::from(path::application_guild_commands_permissions(application_id, guild_id)),
             ),
             RouteInfo::EditGuildChannels {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdChannels(guild_id),
-                Cow::from(Route::guild_channels(guild_id)),
+                Cow::from(path::guild_channels(guild_id)),
             ),
             RouteInfo::EditGuildWidget {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdWidget(guild_id),
-                Cow::from(Route::guild_widget(guild_id)),
+                Cow::from(path::guild_widget(guild_id)),
             ),
             RouteInfo::EditGuildWelcomeScreen {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdWelcomeScreen(guild_id),
-                Cow::from(Route::guild_welcome_screen(guild_id)),
+                Cow::from(path::guild_welcome_screen(guild_id)),
             ),
             RouteInfo::EditMember {
                 guild_id,
@@ -2007,7 +1979,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdMembersId(guild_id),
-                Cow::from(Route::guild_member(guild_id, user_id)),
+                Cow::from(path::guild_member(guild_id, user_id)),
             ),
             RouteInfo::EditMessage {
                 channel_id,
@@ -2015,21 +1987,21 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::ChannelsIdMessagesId(LightMethod::Patch, channel_id),
-                Cow::from(Route::channel_message(channel_id, message_id)),
+                Cow::from(path::channel_message(channel_id, message_id)),
             ),
             RouteInfo::EditMemberMe {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdMembersMe(guild_id),
-                Cow::from(Route::guild_member_me(guild_id)),
+                Cow::from(path::guild_member_me(guild_id)),
             ),
             RouteInfo::EditNickname {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdMembersMeNick(guild_id),
-                Cow::from(Route::guild_nickname(guild_id)),
+                Cow::from(path::guild_nickname(guild_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetOriginalInteractionResponse {
@@ -2038,7 +2010,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::WebhooksApplicationId(application_id),
-                Cow::from(Route::webhook_original_interaction_response(
+                Cow::from(path::webhook_original_interaction_response(
                     application_id,
                     interaction_token,
                 )),
@@ -2050,13 +2022,13 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::WebhooksApplicationId(application_id),
-                Cow::from(Route::webhook_original_interaction_response(
+                Cow::from(path::webhook_original_interaction_response(
                     application_id,
                     interaction_token,
                 )),
             ),
             RouteInfo::EditProfile => {
-                (LightMethod::Patch, Route::UsersMe, Cow::from(Route::user("@me")))
+                (LightMethod::Patch, Route::UsersMe, Cow::from(path::user("@me")))
             },
             RouteInfo::EditRole {
                 guild_id,
@@ -2064,14 +2036,14 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdRolesId(guild_id),
-                Cow::from(Route::guild_role(guild_id, role_id)),
+                Cow::from(path::guild_role(guild_id, role_id)),
             ),
             RouteInfo::EditRolePosition {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdRolesId(guild_id),
-                Cow::from(Route::guild_roles(guild_id)),
+                Cow::from(path::guild_roles(guild_id)),
             ),
             RouteInfo::EditVoiceState {
                 guild_id,
@@ -2079,21 +2051,21 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdVoiceStates(guild_id),
-                Cow::from(Route::guild_voice_states(guild_id, user_id)),
+                Cow::from(path::guild_voice_states(guild_id, user_id)),
             ),
             RouteInfo::EditVoiceStateMe {
                 guild_id,
             } => (
                 LightMethod::Patch,
                 Route::GuildsIdVoiceStatesMe(guild_id),
-                Cow::from(Route::guild_voice_states_me(guild_id)),
+                Cow::from(path::guild_voice_states_me(guild_id)),
             ),
             RouteInfo::EditWebhook {
                 webhook_id,
             } => (
                 LightMethod::Patch,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook(webhook_id)),
+                Cow::from(path::webhook(webhook_id)),
             ),
             RouteInfo::EditWebhookWithToken {
                 token,
@@ -2101,7 +2073,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook_with_token(webhook_id, token)),
+                Cow::from(path::webhook_with_token(webhook_id, token)),
             ),
             RouteInfo::EditWebhookMessage {
                 token,
@@ -2110,7 +2082,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Patch,
                 Route::WebhooksIdMessagesId(webhook_id),
-                Cow::from(Route::webhook_message(webhook_id, token, message_id)),
+                Cow::from(path::webhook_message(webhook_id, token, message_id)),
             ),
             RouteInfo::ExecuteWebhook {
                 token,
@@ -2119,7 +2091,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook_with_token_optioned(webhook_id, token, wait)),
+                Cow::from(path::webhook_with_token_optioned(webhook_id, token, wait)),
             ),
             RouteInfo::GetActiveMaintenance => {
                 (LightMethod::Get, Route::None, Cow::from(Route::status_maintenances_active()))
@@ -2133,66 +2105,66 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdAuditLogs(guild_id),
-                Cow::from(Route::guild_audit_logs(guild_id, action_type, user_id, before, limit)),
+                Cow::from(path::guild_audit_logs(guild_id, action_type, user_id, before, limit)),
             ),
             RouteInfo::GetBans {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdBans(guild_id),
-                Cow::from(Route::guild_bans(guild_id)),
+                Cow::from(path::guild_bans(guild_id)),
             ),
             RouteInfo::GetBotGateway => {
-                (LightMethod::Get, Route::GatewayBot, Cow::from(Route::gateway_bot()))
+                (LightMethod::Get, Route::GatewayBot, Cow::from(path::gateway_bot()))
             },
             RouteInfo::GetChannel {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::ChannelsId(channel_id),
-                Cow::from(Route::channel(channel_id)),
+                Cow::from(path::channel(channel_id)),
             ),
             RouteInfo::GetStageInstance {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::StageInstancesChannelId(channel_id),
-                Cow::from(Route::stage_instance(channel_id)),
+                Cow::from(path::stage_instance(channel_id)),
             ),
             RouteInfo::GetChannelInvites {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdInvites(channel_id),
-                Cow::from(Route::channel_invites(channel_id)),
+                Cow::from(path::channel_invites(channel_id)),
             ),
             RouteInfo::GetChannelWebhooks {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdWebhooks(channel_id),
-                Cow::from(Route::channel_webhooks(channel_id)),
+                Cow::from(path::channel_webhooks(channel_id)),
             ),
             RouteInfo::GetChannels {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdChannels(guild_id),
-                Cow::from(Route::guild_channels(guild_id)),
+                Cow::from(path::guild_channels(guild_id)),
             ),
             RouteInfo::GetChannelThreadMembers {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdThreadMembers(channel_id),
-                Cow::from(Route::channel_thread_members(channel_id)),
+                Cow::from(path::channel_thread_members(channel_id)),
             ),
             RouteInfo::GetChannelActiveThreads {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdActiveThreads(channel_id),
-                Cow::from(Route::channel_active_threads(channel_id)),
+                Cow::from(path::channel_active_threads(channel_id)),
             ),
             RouteInfo::GetChannelArchivedPublicThreads {
                 channel_id,
@@ -2201,7 +2173,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdArchivedPublicThreads(channel_id),
-                Cow::from(Route::channel_archived_public_threads(channel_id, before, limit)),
+                Cow::from(path::channel_archived_public_threads(channel_id, before, limit)),
             ),
             RouteInfo::GetChannelArchivedPrivateThreads {
                 channel_id,
@@ -2210,7 +2182,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdArchivedPrivateThreads(channel_id),
-                Cow::from(Route::channel_archived_private_threads(channel_id, before, limit)),
+                Cow::from(path::channel_archived_private_threads(channel_id, before, limit)),
             ),
             RouteInfo::GetChannelJoinedPrivateArchivedThreads {
                 channel_id,
@@ -2219,21 +2191,21 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdMeJoindedArchivedPrivateThreads(channel_id),
-                Cow::from(Route::channel_joined_private_threads(channel_id, before, limit)),
+                Cow::from(path::channel_joined_private_threads(channel_id, before, limit)),
             ),
             RouteInfo::JoinThread {
                 channel_id,
             } => (
                 LightMethod::Put,
                 Route::ChannelsIdThreadMembersMe(channel_id),
-                Cow::from(Route::channel_thread_member_me(channel_id)),
+                Cow::from(path::channel_thread_member_me(channel_id)),
             ),
             RouteInfo::LeaveThread {
                 channel_id,
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdThreadMembersMe(channel_id),
-                Cow::from(Route::channel_thread_member_me(channel_id)),
+                Cow::from(path::channel_thread_member_me(channel_id)),
             ),
             RouteInfo::AddThreadMember {
                 channel_id,
@@ -2241,7 +2213,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ChannelsIdThreadMembersUserId(channel_id),
-                Cow::from(Route::channel_thread_member(channel_id, user_id)),
+                Cow::from(path::channel_thread_member(channel_id, user_id)),
             ),
             RouteInfo::RemoveThreadMember {
                 channel_id,
@@ -2249,20 +2221,20 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdThreadMembersUserId(channel_id),
-                Cow::from(Route::channel_thread_member(channel_id, user_id)),
+                Cow::from(path::channel_thread_member(channel_id, user_id)),
             ),
             RouteInfo::GetCurrentApplicationInfo => {
-                (LightMethod::Get, Route::None, Cow::from(Route::oauth2_application_current()))
+                (LightMethod::Get, Route::None, Cow::from(path::oauth2_application_current()))
             },
             RouteInfo::GetCurrentUser => {
-                (LightMethod::Get, Route::UsersMe, Cow::from(Route::user("@me")))
+                (LightMethod::Get, Route::UsersMe, Cow::from(path::user("@me")))
             },
             RouteInfo::GetEmojis {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdEmojis(guild_id),
-                Cow::from(Route::guild_emojis(guild_id)),
+                Cow::from(path::guild_emojis(guild_id)),
             ),
             RouteInfo::GetEmoji {
                 guild_id,
@@ -2270,10 +2242,10 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdEmojisId(guild_id),
-                Cow::from(Route::guild_emoji(guild_id, emoji_id)),
+                Cow::from(path::guild_emoji(guild_id, emoji_id)),
             ),
             RouteInfo::GetGateway => {
-                (LightMethod::Get, Route::Gateway, Cow::from(Route::gateway()))
+                (LightMethod::Get, Route::Gateway, Cow::from(path::gateway()))
             },
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGlobalApplicationCommands {
@@ -2281,7 +2253,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdCommands(application_id),
-                Cow::from(Route::application_commands(application_id)),
+                Cow::from(path::application_commands(application_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGlobalApplicationCommand {
@@ -2290,17 +2262,17 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdCommandsId(application_id),
-                Cow::from(Route::application_command(application_id, command_id)),
+                Cow::from(path::application_command(application_id, command_id)),
             ),
             RouteInfo::GetGuild {
                 guild_id,
-            } => (LightMethod::Get, Route::GuildsId(guild_id), Cow::from(Route::guild(guild_id))),
+            } => (LightMethod::Get, Route::GuildsId(guild_id), Cow::from(path::guild(guild_id))),
             RouteInfo::GetGuildWithCounts {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsId(guild_id),
-                Cow::from(Route::guild_with_counts(guild_id)),
+                Cow::from(path::guild_with_counts(guild_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGuildApplicationCommands {
@@ -2309,7 +2281,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdGuildsIdCommands(application_id),
-                Cow::from(Route::application_guild_commands(application_id, guild_id)),
+                Cow::from(path::application_guild_commands(application_id, guild_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGuildApplicationCommand {
@@ -2319,7 +2291,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdGuildsIdCommandsId(application_id),
-                Cow::from(Route::application_guild_command(application_id, guild_id, command_id)),
+                Cow::from(path::application_guild_command(application_id, guild_id, command_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGuildApplicationCommandsPermissions {
@@ -2328,7 +2300,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdGuildsIdCommandsPermissions(application_id),
-                Cow::from(Route::application_guild_commands_permissions(application_id, guild_id)),
+                Cow::from(path::application_guild_commands_permissions(application_id, guild_id)),
             ),
             #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGuildApplicationCommandPermissions {
@@ -2338,7 +2310,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdGuildsIdCommandIdPermissions(application_id),
-                Cow::from(Route::application_guild_command_permissions(
+                Cow::from(path::application_guild_command_permissions(
                     application_id,
                     guild_id,
                     command_id,
@@ -2349,35 +2321,35 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdWidget(guild_id),
-                Cow::from(Route::guild_widget(guild_id)),
+                Cow::from(path::guild_widget(guild_id)),
             ),
             RouteInfo::GetGuildPreview {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdPreview(guild_id),
-                Cow::from(Route::guild_preview(guild_id)),
+                Cow::from(path::guild_preview(guild_id)),
             ),
             RouteInfo::GetGuildWelcomeScreen {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdWelcomeScreen(guild_id),
-                Cow::from(Route::guild_welcome_screen(guild_id)),
+                Cow::from(path::guild_welcome_screen(guild_id)),
             ),
             RouteInfo::GetGuildIntegrations {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdIntegrations(guild_id),
-                Cow::from(Route::guild_integrations(guild_id)),
+                Cow::from(path::guild_integrations(guild_id)),
             ),
             RouteInfo::GetGuildInvites {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdInvites(guild_id),
-                Cow::from(Route::guild_invites(guild_id)),
+                Cow::from(path::guild_invites(guild_id)),
             ),
             RouteInfo::GetGuildMembers {
                 after,
@@ -2386,7 +2358,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdMembers(guild_id),
-                Cow::from(Route::guild_members_optioned(guild_id, after, limit)),
+                Cow::from(path::guild_members_optioned(guild_id, after, limit)),
             ),
             RouteInfo::GetGuildPruneCount {
                 days,
@@ -2394,35 +2366,42 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdPrune(guild_id),
-                Cow::from(Route::guild_prune(guild_id, days)),
+                Cow::from(path::guild_prune(guild_id, days)),
             ),
             RouteInfo::GetGuildRegions {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdRegions(guild_id),
-                Cow::from(Route::guild_regions(guild_id)),
+                Cow::from(path::guild_regions(guild_id)),
             ),
             RouteInfo::GetGuildRoles {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdRoles(guild_id),
-                Cow::from(Route::guild_roles(guild_id)),
+                Cow::from(path::guild_roles(guild_id)),
+            ),
+            RouteInfo::GetGuildActiveThreads {
+                guild_id,
+            } => (
+                LightMethod::Get,
+                Route::GuildsIdThreadsActive(guild_id),
+                Cow::from(path::guild_active_threads(guild_id)),
             ),
             RouteInfo::GetGuildVanityUrl {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdVanityUrl(guild_id),
-                Cow::from(Route::guild_vanity_url(guild_id)),
+                Cow::from(path::guild_vanity_url(guild_id)),
             ),
             RouteInfo::GetGuildWebhooks {
                 guild_id,
             } => (
                 LightMethod::Get,
                 Route::GuildsIdWebhooks(guild_id),
-                Cow::from(Route::guild_webhooks(guild_id)),
+                Cow::from(path::guild_webhooks(guild_id)),
             ),
             RouteInfo::GetGuilds {
                 after,
@@ -2431,7 +2410,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::UsersMeGuilds,
-                Cow::from(Route::user_guilds_optioned("@me", after, before, limit)),
+                Cow::from(path::user_guilds_optioned("@me", after, before, limit)),
             ),
             RouteInfo::GetInvite {
                 code,
@@ -2439,7 +2418,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::InvitesCode,
-                Cow::from(Route::invite_optioned(code, stats)),
+                Cow::from(path::invite_optioned(code, stats)),
             ),
             RouteInfo::GetMember {
                 guild_id,
@@ -2447,7 +2426,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdMembersId(guild_id),
-                Cow::from(Route::guild_member(guild_id, user_id)),
+                Cow::from(path::guild_member(guild_id, user_id)),
             ),
             RouteInfo::GetMessage {
                 channel_id,
@@ -2455,7 +2434,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdMessagesId(LightMethod::Get, channel_id),
-                Cow::from(Route::channel_message(channel_id, message_id)),
+                Cow::from(path::channel_message(channel_id, message_id)),
             ),
             RouteInfo::GetMessages {
                 channel_id,
@@ -2463,14 +2442,27 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdMessages(channel_id),
-                Cow::from(Route::channel_messages(channel_id, Some(query.as_ref()))),
+                Cow::from(path::channel_messages(channel_id, Some(query.as_ref()))),
             ),
             RouteInfo::GetPins {
                 channel_id,
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdPins(channel_id),
-                Cow::from(Route::channel_pins(channel_id)),
+                Cow::from(path::channel_pins(channel_id)),
+            ),
+            RouteInfo::GetPollAnswerVoters {
+                after,
+                answer_id,
+                channel_id,
+                limit,
+                message_id,
+            } => (
+                LightMethod::Get,
+                Route::ChannelsIdPollsMessageIdAnswersAnswerId(channel_id),
+                Cow::from(path::channel_poll_answer_voters(
+                    channel_id, message_id, answer_id, after, limit,
+                )),
             ),
             RouteInfo::GetReactionUsers {
                 after,
@@ -2481,7 +2473,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::ChannelsIdMessagesIdReactions(channel_id),
-                Cow::from(Route::channel_message_reactions_list(
+                Cow::from(path::channel_message_reactions_list(
                     channel_id, message_id, reaction, limit, after,
                 )),
             ),
@@ -2493,26 +2485,26 @@ impl<'a> RouteInfo<'a> {
             },
             RouteInfo::GetUser {
                 user_id,
-            } => (LightMethod::Get, Route::UsersId, Cow::from(Route::user(user_id))),
+            } => (LightMethod::Get, Route::UsersId, Cow::from(path::user(user_id))),
             RouteInfo::GetUserConnections => (
                 LightMethod::Get,
                 Route::UsersMeConnections,
-                Cow::from(Route::user_me_connections()),
+                Cow::from(path::user_me_connections()),
             ),
             RouteInfo::GetUserDmChannels => (
                 LightMethod::Get,
                 Route::UsersMeChannels,
-                Cow::from(Route::user_dm_channels("@me")),
+                Cow::from(path::user_dm_channels("@me")),
             ),
             RouteInfo::GetVoiceRegions => {
-                (LightMethod::Get, Route::VoiceRegions, Cow::from(Route::voice_regions()))
+                (LightMethod::Get, Route::VoiceRegions, Cow::from(path::voice_regions()))
             },
             RouteInfo::GetWebhook {
                 webhook_id,
             } => (
                 LightMethod::Get,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook(webhook_id)),
+                Cow::from(path::webhook(webhook_id)),
             ),
             RouteInfo::GetWebhookWithToken {
                 token,
@@ -2520,30 +2512,38 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook_with_token(webhook_id, token)),
+                Cow::from(path::webhook_with_token(webhook_id, token)),
             ),
             RouteInfo::KickMember {
                 guild_id,
                 user_id,
                 reason,
-            } => (
-                LightMethod::Delete,
-                Route::GuildsIdMembersId(guild_id),
-                Cow::from(Route::guild_kick_optioned(guild_id, user_id, reason)),
-            ),
+            } => {
+                let mut query = Vec::new();
+
+                if !reason.is_empty() {
+                    query.push(format!("reason={}", reason));
+                }
+
+                (
+                    LightMethod::Delete,
+                    Route::GuildsIdMembersId(guild_id),
+                    Cow::from(with_query(path::guild_member(guild_id, user_id), &query)),
+                )
+            },
             RouteInfo::LeaveGroup {
                 group_id,
             } => (
                 LightMethod::Delete,
                 Route::ChannelsId(group_id),
-                Cow::from(Route::channel(group_id)),
+                Cow::from(path::channel(group_id)),
             ),
             RouteInfo::LeaveGuild {
                 guild_id,
             } => (
                 LightMethod::Delete,
                 Route::UsersMeGuildsId,
-                Cow::from(Route::user_guild("@me", guild_id)),
+                Cow::from(path::user_guild("@me", guild_id)),
             ),
             RouteInfo::PinMessage {
                 channel_id,
@@ -2551,7 +2551,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Put,
                 Route::ChannelsIdPins(channel_id),
-                Cow::from(Route::channel_pin(channel_id, message_id)),
+                Cow::from(path::channel_pin(channel_id, message_id)),
             ),
             RouteInfo::RemoveBan {
                 guild_id,
@@ -2559,7 +2559,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::GuildsIdBansUserId(guild_id),
-                Cow::from(Route::guild_ban(guild_id, user_id)),
+                Cow::from(path::guild_ban(guild_id, user_id)),
             ),
             RouteInfo::RemoveMemberRole {
                 guild_id,
@@ -2568,7 +2568,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::GuildsIdMembersIdRolesId(guild_id),
-                Cow::from(Route::guild_member_role(guild_id, user_id, role_id)),
+                Cow::from(path::guild_member_role(guild_id, user_id, role_id)),
             ),
             RouteInfo::SearchGuildMembers {
                 guild_id,
@@ -2577,7 +2577,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Get,
                 Route::GuildsIdMembersSearch(guild_id),
-                Cow::from(Route::guild_members_search(guild_id, query, limit)),
+                Cow::from(path::guild_members_search(guild_id, query, limit)),
             ),
             RouteInfo::StartGuildPrune {
                 days,
@@ -2585,7 +2585,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::GuildsIdPrune(guild_id),
-                Cow::from(Route::guild_prune(guild_id, days)),
+                Cow::from(path::guild_prune(guild_id, days)),
             ),
             RouteInfo::StartIntegrationSync {
                 guild_id,
@@ -2593,7 +2593,7 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Post,
                 Route::GuildsIdIntegrationsId(guild_id),
-                Cow::from(Route::guild_integration_sync(guild_id, integration_id)),
+                Cow::from(path::guild_integration_sync(guild_id, integration_id)),
             ),
             RouteInfo::StatusIncidentsUnresolved => {
                 (LightMethod::Get, Route::None, Cow::from(Route::status_incidents_unresolved()))
@@ -2610,8 +2610,23 @@ impl<'a> RouteInfo<'a> {
             } => (
                 LightMethod::Delete,
                 Route::ChannelsIdPinsMessageId(channel_id),
-                Cow::from(Route::channel_pin(channel_id, message_id)),
+                Cow::from(path::channel_pin(channel_id, message_id)),
             ),
         }
     }
 }
+
+/// Appends the `query` parameters to the `path`, if there are any.
+fn with_query(mut path: String, query: &[String]) -> String {
+    if !query.is_empty() {
+        path.push('?');
+        path.push_str(&query.join("&"));
+    }
+
+    path
+}
+
+/// Prepends the default API URL to the `path` of a route.
+fn default_url(path: &str) -> String {
+    route_url(constants::API_BASE_URL, constants::API_VERSION, path)
+}
@@ -0,0 +1,562 @@
+//! The paths of the routes, relative to the API root.
+
+use std::fmt::{Display, Write};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::constants;
+
+pub(crate) fn channel(channel_id: u64) -> String {
+    format!("/channels/{}", channel_id)
+}
+
+pub(crate) fn channel_invites(channel_id: u64) -> String {
+    format!("/channels/{}/invites", channel_id)
+}
+
+pub(crate) fn channel_message(channel_id: u64, message_id: u64) -> String {
+    format!("/channels/{}/messages/{}", channel_id, message_id)
+}
+
+pub(crate) fn channel_message_crosspost(channel_id: u64, message_id: u64) -> String {
+    format!("/channels/{}/messages/{}/crosspost", channel_id, message_id)
+}
+
+pub(crate) fn channel_message_reaction<D, T>(
+    channel_id: u64,
+    message_id: u64,
+    user_id: D,
+    reaction_type: T,
+) -> String
+where
+    D: Display,
+    T: Display,
+{
+    format!(
+        "/channels/{}/messages/{}/reactions/{}/{}",
+        channel_id, message_id, reaction_type, user_id,
+    )
+}
+
+pub(crate) fn channel_message_reaction_emoji<T>(
+    channel_id: u64,
+    message_id: u64,
+    reaction_type: T,
+) -> String
+where
+    T: Display,
+{
+    format!("/channels/{}/messages/{}/reactions/{}", channel_id, message_id, reaction_type)
+}
+
+pub(crate) fn channel_message_reactions(channel_id: u64, message_id: u64) -> String {
+    format!("/channels/{}/messages/{}/reactions", channel_id, message_id)
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn channel_message_reactions_list(
+    channel_id: u64,
+    message_id: u64,
+    reaction: &str,
+    limit: u8,
+    after: Option<u64>,
+) -> String {
+    let mut uri = format!(
+        "/channels/{}/messages/{}/reactions/{}?limit={}",
+        channel_id, message_id, reaction, limit,
+    );
+
+    if let Some(after) = after {
+        let _ = write!(uri, "&after={}", after);
+    }
+
+    uri
+}
+
+pub(crate) fn channel_messages(channel_id: u64, query: Option<&str>) -> String {
+    format!("/channels/{}/messages{}", channel_id, query.unwrap_or(""),)
+}
+
+pub(crate) fn channel_messages_bulk_delete(channel_id: u64) -> String {
+    format!("/channels/{}/messages/bulk-delete", channel_id)
+}
+
+pub(crate) fn channel_permission(channel_id: u64, target_id: u64) -> String {
+    format!("/channels/{}/permissions/{}", channel_id, target_id)
+}
+
+pub(crate) fn channel_pin(channel_id: u64, message_id: u64) -> String {
+    format!("/channels/{}/pins/{}", channel_id, message_id)
+}
+
+pub(crate) fn channel_pins(channel_id: u64) -> String {
+    format!("/channels/{}/pins", channel_id)
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn channel_poll_answer_voters(
+    channel_id: u64,
+    message_id: u64,
+    answer_id: u8,
+    after: Option<u64>,
+    limit: Option<u8>,
+) -> String {
+    let mut uri = format!("/channels/{}/polls/{}/answers/{}?", channel_id, message_id, answer_id);
+
+    if let Some(after) = after {
+        let _ = write!(uri, "&after={}", after);
+    }
+
+    if let Some(limit) = limit {
+        let _ = write!(uri, "&limit={}", limit);
+    }
+
+    uri
+}
+
+pub(crate) fn channel_typing(channel_id: u64) -> String {
+    format!("/channels/{}/typing", channel_id)
+}
+
+pub(crate) fn channel_webhooks(channel_id: u64) -> String {
+    format!("/channels/{}/webhooks", channel_id)
+}
+
+pub(crate) fn channel_public_threads(channel_id: u64, message_id: u64) -> String {
+    format!("/channels/{}/messages/{}/threads", channel_id, message_id)
+}
+
+pub(crate) fn channel_private_threads(channel_id: u64) -> String {
+    format!("/channels/{}/threads", channel_id)
+}
+
+pub(crate) fn channel_thread_member(channel_id: u64, user_id: u64) -> String {
+    format!("/channels/{}/thread-members/{}", channel_id, user_id)
+}
+
+pub(crate) fn channel_thread_member_me(channel_id: u64) -> String {
+    format!("/channels/{}/thread-members/@me", channel_id)
+}
+
+pub(crate) fn channel_thread_members(channel_id: u64) -> String {
+    format!("/channels/{}/thread-members", channel_id)
+}
+
+pub(crate) fn channel_active_threads(channel_id: u64) -> String {
+    format!("/channels/{}/threads/active", channel_id)
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn channel_archived_public_threads(
+    channel_id: u64,
+    before: Option<DateTime<Utc>>,
+    limit: Option<u64>,
+) -> String {
+    let mut s = format!("/channels/{}/threads/archived/public?", channel_id);
+
+    if let Some(before) = before {
+        let _ = write!(s, "&before={}", before.to_rfc3339_opts(SecondsFormat::Millis, true));
+    }
+
+    if let Some(limit) = limit {
+        let _ = write!(s, "&limit={}", limit);
+    }
+
+    s
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn channel_archived_private_threads(
+    channel_id: u64,
+    before: Option<DateTime<Utc>>,
+    limit: Option<u64>,
+) -> String {
+    let mut s = format!("/channels/{}/threads/archived/private?", channel_id);
+
+    if let Some(before) = before {
+        let _ = write!(s, "&before={}", before.to_rfc3339_opts(SecondsFormat::Millis, true));
+    }
+
+    if let Some(limit) = limit {
+        let _ = write!(s, "&limit={}", limit);
+    }
+
+    s
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn channel_joined_private_threads(
+    channel_id: u64,
+    before: Option<u64>,
+    limit: Option<u64>,
+) -> String {
+    let mut s = format!("/channels/{}/users/@me/threads/archived/private?", channel_id);
+
+    if let Some(id) = before {
+        let _ = write!(s, "&before={}", id);
+    }
+
+    if let Some(limit) = limit {
+        let _ = write!(s, "&limit={}", limit);
+    }
+
+    s
+}
+
+pub(crate) fn gateway() -> &'static str {
+    "/gateway"
+}
+
+pub(crate) fn gateway_bot() -> &'static str {
+    "/gateway/bot"
+}
+
+pub(crate) fn guild(guild_id: u64) -> String {
+    format!("/guilds/{}", guild_id)
+}
+
+pub(crate) fn guild_with_counts(guild_id: u64) -> String {
+    format!("/guilds/{}?with_counts=true", guild_id)
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn guild_audit_logs(
+    guild_id: u64,
+    action_type: Option<u8>,
+    user_id: Option<u64>,
+    before: Option<u64>,
+    limit: Option<u8>,
+) -> String {
+    let mut s = format!("/guilds/{}/audit-logs?", guild_id,);
+
+    if let Some(action_type) = action_type {
+        let _ = write!(s, "&action_type={}", action_type);
+    }
+
+    if let Some(before) = before {
+        let _ = write!(s, "&before={}", before);
+    }
+
+    if let Some(limit) = limit {
+        let _ = write!(s, "&limit={}", limit);
+    }
+
+    if let Some(user_id) = user_id {
+        let _ = write!(s, "&user_id={}", user_id);
+    }
+
+    s
+}
+
+pub(crate) fn guild_ban(guild_id: u64, user_id: u64) -> String {
+    format!("/guilds/{}/bans/{}", guild_id, user_id)
+}
+
+pub(crate) fn guild_ban_optioned(
+    guild_id: u64,
+    user_id: u64,
+    delete_message_days: u8,
+    reason: &str,
+) -> String {
+    format!(
+        "/guilds/{}/bans/{}?delete_message_days={}&reason={}",
+        guild_id, user_id, delete_message_days, reason,
+    )
+}
+
+pub(crate) fn guild_kick_optioned(guild_id: u64, user_id: u64, reason: &str) -> String {
+    format!("/guilds/{}/members/{}?reason={}", guild_id, user_id, reason,)
+}
+
+pub(crate) fn guild_bans(guild_id: u64) -> String {
+    format!("/guilds/{}/bans", guild_id)
+}
+
+pub(crate) fn guild_channels(guild_id: u64) -> String {
+    format!("/guilds/{}/channels", guild_id)
+}
+
+pub(crate) fn guild_widget(guild_id: u64) -> String {
+    format!("/guilds/{}/widget", guild_id)
+}
+
+pub(crate) fn guild_preview(guild_id: u64) -> String {
+    format!("/guilds/{}/preview", guild_id)
+}
+
+pub(crate) fn guild_emojis(guild_id: u64) -> String {
+    format!("/guilds/{}/emojis", guild_id)
+}
+
+pub(crate) fn guild_emoji(guild_id: u64, emoji_id: u64) -> String {
+    format!("/guilds/{}/emojis/{}", guild_id, emoji_id)
+}
+
+pub(crate) fn guild_integration(guild_id: u64, integration_id: u64) -> String {
+    format!("/guilds/{}/integrations/{}", guild_id, integration_id)
+}
+
+pub(crate) fn guild_integration_sync(guild_id: u64, integration_id: u64) -> String {
+    format!("/guilds/{}/integrations/{}/sync", guild_id, integration_id,)
+}
+
+pub(crate) fn guild_integrations(guild_id: u64) -> String {
+    format!("/guilds/{}/integrations", guild_id)
+}
+
+pub(crate) fn guild_invites(guild_id: u64) -> String {
+    format!("/guilds/{}/invites", guild_id)
+}
+
+pub(crate) fn guild_member(guild_id: u64, user_id: u64) -> String {
+    format!("/guilds/{}/members/{}", guild_id, user_id)
+}
+
+pub(crate) fn guild_member_role(guild_id: u64, user_id: u64, role_id: u64) -> String {
+    format!("/guilds/{}/members/{}/roles/{}", guild_id, user_id, role_id,)
+}
+
+pub(crate) fn guild_members(guild_id: u64) -> String {
+    format!("/guilds/{}/members", guild_id)
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn guild_members_search(guild_id: u64, query: &str, limit: Option<u64>) -> String {
+    let mut s = format!("/guilds/{}/members/search?", guild_id);
+
+    let _ = write!(s, "&query={}", query);
+
+    let _ = write!(s, "&limit={}", limit.unwrap_or(constants::MEMBER_FETCH_LIMIT));
+
+    s
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn guild_members_optioned(
+    guild_id: u64,
+    after: Option<u64>,
+    limit: Option<u64>,
+) -> String {
+    let mut s = format!("/guilds/{}/members?", guild_id);
+
+    if let Some(after) = after {
+        let _ = write!(s, "&after={}", after);
+        // should not error, ignoring
+    }
+
+    let _ = write!(s, "&limit={}", limit.unwrap_or(constants::MEMBER_FETCH_LIMIT));
+    // should not error, ignoring
+
+    s
+}
+
+pub(crate) fn guild_member_me(guild_id: u64) -> String {
+    format!("/guilds/{}/members/@me", guild_id)
+}
+
+pub(crate) fn guild_nickname(guild_id: u64) -> String {
+    format!("/guilds/{}/members/@me/nick", guild_id)
+}
+
+pub(crate) fn guild_prune(guild_id: u64, days: u64) -> String {
+    format!("/guilds/{}/prune?days={}", guild_id, days)
+}
+
+pub(crate) fn guild_regions(guild_id: u64) -> String {
+    format!("/guilds/{}/regions", guild_id)
+}
+
+pub(crate) fn guild_role(guild_id: u64, role_id: u64) -> String {
+    format!("/guilds/{}/roles/{}", guild_id, role_id)
+}
+
+pub(crate) fn guild_roles(guild_id: u64) -> String {
+    format!("/guilds/{}/roles", guild_id)
+}
+
+pub(crate) fn guild_active_threads(guild_id: u64) -> String {
+    format!("/guilds/{}/threads/active", guild_id)
+}
+
+pub(crate) fn guild_vanity_url(guild_id: u64) -> String {
+    format!("/guilds/{}/vanity-url", guild_id)
+}
+
+pub(crate) fn guild_voice_states(guild_id: u64, user_id: u64) -> String {
+    format!("/guilds/{}/voice-states/{}", guild_id, user_id)
+}
+
+pub(crate) fn guild_voice_states_me(guild_id: u64) -> String {
+    format!("/guilds/{}/voice-states/@me", guild_id)
+}
+
+pub(crate) fn guild_webhooks(guild_id: u64) -> String {
+    format!("/guilds/{}/webhooks", guild_id)
+}
+
+pub(crate) fn guild_welcome_screen(guild_id: u64) -> String {
+    format!("/guilds/{}/welcome-screen", guild_id)
+}
+
+pub(crate) fn guilds() -> &'static str {
+    "/guilds"
+}
+
+pub(crate) fn invite(code: &str) -> String {
+    format!("/invites/{}", code)
+}
+
+pub(crate) fn invite_optioned(code: &str, stats: bool) -> String {
+    format!("/invites/{}?with_counts={}", code, stats)
+}
+
+pub(crate) fn oauth2_application_current() -> &'static str {
+    "/oauth2/applications/@me"
+}
+
+pub(crate) fn user<D: Display>(target: D) -> String {
+    format!("/users/{}", target)
+}
+
+pub(crate) fn user_me_connections() -> &'static str {
+    "/users/@me/connections"
+}
+
+pub(crate) fn user_dm_channels<D: Display>(target: D) -> String {
+    format!("/users/{}/channels", target)
+}
+
+pub(crate) fn user_guild<D: Display>(target: D, guild_id: u64) -> String {
+    format!("/users/{}/guilds/{}", target, guild_id)
+}
+
+pub(crate) fn user_guilds<D: Display>(target: D) -> String {
+    format!("/users/{}/guilds", target)
+}
+
+#[allow(clippy::let_underscore_must_use)]
+pub(crate) fn user_guilds_optioned<D: Display>(
+    target: D,
+    after: Option<u64>,
+    before: Option<u64>,
+    limit: u64,
+) -> String {
+    let mut s = format!("/users/{}/guilds?limit={}&", target, limit);
+
+    if let Some(after) = after {
+        let _ = write!(s, "&after={}", after);
+        // should not error, ignoring
+    }
+
+    if let Some(before) = before {
+        let _ = write!(s, "&before={}", before);
+        // should not error, ignoring
+    }
+
+    s
+}
+
+pub(crate) fn voice_regions() -> &'static str {
+    "/voice/regions"
+}
+
+pub(crate) fn webhook(webhook_id: u64) -> String {
+    format!("/webhooks/{}", webhook_id)
+}
+
+pub(crate) fn webhook_with_token<D>(webhook_id: u64, token: D) -> String
+where
+    D: Display,
+{
+    format!("/webhooks/{}/{}", webhook_id, token)
+}
+
+pub(crate) fn webhook_with_token_optioned<D>(webhook_id: u64, token: D, wait: bool) -> String
+where
+    D: Display,
+{
+    format!("/webhooks/{}/{}?wait={}", webhook_id, token, wait)
+}
+
+pub(crate) fn webhook_message<D>(webhook_id: u64, token: D, message_id: u64) -> String
+where
+    D: Display,
+{
+    format!("/webhooks/{}/{}/messages/{}", webhook_id, token, message_id)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn webhook_original_interaction_response<D: Display>(
+    application_id: u64,
+    token: D,
+) -> String {
+    format!("/webhooks/{}/{}/messages/@original", application_id, token)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn webhook_followup_message<D: Display>(
+    application_id: u64,
+    token: D,
+    message_id: u64,
+) -> String {
+    format!("/webhooks/{}/{}/messages/{}", application_id, token, message_id)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn webhook_followup_messages<D: Display>(application_id: u64, token: D) -> String {
+    format!("/webhooks/{}/{}", application_id, token)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn interaction_response<D: Display>(application_id: u64, token: D) -> String {
+    format!("/interactions/{}/{}/callback", application_id, token)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn application_command(application_id: u64, command_id: u64) -> String {
+    format!("/applications/{}/commands/{}", application_id, command_id)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn application_commands(application_id: u64) -> String {
+    format!("/applications/{}/commands", application_id)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn application_guild_command(
+    application_id: u64,
+    guild_id: u64,
+    command_id: u64,
+) -> String {
+    format!("/applications/{}/guilds/{}/commands/{}", application_id, guild_id, command_id)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn application_guild_command_permissions(
+    application_id: u64,
+    guild_id: u64,
+    command_id: u64,
+) -> String {
+    format!(
+        "/applications/{}/guilds/{}/commands/{}/permissions",
+        application_id, guild_id, command_id
+    )
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn application_guild_commands(application_id: u64, guild_id: u64) -> String {
+    format!("/applications/{}/guilds/{}/commands", application_id, guild_id)
+}
+
+#[cfg(feature = "unstable_discord_api")]
+pub(crate) fn application_guild_commands_permissions(application_id: u64, guild_id: u64) -> String {
+    format!("/applications/{}/guilds/{}/commands/permissions", application_id, guild_id)
+}
+
+pub(crate) fn stage_instances() -> String {
+    "/stage-instances".to_string()
+}
+
+pub(crate) fn stage_instance(channel_id: u64) -> String {
+    format!("/stage-instances/{}", channel_id)
+}
@@ -49,7 +49,7 @@ fn loop_errors(value: Value, errors: &mut Vec<DiscordJsonSingleError>, path: Vec
                         .as_str()
                         .expect("expected string")
                         .to_owned(),
-                    path: object_path.join("."),
+                    path: json_pointer(&object_path),
                 });
             }
             continue;
@@ -61,3 +61,15 @@ fn loop_errors(value: Value, errors: &mut Vec<DiscordJsonSingleError>, path: Vec
         loop_errors(looped.clone(), errors, new_path);
     }
 }
+
+/// Joins the keys of a path into a JSON pointer, as described by [RFC 6901].
+///
+/// [RFC 6901]: https://datatracker.ietf.org/doc/html/rfc6901
+fn json_pointer(path: &[String]) -> String {
+    path.iter().fold(String::new(), |mut pointer, key| {
+        pointer.push('/');
+        pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+
+        pointer
+    })
+}
@@ -0,0 +1,724 @@
+//! Encoding and decoding of the subset of Erlang's [External Term Format] used
+//! by the gateway.
+//!
+//! Payloads are decoded through a [`Deserializer`], so that they map into the
+//! same models as JSON payloads: binaries and atoms are strings, except the
+//! `nil`, `true` and `false` atoms, lists are sequences, and maps are maps.
+//!
+//! [External Term Format]: https://www.erlang.org/doc/apps/erts/erl_ext_dist.html
+
+use std::{
+    convert::TryInto,
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    str,
+};
+
+use serde::de::{
+    self,
+    value::SeqDeserializer,
+    DeserializeSeed,
+    EnumAccess,
+    IgnoredAny,
+    IntoDeserializer,
+    MapAccess,
+    SeqAccess,
+    VariantAccess,
+    Visitor,
+};
+use serde::{forward_to_deserialize_any, Deserialize};
+use serde_json::{Number, Value};
+
+use crate::constants::OpCode;
+use crate::model::event::GatewayEvent;
+
+/// The version byte every payload starts with.
+pub(crate) const VERSION: u8 = 131;
+
+const NEW_FLOAT_EXT: u8 = 70;
+const SMALL_INTEGER_EXT: u8 = 97;
+const INTEGER_EXT: u8 = 98;
+const ATOM_EXT: u8 = 100;
+const SMALL_TUPLE_EXT: u8 = 104;
+const LARGE_TUPLE_EXT: u8 = 105;
+const NIL_EXT: u8 = 106;
+const STRING_EXT: u8 = 107;
+const LIST_EXT: u8 = 108;
+const BINARY_EXT: u8 = 109;
+const SMALL_BIG_EXT: u8 = 110;
+const LARGE_BIG_EXT: u8 = 111;
+const SMALL_ATOM_EXT: u8 = 115;
+const MAP_EXT: u8 = 116;
+const ATOM_UTF8_EXT: u8 = 118;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+
+/// An error decoding a payload.
+#[derive(Clone, Debug)]
+pub(crate) struct EtfError(String);
+
+impl Display for EtfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl StdError for EtfError {}
+
+impl de::Error for EtfError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, EtfError>;
+
+fn error<T>(msg: &str) -> Result<T> {
+    Err(EtfError(msg.to_string()))
+}
+
+/// Decodes a payload, which must start with the [`VERSION`] byte.
+pub(crate) fn from_slice<'de, T: de::Deserialize<'de>>(bytes: &'de [u8]) -> Result<T> {
+    match bytes.split_first() {
+        Some((&VERSION, input)) => {
+            let mut deserializer = Deserializer {
+                input,
+            };
+            let value = T::deserialize(&mut deserializer)?;
+
+            if deserializer.input.is_empty() {
+                Ok(value)
+            } else {
+                error("trailing bytes after the term")
+            }
+        },
+        Some((version, _)) => Err(EtfError(format!("unsupported version {}", version))),
+        None => error("empty payload"),
+    }
+}
+
+/// Decodes a gateway payload into an event.
+///
+/// The data of the payload is deserialized straight into the event, instead of
+/// going through a [`Value`]. As Discord sends the data before the event type,
+/// its term is skipped first and deserialized once the whole map was read.
+pub(crate) fn event_from_slice(bytes: &[u8]) -> Result<GatewayEvent> {
+    let input = match bytes.split_first() {
+        Some((&VERSION, input)) => input,
+        Some((version, _)) => return Err(EtfError(format!("unsupported version {}", version))),
+        None => return error("empty payload"),
+    };
+    let mut de = Deserializer {
+        input,
+    };
+
+    if de.u8()? != MAP_EXT {
+        return error("expected a map");
+    }
+
+    let (mut op, mut seq, mut kind, mut data) = (None, None, None, None);
+
+    for _ in 0..de.u32()? {
+        match <&str>::deserialize(&mut de)? {
+            "op" => op = Some(OpCode::deserialize(&mut de)?),
+            "s" => seq = Option::deserialize(&mut de)?,
+            "t" => kind = Option::deserialize(&mut de)?,
+            "d" => {
+                let start = de.input;
+                IgnoredAny::deserialize(&mut de)?;
+
+                data = Some(Deserializer {
+                    input: &start[..start.len() - de.input.len()],
+                });
+            },
+            _ => {
+                IgnoredAny::deserialize(&mut de)?;
+            },
+        }
+    }
+
+    if !de.input.is_empty() {
+        return error("trailing bytes after the term");
+    }
+
+    let op = op.ok_or_else(|| EtfError("expected op".to_string()))?;
+
+    GatewayEvent::from_parts(op, seq, kind, data.as_mut())
+}
+
+/// Encodes a JSON value as a payload.
+///
+/// `null` and booleans are encoded as atoms, strings as binaries, and object
+/// keys as binaries too.
+pub(crate) fn to_vec(value: &Value) -> Vec<u8> {
+    let mut out = vec![VERSION];
+    encode(value, &mut out);
+
+    out
+}
+
+fn encode(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => encode_atom("nil", out),
+        Value::Bool(true) => encode_atom("true", out),
+        Value::Bool(false) => encode_atom("false", out),
+        Value::Number(number) => encode_number(number, out),
+        Value::String(string) => encode_binary(string.as_bytes(), out),
+        Value::Array(values) if values.is_empty() => out.push(NIL_EXT),
+        Value::Array(values) => {
+            out.push(LIST_EXT);
+            out.extend_from_slice(&(values.len() as u32).to_be_bytes());
+
+            for value in values {
+                encode(value, out);
+            }
+
+            out.push(NIL_EXT);
+        },
+        Value::Object(map) => {
+            out.push(MAP_EXT);
+            out.extend_from_slice(&(map.len() as u32).to_be_bytes());
+
+            for (key, value) in map {
+                encode_binary(key.as_bytes(), out);
+                encode(value, out);
+            }
+        },
+    }
+}
+
+fn encode_atom(name: &str, out: &mut Vec<u8>) {
+    out.push(SMALL_ATOM_UTF8_EXT);
+    out.push(name.len() as u8);
+    out.extend_from_slice(name.as_bytes());
+}
+
+fn encode_binary(bytes: &[u8], out: &mut Vec<u8>) {
+    out.push(BINARY_EXT);
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn encode_number(number: &Number, out: &mut Vec<u8>) {
+    if let Some(value) = number.as_u64() {
+        if value <= u64::from(u8::MAX) {
+            out.push(SMALL_INTEGER_EXT);
+            out.push(value as u8);
+        } else if value <= i32::MAX as u64 {
+            out.push(INTEGER_EXT);
+            out.extend_from_slice(&(value as i32).to_be_bytes());
+        } else {
+            encode_big(value, false, out);
+        }
+    } else if let Some(value) = number.as_i64() {
+        if value >= i64::from(i32::MIN) {
+            out.push(INTEGER_EXT);
+            out.extend_from_slice(&(value as i32).to_be_bytes());
+        } else {
+            encode_big((value as u64).wrapping_neg(), true, out);
+        }
+    } else if let Some(value) = number.as_f64() {
+        out.push(NEW_FLOAT_EXT);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn encode_big(value: u64, negative: bool, out: &mut Vec<u8>) {
+    let bytes = value.to_le_bytes();
+    let len = bytes.iter().rposition(|&byte| byte != 0).map_or(0, |last| last + 1);
+
+    out.push(SMALL_BIG_EXT);
+    out.push(len as u8);
+    out.push(negative as u8);
+    out.extend_from_slice(&bytes[..len]);
+}
+
+/// Deserializes the terms of a payload, without its version byte.
+struct Deserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        if self.input.len() < len {
+            return error("unexpected end of payload");
+        }
+
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<usize> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]) as usize)
+    }
+
+    fn u32(&mut self) -> Result<usize> {
+        let bytes = self.take(4)?;
+
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+
+    /// Reads the name of the atom with the given tag.
+    fn atom(&mut self, tag: u8) -> Result<&'de str> {
+        let len = match tag {
+            SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT => self.u8()? as usize,
+            _ => self.u16()?,
+        };
+
+        str::from_utf8(self.take(len)?).or_else(|_| error("atom is not valid UTF-8"))
+    }
+
+    fn big<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value> {
+        let negative = self.u8()? != 0;
+        let digits = self.take(len)?;
+
+        if len > 8 {
+            return error("integer does not fit in 64 bits");
+        }
+
+        let mut bytes = [0; 8];
+        bytes[..len].copy_from_slice(digits);
+        let value = u64::from_le_bytes(bytes);
+
+        if !negative {
+            visitor.visit_u64(value)
+        } else if value <= i64::MAX as u64 + 1 {
+            visitor.visit_i64((value as i64).wrapping_neg())
+        } else {
+            error("integer does not fit in 64 bits")
+        }
+    }
+
+    /// Whether the next term is the `nil` atom.
+    fn peek_nil(&self) -> bool {
+        match self.input {
+            [SMALL_ATOM_EXT, 3, b'n', b'i', b'l', ..]
+            | [SMALL_ATOM_UTF8_EXT, 3, b'n', b'i', b'l', ..] => true,
+            [ATOM_EXT, 0, 3, b'n', b'i', b'l', ..]
+            | [ATOM_UTF8_EXT, 0, 3, b'n', b'i', b'l', ..] => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+    type Error = EtfError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.u8()? {
+            SMALL_INTEGER_EXT => visitor.visit_u64(u64::from(self.u8()?)),
+            INTEGER_EXT => {
+                let bytes = self.take(4)?;
+                let value = i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+                match value.try_into() {
+                    Ok(value) => visitor.visit_u64(value),
+                    Err(_) => visitor.visit_i64(i64::from(value)),
+                }
+            },
+            SMALL_BIG_EXT => {
+                let len = self.u8()? as usize;
+                self.big(len, visitor)
+            },
+            LARGE_BIG_EXT => {
+                let len = self.u32()?;
+                self.big(len, visitor)
+            },
+            NEW_FLOAT_EXT => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.take(8)?);
+
+                visitor.visit_f64(f64::from_be_bytes(bytes))
+            },
+            tag @ ATOM_EXT
+            | tag @ SMALL_ATOM_EXT
+            | tag @ ATOM_UTF8_EXT
+            | tag @ SMALL_ATOM_UTF8_EXT => match self.atom(tag)? {
+                "nil" => visitor.visit_unit(),
+                "true" => visitor.visit_bool(true),
+                "false" => visitor.visit_bool(false),
+                name => visitor.visit_borrowed_str(name),
+            },
+            BINARY_EXT => {
+                let len = self.u32()?;
+                let bytes = self.take(len)?;
+
+                match str::from_utf8(bytes) {
+                    Ok(string) => visitor.visit_borrowed_str(string),
+                    Err(_) => visitor.visit_borrowed_bytes(bytes),
+                }
+            },
+            NIL_EXT => visitor.visit_seq(ListAccess {
+                de: self,
+                remaining: 0,
+            }),
+            // Lists of bytes are encoded as strings.
+            STRING_EXT => {
+                let len = self.u16()?;
+                let mut bytes =
+                    SeqDeserializer::<_, EtfError>::new(self.take(len)?.iter().copied());
+                let value = visitor.visit_seq(&mut bytes)?;
+                bytes.end()?;
+
+                Ok(value)
+            },
+            LIST_EXT => {
+                let len = self.u32()?;
+                let value = visit_list(self, len, visitor)?;
+
+                // Only proper lists, ending with an empty list, are supported.
+                match self.u8()? {
+                    NIL_EXT => Ok(value),
+                    _ => error("improper lists are not supported"),
+                }
+            },
+            SMALL_TUPLE_EXT => {
+                let len = self.u8()? as usize;
+                visit_list(self, len, visitor)
+            },
+            LARGE_TUPLE_EXT => {
+                let len = self.u32()?;
+                visit_list(self, len, visitor)
+            },
+            MAP_EXT => {
+                let len = self.u32()?;
+                let mut map = MapAccessor {
+                    de: &mut *self,
+                    remaining: len,
+                };
+                let value = visitor.visit_map(&mut map)?;
+
+                while map.next_key::<IgnoredAny>()?.is_some() {
+                    map.next_value::<IgnoredAny>()?;
+                }
+
+                Ok(value)
+            },
+            tag => Err(EtfError(format!("unsupported term tag {}", tag))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.peek_nil() {
+            self.deserialize_any(IgnoredAny)?;
+
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.input.first() {
+            // A variant with content is a map with a single entry.
+            Some(&MAP_EXT) => {
+                self.u8()?;
+
+                match self.u32()? {
+                    1 => visitor.visit_enum(self),
+                    _ => error("expected a map with a single entry for an enum"),
+                }
+            },
+            _ => {
+                let variant: &str = de::Deserialize::deserialize(&mut *self)?;
+
+                visitor.visit_enum(variant.into_deserializer())
+            },
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Visits the `len` elements of a list or tuple, skipping those the visitor
+/// did not consume.
+fn visit_list<'de, V: Visitor<'de>>(
+    de: &mut Deserializer<'de>,
+    len: usize,
+    visitor: V,
+) -> Result<V::Value> {
+    let mut list = ListAccess {
+        de,
+        remaining: len,
+    };
+    let value = visitor.visit_seq(&mut list)?;
+
+    while list.next_element::<IgnoredAny>()?.is_some() {}
+
+    Ok(value)
+}
+
+struct ListAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de, 'a> SeqAccess<'de> for ListAccess<'a, 'de> {
+    type Error = EtfError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct MapAccessor<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de, 'a> MapAccess<'de> for MapAccessor<'a, 'de> {
+    type Error = EtfError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, 'a> EnumAccess<'de> for &'a mut Deserializer<'de> {
+    type Error = EtfError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let variant = seed.deserialize(&mut *self)?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a> VariantAccess<'de> for &'a mut Deserializer<'de> {
+    type Error = EtfError;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use super::{event_from_slice, from_slice, to_vec};
+    use crate::model::event::{Event, GatewayEvent};
+
+    /// Decodes an ETF fixture and its JSON equivalent into events.
+    fn events(etf: &[u8], json: &str) -> (GatewayEvent, GatewayEvent) {
+        // Events can also be deserialized through a `Value`, as done when a
+        // raw handler needs the payload.
+        let value: Value = from_slice(etf).unwrap();
+        GatewayEvent::deserialize(value).unwrap();
+        from_slice::<GatewayEvent>(etf).unwrap();
+
+        let from_etf = event_from_slice(etf).unwrap();
+        let from_json = serde_json::from_str(json).unwrap();
+
+        (from_etf, from_json)
+    }
+
+    #[test]
+    fn test_hello() {
+        let (etf, json) =
+            events(include_bytes!("fixtures/hello.etf"), include_str!("fixtures/hello.json"));
+
+        assert!(matches!(etf, GatewayEvent::Hello(41250)));
+        assert!(matches!(json, GatewayEvent::Hello(41250)));
+    }
+
+    #[test]
+    fn test_message_create() {
+        let (etf, json) = events(
+            include_bytes!("fixtures/message_create.etf"),
+            include_str!("fixtures/message_create.json"),
+        );
+
+        match (etf, json) {
+            (
+                GatewayEvent::Dispatch(3, Event::MessageCreate(etf)),
+                GatewayEvent::Dispatch(3, Event::MessageCreate(json)),
+            ) => {
+                assert_eq!(etf.message.content, "Supa Hot — café");
+                assert_eq!(etf.message.mention_roles, json.message.mention_roles);
+                assert_eq!(
+                    serde_json::to_value(&etf.message).unwrap(),
+                    serde_json::to_value(&json.message).unwrap()
+                );
+            },
+            events => panic!("unexpected events: {:?}", events),
+        }
+    }
+
+    #[test]
+    fn test_guild_create() {
+        let (etf, json) = events(
+            include_bytes!("fixtures/guild_create.etf"),
+            include_str!("fixtures/guild_create.json"),
+        );
+
+        match (etf, json) {
+            (
+                GatewayEvent::Dispatch(2, Event::GuildCreate(etf)),
+                GatewayEvent::Dispatch(2, Event::GuildCreate(json)),
+            ) => {
+                // The guild's id, an integer in ETF, is given to its members.
+                let member = etf.guild.members.values().next().unwrap();
+                assert_eq!(member.guild_id, etf.guild.id);
+                assert_eq!(
+                    serde_json::to_value(&etf.guild).unwrap(),
+                    serde_json::to_value(&json.guild).unwrap()
+                );
+            },
+            events => panic!("unexpected events: {:?}", events),
+        }
+    }
+
+    #[test]
+    fn test_data_before_type() {
+        // The data is only deserialized once the event type is known.
+        let payload = json!({"d": {"_trace": ["gateway"]}, "op": 0, "s": 5, "t": "RESUMED"});
+        let event = event_from_slice(&to_vec(&payload)).unwrap();
+        assert!(matches!(event, GatewayEvent::Dispatch(5, Event::Resumed(_))));
+
+        let payload = json!({"d": 41250, "op": 11, "s": null, "t": null, "x": [1, 2]});
+        let event = event_from_slice(&to_vec(&payload)).unwrap();
+        assert!(matches!(event, GatewayEvent::HeartbeatAck));
+
+        assert!(event_from_slice(&to_vec(&json!({"d": {}, "op": 0, "s": 5}))).is_err());
+        assert!(event_from_slice(&to_vec(&json!({"d": {}, "s": 5, "t": "RESUMED"}))).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_sent_payloads() {
+        let payloads = [
+            json!({"op": 1, "d": 251}),
+            json!({"op": 1, "d": null}),
+            json!({
+                "op": 2,
+                "d": {
+                    "compress": true,
+                    "large_threshold": 250,
+                    "shard": [0, 1],
+                    "token": "token",
+                    "intents": 32509,
+                    "v": 9,
+                    "properties": {"$browser": "serenity", "$device": "serenity", "$os": "linux"},
+                },
+            }),
+            json!({
+                "op": 3,
+                "d": {
+                    "afk": false,
+                    "since": 1_628_000_000_000_u64,
+                    "status": "online",
+                    "activities": [{"name": "ETF", "type": 0}],
+                },
+            }),
+            json!({"op": 6, "d": {"session_id": "abc", "seq": 70_000, "token": "token"}}),
+            json!({"op": 0, "d": [-1, -70_000, i64::MIN, u64::MAX, 0.5, []]}),
+        ];
+
+        for payload in &payloads {
+            assert_eq!(&from_slice::<Value>(&to_vec(payload)).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_encode_heartbeat() {
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            131, 116, 0, 0, 0, 2,
+            109, 0, 0, 0, 1, b'd', 97, 251,
+            109, 0, 0, 0, 2, b'o', b'p', 97, 1,
+        ];
+
+        assert_eq!(to_vec(&json!({"d": 251, "op": 1})), expected);
+    }
+
+    #[test]
+    fn test_decode_terms() {
+        // A list of bytes, encoded as a string.
+        assert_eq!(from_slice::<Value>(&[131, 107, 0, 2, 0, 1]).unwrap(), json!([0, 1]));
+        // A negative big integer.
+        assert_eq!(from_slice::<Value>(&[131, 110, 1, 1, 5]).unwrap(), json!(-5));
+        // A latin-1 atom.
+        assert_eq!(from_slice::<Value>(&[131, 100, 0, 2, b'o', b'k']).unwrap(), json!("ok"));
+        // A tuple.
+        assert_eq!(from_slice::<Value>(&[131, 104, 2, 97, 1, 106]).unwrap(), json!([1, []]));
+        assert_eq!(from_slice::<Option<u8>>(&[131, 119, 3, b'n', b'i', b'l']).unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        // Missing version.
+        assert!(from_slice::<Value>(&[97, 1]).is_err());
+        // Truncated binary.
+        assert!(from_slice::<Value>(&[131, 109, 0, 0, 0, 5, b'a']).is_err());
+        // Trailing bytes.
+        assert!(from_slice::<Value>(&[131, 97, 1, 97, 2]).is_err());
+        // Integers larger than 64 bits.
+        assert!(from_slice::<Value>(&[131, 110, 9, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1]).is_err());
+        // Improper lists.
+        assert!(from_slice::<Value>(&[131, 108, 0, 0, 0, 1, 97, 1, 97, 2]).is_err());
+    }
+}
@@ -10,6 +10,12 @@ macro_rules! cdn {
     };
 }
 
+/// Prepends the default API URL to a path, for the URLs that must be known at
+/// compile time. This must be kept in sync with [`constants::API_BASE_URL`]
+/// and [`constants::API_VERSION`], which are used for everything else.
+///
+/// [`constants::API_BASE_URL`]: crate::constants::API_BASE_URL
+/// [`constants::API_VERSION`]: crate::constants::API_VERSION
 #[cfg(feature = "http")]
 macro_rules! api {
     ($e:expr) => {
@@ -3,5 +3,8 @@ pub mod macros;
 
 pub mod prelude;
 
+#[cfg(feature = "etf")]
+pub(crate) mod etf;
+
 #[cfg(feature = "gateway")]
 pub mod ws_impl;
@@ -1,3 +1,5 @@
+#[cfg(feature = "etf")]
+use std::io::Read;
 #[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
 use std::{
     error::Error as StdError,
@@ -7,84 +9,159 @@ use std::{
 
 use async_trait::async_trait;
 use async_tungstenite::tungstenite::Message;
-use flate2::read::ZlibDecoder;
+use flate2::{read::ZlibDecoder, Decompress, FlushDecompress};
 use futures::stream::SplitSink;
-use futures::{SinkExt, StreamExt, TryStreamExt};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
 use tokio::time::timeout;
 use tracing::{instrument, warn};
 use url::Url;
 
 use crate::gateway::{GatewayError, WsStream};
+use crate::model::event::GatewayEvent;
+#[cfg(feature = "etf")]
+use crate::internal::etf;
 use crate::internal::prelude::*;
 
-#[async_trait]
-pub trait ReceiverExt {
-    async fn recv_json(&mut self) -> Result<Option<Value>>;
-    async fn try_recv_json(&mut self) -> Result<Option<Value>>;
-}
-
 #[async_trait]
 pub trait SenderExt {
     async fn send_json(&mut self, value: &Value) -> Result<()>;
 }
 
 #[async_trait]
-impl ReceiverExt for WsStream {
-    async fn recv_json(&mut self) -> Result<Option<Value>> {
-        const TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_millis(500);
-
-        let ws_message = match timeout(TIMEOUT, self.next()).await {
-            Ok(Some(Ok(v))) => Some(v),
-            Ok(Some(Err(e))) => return Err(e.into()),
-            Ok(None) | Err(_) => None,
-        };
-
-        convert_ws_message(ws_message)
-    }
+impl SenderExt for SplitSink<WsStream, Message> {
+    async fn send_json(&mut self, value: &Value) -> Result<()> {
+        let message = encode_payload(value)?;
 
-    async fn try_recv_json(&mut self) -> Result<Option<Value>> {
-        convert_ws_message(self.try_next().await.ok().flatten())
+        Ok(self.send(message).await?)
     }
 }
 
 #[async_trait]
-impl SenderExt for SplitSink<WsStream, Message> {
+impl SenderExt for WsStream {
     async fn send_json(&mut self, value: &Value) -> Result<()> {
-        Ok(serde_json::to_string(value)
-            .map(Message::Text)
-            .map_err(Error::from)
-            .map(|m| self.send(m))?
-            .await?)
+        let message = encode_payload(value)?;
+
+        Ok(self.send(message).await?)
     }
 }
 
-#[async_trait]
-impl SenderExt for WsStream {
-    async fn send_json(&mut self, value: &Value) -> Result<()> {
-        Ok(serde_json::to_string(value)
-            .map(Message::Text)
-            .map_err(Error::from)
-            .map(|m| self.send(m))?
-            .await?)
+/// Receives the next message, waiting for at most half a second.
+pub(crate) async fn recv_ws_message(stream: &mut WsStream) -> Result<Option<Message>> {
+    const TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_millis(500);
+
+    Ok(match timeout(TIMEOUT, stream.next()).await {
+        Ok(Some(Ok(v))) => Some(v),
+        Ok(Some(Err(e))) => return Err(e.into()),
+        Ok(None) | Err(_) => None,
+    })
+}
+
+/// Encodes a payload to be sent to the gateway.
+#[cfg(not(feature = "etf"))]
+fn encode_payload(value: &Value) -> Result<Message> {
+    Ok(Message::Text(serde_json::to_string(value)?))
+}
+
+/// Encodes a payload to be sent to the gateway.
+#[cfg(feature = "etf")]
+fn encode_payload(value: &Value) -> Result<Message> {
+    Ok(Message::Binary(etf::to_vec(value)))
+}
+
+/// Decodes an uncompressed binary payload received from the gateway.
+#[cfg(not(feature = "etf"))]
+fn decode_payload(bytes: &[u8]) -> Result<Value> {
+    serde_json::from_slice(bytes).map_err(|why| {
+        warn!("Err deserializing bytes: {:?}; bytes: {:?}", why, bytes);
+
+        why.into()
+    })
+}
+
+/// Decodes an uncompressed binary payload received from the gateway.
+#[cfg(feature = "etf")]
+fn decode_payload(bytes: &[u8]) -> Result<Value> {
+    etf::from_slice(bytes).map_err(|why| {
+        warn!("Err decoding ETF: {:?}; bytes: {:?}", why, bytes);
+
+        Error::Gateway(GatewayError::InvalidEtf(why.to_string()))
+    })
+}
+
+/// A payload received from the gateway, decompressed but not decoded yet.
+pub(crate) enum Payload {
+    /// A payload already decoded, as JSON payloads are.
+    Value(Value),
+    /// An ETF payload, kept as is so that events can be deserialized directly.
+    #[cfg(feature = "etf")]
+    Etf(Vec<u8>),
+}
+
+impl Payload {
+    /// Decodes the payload into a [`Value`].
+    pub(crate) fn into_value(self) -> Result<Value> {
+        match self {
+            Payload::Value(value) => Ok(value),
+            #[cfg(feature = "etf")]
+            Payload::Etf(bytes) => decode_payload(&bytes),
+        }
+    }
+
+    /// Deserializes the event of the payload.
+    pub(crate) fn into_event(self) -> Result<GatewayEvent> {
+        match self {
+            Payload::Value(value) => Ok(GatewayEvent::deserialize(value)?),
+            #[cfg(feature = "etf")]
+            Payload::Etf(bytes) => decode_event(&bytes),
+        }
     }
 }
 
+/// Deserializes the event of an uncompressed ETF payload.
+#[cfg(feature = "etf")]
+fn decode_event(bytes: &[u8]) -> Result<GatewayEvent> {
+    etf::event_from_slice(bytes).map_err(|why| {
+        warn!("Err deserializing ETF event: {:?}; bytes: {:?}", why, bytes);
+
+        Error::Gateway(GatewayError::InvalidEtf(why.to_string()))
+    })
+}
+
 #[inline]
-pub(crate) fn convert_ws_message(message: Option<Message>) -> Result<Option<Value>> {
+pub(crate) fn convert_ws_message(message: Option<Message>) -> Result<Option<Payload>> {
     Ok(match message {
+        // Payloads are only compressed one by one if requested when
+        // identifying.
+        #[cfg(feature = "etf")]
+        Some(Message::Binary(bytes)) if bytes.first() == Some(&etf::VERSION) => {
+            Some(Payload::Etf(bytes))
+        },
+        #[cfg(feature = "etf")]
+        Some(Message::Binary(bytes)) => {
+            let mut payload = Vec::new();
+            ZlibDecoder::new(&bytes[..]).read_to_end(&mut payload)?;
+
+            Some(Payload::Etf(payload))
+        },
+        #[cfg(not(feature = "etf"))]
         Some(Message::Binary(bytes)) => {
-            serde_json::from_reader(ZlibDecoder::new(&bytes[..])).map(Some).map_err(|why| {
+            let value = serde_json::from_reader(ZlibDecoder::new(&bytes[..])).map_err(|why| {
                 warn!("Err deserializing bytes: {:?}; bytes: {:?}", why, bytes);
 
                 why
-            })?
+            })?;
+
+            Some(Payload::Value(value))
         },
         Some(Message::Text(payload)) => {
-            serde_json::from_str(&payload).map(Some).map_err(|why| {
+            let value = serde_json::from_str(&payload).map_err(|why| {
                 warn!("Err deserializing text: {:?}; text: {}", why, payload,);
 
                 why
-            })?
+            })?;
+
+            Some(Payload::Value(value))
         },
         Some(Message::Close(Some(frame))) => {
             return Err(Error::Gateway(GatewayError::Closed(Some(frame))));
@@ -94,6 +171,116 @@ pub(crate) fn convert_ws_message(message: Option<Message>) -> Result<Option<Valu
     })
 }
 
+/// The suffix of the last frame of a payload sent with `zlib-stream`
+/// compression.
+const ZLIB_SUFFIX: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Decompresses the payloads of a connection using `zlib-stream` compression.
+///
+/// The compression context is shared by all the payloads of a connection, so a
+/// new inflater must be used for every new connection.
+pub(crate) struct ZlibStreamInflater {
+    decompress: Decompress,
+    compressed: Vec<u8>,
+    buffer: Vec<u8>,
+}
+
+impl ZlibStreamInflater {
+    pub(crate) fn new() -> Self {
+        Self {
+            decompress: Decompress::new(true),
+            compressed: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Resets the compression context, for a new connection.
+    pub(crate) fn reset(&mut self) {
+        self.decompress.reset(true);
+        self.compressed.clear();
+        self.buffer.clear();
+    }
+
+    /// Buffers a frame, returning the decompressed payload once its last frame
+    /// was received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GatewayError::DecompressionFailed`] if the stream is
+    /// corrupted. The connection can not be used anymore after this.
+    pub(crate) fn inflate(&mut self, frame: &[u8]) -> Result<Option<&[u8]>> {
+        self.compressed.extend_from_slice(frame);
+
+        if !self.compressed.ends_with(&ZLIB_SUFFIX) {
+            return Ok(None);
+        }
+
+        self.buffer.clear();
+        let start = self.decompress.total_in();
+
+        loop {
+            if self.buffer.len() == self.buffer.capacity() {
+                self.buffer.reserve(self.compressed.len().max(1024));
+            }
+
+            let consumed = (self.decompress.total_in() - start) as usize;
+            let written = self.buffer.len();
+
+            self.decompress
+                .decompress_vec(
+                    &self.compressed[consumed..],
+                    &mut self.buffer,
+                    FlushDecompress::Sync,
+                )
+                .map_err(|why| {
+                    warn!("Err decompressing zlib-stream payload: {:?}", why);
+
+                    Error::Gateway(GatewayError::DecompressionFailed)
+                })?;
+
+            let progressed = (self.decompress.total_in() - start) as usize != consumed
+                || self.buffer.len() != written;
+            let consumed = (self.decompress.total_in() - start) as usize;
+
+            // More output may be pending as long as the buffer was filled.
+            if consumed == self.compressed.len() && self.buffer.len() < self.buffer.capacity() {
+                break;
+            }
+
+            if !progressed {
+                warn!("Err decompressing zlib-stream payload: no progress");
+
+                return Err(Error::Gateway(GatewayError::DecompressionFailed));
+            }
+        }
+
+        self.compressed.clear();
+
+        Ok(Some(&self.buffer))
+    }
+
+    /// Converts a message received over a connection using `zlib-stream`
+    /// compression, returning `None` until a whole payload was received.
+    pub(crate) fn convert(&mut self, message: Option<Message>) -> Result<Option<Payload>> {
+        match message {
+            Some(Message::Binary(bytes)) => match self.inflate(&bytes)? {
+                #[cfg(feature = "etf")]
+                Some(payload) => Ok(Some(Payload::Etf(payload.to_vec()))),
+                #[cfg(not(feature = "etf"))]
+                Some(payload) => decode_payload(payload).map(Payload::Value).map(Some),
+                None => Ok(None),
+            },
+            message => convert_ws_message(message),
+        }
+    }
+}
+
+impl Default for ZlibStreamInflater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// An error that occured while connecting over rustls
 #[derive(Debug)]
 #[non_exhaustive]
@@ -169,3 +356,98 @@ pub(crate) async fn create_native_tls_client(url: Url) -> Result<WsStream> {
 
     Ok(stream)
 }
+
+// The payloads are JSON, which the inflater only decodes without `etf`.
+#[cfg(all(test, not(feature = "etf")))]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use async_tungstenite::tungstenite::Message;
+    use flate2::{Compress, Compression, FlushCompress};
+    use serde_json::json;
+
+    use super::{Payload, ZlibStreamInflater, ZLIB_SUFFIX};
+    use crate::gateway::GatewayError;
+    use crate::internal::prelude::*;
+
+    /// Converts a message, decoding the payload if one was received.
+    fn convert(inflater: &mut ZlibStreamInflater, message: Message) -> Result<Option<Value>> {
+        inflater.convert(Some(message))?.map(Payload::into_value).transpose()
+    }
+
+    /// Compresses a payload as the next one of a `zlib-stream` connection.
+    fn compress(compress: &mut Compress, payload: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(payload.len() + 64);
+        // The totals of the context count all payloads of the connection.
+        let start = compress.total_in();
+
+        loop {
+            let consumed = (compress.total_in() - start) as usize;
+            compress.compress_vec(&payload[consumed..], &mut out, FlushCompress::Sync).unwrap();
+
+            if out.ends_with(&ZLIB_SUFFIX) && out.len() < out.capacity() {
+                return out;
+            }
+
+            out.reserve(64);
+        }
+    }
+
+    #[test]
+    fn test_inflate_zlib_stream() {
+        let mut compressor = Compress::new(Compression::default(), true);
+        let mut inflater = ZlibStreamInflater::new();
+
+        // The compression context is kept between payloads.
+        for seq in 0..3 {
+            let payload = json!({"op": 0, "s": seq, "t": "READY", "d": {}});
+            let frame = compress(&mut compressor, payload.to_string().as_bytes());
+
+            let value = convert(&mut inflater, Message::Binary(frame)).unwrap();
+            assert_eq!(value, Some(payload));
+        }
+    }
+
+    #[test]
+    fn test_inflate_zlib_stream_split_payload() {
+        let mut compressor = Compress::new(Compression::default(), true);
+        let mut inflater = ZlibStreamInflater::new();
+
+        // Large enough to be decompressed in multiple steps.
+        let names = (0..5_000).map(|i| format!("channel-{}", i)).collect::<Vec<_>>();
+        let payload = json!({"op": 0, "s": 1, "t": "GUILD_CREATE", "d": {"names": names}});
+        let frame = compress(&mut compressor, payload.to_string().as_bytes());
+
+        let (first, rest) = frame.split_at(frame.len() / 2);
+        let (second, third) = rest.split_at(rest.len() - 2);
+
+        assert_eq!(convert(&mut inflater, Message::Binary(first.to_vec())).unwrap(), None);
+        assert_eq!(convert(&mut inflater, Message::Binary(second.to_vec())).unwrap(), None);
+
+        let value = convert(&mut inflater, Message::Binary(third.to_vec())).unwrap();
+        assert_eq!(value, Some(payload));
+
+        // Text payloads are still accepted.
+        let text = Message::Text(r#"{"op":11}"#.to_string());
+        assert_eq!(convert(&mut inflater, text).unwrap(), Some(json!({"op": 11})));
+    }
+
+    #[test]
+    fn test_inflate_corrupted_zlib_stream() {
+        let mut compressor = Compress::new(Compression::default(), true);
+        let mut inflater = ZlibStreamInflater::new();
+
+        let mut frame = compress(&mut compressor, br#"{"op":10,"d":{}}"#);
+        frame[0] = 0;
+
+        let result = convert(&mut inflater, Message::Binary(frame));
+        assert!(matches!(result, Err(Error::Gateway(GatewayError::DecompressionFailed))));
+
+        // A new connection starts over with a new context.
+        inflater.reset();
+        let mut compressor = Compress::new(Compression::default(), true);
+        let frame = compress(&mut compressor, br#"{"op":11}"#);
+
+        let value = convert(&mut inflater, Message::Binary(frame)).unwrap();
+        assert_eq!(value, Some(json!({"op": 11})));
+    }
+}
@@ -125,6 +125,18 @@ pub struct CacheAndHttp {
     pub cache: Arc<Cache>,
     #[cfg(feature = "cache")]
     pub update_cache_timeout: Option<Duration>,
+    /// How long to wait for the guilds of a shard to arrive before
+    /// dispatching [`EventHandler::cache_ready`] anyway.
+    ///
+    /// [`EventHandler::cache_ready`]: crate::client::EventHandler::cache_ready
+    #[cfg(feature = "cache")]
+    pub cache_ready_max_wait: Option<Duration>,
+    #[cfg(all(feature = "cache", feature = "gateway"))]
+    pub(crate) cache_ready: Arc<crate::client::CacheReadyTracker>,
+    #[cfg(feature = "gateway")]
+    pub(crate) dispatches: Arc<crate::client::DispatchTracker>,
+    #[cfg(feature = "gateway")]
+    pub(crate) shards_ready: Arc<crate::client::ShardsReadyTracker>,
     pub http: Arc<Http>,
 }
 
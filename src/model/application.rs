@@ -130,6 +130,10 @@ pub struct CurrentApplicationInfo {
     pub bot_public: bool,
     pub bot_require_code_grant: bool,
     pub team: Option<Team>,
+    /// A set of bitflags assigned to the application, which represent gated
+    /// feature flags that have been enabled for the application.
+    #[serde(default)]
+    pub flags: Option<u64>,
 }
 
 /// Information about the Team group of the application.
@@ -1,8 +1,18 @@
 #[cfg(feature = "model")]
+use bytes::Bytes;
+#[cfg(feature = "model")]
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+#[cfg(feature = "model")]
 use reqwest::Client as ReqwestClient;
+#[cfg(feature = "model")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
+#[cfg(feature = "model")]
+use crate::http::Http;
 #[cfg(feature = "model")]
 use crate::internal::prelude::*;
+#[cfg(feature = "model")]
+use crate::model::ModelError;
 use crate::model::id::AttachmentId;
 
 /// A file uploaded with a message. Not to be confused with [`Embed`]s.
@@ -42,6 +52,9 @@ impl Attachment {
 
     /// Downloads the attachment, returning back a vector of bytes.
     ///
+    /// This buffers the whole file in memory. For large attachments, prefer
+    /// [`Self::download_to`] or [`Self::download_stream`].
+    ///
     /// # Examples
     ///
     /// Download all of the attachments associated with a [`Message`]:
@@ -112,12 +125,263 @@ impl Attachment {
     /// Returns an [`Error::Http`] when there is a problem retrieving the
     /// attachment.
     ///
+    /// Returns a [`ModelError::AttachmentSizeMismatch`] if the size of the
+    /// downloaded file does not match [`Self::size`].
+    ///
     /// [`Error::Http`]: crate::Error::Http
     /// [`Error::Io`]: crate::Error::Io
     /// [`Message`]: super::Message
     pub async fn download(&self) -> Result<Vec<u8>> {
         let reqwest = ReqwestClient::new();
+        let buf = Vec::with_capacity(self.size as usize);
+
+        self._download_stream(&reqwest, None)
+            .await?
+            .try_fold(buf, |mut buf, chunk| async move {
+                buf.extend_from_slice(&chunk);
+
+                Ok(buf)
+            })
+            .await
+    }
+
+    /// Downloads the attachment into the `writer` as it is received, without
+    /// buffering the whole file in memory. Returns the number of bytes
+    /// written.
+    ///
+    /// If `max_size` is given, the download is aborted as soon as the
+    /// attachment turns out to be larger than it.
+    ///
+    /// # Examples
+    ///
+    /// Save an attachment to a file:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use serenity::model::channel::Attachment;
+    /// #
+    /// # async fn run(http: Http, attachment: Attachment) -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = tokio::fs::File::create(&attachment.filename).await?;
+    ///
+    /// attachment.download_to(&http, file, Some(50 * 1024 * 1024)).await?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::download_stream`], as well as an
+    /// [`Error::Io`] if writing to the `writer` failed.
+    ///
+    /// [`Error::Io`]: crate::Error::Io
+    pub async fn download_to(
+        &self,
+        http: impl AsRef<Http>,
+        mut writer: impl AsyncWrite + Unpin,
+        max_size: Option<u64>,
+    ) -> Result<u64> {
+        let mut stream = Box::pin(self.download_stream(http, max_size).await?);
+        let mut written = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+
+        writer.flush().await?;
+
+        Ok(written)
+    }
+
+    /// Starts downloading the attachment, returning a stream of the chunks of
+    /// the file as they are received.
+    ///
+    /// The received data is checked against [`Self::size`]; if the file turns
+    /// out to be larger or smaller, the stream ends with an error. If
+    /// `max_size` is given, the stream also ends with an error as soon as more
+    /// than `max_size` bytes were received.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::AttachmentTooLarge`] if the attachment is known
+    /// to be larger than `max_size` before downloading it, and a
+    /// [`ModelError::AttachmentSizeMismatch`] if the response's
+    /// `Content-Length` does not match [`Self::size`].
+    ///
+    /// Returns an [`Error::Http`] when there is a problem retrieving the
+    /// attachment.
+    ///
+    /// The stream itself yields the same errors when they are only detected
+    /// while downloading.
+    ///
+    /// [`Error::Http`]: crate::Error::Http
+    pub async fn download_stream(
+        &self,
+        http: impl AsRef<Http>,
+        max_size: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        self._download_stream(&http.as_ref().client, max_size).await
+    }
+
+    async fn _download_stream(
+        &self,
+        client: &ReqwestClient,
+        max_size: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        let expected = self.size;
+
+        if let Some(max_size) = max_size {
+            if expected > max_size {
+                return Err(Error::Model(ModelError::AttachmentTooLarge(max_size)));
+            }
+        }
+
+        let response = client.get(&self.url).send().await?.error_for_status()?;
+
+        if let Some(actual) = response.content_length() {
+            if actual != expected {
+                return Err(Error::Model(ModelError::AttachmentSizeMismatch {
+                    expected,
+                    actual,
+                }));
+            }
+        }
+
+        let body = Box::pin(response.bytes_stream());
+
+        Ok(stream::unfold(Some((body, 0)), move |state| async move {
+            let (mut body, received) = state?;
+
+            let chunk = match body.next().await {
+                Some(Ok(chunk)) => chunk,
+                Some(Err(why)) => return Some((Err(why.into()), None)),
+                None if received == expected => return None,
+                None => {
+                    let error = ModelError::AttachmentSizeMismatch {
+                        expected,
+                        actual: received,
+                    };
+
+                    return Some((Err(Error::Model(error)), None));
+                },
+            };
+
+            let received = received + chunk.len() as u64;
+
+            if let Some(max_size) = max_size {
+                if received > max_size {
+                    return Some((Err(Error::Model(ModelError::AttachmentTooLarge(max_size))), None));
+                }
+            }
+
+            if received > expected {
+                let error = ModelError::AttachmentSizeMismatch {
+                    expected,
+                    actual: received,
+                };
+
+                return Some((Err(Error::Model(error)), None));
+            }
+
+            Some((Ok(chunk), Some((body, received))))
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "model"))]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use futures::stream::StreamExt;
+
+    use super::Attachment;
+    use crate::http::mock::{response, MockServer};
+    use crate::model::id::AttachmentId;
+    use crate::model::ModelError;
+    use crate::Error;
+
+    fn attachment(server: &MockServer, size: u64) -> Attachment {
+        Attachment {
+            id: AttachmentId::from(1),
+            filename: "file.txt".to_string(),
+            height: None,
+            proxy_url: String::new(),
+            size,
+            url: format!("{}/attachments/1/1/file.txt", server.addr()),
+            width: None,
+            content_type: None,
+        }
+    }
+
+    /// A response without a `Content-Length`, so that the body is read until
+    /// the connection closes.
+    fn unsized_response(body: &str) -> String {
+        format!("HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{}", body)
+    }
+
+    #[tokio::test]
+    async fn test_download_to() {
+        let server = MockServer::new(vec![response("200 OK", &[], "0123456789")]);
+        let http = server.http().await;
+        let mut buf = Vec::new();
+
+        let written = attachment(&server, 10).download_to(&http, &mut buf, None).await.unwrap();
+
+        assert_eq!(written, 10);
+        assert_eq!(buf, b"0123456789");
+    }
+
+    #[tokio::test]
+    async fn test_download_content_length_mismatch() {
+        let server = MockServer::new(vec![response("200 OK", &[], "0123456789")]);
+        let http = server.http().await;
+
+        match attachment(&server, 4).download_stream(&http, None).await {
+            Err(Error::Model(ModelError::AttachmentSizeMismatch {
+                expected: 4,
+                actual: 10,
+            })) => {},
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        };
+    }
+
+    #[tokio::test]
+    async fn test_download_streamed_size_mismatch() {
+        let server = MockServer::new(vec![unsized_response("0123456789")]);
+        let http = server.http().await;
+
+        let stream = attachment(&server, 4).download_stream(&http, None).await.unwrap();
+        let results = stream.collect::<Vec<_>>().await;
+
+        assert!(matches!(
+            results.last(),
+            Some(Err(Error::Model(ModelError::AttachmentSizeMismatch {
+                expected: 4,
+                ..
+            })))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_aborts_over_max_size() {
+        let server = MockServer::new(vec![unsized_response("0123456789")]);
+        let http = server.http().await;
+        let mut buf = Vec::new();
+
+        let result = attachment(&server, 4).download_to(&http, &mut buf, Some(6)).await;
+
+        assert!(matches!(result, Err(Error::Model(ModelError::AttachmentTooLarge(6)))));
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_download_rejects_known_size_over_max_size() {
+        let server = MockServer::new(vec![]);
+        let http = server.http().await;
+
+        let result = attachment(&server, 100).download_stream(&http, Some(10)).await;
 
-        Ok(reqwest.get(&self.url).send().await?.bytes().await?.into_iter().collect::<Vec<u8>>())
+        assert!(matches!(result, Err(Error::Model(ModelError::AttachmentTooLarge(10)))));
     }
 }
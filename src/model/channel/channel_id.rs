@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 #[cfg(feature = "model")]
 use bytes::buf::Buf;
+#[cfg(feature = "model")]
+use chrono::{DateTime, Utc};
 use futures::stream::Stream;
 #[cfg(feature = "model")]
 use reqwest::Url;
@@ -524,6 +526,15 @@ impl ChannelId {
         MessagesIter::<H>::stream(http, self)
     }
 
+    /// Returns the cached messages of the channel, ordered by their Ids.
+    ///
+    /// Refer to [`Cache::channel_messages`] for more information.
+    #[cfg(feature = "cache")]
+    #[inline]
+    pub async fn messages_cached(self, cache: impl AsRef<Cache>) -> Option<Vec<Message>> {
+        cache.as_ref().channel_messages(self).await
+    }
+
     /// Returns the name of whatever channel this id holds.
     #[cfg(feature = "cache")]
     pub async fn name(self, cache: impl AsRef<Cache>) -> Option<String> {
@@ -1095,7 +1106,11 @@ impl ChannelId {
         http.as_ref().get_channel_active_threads(self.0).await
     }
 
-    /// Gets private archived threads of a channel.
+    /// Gets private archived threads of a channel, most recently archived
+    /// first.
+    ///
+    /// The `before` cursor is the [`ThreadMetadata::archive_timestamp`] of
+    /// the last thread of a previous page, see [`Self::archived_threads_iter`].
     ///
     /// # Errors
     ///
@@ -1104,13 +1119,17 @@ impl ChannelId {
     pub async fn get_archived_private_threads(
         &self,
         http: impl AsRef<Http>,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     ) -> Result<ThreadsData> {
         http.as_ref().get_channel_archived_private_threads(self.0, before, limit).await
     }
 
-    /// Gets public archived threads of a channel.
+    /// Gets public archived threads of a channel, most recently archived
+    /// first.
+    ///
+    /// The `before` cursor is the [`ThreadMetadata::archive_timestamp`] of
+    /// the last thread of a previous page, see [`Self::archived_threads_iter`].
     ///
     /// # Errors
     ///
@@ -1119,7 +1138,7 @@ impl ChannelId {
     pub async fn get_archived_public_threads(
         &self,
         http: impl AsRef<Http>,
-        before: Option<u64>,
+        before: Option<DateTime<Utc>>,
         limit: Option<u64>,
     ) -> Result<ThreadsData> {
         http.as_ref().get_channel_archived_public_threads(self.0, before, limit).await
@@ -1139,6 +1158,110 @@ impl ChannelId {
     ) -> Result<ThreadsData> {
         http.as_ref().get_channel_joined_archived_private_threads(self.0, before, limit).await
     }
+
+    /// Streams over all archived threads of a channel, most recently archived
+    /// first, either the public or the `private` ones.
+    ///
+    /// This is accomplished and equivalent to repeated calls to
+    /// [`Self::get_archived_public_threads`] or
+    /// [`Self::get_archived_private_threads`], following the `before` cursor
+    /// for as long as there are more threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::model::id::ChannelId;
+    /// # use serenity::http::Http;
+    /// #
+    /// # async fn run() {
+    /// # let channel_id = ChannelId::default();
+    /// # let http = Http::default();
+    /// use serenity::futures::StreamExt;
+    ///
+    /// let mut threads = channel_id.archived_threads_iter(&http, false).boxed();
+    /// while let Some(thread_result) = threads.next().await {
+    ///     match thread_result {
+    ///         Ok(thread) => println!("{} was archived", thread.name),
+    ///         Err(error) => eprintln!("Uh oh! Error: {}", error),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn archived_threads_iter<H: AsRef<Http>>(
+        self,
+        http: H,
+        private: bool,
+    ) -> impl Stream<Item = Result<GuildChannel>> {
+        ArchivedThreadsIter::<H>::stream(http, self, private)
+    }
+
+    /// Gets the users that voted for an answer of the poll of a [`Message`].
+    ///
+    /// The `answer_id` is the poll's own id of the answer, starting at `1`.
+    ///
+    /// The optional `after` attribute is to retrieve the users after a certain
+    /// user. This is useful for pagination. Refer to
+    /// [`Self::poll_answer_voters_iter`] to go through all voters instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the message does not exist or has no poll.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    pub async fn poll_answer_voters(
+        self,
+        http: impl AsRef<Http>,
+        message_id: impl Into<MessageId>,
+        answer_id: u8,
+        after: impl Into<Option<UserId>>,
+        limit: Option<u8>,
+    ) -> Result<Vec<User>> {
+        http.as_ref()
+            .get_poll_answer_voters(
+                self.0,
+                message_id.into().0,
+                answer_id,
+                after.into().map(|after| after.0),
+                limit,
+            )
+            .await
+    }
+
+    /// Streams over all the users that voted for an answer of the poll of a
+    /// [`Message`].
+    ///
+    /// This is accomplished and equivalent to repeated calls to
+    /// [`Self::poll_answer_voters`], using the last user of every page as the
+    /// `after` cursor of the next one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::model::id::{ChannelId, MessageId};
+    /// # use serenity::http::Http;
+    /// #
+    /// # async fn run() {
+    /// # let channel_id = ChannelId::default();
+    /// # let http = Http::default();
+    /// use serenity::futures::StreamExt;
+    ///
+    /// let mut voters = channel_id.poll_answer_voters_iter(&http, MessageId(1), 1).boxed();
+    /// while let Some(voter_result) = voters.next().await {
+    ///     match voter_result {
+    ///         Ok(user) => println!("{} voted for the first answer", user.name),
+    ///         Err(error) => eprintln!("Uh oh! Error: {}", error),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn poll_answer_voters_iter<H: AsRef<Http>>(
+        self,
+        http: H,
+        message_id: impl Into<MessageId>,
+        answer_id: u8,
+    ) -> impl Stream<Item = Result<User>> {
+        PollAnswerVotersIter::<H>::stream(http, self, message_id.into(), answer_id)
+    }
 }
 
 impl From<Channel> for ChannelId {
@@ -1299,3 +1422,291 @@ impl<H: AsRef<Http>> MessagesIter<H> {
         })
     }
 }
+
+/// A helper class returned by [`ChannelId::archived_threads_iter`]
+#[derive(Clone, Debug)]
+#[cfg(feature = "model")]
+pub struct ArchivedThreadsIter<H: AsRef<Http>> {
+    http: H,
+    channel_id: ChannelId,
+    private: bool,
+    buffer: Vec<GuildChannel>,
+    before: Option<DateTime<Utc>>,
+    has_more: bool,
+}
+
+#[cfg(feature = "model")]
+impl<H: AsRef<Http>> ArchivedThreadsIter<H> {
+    fn new(http: H, channel_id: ChannelId, private: bool) -> ArchivedThreadsIter<H> {
+        ArchivedThreadsIter {
+            http,
+            channel_id,
+            private,
+            buffer: Vec::new(),
+            before: None,
+            has_more: true,
+        }
+    }
+
+    /// Fills the `self.buffer` cache with the next page of threads.
+    ///
+    /// This updates `self.before` to the archive timestamp of the last thread
+    /// of the page, and `self.has_more` to whether another page ought to be
+    /// fetched.
+    async fn refresh(&mut self) -> Result<()> {
+        let data = if self.private {
+            self.channel_id.get_archived_private_threads(&self.http, self.before, None).await?
+        } else {
+            self.channel_id.get_archived_public_threads(&self.http, self.before, None).await?
+        };
+
+        self.before = data
+            .threads
+            .last()
+            .and_then(|thread| thread.thread_metadata)
+            .and_then(|metadata| metadata.archive_timestamp);
+        self.has_more = data.has_more && self.before.is_some();
+
+        self.buffer = data.threads;
+        self.buffer.reverse();
+
+        Ok(())
+    }
+
+    /// Streams over all the archived threads of a channel.
+    ///
+    /// Refer to [`ChannelId::archived_threads_iter`] for more information.
+    pub fn stream(
+        http: impl AsRef<Http>,
+        channel_id: ChannelId,
+        private: bool,
+    ) -> impl Stream<Item = Result<GuildChannel>> {
+        let init_state = ArchivedThreadsIter::new(http, channel_id, private);
+
+        futures::stream::unfold(init_state, |mut state| async {
+            if state.buffer.is_empty() && state.has_more {
+                if let Err(error) = state.refresh().await {
+                    state.has_more = false;
+
+                    return Some((Err(error), state));
+                }
+            }
+
+            state.buffer.pop().map(|thread| (Ok(thread), state))
+        })
+    }
+}
+
+/// A helper class returned by [`ChannelId::poll_answer_voters_iter`]
+#[derive(Clone, Debug)]
+#[cfg(feature = "model")]
+pub struct PollAnswerVotersIter<H: AsRef<Http>> {
+    http: H,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    answer_id: u8,
+    buffer: Vec<User>,
+    after: Option<UserId>,
+    tried_fetch: bool,
+}
+
+#[cfg(feature = "model")]
+impl<H: AsRef<Http>> PollAnswerVotersIter<H> {
+    /// The amount of voters requested per page, the most Discord allows.
+    const PAGE_SIZE: u8 = 100;
+
+    fn new(
+        http: H,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        answer_id: u8,
+    ) -> PollAnswerVotersIter<H> {
+        PollAnswerVotersIter {
+            http,
+            channel_id,
+            message_id,
+            answer_id,
+            buffer: Vec::new(),
+            after: None,
+            tried_fetch: false,
+        }
+    }
+
+    /// Fills the `self.buffer` cache with the next page of voters.
+    ///
+    /// This updates `self.after` to the last voter of the page, or to [`None`]
+    /// once a page was not full, meaning there are no more voters.
+    async fn refresh(&mut self) -> Result<()> {
+        let mut voters = self
+            .channel_id
+            .poll_answer_voters(
+                &self.http,
+                self.message_id,
+                self.answer_id,
+                self.after,
+                Some(Self::PAGE_SIZE),
+            )
+            .await?;
+
+        self.after = if voters.len() < usize::from(Self::PAGE_SIZE) {
+            None
+        } else {
+            voters.last().map(|user| user.id)
+        };
+
+        voters.reverse();
+        self.buffer = voters;
+        self.tried_fetch = true;
+
+        Ok(())
+    }
+
+    /// Streams over all the voters of an answer of a poll.
+    ///
+    /// Refer to [`ChannelId::poll_answer_voters_iter`] for more information.
+    pub fn stream(
+        http: impl AsRef<Http>,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        answer_id: u8,
+    ) -> impl Stream<Item = Result<User>> {
+        let init_state = PollAnswerVotersIter::new(http, channel_id, message_id, answer_id);
+
+        futures::stream::unfold(init_state, |mut state| async {
+            if state.buffer.is_empty() && (state.after.is_some() || !state.tried_fetch) {
+                if let Err(error) = state.refresh().await {
+                    state.after = None;
+                    state.tried_fetch = true;
+
+                    return Some((Err(error), state));
+                }
+            }
+
+            state.buffer.pop().map(|user| (Ok(user), state))
+        })
+    }
+}
+
+#[cfg(all(test, feature = "model"))]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use futures::stream::StreamExt;
+    use serde_json::json;
+
+    use crate::http::mock::{response, MockServer};
+    use crate::model::id::{ChannelId, MessageId};
+
+    fn thread(id: u64, archived_at: &str) -> serde_json::Value {
+        json!({
+            "id": id.to_string(),
+            "guild_id": "1",
+            "type": 11,
+            "name": format!("thread {}", id),
+            "thread_metadata": {
+                "archived": true,
+                "archive_timestamp": archived_at,
+                "locked": false,
+            },
+        })
+    }
+
+    fn page(threads: Vec<serde_json::Value>, has_more: bool) -> String {
+        let body = json!({
+            "threads": threads,
+            "members": [],
+            "has_more": has_more,
+        });
+
+        response("200 OK", &[], &body.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_archived_threads_iter_pages() {
+        let server = MockServer::new(vec![
+            page(
+                vec![
+                    thread(3, "2021-08-03T00:00:00+00:00"),
+                    thread(2, "2021-08-02T00:00:00+00:00"),
+                ],
+                true,
+            ),
+            page(vec![thread(1, "2021-08-01T00:00:00+00:00")], false),
+        ]);
+        let http = server.http().await;
+
+        let threads = ChannelId(5)
+            .archived_threads_iter(&http, false)
+            .map(|thread| thread.unwrap().id.0)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(threads, vec![3, 2, 1]);
+        assert!(server
+            .next_request()
+            .starts_with("GET /api/v9/channels/5/threads/archived/public? "));
+        assert!(server.next_request().starts_with(
+            "GET /api/v9/channels/5/threads/archived/public?&before=2021-08-02T00:00:00.000Z "
+        ));
+    }
+
+    fn voters(ids: std::ops::RangeInclusive<u64>) -> String {
+        let users = ids
+            .map(|id| {
+                json!({
+                    "id": id.to_string(),
+                    "username": format!("user {}", id),
+                    "discriminator": "0001",
+                    "avatar": null,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        response("200 OK", &[], &json!({ "users": users }).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_poll_answer_voters_iter_pages() {
+        let server = MockServer::new(vec![voters(1..=100), voters(101..=150)]);
+        let http = server.http().await;
+
+        let users = ChannelId(5)
+            .poll_answer_voters_iter(&http, MessageId(6), 2)
+            .map(|user| user.unwrap().id.0)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(users, (1..=150).collect::<Vec<_>>());
+        assert!(server
+            .next_request()
+            .starts_with("GET /api/v9/channels/5/polls/6/answers/2?&limit=100 "));
+        assert!(server
+            .next_request()
+            .starts_with("GET /api/v9/channels/5/polls/6/answers/2?&after=100&limit=100 "));
+    }
+
+    #[tokio::test]
+    async fn test_poll_answer_voters_iter_empty() {
+        let server = MockServer::new(vec![response("200 OK", &[], r#"{"users":[]}"#)]);
+        let http = server.http().await;
+
+        let mut users = ChannelId(5).poll_answer_voters_iter(&http, MessageId(6), 1).boxed();
+
+        assert!(users.next().await.is_none());
+        assert!(server
+            .next_request()
+            .starts_with("GET /api/v9/channels/5/polls/6/answers/1?&limit=100 "));
+    }
+
+    #[tokio::test]
+    async fn test_archived_threads_iter_empty() {
+        let server = MockServer::new(vec![page(vec![], false)]);
+        let http = server.http().await;
+
+        let mut threads = ChannelId(5).archived_threads_iter(&http, true).boxed();
+
+        assert!(threads.next().await.is_none());
+        assert!(server
+            .next_request()
+            .starts_with("GET /api/v9/channels/5/threads/archived/private? "));
+    }
+}
@@ -756,7 +756,7 @@ impl GuildChannel {
     ///                 },
     ///             };
     ///
-    ///             let _ = msg.channel_id.send_files(&context.http, vec![(&file, "cat.png")], |mut m| {
+    ///             let _ = msg.channel_id.send_files(context.http(), vec![(&file, "cat.png")], |mut m| {
     ///                 m.content("here's a cat");
     ///                 m
     ///             })
@@ -1287,3 +1287,36 @@ pub struct PartialGuildChannel {
     #[serde(rename = "type")]
     pub kind: ChannelType,
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let channel: GuildChannel = assert_roundtrip(
+            r#"{
+                "id": "41771983423143937",
+                "guild_id": "41771983423143938",
+                "name": "general",
+                "type": 0,
+                "position": 6,
+                "permission_overwrites": [
+                    {"id": "41771983423143938", "type": 0, "allow": "0", "deny": "2048"}
+                ],
+                "rate_limit_per_user": 2,
+                "nsfw": true,
+                "topic": "24/7 chat about how to gank Mike #2",
+                "last_message_id": "155117677105512449",
+                "parent_id": "399942396007890945",
+                "default_auto_archive_duration": 60
+            }"#,
+        );
+
+        assert_eq!(channel.kind, ChannelType::Text);
+        assert_eq!(channel.category_id, Some(ChannelId(399942396007890945)));
+        assert_eq!(channel.slow_mode_rate, Some(2));
+        assert_eq!(channel.permission_overwrites[0].deny, Permissions::SEND_MESSAGES);
+    }
+}
@@ -10,6 +10,8 @@ use std::result::Result as StdResult;
 use bitflags::__impl_bitflags;
 use chrono::{DateTime, Utc};
 #[cfg(feature = "model")]
+use futures::stream::Stream;
+#[cfg(feature = "model")]
 use serde::{
     de::{Deserialize, Deserializer},
     ser::{Serialize, Serializer},
@@ -452,6 +454,20 @@ impl Message {
         self.channel_id.reaction_users(&http, self.id, reaction_type, limit, after).await
     }
 
+    /// Streams over all the users that voted for an answer of the message's
+    /// poll.
+    ///
+    /// The `answer_id` is the poll's own id of the answer, starting at `1`.
+    ///
+    /// Refer to [`ChannelId::poll_answer_voters_iter`] for more information.
+    pub fn poll_answer_voters_iter<H: AsRef<Http>>(
+        &self,
+        http: H,
+        answer_id: u8,
+    ) -> impl Stream<Item = Result<User>> {
+        self.channel_id.poll_answer_voters_iter(http, self.id, answer_id)
+    }
+
     /// Returns the associated [`Guild`] for the message if one is in the cache.
     ///
     /// Returns [`None`] if the guild's Id could not be found via [`Self::guild_id`] or
@@ -1281,3 +1297,51 @@ impl MessageId {
         self.link(channel_id, guild_id)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let message: Message = assert_roundtrip(
+            r#"{
+                "id": "334385199974967042",
+                "channel_id": "290926798999357250",
+                "guild_id": "290926798626357250",
+                "author": {
+                    "id": "53908099506183680",
+                    "username": "Mason",
+                    "discriminator": "9999",
+                    "avatar": "a_bab14f271d565501444b2ca3be944b25"
+                },
+                "content": "Supa Hot",
+                "timestamp": "2017-07-11T17:27:07.299000+00:00",
+                "edited_timestamp": null,
+                "tts": false,
+                "mention_everyone": false,
+                "mentions": [],
+                "mention_roles": ["290926798626357251"],
+                "attachments": [],
+                "embeds": [{
+                    "type": "rich",
+                    "title": "Status",
+                    "description": "All systems operational",
+                    "color": 3066993,
+                    "fields": [{"name": "Uptime", "value": "99.9%", "inline": true}]
+                }],
+                "nonce": "334385199974967040",
+                "pinned": false,
+                "type": 0,
+                "flags": 0
+            }"#,
+        );
+
+        assert_eq!(message.id, MessageId(334385199974967042));
+        assert_eq!(message.author.name, "Mason");
+        assert_eq!(message.kind, MessageType::Regular);
+        assert_eq!(message.embeds[0].fields[0].name, "Uptime");
+        assert!(message.edited_timestamp.is_none());
+    }
+}
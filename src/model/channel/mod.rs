@@ -483,6 +483,9 @@ pub struct ThreadsData {
     /// A thread member for each returned thread the current user has joined.
     pub members: Vec<ThreadMember>,
     /// Whether there are potentially additional threads that could be returned on a subsequent call.
+    ///
+    /// This is always `false` for active threads, which are returned all at once.
+    #[serde(default)]
     pub has_more: bool,
 }
 
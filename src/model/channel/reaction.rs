@@ -502,7 +502,7 @@ impl From<char> for ReactionType {
     /// # #[cfg(all(feature = "client", feature = "framework", feature = "http"))]
     /// # #[command]
     /// # async fn example(ctx: &Context) -> CommandResult {
-    /// #   let message = ChannelId(0).message(&ctx.http, 0).await?;
+    /// #   let message = ChannelId(0).message(ctx.http(), 0).await?;
     /// #
     /// message.react(ctx, '🍎').await?;
     /// # Ok(())
@@ -166,6 +166,39 @@ pub enum Error {
     NoTokenSet,
     /// Indicates that the component type cannot be used in this context.
     InvalidComponentType,
+    /// Indicates that the name of an application command or of one of its
+    /// options is not 1 to 32 word characters or dashes.
+    ///
+    /// The invalid name is provided.
+    InvalidCommandName(String),
+    /// Indicates that a downloaded [`Attachment`] is not of the size Discord
+    /// reported for it.
+    ///
+    /// [`Attachment`]: super::channel::Attachment
+    AttachmentSizeMismatch {
+        /// The size of the attachment reported by Discord.
+        expected: u64,
+        /// The number of bytes received.
+        actual: u64,
+    },
+    /// Indicates that an [`Attachment`] is larger than the maximum size
+    /// allowed for downloading it.
+    ///
+    /// The maximum size is provided.
+    ///
+    /// [`Attachment`]: super::channel::Attachment
+    AttachmentTooLarge(u64),
+    /// Indicates that a streaming [`Activity`] does not link to Twitch or
+    /// YouTube.
+    ///
+    /// The invalid URL is provided.
+    ///
+    /// [`Activity`]: super::gateway::Activity
+    InvalidStreamUrl(String),
+    /// When requesting the members of over 100 users by Id at once.
+    ///
+    /// The number of requested Ids is provided.
+    TooManyUserIds(usize),
 }
 
 impl Error {
@@ -197,6 +230,7 @@ impl Display for Error {
             Error::Hierarchy => f.write_str("Role hierarchy prevents this action."),
             Error::InvalidChannelType => f.write_str("The channel cannot perform the action."),
             Error::InvalidComponentType => f.write_str("The component cannot perform this action."),
+            Error::InvalidCommandName(_) => f.write_str("Invalid application command name."),
             Error::InvalidPermissions(_) => f.write_str("Invalid permissions."),
             Error::InvalidUser => f.write_str("The current user cannot perform the action."),
             Error::ItemMissing => f.write_str("The required item is missing from the cache."),
@@ -209,6 +243,12 @@ impl Display for Error {
             Error::NameTooLong => f.write_str("Name is over the character limit."),
             Error::NotAuthor => f.write_str("The bot is not author of this message."),
             Error::NoTokenSet => f.write_str("Token is not set."),
+            Error::AttachmentSizeMismatch {
+                ..
+            } => f.write_str("Attachment size does not match the downloaded size."),
+            Error::AttachmentTooLarge(_) => f.write_str("Attachment is too large."),
+            Error::InvalidStreamUrl(_) => f.write_str("Stream URL is not a Twitch or YouTube URL."),
+            Error::TooManyUserIds(_) => f.write_str("Too many user Ids to request at once."),
         }
     }
 }
@@ -3,7 +3,7 @@
 use std::convert::TryFrom;
 #[cfg(feature = "cache")]
 use std::mem;
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, sync::Arc};
 
 #[cfg(feature = "cache")]
 use async_trait::async_trait;
@@ -14,6 +14,8 @@ use serde::ser::{Serialize, SerializeSeq, Serializer};
 use super::prelude::*;
 use super::utils::deserialize_emojis;
 #[cfg(feature = "cache")]
+use super::utils::{merge_presence, merge_role, merge_voice_state, remove_role};
+#[cfg(feature = "cache")]
 use crate::cache::{Cache, CacheUpdate};
 use crate::constants::OpCode;
 use crate::internal::prelude::*;
@@ -62,13 +64,13 @@ impl CacheUpdate for ChannelCreateEvent {
 
                 let old_channel = cache
                     .guilds
-                    .write()
+                    .write(&guild_id)
                     .await
                     .get_mut(&guild_id)
                     .and_then(|g| g.channels.insert(channel_id, channel.clone()))
                     .map(Channel::Guild);
 
-                cache.channels.write().await.insert(channel_id, channel.clone());
+                cache.channels.insert(channel_id, channel.clone()).await;
 
                 old_channel
             },
@@ -124,11 +126,11 @@ impl CacheUpdate for ChannelDeleteEvent {
             Channel::Guild(ref channel) => {
                 let (guild_id, channel_id) = (channel.guild_id, channel.id);
 
-                cache.channels.write().await.remove(&channel_id);
+                cache.channels.remove(&channel_id).await;
 
                 cache
                     .guilds
-                    .write()
+                    .write(&guild_id)
                     .await
                     .get_mut(&guild_id)
                     .map(|g| g.channels.remove(&channel_id));
@@ -146,7 +148,7 @@ impl CacheUpdate for ChannelDeleteEvent {
         };
 
         // Remove the cached messages for the channel.
-        cache.messages.write().await.remove(&self.channel.id());
+        cache.remove_channel_messages(self.channel.id()).await;
 
         None
     }
@@ -183,7 +185,9 @@ impl CacheUpdate for ChannelPinsUpdateEvent {
     type Output = ();
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(channel) = cache.channels.write().await.get_mut(&self.channel_id) {
+        if let Some(channel) =
+            cache.channels.write(&self.channel_id).await.get_mut(&self.channel_id)
+        {
             channel.last_pin_timestamp = self.last_pin_timestamp;
 
             return None;
@@ -208,35 +212,42 @@ pub struct ChannelUpdateEvent {
 #[cfg(feature = "cache")]
 #[async_trait]
 impl CacheUpdate for ChannelUpdateEvent {
-    type Output = ();
+    /// The channel before the update, if it was cached.
+    type Output = Channel;
 
-    async fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<Channel> {
         match self.channel {
             Channel::Guild(ref channel) => {
                 let (guild_id, channel_id) = (channel.guild_id, channel.id);
 
-                cache.channels.write().await.insert(channel_id, channel.clone());
+                let old = cache.channels.insert(channel_id, channel.clone()).await;
 
                 cache
                     .guilds
-                    .write()
+                    .write(&guild_id)
                     .await
                     .get_mut(&guild_id)
                     .map(|g| g.channels.insert(channel_id, channel.clone()));
+
+                old.map(Channel::Guild)
             },
             Channel::Private(ref channel) => {
-                if let Some(c) = cache.private_channels.write().await.get_mut(&channel.id) {
-                    c.clone_from(channel);
-                }
+                let mut private_channels = cache.private_channels.write().await;
+                let c = private_channels.get_mut(&channel.id)?;
+                let old = c.clone();
+                c.clone_from(channel);
+
+                Some(Channel::Private(old))
             },
             Channel::Category(ref category) => {
-                if let Some(c) = cache.categories.write().await.get_mut(&category.id) {
-                    c.clone_from(category);
-                }
+                let mut categories = cache.categories.write().await;
+                let c = categories.get_mut(&category.id)?;
+                let old = c.clone();
+                c.clone_from(category);
+
+                Some(Channel::Category(old))
             },
         }
-
-        None
     }
 }
 
@@ -285,6 +296,7 @@ impl CacheUpdate for GuildCreateEvent {
     async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.write().await.remove(&self.guild.id);
         let mut guild = self.guild.clone();
+        cache.strip_disabled_collections(&mut guild).await;
 
         for (user_id, member) in &mut guild.members {
             cache.update_user_entry(&member.user).await;
@@ -293,8 +305,8 @@ impl CacheUpdate for GuildCreateEvent {
             }
         }
 
-        cache.channels.write().await.extend(guild.channels.clone().into_iter());
-        cache.guilds.write().await.insert(self.guild.id, guild);
+        cache.channels.extend(guild.channels.clone()).await;
+        cache.guilds.insert(self.guild.id, guild).await;
 
         None
     }
@@ -329,14 +341,20 @@ impl CacheUpdate for GuildDeleteEvent {
     type Output = Guild;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        match cache.guilds.write().await.remove(&self.guild.id) {
+        // The guild is expected to come back after an outage, unlike when the
+        // current user left it.
+        if self.guild.unavailable {
+            cache.unavailable_guilds.write().await.insert(self.guild.id);
+        }
+
+        match cache.guilds.remove(&self.guild.id).await {
             Some(guild) => {
                 for channel_id in guild.channels.keys() {
                     // Remove the channel from the cache.
-                    cache.channels.write().await.remove(channel_id);
+                    cache.channels.remove(channel_id).await;
 
                     // Remove the channel's cached messages.
-                    cache.messages.write().await.remove(channel_id);
+                    cache.remove_channel_messages(*channel_id).await;
                 }
 
                 Some(guild)
@@ -377,7 +395,7 @@ impl CacheUpdate for GuildEmojisUpdateEvent {
     type Output = ();
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
+        if let Some(guild) = cache.guilds.write(&self.guild_id).await.get_mut(&self.guild_id) {
             guild.emojis.clone_from(&self.emojis);
         }
 
@@ -410,7 +428,7 @@ impl CacheUpdate for GuildMemberAddEvent {
             self.member.user = u;
         }
 
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
+        if let Some(guild) = cache.guilds.write(&self.guild_id).await.get_mut(&self.guild_id) {
             guild.member_count += 1;
             guild.members.insert(user_id, self.member.clone());
         }
@@ -449,7 +467,7 @@ impl CacheUpdate for GuildMemberRemoveEvent {
     type Output = Member;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
+        if let Some(guild) = cache.guilds.write(&self.guild_id).await.get_mut(&self.guild_id) {
             guild.member_count -= 1;
             return guild.members.remove(&self.user.id);
         }
@@ -484,7 +502,7 @@ impl CacheUpdate for GuildMemberUpdateEvent {
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         cache.update_user_entry(&self.user).await;
 
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
+        if let Some(guild) = cache.guilds.write(&self.guild_id).await.get_mut(&self.guild_id) {
             let item = if let Some(member) = guild.members.get_mut(&self.user.id) {
                 let item = Some(member.clone());
 
@@ -535,23 +553,29 @@ pub struct GuildMembersChunkEvent {
     pub chunk_index: u32,
     pub chunk_count: u32,
     pub nonce: Option<String>,
+    /// The Ids of the requested users that are not members of the guild.
+    pub not_found: Vec<UserId>,
+    /// The presences of the members, if they were requested.
+    pub presences: Vec<Presence>,
 }
 
 #[cfg(feature = "cache")]
 #[async_trait]
 impl CacheUpdate for GuildMembersChunkEvent {
-    type Output = ();
+    type Output = Vec<GuildId>;
 
-    async fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<Vec<GuildId>> {
         for member in self.members.values() {
             cache.update_user_entry(&member.user).await;
         }
 
-        if let Some(g) = cache.guilds.write().await.get_mut(&self.guild_id) {
+        if let Some(g) = cache.guilds.write(&self.guild_id).await.get_mut(&self.guild_id) {
             g.members.extend(self.members.clone());
         }
 
-        None
+        let nonce = self.nonce.as_deref()?;
+
+        cache.member_chunks.write().await.receive(nonce, self.chunk_index, self.chunk_count)
     }
 }
 
@@ -605,12 +629,28 @@ impl<'de> Deserialize<'de> for GuildMembersChunkEvent {
         let nonce =
             map.get("nonce").and_then(|nonce| nonce.as_str()).map(|nonce| nonce.to_string());
 
+        let not_found = match map.remove("not_found") {
+            Some(not_found) => {
+                serde_json::from_value::<Vec<UserId>>(not_found).map_err(DeError::custom)?
+            },
+            None => Vec::new(),
+        };
+
+        let presences = match map.remove("presences") {
+            Some(presences) => {
+                serde_json::from_value::<Vec<Presence>>(presences).map_err(DeError::custom)?
+            },
+            None => Vec::new(),
+        };
+
         Ok(GuildMembersChunkEvent {
             guild_id,
             members,
             chunk_index,
             chunk_count,
             nonce,
+            not_found,
+            presences,
         })
     }
 }
@@ -630,10 +670,10 @@ impl CacheUpdate for GuildRoleCreateEvent {
     async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache
             .guilds
-            .write()
+            .write(&self.guild_id)
             .await
             .get_mut(&self.guild_id)
-            .map(|g| g.roles.insert(self.role.id, self.role.clone()));
+            .map(|g| merge_role(&mut g.roles, self.role.clone()));
 
         None
     }
@@ -685,10 +725,10 @@ impl CacheUpdate for GuildRoleDeleteEvent {
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         cache
             .guilds
-            .write()
+            .write(&self.guild_id)
             .await
             .get_mut(&self.guild_id)
-            .and_then(|g| g.roles.remove(&self.role_id))
+            .and_then(|g| remove_role(&mut g.roles, self.role_id))
     }
 }
 
@@ -705,13 +745,12 @@ impl CacheUpdate for GuildRoleUpdateEvent {
     type Output = Role;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
-            if let Some(role) = guild.roles.get_mut(&self.role.id) {
-                return Some(mem::replace(role, self.role.clone()));
-            }
-        }
-
-        None
+        cache
+            .guilds
+            .write(&self.guild_id)
+            .await
+            .get_mut(&self.guild_id)
+            .and_then(|g| merge_role(&mut g.roles, self.role.clone()))
     }
 }
 
@@ -780,7 +819,7 @@ impl CacheUpdate for GuildUnavailableEvent {
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.write().await.insert(self.guild_id);
-        cache.guilds.write().await.remove(&self.guild_id);
+        cache.guilds.remove(&self.guild_id).await;
 
         None
     }
@@ -795,10 +834,15 @@ pub struct GuildUpdateEvent {
 #[cfg(feature = "cache")]
 #[async_trait]
 impl CacheUpdate for GuildUpdateEvent {
-    type Output = ();
+    /// The guild before the update, if it was cached.
+    type Output = Guild;
 
-    async fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild.id) {
+    async fn update(&mut self, cache: &Cache) -> Option<Guild> {
+        let mut guilds = cache.guilds.write(&self.guild.id).await;
+        let guild = guilds.get_mut(&self.guild.id)?;
+        let old = guild.clone();
+
+        {
             guild.afk_channel_id.clone_from(&self.guild.afk_channel_id);
             guild.afk_timeout = self.guild.afk_timeout;
             guild.banner.clone_from(&self.guild.banner);
@@ -834,7 +878,7 @@ impl CacheUpdate for GuildUpdateEvent {
             guild.widget_enabled = self.guild.widget_enabled;
         }
 
-        None
+        Some(old)
     }
 }
 
@@ -868,9 +912,33 @@ impl CacheUpdate for MessageCreateEvent {
     type Output = Message;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        let max = cache.settings().await.max_messages;
+        let settings = cache.settings().await;
+
+        if settings.cache_users {
+            let member = match (self.message.guild_id, &self.message.member) {
+                (Some(guild_id), Some(member)) => Some(Member {
+                    deaf: member.deaf,
+                    guild_id,
+                    joined_at: member.joined_at,
+                    mute: member.mute,
+                    nick: member.nick.clone(),
+                    user: self.message.author.clone(),
+                    roles: member.roles.clone(),
+                    pending: member.pending,
+                    premium_since: member.premium_since,
+                    #[cfg(feature = "unstable_discord_api")]
+                    permissions: member.permissions,
+                    avatar: None,
+                }),
+                _ => None,
+            };
+
+            cache.temp_cache_user(&self.message.author, member.as_ref()).await;
+        }
+
+        let max = settings.max_messages;
 
-        if max == 0 {
+        if max == 0 || !settings.cache_messages {
             return None;
         }
 
@@ -880,17 +948,29 @@ impl CacheUpdate for MessageCreateEvent {
 
         let queue = message_queues.entry(self.message.channel_id).or_insert_with(Default::default);
 
+        // The message was already cached, e.g. because the event was sent
+        // twice, so it keeps its place in the queue.
+        if messages.insert(self.message.id, self.message.clone()).is_some() {
+            return None;
+        }
+
+        queue.push_back(self.message.id);
+
         let mut removed_msg = None;
 
-        if messages.len() == max {
-            if let Some(id) = queue.pop_front() {
-                removed_msg = messages.remove(&id);
+        // Evict the oldest messages until the cache is within its limit again,
+        // which may be more than one if the limit was lowered in the meantime.
+        while messages.len() > max {
+            let id = match queue.pop_front() {
+                Some(id) => id,
+                None => break,
+            };
+
+            if let Some(message) = messages.remove(&id) {
+                removed_msg.get_or_insert(message);
             }
         }
 
-        queue.push_back(self.message.id);
-        messages.insert(self.message.id, self.message.clone());
-
         removed_msg
     }
 }
@@ -929,6 +1009,34 @@ pub struct MessageDeleteEvent {
     pub message_id: MessageId,
 }
 
+#[cfg(feature = "cache")]
+#[async_trait]
+impl CacheUpdate for MessageDeleteBulkEvent {
+    /// The deleted messages that were cached.
+    type Output = Vec<Message>;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let removed = cache.remove_messages(self.channel_id, &self.ids).await;
+
+        if removed.is_empty() {
+            None
+        } else {
+            Some(removed)
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+#[async_trait]
+impl CacheUpdate for MessageDeleteEvent {
+    /// The deleted message, if it was cached.
+    type Output = Message;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        cache.remove_messages(self.channel_id, &[self.message_id]).await.pop()
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct MessageUpdateEvent {
@@ -1011,9 +1119,10 @@ pub struct PresenceUpdateEvent {
 #[cfg(feature = "cache")]
 #[async_trait]
 impl CacheUpdate for PresenceUpdateEvent {
-    type Output = ();
+    /// The presence before the update, if it was cached.
+    type Output = Presence;
 
-    async fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<Presence> {
         let user_id = self.presence.user_id;
 
         if let Some(user) = self.presence.user.as_mut() {
@@ -1023,41 +1132,48 @@ impl CacheUpdate for PresenceUpdateEvent {
             }
         }
 
+        let cache_presences = cache.settings().await.cache_presences;
+
         if let Some(guild_id) = self.guild_id {
-            if let Some(guild) = cache.guilds.write().await.get_mut(&guild_id) {
-                // If the member went offline, remove them from the presence list.
-                if self.presence.status == OnlineStatus::Offline {
-                    guild.presences.remove(&self.presence.user_id);
-                } else {
-                    guild.presences.insert(self.presence.user_id, self.presence.clone());
-                }
+            let mut guilds = cache.guilds.write(&guild_id).await;
+            let guild = guilds.get_mut(&guild_id)?;
 
-                // Create a partial member instance out of the presence update
-                // data.
-                if let Some(user) = self.presence.user.as_ref() {
-                    guild.members.entry(self.presence.user_id).or_insert_with(|| Member {
-                        deaf: false,
-                        guild_id,
-                        joined_at: None,
-                        mute: false,
-                        nick: None,
-                        user: user.clone(),
-                        roles: vec![],
-                        pending: false,
-                        premium_since: None,
-                        #[cfg(feature = "unstable_discord_api")]
-                        permissions: None,
-                        avatar: None,
-                    });
-                }
+            // If the member went offline, remove them from the presence list.
+            let old = if !cache_presences {
+                None
+            } else if self.presence.status == OnlineStatus::Offline {
+                guild.presences.remove(&self.presence.user_id)
+            } else {
+                merge_presence(&mut guild.presences, self.presence.clone())
+            };
+
+            // Create a partial member instance out of the presence update
+            // data.
+            if let Some(user) = self.presence.user.as_ref() {
+                guild.members.entry(self.presence.user_id).or_insert_with(|| Member {
+                    deaf: false,
+                    guild_id,
+                    joined_at: None,
+                    mute: false,
+                    nick: None,
+                    user: user.clone(),
+                    roles: vec![],
+                    pending: false,
+                    premium_since: None,
+                    #[cfg(feature = "unstable_discord_api")]
+                    permissions: None,
+                    avatar: None,
+                });
             }
+
+            old
+        } else if !cache_presences {
+            None
         } else if self.presence.status == OnlineStatus::Offline {
-            cache.presences.write().await.remove(&self.presence.user_id);
+            cache.presences.write().await.remove(&self.presence.user_id)
         } else {
-            cache.presences.write().await.insert(self.presence.user_id, self.presence.clone());
+            merge_presence(&mut *cache.presences.write().await, self.presence.clone())
         }
-
-        None
     }
 }
 
@@ -1090,6 +1206,10 @@ impl CacheUpdate for PresencesReplaceEvent {
     type Output = ();
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings().await.cache_presences {
+            return None;
+        }
+
         cache.presences.write().await.extend({
             let mut p: HashMap<UserId, Presence> = HashMap::default();
 
@@ -1201,12 +1321,13 @@ impl CacheUpdate for ReadyEvent {
         for guild in ready.guilds {
             match guild {
                 GuildStatus::Offline(unavailable) => {
-                    cache.guilds.write().await.remove(&unavailable.id);
+                    cache.guilds.remove(&unavailable.id).await;
                     cache.unavailable_guilds.write().await.insert(unavailable.id);
                 },
-                GuildStatus::OnlineGuild(guild) => {
+                GuildStatus::OnlineGuild(mut guild) => {
+                    cache.strip_disabled_collections(&mut guild).await;
                     cache.unavailable_guilds.write().await.remove(&guild.id);
-                    cache.guilds.write().await.insert(guild.id, guild);
+                    cache.guilds.insert(guild.id, guild).await;
                 },
                 GuildStatus::OnlinePartialGuild(_) => {},
             }
@@ -1223,7 +1344,10 @@ impl CacheUpdate for ReadyEvent {
             presence.user = cache.user(user_id).await;
         }
 
-        cache.presences.write().await.extend(ready.presences);
+        if cache.settings().await.cache_presences {
+            cache.presences.write().await.extend(ready.presences);
+        }
+
         *cache.shard_count.write().await = ready.shard.map_or(1, |s| s[1]);
         *cache.user.write().await = ready.user;
 
@@ -1269,6 +1393,42 @@ pub struct TypingStartEvent {
 pub struct UnknownEvent {
     pub kind: String,
     pub value: Value,
+    /// The error from deserializing the event, if its type is known but its
+    /// payload is malformed.
+    ///
+    /// Such events are only dispatched to the [`RawEventHandler`].
+    ///
+    /// [`RawEventHandler`]: crate::client::RawEventHandler
+    #[serde(skip)]
+    pub error: Option<Arc<serde_json::Error>>,
+}
+
+impl UnknownEvent {
+    /// Takes the event type and data out of a raw dispatch payload, e.g. one
+    /// that could not be deserialized, so that it can still be handled raw.
+    ///
+    /// Returns `None` if the payload is not a dispatch.
+    pub(crate) fn from_dispatch(payload: Value) -> Option<Self> {
+        let mut map = match payload {
+            Value::Object(map) => map,
+            _ => return None,
+        };
+
+        if map.get("op").and_then(Value::as_u64) != Some(OpCode::Event.num()) {
+            return None;
+        }
+
+        let kind = match map.remove("t") {
+            Some(Value::String(kind)) => kind,
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            value: map.remove("d")?,
+            error: None,
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1337,18 +1497,18 @@ impl CacheUpdate for VoiceStateUpdateEvent {
     type Output = VoiceState;
 
     async fn update(&mut self, cache: &Cache) -> Option<VoiceState> {
+        let cache_voice_states = cache.settings().await.cache_voice_states;
+
         if let Some(guild_id) = self.guild_id {
-            if let Some(guild) = cache.guilds.write().await.get_mut(&guild_id) {
+            if let Some(guild) = cache.guilds.write(&guild_id).await.get_mut(&guild_id) {
                 if let Some(member) = &self.voice_state.member {
                     guild.members.insert(member.user.id, member.clone());
                 }
 
-                if self.voice_state.channel_id.is_some() {
-                    // Update or add to the voice state list
-                    guild.voice_states.insert(self.voice_state.user_id, self.voice_state.clone())
+                if cache_voice_states {
+                    merge_voice_state(&mut guild.voice_states, self.voice_state.clone())
                 } else {
-                    // Remove the user from the voice state list
-                    guild.voice_states.remove(&self.voice_state.user_id)
+                    None
                 }
             } else {
                 None
@@ -1389,6 +1549,28 @@ pub struct InteractionCreateEvent {
     pub interaction: Interaction,
 }
 
+#[cfg(all(feature = "cache", feature = "unstable_discord_api"))]
+#[async_trait]
+impl CacheUpdate for InteractionCreateEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let (user, member) = match &self.interaction {
+            Interaction::ApplicationCommand(interaction) => {
+                (&interaction.user, interaction.member.as_ref())
+            },
+            Interaction::MessageComponent(interaction) => {
+                (&interaction.user, interaction.member.as_ref())
+            },
+            Interaction::Ping(_) => return None,
+        };
+
+        cache.temp_cache_user(user, member).await;
+
+        None
+    }
+}
+
 #[cfg(feature = "unstable_discord_api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "unstable_discord_api")))]
 impl<'de> Deserialize<'de> for InteractionCreateEvent {
@@ -1574,6 +1756,18 @@ pub struct ThreadCreateEvent {
     pub thread: GuildChannel,
 }
 
+#[cfg(feature = "cache")]
+#[async_trait]
+impl CacheUpdate for ThreadCreateEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        cache.temp_cache_channel(&self.thread).await;
+
+        None
+    }
+}
+
 impl<'de> Deserialize<'de> for ThreadCreateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let thread = GuildChannel::deserialize(deserializer)?;
@@ -1590,6 +1784,18 @@ pub struct ThreadUpdateEvent {
     pub thread: GuildChannel,
 }
 
+#[cfg(feature = "cache")]
+#[async_trait]
+impl CacheUpdate for ThreadUpdateEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        cache.temp_cache_channel(&self.thread).await;
+
+        None
+    }
+}
+
 impl<'de> Deserialize<'de> for ThreadUpdateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let thread = GuildChannel::deserialize(deserializer)?;
@@ -1606,6 +1812,18 @@ pub struct ThreadDeleteEvent {
     pub thread: PartialGuildChannel,
 }
 
+#[cfg(feature = "cache")]
+#[async_trait]
+impl CacheUpdate for ThreadDeleteEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        cache.remove_temp_channel(self.thread.id).await;
+
+        None
+    }
+}
+
 impl<'de> Deserialize<'de> for ThreadDeleteEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let thread = PartialGuildChannel::deserialize(deserializer)?;
@@ -1679,34 +1897,30 @@ pub enum GatewayEvent {
     HeartbeatAck,
 }
 
-impl<'de> Deserialize<'de> for GatewayEvent {
-    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
-        let mut map = JsonMap::deserialize(deserializer)?;
-
-        let op = map
-            .remove("op")
-            .ok_or_else(|| DeError::custom("expected op"))
-            .and_then(OpCode::deserialize)
-            .map_err(DeError::custom)?;
+impl GatewayEvent {
+    /// Builds an event from the fields of a payload: its opcode, sequence
+    /// number, event type and data.
+    ///
+    /// The data is deserialized from the given deserializer, according to the
+    /// opcode and the event type.
+    pub(crate) fn from_parts<'de, D: Deserializer<'de>>(
+        op: OpCode,
+        seq: Option<u64>,
+        kind: Option<EventType>,
+        data: Option<D>,
+    ) -> StdResult<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Hello {
+            heartbeat_interval: u64,
+        }
 
         Ok(match op {
             OpCode::Event => {
-                let s = map
-                    .remove("s")
-                    .ok_or_else(|| DeError::custom("expected gateway event sequence"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?;
-                let kind = map
-                    .remove("t")
-                    .ok_or_else(|| DeError::custom("expected gateway event type"))
-                    .and_then(EventType::deserialize)
-                    .map_err(DeError::custom)?;
-                let payload = map
-                    .remove("d")
-                    .ok_or(Error::Decode("expected gateway event d", Value::Object(map)))
-                    .map_err(DeError::custom)?;
-
-                let x = match deserialize_event_with_type(kind.clone(), payload) {
+                let s = seq.ok_or_else(|| DeError::custom("expected gateway event sequence"))?;
+                let kind = kind.ok_or_else(|| DeError::custom("expected gateway event type"))?;
+                let data = data.ok_or_else(|| DeError::custom("expected gateway event d"))?;
+
+                let x = match deserialize_event(kind.clone(), data) {
                     Ok(x) => x,
                     Err(why) => {
                         return Err(DeError::custom(format_args!("event {:?}: {}", kind, why)));
@@ -1716,37 +1930,21 @@ impl<'de> Deserialize<'de> for GatewayEvent {
                 GatewayEvent::Dispatch(s, x)
             },
             OpCode::Heartbeat => {
-                let s = map
-                    .remove("s")
-                    .ok_or_else(|| DeError::custom("Expected heartbeat s"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?;
+                let s = seq.ok_or_else(|| DeError::custom("Expected heartbeat s"))?;
 
                 GatewayEvent::Heartbeat(s)
             },
             OpCode::Reconnect => GatewayEvent::Reconnect,
             OpCode::InvalidSession => {
-                let resumable = map
-                    .remove("d")
-                    .ok_or_else(|| DeError::custom("expected gateway invalid session d"))
-                    .and_then(bool::deserialize)
-                    .map_err(DeError::custom)?;
+                let data =
+                    data.ok_or_else(|| DeError::custom("expected gateway invalid session d"))?;
 
-                GatewayEvent::InvalidateSession(resumable)
+                GatewayEvent::InvalidateSession(bool::deserialize(data)?)
             },
             OpCode::Hello => {
-                let mut d = map
-                    .remove("d")
-                    .ok_or_else(|| DeError::custom("expected gateway hello d"))
-                    .and_then(JsonMap::deserialize)
-                    .map_err(DeError::custom)?;
-                let interval = d
-                    .remove("heartbeat_interval")
-                    .ok_or_else(|| DeError::custom("expected gateway hello interval"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?;
-
-                GatewayEvent::Hello(interval)
+                let data = data.ok_or_else(|| DeError::custom("expected gateway hello d"))?;
+
+                GatewayEvent::Hello(Hello::deserialize(data)?.heartbeat_interval)
             },
             OpCode::HeartbeatAck => GatewayEvent::HeartbeatAck,
             _ => return Err(DeError::custom("invalid opcode")),
@@ -1754,6 +1952,32 @@ impl<'de> Deserialize<'de> for GatewayEvent {
     }
 }
 
+impl<'de> Deserialize<'de> for GatewayEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let mut map = JsonMap::deserialize(deserializer)?;
+
+        let op = map
+            .remove("op")
+            .ok_or_else(|| DeError::custom("expected op"))
+            .and_then(OpCode::deserialize)
+            .map_err(DeError::custom)?;
+        let seq = map
+            .remove("s")
+            .map(Option::<u64>::deserialize)
+            .transpose()
+            .map_err(DeError::custom)?
+            .flatten();
+        let kind = map
+            .remove("t")
+            .map(Option::<EventType>::deserialize)
+            .transpose()
+            .map_err(DeError::custom)?
+            .flatten();
+
+        GatewayEvent::from_parts(op, seq, kind, map.remove("d")).map_err(DeError::custom)
+    }
+}
+
 /// Event received over a websocket connection
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -2470,100 +2694,116 @@ impl<T> TryFrom<RelatedId<T>> for Option<T> {
 ///
 /// Returns [`Error::Json`] if there is an error in deserializing the event data.
 pub fn deserialize_event_with_type(kind: EventType, v: Value) -> Result<Event> {
+    Ok(deserialize_event(kind, v)?)
+}
+
+/// Deserializes the data of an event with the given type, as done by
+/// [`deserialize_event_with_type`] but from any deserializer.
+fn deserialize_event<'de, D: Deserializer<'de>>(
+    kind: EventType,
+    d: D,
+) -> StdResult<Event, D::Error> {
     Ok(match kind {
-        EventType::ChannelCreate => Event::ChannelCreate(serde_json::from_value(v)?),
-        EventType::ChannelDelete => Event::ChannelDelete(serde_json::from_value(v)?),
-        EventType::ChannelPinsUpdate => Event::ChannelPinsUpdate(serde_json::from_value(v)?),
-        EventType::ChannelUpdate => Event::ChannelUpdate(serde_json::from_value(v)?),
-        EventType::GuildBanAdd => Event::GuildBanAdd(serde_json::from_value(v)?),
-        EventType::GuildBanRemove => Event::GuildBanRemove(serde_json::from_value(v)?),
+        EventType::ChannelCreate => Event::ChannelCreate(Deserialize::deserialize(d)?),
+        EventType::ChannelDelete => Event::ChannelDelete(Deserialize::deserialize(d)?),
+        EventType::ChannelPinsUpdate => Event::ChannelPinsUpdate(Deserialize::deserialize(d)?),
+        EventType::ChannelUpdate => Event::ChannelUpdate(Deserialize::deserialize(d)?),
+        EventType::GuildBanAdd => Event::GuildBanAdd(Deserialize::deserialize(d)?),
+        EventType::GuildBanRemove => Event::GuildBanRemove(Deserialize::deserialize(d)?),
         EventType::GuildCreate | EventType::GuildUnavailable => {
             // GuildUnavailable isn't actually received from the gateway, so it
             // can be lumped in with GuildCreate's arm.
 
-            let mut map = JsonMap::deserialize(v)?;
+            let mut map = JsonMap::deserialize(d)?;
 
             if map.remove("unavailable").and_then(|v| v.as_bool()).unwrap_or(false) {
-                let guild_data = serde_json::from_value(Value::Object(map))?;
+                let guild_data =
+                    serde_json::from_value(Value::Object(map)).map_err(DeError::custom)?;
 
                 Event::GuildUnavailable(guild_data)
             } else {
-                Event::GuildCreate(serde_json::from_value(Value::Object(map))?)
+                let guild = serde_json::from_value(Value::Object(map)).map_err(DeError::custom)?;
+
+                Event::GuildCreate(guild)
             }
         },
         EventType::GuildDelete => {
-            let mut map = JsonMap::deserialize(v)?;
+            let mut map = JsonMap::deserialize(d)?;
 
             if map.remove("unavailable").and_then(|v| v.as_bool()).unwrap_or(false) {
-                let guild_data = serde_json::from_value(Value::Object(map))?;
+                let guild_data =
+                    serde_json::from_value(Value::Object(map)).map_err(DeError::custom)?;
 
                 Event::GuildUnavailable(guild_data)
             } else {
-                Event::GuildDelete(serde_json::from_value(Value::Object(map))?)
+                let guild = serde_json::from_value(Value::Object(map)).map_err(DeError::custom)?;
+
+                Event::GuildDelete(guild)
             }
         },
-        EventType::GuildEmojisUpdate => Event::GuildEmojisUpdate(serde_json::from_value(v)?),
+        EventType::GuildEmojisUpdate => Event::GuildEmojisUpdate(Deserialize::deserialize(d)?),
         EventType::GuildIntegrationsUpdate => {
-            Event::GuildIntegrationsUpdate(serde_json::from_value(v)?)
+            Event::GuildIntegrationsUpdate(Deserialize::deserialize(d)?)
         },
-        EventType::GuildMemberAdd => Event::GuildMemberAdd(serde_json::from_value(v)?),
-        EventType::GuildMemberRemove => Event::GuildMemberRemove(serde_json::from_value(v)?),
-        EventType::GuildMemberUpdate => Event::GuildMemberUpdate(serde_json::from_value(v)?),
-        EventType::GuildMembersChunk => Event::GuildMembersChunk(serde_json::from_value(v)?),
-        EventType::GuildRoleCreate => Event::GuildRoleCreate(serde_json::from_value(v)?),
-        EventType::GuildRoleDelete => Event::GuildRoleDelete(serde_json::from_value(v)?),
-        EventType::GuildRoleUpdate => Event::GuildRoleUpdate(serde_json::from_value(v)?),
-        EventType::InviteCreate => Event::InviteCreate(serde_json::from_value(v)?),
-        EventType::InviteDelete => Event::InviteDelete(serde_json::from_value(v)?),
-        EventType::GuildUpdate => Event::GuildUpdate(serde_json::from_value(v)?),
-        EventType::MessageCreate => Event::MessageCreate(serde_json::from_value(v)?),
-        EventType::MessageDelete => Event::MessageDelete(serde_json::from_value(v)?),
-        EventType::MessageDeleteBulk => Event::MessageDeleteBulk(serde_json::from_value(v)?),
-        EventType::ReactionAdd => Event::ReactionAdd(serde_json::from_value(v)?),
-        EventType::ReactionRemove => Event::ReactionRemove(serde_json::from_value(v)?),
-        EventType::ReactionRemoveAll => Event::ReactionRemoveAll(serde_json::from_value(v)?),
-        EventType::MessageUpdate => Event::MessageUpdate(serde_json::from_value(v)?),
-        EventType::PresenceUpdate => Event::PresenceUpdate(serde_json::from_value(v)?),
-        EventType::PresencesReplace => Event::PresencesReplace(serde_json::from_value(v)?),
-        EventType::Ready => Event::Ready(serde_json::from_value(v)?),
-        EventType::Resumed => Event::Resumed(serde_json::from_value(v)?),
-        EventType::TypingStart => Event::TypingStart(serde_json::from_value(v)?),
-        EventType::UserUpdate => Event::UserUpdate(serde_json::from_value(v)?),
-        EventType::VoiceServerUpdate => Event::VoiceServerUpdate(serde_json::from_value(v)?),
-        EventType::VoiceStateUpdate => Event::VoiceStateUpdate(serde_json::from_value(v)?),
-        EventType::WebhookUpdate => Event::WebhookUpdate(serde_json::from_value(v)?),
+        EventType::GuildMemberAdd => Event::GuildMemberAdd(Deserialize::deserialize(d)?),
+        EventType::GuildMemberRemove => Event::GuildMemberRemove(Deserialize::deserialize(d)?),
+        EventType::GuildMemberUpdate => Event::GuildMemberUpdate(Deserialize::deserialize(d)?),
+        EventType::GuildMembersChunk => Event::GuildMembersChunk(Deserialize::deserialize(d)?),
+        EventType::GuildRoleCreate => Event::GuildRoleCreate(Deserialize::deserialize(d)?),
+        EventType::GuildRoleDelete => Event::GuildRoleDelete(Deserialize::deserialize(d)?),
+        EventType::GuildRoleUpdate => Event::GuildRoleUpdate(Deserialize::deserialize(d)?),
+        EventType::InviteCreate => Event::InviteCreate(Deserialize::deserialize(d)?),
+        EventType::InviteDelete => Event::InviteDelete(Deserialize::deserialize(d)?),
+        EventType::GuildUpdate => Event::GuildUpdate(Deserialize::deserialize(d)?),
+        EventType::MessageCreate => Event::MessageCreate(Deserialize::deserialize(d)?),
+        EventType::MessageDelete => Event::MessageDelete(Deserialize::deserialize(d)?),
+        EventType::MessageDeleteBulk => Event::MessageDeleteBulk(Deserialize::deserialize(d)?),
+        EventType::ReactionAdd => Event::ReactionAdd(Deserialize::deserialize(d)?),
+        EventType::ReactionRemove => Event::ReactionRemove(Deserialize::deserialize(d)?),
+        EventType::ReactionRemoveAll => Event::ReactionRemoveAll(Deserialize::deserialize(d)?),
+        EventType::MessageUpdate => Event::MessageUpdate(Deserialize::deserialize(d)?),
+        EventType::PresenceUpdate => Event::PresenceUpdate(Deserialize::deserialize(d)?),
+        EventType::PresencesReplace => Event::PresencesReplace(Deserialize::deserialize(d)?),
+        EventType::Ready => Event::Ready(Deserialize::deserialize(d)?),
+        EventType::Resumed => Event::Resumed(Deserialize::deserialize(d)?),
+        EventType::TypingStart => Event::TypingStart(Deserialize::deserialize(d)?),
+        EventType::UserUpdate => Event::UserUpdate(Deserialize::deserialize(d)?),
+        EventType::VoiceServerUpdate => Event::VoiceServerUpdate(Deserialize::deserialize(d)?),
+        EventType::VoiceStateUpdate => Event::VoiceStateUpdate(Deserialize::deserialize(d)?),
+        EventType::WebhookUpdate => Event::WebhookUpdate(Deserialize::deserialize(d)?),
         #[cfg(feature = "unstable_discord_api")]
-        EventType::InteractionCreate => Event::InteractionCreate(serde_json::from_value(v)?),
+        EventType::InteractionCreate => Event::InteractionCreate(Deserialize::deserialize(d)?),
         #[cfg(feature = "unstable_discord_api")]
-        EventType::IntegrationCreate => Event::IntegrationCreate(serde_json::from_value(v)?),
+        EventType::IntegrationCreate => Event::IntegrationCreate(Deserialize::deserialize(d)?),
         #[cfg(feature = "unstable_discord_api")]
-        EventType::IntegrationUpdate => Event::IntegrationUpdate(serde_json::from_value(v)?),
+        EventType::IntegrationUpdate => Event::IntegrationUpdate(Deserialize::deserialize(d)?),
         #[cfg(feature = "unstable_discord_api")]
-        EventType::IntegrationDelete => Event::IntegrationDelete(serde_json::from_value(v)?),
+        EventType::IntegrationDelete => Event::IntegrationDelete(Deserialize::deserialize(d)?),
         #[cfg(feature = "unstable_discord_api")]
         EventType::ApplicationCommandCreate => {
-            Event::ApplicationCommandCreate(serde_json::from_value(v)?)
+            Event::ApplicationCommandCreate(Deserialize::deserialize(d)?)
         },
         #[cfg(feature = "unstable_discord_api")]
         EventType::ApplicationCommandUpdate => {
-            Event::ApplicationCommandUpdate(serde_json::from_value(v)?)
+            Event::ApplicationCommandUpdate(Deserialize::deserialize(d)?)
         },
         #[cfg(feature = "unstable_discord_api")]
         EventType::ApplicationCommandDelete => {
-            Event::ApplicationCommandDelete(serde_json::from_value(v)?)
+            Event::ApplicationCommandDelete(Deserialize::deserialize(d)?)
         },
-        EventType::StageInstanceCreate => Event::StageInstanceCreate(serde_json::from_value(v)?),
-        EventType::StageInstanceUpdate => Event::StageInstanceUpdate(serde_json::from_value(v)?),
-        EventType::StageInstanceDelete => Event::StageInstanceDelete(serde_json::from_value(v)?),
-        EventType::ThreadCreate => Event::ThreadCreate(serde_json::from_value(v)?),
-        EventType::ThreadUpdate => Event::ThreadUpdate(serde_json::from_value(v)?),
-        EventType::ThreadDelete => Event::ThreadDelete(serde_json::from_value(v)?),
-        EventType::ThreadListSync => Event::ThreadListSync(serde_json::from_value(v)?),
-        EventType::ThreadMemberUpdate => Event::ThreadMemberUpdate(serde_json::from_value(v)?),
-        EventType::ThreadMembersUpdate => Event::ThreadMembersUpdate(serde_json::from_value(v)?),
+        EventType::StageInstanceCreate => Event::StageInstanceCreate(Deserialize::deserialize(d)?),
+        EventType::StageInstanceUpdate => Event::StageInstanceUpdate(Deserialize::deserialize(d)?),
+        EventType::StageInstanceDelete => Event::StageInstanceDelete(Deserialize::deserialize(d)?),
+        EventType::ThreadCreate => Event::ThreadCreate(Deserialize::deserialize(d)?),
+        EventType::ThreadUpdate => Event::ThreadUpdate(Deserialize::deserialize(d)?),
+        EventType::ThreadDelete => Event::ThreadDelete(Deserialize::deserialize(d)?),
+        EventType::ThreadListSync => Event::ThreadListSync(Deserialize::deserialize(d)?),
+        EventType::ThreadMemberUpdate => Event::ThreadMemberUpdate(Deserialize::deserialize(d)?),
+        EventType::ThreadMembersUpdate => Event::ThreadMembersUpdate(Deserialize::deserialize(d)?),
         EventType::Other(kind) => Event::Unknown(UnknownEvent {
             kind,
-            value: v,
+            value: Value::deserialize(d)?,
+            error: None,
         }),
     })
 }
@@ -3101,3 +3341,40 @@ impl<'de> Deserialize<'de> for EventType {
         deserializer.deserialize_str(EventTypeVisitor)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde_json::json;
+
+    use super::{Event, GatewayEvent, UnknownEvent};
+
+    #[test]
+    fn test_unknown_dispatch() {
+        let payload = json!({"op": 0, "s": 3, "t": "SOMETHING_NEW", "d": {"id": "1"}});
+
+        match serde_json::from_value::<GatewayEvent>(payload).unwrap() {
+            GatewayEvent::Dispatch(3, Event::Unknown(unknown)) => {
+                assert_eq!(unknown.kind, "SOMETHING_NEW");
+                assert_eq!(unknown.value, json!({"id": "1"}));
+                assert!(unknown.error.is_none());
+            },
+            other => panic!("expected an unknown dispatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_dispatch() {
+        let payload = json!({"op": 0, "s": 3, "t": "CHANNEL_PINS_UPDATE", "d": {"channel_id": []}});
+
+        assert!(serde_json::from_value::<GatewayEvent>(payload.clone()).is_err());
+
+        let unknown = UnknownEvent::from_dispatch(payload).unwrap();
+        assert_eq!(unknown.kind, "CHANNEL_PINS_UPDATE");
+        assert_eq!(unknown.value, json!({"channel_id": []}));
+
+        // Only dispatches carry an event.
+        assert!(UnknownEvent::from_dispatch(json!({"op": 11})).is_none());
+        assert!(UnknownEvent::from_dispatch(json!({"op": 9, "d": false})).is_none());
+    }
+}
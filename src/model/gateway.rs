@@ -335,6 +335,101 @@ impl Activity {
             buttons: vec![],
         }
     }
+
+    /// Creates an [`Activity`] struct that appears as a custom status, only
+    /// showing the given `state`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::gateway::{Activity, ActivityType};
+    ///
+    /// let activity = Activity::custom("Taking a break");
+    ///
+    /// assert_eq!(activity.kind, ActivityType::Custom);
+    /// assert_eq!(activity.state.as_deref(), Some("Taking a break"));
+    /// ```
+    pub fn custom<S>(state: S) -> Activity
+    where
+        S: ToString,
+    {
+        Activity {
+            kind: ActivityType::Custom,
+            state: Some(state.to_string()),
+            // Discord requires a name, but shows the state instead.
+            ..Activity::playing("Custom Status")
+        }
+    }
+
+    /// Like [`Self::streaming`], but checks that `url` points to Twitch or
+    /// YouTube, the only services Discord shows streams from.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::gateway::Activity;
+    ///
+    /// assert!(Activity::try_streaming("Coding", "https://twitch.tv/serenity").is_ok());
+    /// assert!(Activity::try_streaming("Coding", "https://example.com").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::InvalidStreamUrl`] if the URL is not a Twitch
+    /// or YouTube URL.
+    ///
+    /// [`ModelError::InvalidStreamUrl`]: crate::model::ModelError::InvalidStreamUrl
+    pub fn try_streaming<N, U>(name: N, url: U) -> Result<Activity>
+    where
+        N: ToString,
+        U: ToString,
+    {
+        let activity = Activity::streaming(name, url);
+        activity.check_stream_url()?;
+
+        Ok(activity)
+    }
+
+    /// Sets what the user is doing, shown below the name of the activity.
+    #[must_use]
+    pub fn with_details<D>(mut self, details: D) -> Activity
+    where
+        D: ToString,
+    {
+        self.details = Some(details.to_string());
+        self
+    }
+
+    /// Sets the state of the activity, such as the text of a custom status.
+    #[must_use]
+    pub fn with_state<S>(mut self, state: S) -> Activity
+    where
+        S: ToString,
+    {
+        self.state = Some(state.to_string());
+        self
+    }
+
+    /// Checks that a streaming activity has a Twitch or YouTube URL.
+    ///
+    /// Activities of other kinds always pass.
+    pub(crate) fn check_stream_url(&self) -> Result<()> {
+        if self.kind != ActivityType::Streaming {
+            return Ok(());
+        }
+
+        let url = self.url.as_deref().unwrap_or_default();
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .and_then(|rest| rest.split('/').next())
+            .map(|host| host.strip_prefix("www.").unwrap_or(host));
+
+        match host {
+            Some("twitch.tv") | Some("youtube.com") => Ok(()),
+            _ => Err(Error::Model(ModelError::InvalidStreamUrl(url.to_string()))),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Activity {
@@ -701,6 +796,11 @@ pub struct Ready {
         deserialize_with = "deserialize_private_channels"
     )]
     pub private_channels: HashMap<ChannelId, Channel>,
+    /// The gateway URL to connect to when resuming the session.
+    ///
+    /// This is `None` for payloads from gateway versions without it.
+    #[serde(default)]
+    pub resume_gateway_url: Option<String>,
     pub session_id: String,
     pub shard: Option<[u64; 2]>,
     #[serde(default, rename = "_trace")]
@@ -722,7 +822,17 @@ pub struct SessionStartLimit {
     pub reset_after: u64,
     /// The total number of session starts within the ratelimit period allowed.
     pub total: u64,
+    /// The number of shards that may identify at the same time, as long as
+    /// they are in different rate limit buckets, i.e. their Ids modulo this
+    /// number differ.
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: u64,
 }
+
+fn default_max_concurrency() -> u64 {
+    1
+}
+
 /// Timestamps of when a user started and/or is ending their activity.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
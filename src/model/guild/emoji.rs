@@ -9,6 +9,8 @@ use crate::http::Http;
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::internal::prelude::*;
 #[cfg(all(feature = "cache", feature = "model"))]
+use crate::model::guild::Guild;
+#[cfg(all(feature = "cache", feature = "model"))]
 use crate::model::id::GuildId;
 use crate::model::id::{EmojiId, RoleId};
 use crate::model::user::User;
@@ -176,13 +178,15 @@ impl Emoji {
     /// ```
     #[cfg(feature = "cache")]
     pub async fn find_guild_id(&self, cache: impl AsRef<Cache>) -> Option<GuildId> {
-        for guild in cache.as_ref().guilds.read().await.values() {
+        let owns_emoji = |guild_id: &GuildId, guild: &Guild| {
             if guild.emojis.contains_key(&self.id) {
-                return Some(guild.id);
+                Some(*guild_id)
+            } else {
+                None
             }
-        }
+        };
 
-        None
+        cache.as_ref().guilds.find_map(owns_emoji).await
     }
 
     /// Generates a URL to the emoji's image.
@@ -251,3 +255,37 @@ impl<'a> From<&'a Emoji> for EmojiId {
         emoji.id
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let emoji: Emoji = assert_roundtrip(
+            r#"{
+                "id": "41771983429993937",
+                "name": "LUL",
+                "roles": ["41771983429993000", "41771983429993111"],
+                "user": {
+                    "username": "Luigi",
+                    "discriminator": "0002",
+                    "id": "96008815106887111",
+                    "avatar": "5500909a3274e1812beb4e8de6631111",
+                    "public_flags": 131328
+                },
+                "require_colons": true,
+                "managed": false,
+                "animated": true,
+                "available": true
+            }"#,
+        );
+
+        assert_eq!(emoji.id, EmojiId(41771983429993937));
+        assert_eq!(emoji.roles.len(), 2);
+        assert!(emoji.animated);
+        assert_eq!(emoji.user.unwrap().name, "Luigi");
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::internal::prelude::StdResult;
+
+macro_rules! guild_features {
+    ($($(#[$attr:meta])* $variant:ident => $name:literal,)*) => {
+        /// A feature of a guild. More information available at the
+        /// [`discord documentation`].
+        ///
+        /// Discord adds new features without notice, those are kept as
+        /// [`Self::Unknown`].
+        ///
+        /// [`discord documentation`]: https://discord.com/developers/docs/resources/guild#guild-object-guild-features
+        #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+        #[non_exhaustive]
+        pub enum GuildFeature {
+            $($(#[$attr])* $variant,)*
+            /// A feature that is not known to the library.
+            Unknown(String),
+        }
+
+        impl GuildFeature {
+            /// Returns the name of the feature as used by Discord, e.g.
+            /// `COMMUNITY`.
+            pub fn name(&self) -> &str {
+                match self {
+                    $(Self::$variant => $name,)*
+                    Self::Unknown(name) => name,
+                }
+            }
+        }
+
+        impl From<String> for GuildFeature {
+            fn from(name: String) -> Self {
+                match name.as_str() {
+                    $($name => Self::$variant,)*
+                    _ => Self::Unknown(name),
+                }
+            }
+        }
+    };
+}
+
+guild_features! {
+    /// The guild has access to set an animated icon.
+    AnimatedIcon => "ANIMATED_ICON",
+    /// The guild has access to set a banner.
+    Banner => "BANNER",
+    /// The guild has access to use commerce features.
+    Commerce => "COMMERCE",
+    /// The guild can enable the welcome screen, membership screening, stage
+    /// channels, discovery and receives community updates.
+    Community => "COMMUNITY",
+    /// The guild is able to be discovered in the directory.
+    Discoverable => "DISCOVERABLE",
+    /// The guild is able to be featured in the directory.
+    Featurable => "FEATURABLE",
+    /// The guild has access to set an invite splash background.
+    InviteSplash => "INVITE_SPLASH",
+    /// The guild has enabled membership screening.
+    MemberVerificationGateEnabled => "MEMBER_VERIFICATION_GATE_ENABLED",
+    /// The guild has access to create news channels.
+    News => "NEWS",
+    /// The guild is partnered.
+    Partnered => "PARTNERED",
+    /// The guild can be previewed before joining via membership screening or
+    /// the directory.
+    PreviewEnabled => "PREVIEW_ENABLED",
+    /// The guild has access to set a vanity URL.
+    VanityUrl => "VANITY_URL",
+    /// The guild is verified.
+    Verified => "VERIFIED",
+    /// The guild has access to set 384kbps bitrate in voice.
+    VipRegions => "VIP_REGIONS",
+    /// The guild has enabled the welcome screen.
+    WelcomeScreenEnabled => "WELCOME_SCREEN_ENABLED",
+    /// The guild has access to the three day archive time for threads.
+    ThreeDayThreadArchive => "THREE_DAY_THREAD_ARCHIVE",
+    /// The guild has access to the seven day archive time for threads.
+    SevenDayThreadArchive => "SEVEN_DAY_THREAD_ARCHIVE",
+    /// The guild has access to create private threads.
+    PrivateThreads => "PRIVATE_THREADS",
+}
+
+impl From<&str> for GuildFeature {
+    fn from(name: &str) -> Self {
+        Self::from(name.to_string())
+    }
+}
+
+impl Display for GuildFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for GuildFeature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl Serialize for GuildFeature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GuildFeature;
+
+    #[test]
+    fn test_name_round_trip() {
+        assert_eq!(GuildFeature::from("COMMUNITY"), GuildFeature::Community);
+        assert_eq!(GuildFeature::Community.name(), "COMMUNITY");
+
+        let unknown = GuildFeature::from("SHINY_NEW_FEATURE");
+        assert_eq!(unknown, GuildFeature::Unknown("SHINY_NEW_FEATURE".to_string()));
+        assert_eq!(unknown.to_string(), "SHINY_NEW_FEATURE");
+    }
+}
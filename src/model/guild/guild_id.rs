@@ -8,7 +8,7 @@ use crate::builder::CreateChannel;
 use crate::builder::{EditGuild, EditGuildWelcomeScreen, EditGuildWidget, EditMember, EditRole};
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::cache::Cache;
-#[cfg(feature = "collector")]
+#[cfg(feature = "gateway")]
 use crate::client::bridge::gateway::ShardMessenger;
 #[cfg(feature = "collector")]
 use crate::collector::{
@@ -77,6 +77,9 @@ impl GuildId {
 
     /// Ban a [`User`] from the guild with a reason. Refer to [`Self::ban`] to further documentation.
     ///
+    /// The `reason` is sent as the `X-Audit-Log-Reason` header and shows up in
+    /// the guild's audit log.
+    ///
     /// # Errors
     ///
     /// In addition to the reasons [`Self::ban`] may return an error, may
@@ -213,6 +216,61 @@ impl GuildId {
         http.as_ref().create_channel(self.0, &map).await
     }
 
+    /// Creates a [`GuildChannel`] in the guild, placed under the given
+    /// `category`.
+    ///
+    /// Refer to [`Self::create_channel`] for more information.
+    ///
+    /// Requires the [Manage Channels] permission.
+    ///
+    /// # Examples
+    ///
+    /// Create a text channel named `logs` in a category:
+    ///
+    /// ```rust,no_run
+    /// use serenity::model::id::{ChannelId, GuildId};
+    ///
+    /// # async fn run() {
+    /// # use serenity::http::Http;
+    /// # let http = Http::default();
+    /// let _channel = GuildId(7).create_channel_in_category(&http, ChannelId(8), |c| c.name("logs")).await;
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the `cache` is enabled and the category is cached, returns a
+    /// [`ModelError::InvalidChannelType`] if `category` is not a category,
+    /// or a [`ModelError::WrongGuild`] if it belongs to another guild.
+    ///
+    /// Otherwise returns [`Error::Http`] if the current user lacks permission,
+    /// or if invalid values are set.
+    ///
+    /// [Manage Channels]: Permissions::MANAGE_CHANNELS
+    pub async fn create_channel_in_category(
+        self,
+        cache_http: impl CacheHttp,
+        category: impl Into<ChannelId>,
+        f: impl FnOnce(&mut CreateChannel) -> &mut CreateChannel,
+    ) -> Result<GuildChannel> {
+        let category = category.into();
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(cache) = cache_http.cache() {
+                match cache.channel(category).await {
+                    Some(Channel::Category(channel)) if channel.guild_id != self => {
+                        return Err(Error::Model(ModelError::WrongGuild));
+                    },
+                    Some(Channel::Category(_)) | None => {},
+                    Some(_) => return Err(Error::Model(ModelError::InvalidChannelType)),
+                }
+            }
+        }
+
+        self.create_channel(cache_http.http(), |c| f(c).category(category)).await
+    }
+
     /// Creates an emoji in the guild with a name and base64-encoded image.
     ///
     /// Refer to the documentation for [`Guild::create_emoji`] for more
@@ -303,6 +361,53 @@ impl GuildId {
         Ok(role)
     }
 
+    /// Returns the guild's role with the given `name`, creating it with the
+    /// data set in `f` if no such role exists yet.
+    ///
+    /// This is useful for roles a bot manages itself, such as a "Muted" role,
+    /// where calling this repeatedly must not create duplicates. The `name`
+    /// always takes precedence over any name set in `f`.
+    ///
+    /// **Note**: Requires the [Manage Roles] permission if the role has to be
+    /// created.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use serenity::model::id::GuildId;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use serenity::http::Http;
+    /// # let http = Http::default();
+    /// let muted = GuildId(7).get_or_create_role(&http, "Muted", |r| r.hoist(false)).await?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the guild's roles could not be retrieved,
+    /// or if the role had to be created and the current user lacks permission.
+    ///
+    /// [Manage Roles]: Permissions::MANAGE_ROLES
+    pub async fn get_or_create_role<F>(
+        self,
+        http: impl AsRef<Http>,
+        name: &str,
+        f: F,
+    ) -> Result<Role>
+    where
+        F: FnOnce(&mut EditRole) -> &mut EditRole,
+    {
+        let roles = http.as_ref().get_guild_roles(self.0).await?;
+
+        if let Some(role) = roles.into_iter().find(|role| role.name == name) {
+            return Ok(role);
+        }
+
+        self.create_role(&http, |r| f(r).name(name)).await
+    }
+
     /// Deletes the current guild if the current account is the owner of the
     /// guild.
     ///
@@ -403,6 +508,52 @@ impl GuildId {
         http.as_ref().edit_guild(self.0, &map).await
     }
 
+    /// Enables and disables features of the guild, such as
+    /// [`GuildFeature::Community`] or [`GuildFeature::Discoverable`], keeping
+    /// all of its other features as they are.
+    ///
+    /// As Discord expects the complete list of features, this first fetches
+    /// the guild to retrieve its current features.
+    ///
+    /// **Note**: Requires the current user to have the [Manage Guild]
+    /// permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if
+    /// the guild does not meet the requirements of a feature to enable.
+    ///
+    /// [Manage Guild]: Permissions::MANAGE_GUILD
+    /// [`Error::Http`]: crate::error::Error::Http
+    pub async fn set_community_features(
+        self,
+        http: impl AsRef<Http>,
+        enable: &[GuildFeature],
+        disable: &[GuildFeature],
+    ) -> Result<PartialGuild> {
+        let http = http.as_ref();
+        let guild = self.to_partial_guild(http).await?;
+
+        let mut features: Vec<GuildFeature> = guild
+            .features
+            .into_iter()
+            .map(GuildFeature::from)
+            .filter(|f| !disable.contains(f))
+            .collect();
+
+        for feature in enable {
+            if !features.contains(feature) {
+                features.push(feature.clone());
+            }
+        }
+
+        let mut edit_guild = EditGuild::default();
+        edit_guild.features(features);
+        let map = utils::hashmap_to_json_map(edit_guild.0);
+
+        http.edit_guild(self.0, &map).await
+    }
+
     /// Edits an [`Emoji`]'s name in the guild.
     ///
     /// Also see [`Emoji::edit`] if you have the `cache` and `methods` features
@@ -654,6 +805,19 @@ impl GuildId {
         http.as_ref().get_guild_with_counts(self.0).await
     }
 
+    /// Gets all active threads of the guild, along with a thread member for
+    /// each thread the current user has joined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the current user is not in the guild.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    #[inline]
+    pub async fn get_active_threads(self, http: impl AsRef<Http>) -> Result<ThreadsData> {
+        http.as_ref().get_guild_active_threads(self.0).await
+    }
+
     /// Gets all [`Emoji`]s of this guild via HTTP.
     ///
     /// # Errors
@@ -730,11 +894,17 @@ impl GuildId {
         http.as_ref().kick_member(self.0, user_id.into().0).await
     }
 
-    #[inline]
+    /// Kicks a [`Member`] from the guild with a reason. Refer to [`Self::kick`]
+    /// for further documentation.
+    ///
+    /// The `reason` is sent as the `X-Audit-Log-Reason` header and shows up in
+    /// the guild's audit log.
+    ///
     /// # Errors
     ///
     /// In addition to the reasons [`Self::kick`] may return an error,
     /// may also return an error if the reason is too long.
+    #[inline]
     pub async fn kick_with_reason(
         self,
         http: impl AsRef<Http>,
@@ -757,6 +927,41 @@ impl GuildId {
         http.as_ref().leave_guild(self.0).await
     }
 
+    /// Joins a voice or stage channel of the guild, or moves to it if the
+    /// current user is already in another one.
+    ///
+    /// Refer to [`ShardMessenger::update_voice_state`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Context;
+    /// # use serenity::model::id::{ChannelId, GuildId};
+    /// # fn run(ctx: &Context, guild_id: GuildId) {
+    /// guild_id.join_voice_channel(ctx, ChannelId(7));
+    /// # }
+    /// ```
+    #[cfg(feature = "gateway")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gateway")))]
+    #[inline]
+    pub fn join_voice_channel(
+        self,
+        shard_messenger: impl AsRef<ShardMessenger>,
+        channel_id: impl Into<ChannelId>,
+    ) {
+        shard_messenger.as_ref().update_voice_state(self, Some(channel_id.into()), false, false);
+    }
+
+    /// Leaves the voice or stage channel the current user is in, if any.
+    ///
+    /// Refer to [`ShardMessenger::update_voice_state`] for more information.
+    #[cfg(feature = "gateway")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gateway")))]
+    #[inline]
+    pub fn leave_voice_channel(self, shard_messenger: impl AsRef<ShardMessenger>) {
+        shard_messenger.as_ref().update_voice_state(self, None, false, false);
+    }
+
     /// Gets a user's [`Member`] for the guild by Id.
     ///
     /// If the cache feature is enabled the cache will be checked
@@ -1165,7 +1370,7 @@ impl GuildId {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command(f)?;
         http.as_ref().create_guild_application_command(self.0, &Value::Object(map)).await
     }
 
@@ -1184,6 +1389,8 @@ impl GuildId {
 
         f(&mut array);
 
+        array.0.iter().filter_map(Value::as_object).try_for_each(ApplicationCommand::check_names)?;
+
         http.as_ref().create_guild_application_commands(self.0, &Value::Array(array.0)).await
     }
 
@@ -1271,7 +1478,7 @@ impl GuildId {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command(f)?;
         http.as_ref()
             .edit_guild_application_command(self.0, command_id.into(), &Value::Object(map))
             .await
@@ -1516,3 +1723,34 @@ impl Display for GuildWidgetStyle {
         }
     }
 }
+
+#[cfg(all(test, feature = "model"))]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::http::mock::{response, MockServer};
+    use crate::model::id::{GuildId, UserId};
+
+    #[tokio::test]
+    async fn test_kick_with_reason() {
+        let server = MockServer::new(vec![response("204 No Content", &[], "")]);
+        let http = server.http().await;
+
+        GuildId(1).kick_with_reason(&http, UserId(2), "Spamming links").await.unwrap();
+
+        let request = server.next_request();
+        assert!(request.starts_with("DELETE /api/v9/guilds/1/members/2 "));
+        assert!(request.contains("x-audit-log-reason: Spamming%20links\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_kick_without_reason() {
+        let server = MockServer::new(vec![response("204 No Content", &[], "")]);
+        let http = server.http().await;
+
+        GuildId(1).kick(&http, UserId(2)).await.unwrap();
+
+        let request = server.next_request();
+        assert!(request.starts_with("DELETE /api/v9/guilds/1/members/2 "));
+        assert!(!request.contains("x-audit-log-reason"));
+    }
+}
@@ -319,7 +319,7 @@ impl Member {
     /// Kicks a member from it's guild, with an optional reason:
     ///
     /// ```rust,ignore
-    /// match member.kick(&ctx.http, "A Reason").await {
+    /// match member.kick(ctx.http(), "A Reason").await {
     ///     Ok(()) => println!("Successfully kicked member"),
     ///     Err(Error::Model(ModelError::GuildNotFound)) => {
     ///         println!("Couldn't determine guild of member");
@@ -341,7 +341,7 @@ impl Member {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(guild) = cache.guilds.read().await.get(&self.guild_id) {
+                if let Some(guild) = cache.guilds.read(&self.guild_id).await.get(&self.guild_id) {
                     let req = Permissions::KICK_MEMBERS;
 
                     if !guild.has_perms(&cache_http, req).await {
@@ -645,3 +645,37 @@ impl Serialize for ThreadMemberFlags {
         serializer.serialize_u64(self.bits())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let member: Member = assert_roundtrip(
+            r#"{
+                "guild_id": "41771983423143937",
+                "user": {
+                    "id": "80351110224678912",
+                    "username": "Nelly",
+                    "discriminator": "1337",
+                    "avatar": "8342729096ea3675442027381ff50dfe"
+                },
+                "nick": "NOT API SUPPORT",
+                "avatar": null,
+                "roles": ["41771983423143936"],
+                "joined_at": "2015-04-26T06:26:56.936000+00:00",
+                "premium_since": null,
+                "deaf": false,
+                "mute": false,
+                "pending": false
+            }"#,
+        );
+
+        assert_eq!(member.guild_id, GuildId(41771983423143937));
+        assert_eq!(member.user.id, UserId(80351110224678912));
+        assert_eq!(member.nick.as_deref(), Some("NOT API SUPPORT"));
+        assert_eq!(member.roles, vec![RoleId(41771983423143936)]);
+    }
+}
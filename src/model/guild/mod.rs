@@ -5,6 +5,7 @@
 
 mod audit_log;
 mod emoji;
+mod feature;
 mod guild_id;
 mod guild_preview;
 mod integration;
@@ -27,6 +28,7 @@ use tracing::warn;
 
 pub use self::audit_log::*;
 pub use self::emoji::*;
+pub use self::feature::*;
 pub use self::guild_id::*;
 pub use self::guild_preview::*;
 pub use self::integration::*;
@@ -35,6 +37,16 @@ pub use self::partial_guild::*;
 pub use self::premium_tier::*;
 pub use self::role::*;
 pub use self::system_channel::*;
+pub use super::utils::{
+    deserialize_emojis_split,
+    deserialize_guild_channels_sorted,
+    deserialize_members_with_name_index,
+    deserialize_presences_into,
+    deserialize_roles_with_everyone,
+    diff_roles,
+    partition_members_by_presence,
+    RoleDiff,
+};
 use super::utils::*;
 #[cfg(feature = "model")]
 use crate::builder::{
@@ -130,6 +142,7 @@ pub struct Guild {
     /// - `SEVEN_DAY_THREAD_ARCHIVE`
     /// - `PRIVATE_THREADS`
     ///
+    /// These can be converted into a [`GuildFeature`] for matching.
     ///
     /// [`discord documentation`]: https://discord.com/developers/docs/resources/guild#guild-object-guild-features
     pub features: Vec<String>,
@@ -2340,7 +2353,7 @@ impl<'de> Deserialize<'de> for Guild {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let mut map = JsonMap::deserialize(deserializer)?;
 
-        let id = map.get("id").and_then(|x| x.as_str()).and_then(|x| x.parse::<u64>().ok());
+        let id = map.get("id").and_then(snowflake_of);
 
         if let Some(guild_id) = id {
             if let Some(array) = map.get_mut("channels").and_then(|x| x.as_array_mut()) {
@@ -2514,44 +2527,24 @@ impl<'de> Deserialize<'de> for Guild {
             .and_then(String::deserialize)
             .map_err(DeError::custom)?;
 
-        let welcome_screen = match map.contains_key("welcome_screen") {
-            true => Some(
-                map.remove("welcome_screen")
-                    .ok_or_else(|| DeError::custom("expected welcome_screen"))
-                    .and_then(GuildWelcomeScreen::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
+        let welcome_screen = match map.remove("welcome_screen") {
+            Some(v) => Option::<GuildWelcomeScreen>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
         };
 
-        let approximate_member_count = match map.contains_key("approximate_member_count") {
-            true => Some(
-                map.remove("approximate_member_count")
-                    .ok_or_else(|| DeError::custom("expected approximate_member_count"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
+        let approximate_member_count = match map.remove("approximate_member_count") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
         };
 
-        let approximate_presence_count = match map.contains_key("approximate_presence_count") {
-            true => Some(
-                map.remove("approximate_presence_count")
-                    .ok_or_else(|| DeError::custom("expected approximate_presence_count"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
+        let approximate_presence_count = match map.remove("approximate_presence_count") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
         };
 
-        let max_video_channel_users = match map.contains_key("max_video_channel_users") {
-            true => Some(
-                map.remove("max_video_channel_users")
-                    .ok_or_else(|| DeError::custom("expected max_video_channel_users"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
+        let max_video_channel_users = match map.remove("max_video_channel_users") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
         };
 
         let max_presences = match map.remove("max_presences") {
@@ -2559,14 +2552,9 @@ impl<'de> Deserialize<'de> for Guild {
             None => None,
         };
 
-        let max_members = match map.contains_key("max_members") {
-            true => Some(
-                map.remove("max_members")
-                    .ok_or_else(|| DeError::custom("expected max_members"))
-                    .and_then(u64::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
+        let max_members = match map.remove("max_members") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
         };
 
         let discovery_splash = match map.remove("discovery_splash") {
@@ -3125,6 +3113,87 @@ enum_number!(NsfwLevel {
 
 #[cfg(test)]
 mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let guild: Guild = assert_roundtrip(
+            r#"{
+                "id": "41771983423143937",
+                "name": "Discord Developers",
+                "icon": "86e39f7ae3307e811784e2ffd11a7310",
+                "afk_timeout": 300,
+                "afk_channel_id": null,
+                "channels": [{
+                    "id": "41771983423143938",
+                    "name": "general",
+                    "type": 0,
+                    "position": 0,
+                    "permission_overwrites": []
+                }],
+                "default_message_notifications": 1,
+                "emojis": [{
+                    "id": "41771983429993937",
+                    "name": "LUL",
+                    "roles": [],
+                    "require_colons": true,
+                    "managed": false,
+                    "animated": false,
+                    "available": true
+                }],
+                "explicit_content_filter": 2,
+                "features": ["COMMUNITY"],
+                "joined_at": "2021-08-01T00:00:00Z",
+                "large": false,
+                "member_count": 1,
+                "members": [{
+                    "user": {
+                        "id": "80351110224678912",
+                        "username": "Nelly",
+                        "discriminator": "1337",
+                        "avatar": null
+                    },
+                    "roles": [],
+                    "joined_at": "2015-04-26T06:26:56.936000+00:00",
+                    "deaf": false,
+                    "mute": false
+                }],
+                "mfa_level": 1,
+                "owner_id": "80351110224678912",
+                "presences": [],
+                "region": "us-west",
+                "roles": [{
+                    "id": "41771983423143937",
+                    "name": "@everyone",
+                    "color": 0,
+                    "hoist": false,
+                    "position": 0,
+                    "permissions": "104324673",
+                    "managed": false,
+                    "mentionable": false
+                }],
+                "verification_level": 3,
+                "voice_states": [],
+                "preferred_locale": "en-US",
+                "nsfw": false,
+                "nsfw_level": 0,
+                "system_channel_flags": 0,
+                "approximate_member_count": 1
+            }"#,
+        );
+
+        let channel_id = ChannelId(41771983423143938);
+        let user_id = UserId(80351110224678912);
+
+        assert_eq!(guild.channels[&channel_id].guild_id, guild.id);
+        assert_eq!(guild.members[&user_id].guild_id, guild.id);
+        assert_eq!(guild.roles.len(), 1);
+        assert_eq!(guild.emojis.len(), 1);
+        assert_eq!(guild.approximate_member_count, Some(1));
+        assert!(guild.max_members.is_none());
+    }
+
     #[cfg(feature = "model")]
     mod model {
         use std::collections::*;
@@ -28,7 +28,7 @@ use crate::collector::{
 #[cfg(feature = "model")]
 use crate::http::{CacheHttp, Http};
 use crate::model::prelude::*;
-use crate::model::utils::{deserialize_emojis, deserialize_roles};
+use crate::model::utils::{deserialize_emojis, deserialize_roles, snowflake_of};
 #[cfg(all(feature = "model", feature = "unstable_discord_api"))]
 use crate::{
     builder::{
@@ -1468,7 +1468,7 @@ impl<'de> Deserialize<'de> for PartialGuild {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let mut map = JsonMap::deserialize(deserializer)?;
 
-        let id = map.get("id").and_then(|x| x.as_str()).and_then(|x| x.parse::<u64>().ok());
+        let id = map.get("id").and_then(snowflake_of);
 
         if let Some(guild_id) = id {
             if let Some(array) = map.get_mut("roles").and_then(|x| x.as_array_mut()) {
@@ -4,6 +4,7 @@ use std::cmp::Ordering;
 use async_trait::async_trait;
 #[cfg(feature = "model")]
 use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Serialize, SerializeMap, Serializer};
 
 #[cfg(feature = "model")]
 use crate::builder::EditRole;
@@ -27,7 +28,10 @@ use crate::utils::parse_role;
 /// are unique per guild and do not cross over to other guilds in any way, and
 /// can have channel-specific permission overrides in addition to guild-level
 /// permissions.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+///
+/// Roles compare by value, so an updated role differs from its previous
+/// version. Compare their [`Self::id`]s to tell whether they are the same role.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct Role {
     /// The Id of the role. Can be used to calculate the role's creation date.
@@ -159,8 +163,6 @@ impl Display for Role {
     }
 }
 
-impl Eq for Role {}
-
 impl Ord for Role {
     fn cmp(&self, other: &Role) -> Ordering {
         if self.position == other.position {
@@ -171,12 +173,6 @@ impl Ord for Role {
     }
 }
 
-impl PartialEq for Role {
-    fn eq(&self, other: &Role) -> bool {
-        self.id == other.id
-    }
-}
-
 impl PartialOrd for Role {
     fn partial_cmp(&self, other: &Role) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -188,17 +184,7 @@ impl RoleId {
     /// Tries to find the [`Role`] by its Id in the cache.
     #[cfg(feature = "cache")]
     pub async fn to_role_cached(self, cache: impl AsRef<Cache>) -> Option<Role> {
-        for guild in cache.as_ref().guilds.read().await.values() {
-            if !guild.roles.contains_key(&self) {
-                continue;
-            }
-
-            if let Some(role) = guild.roles.get(&self) {
-                return Some(role.clone());
-            }
-        }
-
-        None
+        cache.as_ref().guilds.find_map(|_, guild| guild.roles.get(&self).cloned()).await
     }
 }
 
@@ -236,7 +222,7 @@ impl FromStrAndCache for Role {
 }
 
 /// The tags of a [`Role`].
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct RoleTags {
     /// The Id of the bot the [`Role`] belongs to.
@@ -244,7 +230,6 @@ pub struct RoleTags {
     /// The Id of the integration the [`Role`] belongs to.
     pub integration_id: Option<IntegrationId>,
     /// Whether this is the guild's premium subscriber role.
-    #[serde(default)]
     pub premium_subscriber: bool,
 }
 
@@ -281,3 +266,72 @@ impl<'de> Deserialize<'de> for RoleTags {
         })
     }
 }
+
+impl Serialize for RoleTags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        // Like Discord, only include the tags that are set, as the
+        // deserialization relies on their presence. The premium subscriber
+        // tag is `null` when set.
+        let mut map = serializer.serialize_map(None)?;
+
+        if let Some(bot_id) = &self.bot_id {
+            map.serialize_entry("bot_id", bot_id)?;
+        }
+
+        if let Some(integration_id) = &self.integration_id {
+            map.serialize_entry("integration_id", integration_id)?;
+        }
+
+        if self.premium_subscriber {
+            map.serialize_entry("premium_subscriber", &())?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let role: Role = assert_roundtrip(
+            r#"{
+                "id": "41771983423143936",
+                "guild_id": "41771983423143937",
+                "name": "WE DEM BOYZZ!!!!!!",
+                "color": 3447003,
+                "hoist": true,
+                "position": 1,
+                "permissions": "66321471",
+                "managed": false,
+                "mentionable": false,
+                "tags": {"bot_id": "41771983423143938"}
+            }"#,
+        );
+
+        assert_eq!(role.permissions.bits(), 66321471);
+        assert_eq!(role.tags.bot_id, Some(UserId(41771983423143938)));
+        assert!(!role.tags.premium_subscriber);
+
+        let role: Role = assert_roundtrip(
+            r#"{
+                "id": "41771983423143936",
+                "guild_id": "41771983423143937",
+                "name": "Server Booster",
+                "color": 0,
+                "hoist": false,
+                "position": 2,
+                "permissions": "0",
+                "managed": true,
+                "mentionable": false,
+                "tags": {"premium_subscriber": null}
+            }"#,
+        );
+
+        assert!(role.tags.premium_subscriber);
+        assert!(role.tags.bot_id.is_none());
+    }
+}
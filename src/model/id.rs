@@ -5,21 +5,81 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::de::{Deserialize, Deserializer};
 
+pub use super::utils::{deserialize_nonzero_u64, deserialize_optional_id, optional_id};
 use super::utils::U64Visitor;
+use crate::constants::DISCORD_EPOCH;
 use crate::internal::prelude::*;
 
+/// The fields encoded in a Discord snowflake, as described in the
+/// [Discord documentation].
+///
+/// This is mostly useful for debugging, as the worker and process Ids reveal
+/// which of Discord's internal processes generated an Id.
+///
+/// [Discord documentation]: https://discord.com/developers/docs/reference#snowflakes
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct SnowflakeParts {
+    /// The time the snowflake was generated at, with millisecond precision.
+    pub timestamp: DateTime<Utc>,
+    /// The internal worker Id.
+    pub worker_id: u8,
+    /// The internal process Id.
+    pub process_id: u8,
+    /// Incremented for every Id generated on the process.
+    pub increment: u16,
+}
+
+impl SnowflakeParts {
+    /// Extracts the timestamp, worker Id, process Id and increment from a raw
+    /// snowflake.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::id::SnowflakeParts;
+    ///
+    /// let parts = SnowflakeParts::decompose(175928847299117063);
+    ///
+    /// assert_eq!(parts.timestamp.timestamp_millis(), 1462015105796);
+    /// assert_eq!(parts.worker_id, 1);
+    /// assert_eq!(parts.process_id, 0);
+    /// assert_eq!(parts.increment, 7);
+    /// ```
+    pub fn decompose(id: u64) -> Self {
+        Self {
+            timestamp: snowflake_timestamp(id),
+            worker_id: ((id & 0x3E_0000) >> 17) as u8,
+            process_id: ((id & 0x1_F000) >> 12) as u8,
+            increment: (id & 0xFFF) as u16,
+        }
+    }
+}
+
+pub(crate) fn snowflake_timestamp(id: u64) -> DateTime<Utc> {
+    let millis_since_unix = (id >> 22) + DISCORD_EPOCH;
+    let secs = millis_since_unix / 1000;
+    let nanos = (millis_since_unix % 1000) * 1_000_000; // 1 million nanoseconds in a millisecond
+
+    let tm = NaiveDateTime::from_timestamp(secs as i64, nanos as u32);
+    DateTime::from_utc(tm, Utc)
+}
+
 macro_rules! id_u64 {
     ($($name:ident;)*) => {
         $(
             impl $name {
                 /// Retrieves the time that the Id was created at.
                 pub fn created_at(&self) -> DateTime<Utc> {
-                    let offset = self.0 >> 22;
-                    let secs = offset / 1000;
-                    let millis = (offset % 1000) * 1_000_000; // 1 million nanoseconds in a millisecond
+                    snowflake_timestamp(self.0)
+                }
 
-                    let tm = NaiveDateTime::from_timestamp(1_420_070_400 + secs as i64, millis as u32);
-                    DateTime::from_utc(tm, Utc)
+                /// Splits the Id into the individual fields encoded in it.
+                ///
+                /// Refer to [`SnowflakeParts::decompose`] for more information.
+                #[inline]
+                pub fn snowflake_parts(&self) -> SnowflakeParts {
+                    SnowflakeParts::decompose(self.0)
                 }
 
                 /// Immutably borrow inner Id.
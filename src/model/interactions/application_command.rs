@@ -12,11 +12,12 @@ use crate::builder::{
     EditInteractionResponse,
 };
 use crate::http::Http;
-use crate::internal::prelude::{JsonMap, StdResult, Value};
+use crate::internal::prelude::{Error, JsonMap, StdResult, Value};
 use crate::model::channel::PartialChannel;
 use crate::model::guild::{Member, PartialMember, Role};
 use crate::model::id::{
     ApplicationId,
+    AttachmentId,
     ChannelId,
     CommandId,
     GuildId,
@@ -24,6 +25,7 @@ use crate::model::id::{
     RoleId,
     UserId,
 };
+use crate::model::interactions::response_builder::InteractionResponseBuilder;
 use crate::model::interactions::InteractionType;
 use crate::model::prelude::User;
 use crate::model::utils::{
@@ -33,6 +35,7 @@ use crate::model::utils::{
     deserialize_partial_members_map,
     deserialize_roles_map,
     deserialize_users,
+    snowflake_of,
 };
 use crate::utils;
 
@@ -68,6 +71,33 @@ pub struct ApplicationCommandInteraction {
 }
 
 impl ApplicationCommandInteraction {
+    /// Gets the value of the option with the given `name`, if it was given
+    /// and is of type `T`.
+    ///
+    /// Refer to [`ApplicationCommandInteractionData::get_option`] for more
+    /// information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::model::interactions::application_command::ApplicationCommandInteraction;
+    /// # use serenity::model::id::UserId;
+    /// # fn run(command: &ApplicationCommandInteraction) {
+    /// let user: Option<UserId> = command.get_option("user");
+    /// let reason = command.get_option::<String>("reason").unwrap_or_default();
+    /// # }
+    /// ```
+    pub fn get_option<T: FromOptionValue>(&self, name: &str) -> Option<T> {
+        self.data.get_option(name)
+    }
+
+    /// Creates an [`InteractionResponseBuilder`] for responding to this
+    /// interaction over several steps, such as deferring the response and
+    /// following up on it later.
+    pub fn response_builder<'a>(&'a self, http: &'a Http) -> InteractionResponseBuilder<'a> {
+        InteractionResponseBuilder::new(http, self.id, &self.token)
+    }
+
     /// Gets the interaction response.
     ///
     /// # Errors
@@ -248,7 +278,7 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteraction {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let mut map = JsonMap::deserialize(deserializer)?;
 
-        let id = map.get("guild_id").and_then(|x| x.as_str()).and_then(|x| x.parse::<u64>().ok());
+        let id = map.get("guild_id").and_then(snowflake_of);
 
         if let Some(guild_id) = id {
             if let Some(member) = map.get_mut("member").and_then(|x| x.as_object_mut()) {
@@ -384,6 +414,32 @@ pub struct ApplicationCommandInteractionData {
     pub resolved: ApplicationCommandInteractionDataResolved,
 }
 
+impl ApplicationCommandInteractionData {
+    /// Gets the value of the option with the given `name`, if it was given
+    /// and is of type `T`.
+    ///
+    /// Options of an invoked subcommand or subcommand group are searched as
+    /// well.
+    pub fn get_option<T: FromOptionValue>(&self, name: &str) -> Option<T> {
+        let option = find_option(&self.options, name)?;
+
+        option.value.as_ref().and_then(T::from_option_value)
+    }
+}
+
+fn find_option<'a>(
+    options: &'a [ApplicationCommandInteractionDataOption],
+    name: &str,
+) -> Option<&'a ApplicationCommandInteractionDataOption> {
+    use ApplicationCommandOptionType::{SubCommand, SubCommandGroup};
+
+    options.iter().find_map(|option| match option.kind {
+        SubCommand | SubCommandGroup => find_option(&option.options, name),
+        _ if option.name == name => Some(option),
+        _ => None,
+    })
+}
+
 impl<'de> Deserialize<'de> for ApplicationCommandInteractionData {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let mut map = JsonMap::deserialize(deserializer)?;
@@ -577,6 +633,49 @@ pub enum ApplicationCommandInteractionDataOptionValue {
     Number(f64),
 }
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// A type that the value of an [`ApplicationCommandInteractionDataOption`]
+/// can be read as.
+///
+/// This trait is sealed, and implemented for [`String`], [`i64`], [`f64`],
+/// [`bool`], [`UserId`], [`ChannelId`], [`RoleId`] and [`AttachmentId`].
+/// Ids are read from the raw option value, so they are available without
+/// the resolved objects.
+///
+/// Used by [`ApplicationCommandInteraction::get_option`].
+pub trait FromOptionValue: private::Sealed + Sized {
+    /// Reads the value of the option, if it is of this type.
+    fn from_option_value(value: &Value) -> Option<Self>;
+}
+
+macro_rules! from_option_value {
+    ($($ty:ty => |$value:ident| $read:expr;)*) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl FromOptionValue for $ty {
+                fn from_option_value($value: &Value) -> Option<Self> {
+                    $read
+                }
+            }
+        )*
+    };
+}
+
+from_option_value! {
+    String => |value| value.as_str().map(String::from);
+    i64 => |value| value.as_i64();
+    f64 => |value| value.as_f64();
+    bool => |value| value.as_bool();
+    UserId => |value| snowflake_of(value).map(UserId);
+    ChannelId => |value| snowflake_of(value).map(ChannelId);
+    RoleId => |value| snowflake_of(value).map(RoleId);
+    AttachmentId => |value| snowflake_of(value).map(AttachmentId::from);
+}
+
 fn default_permission_value() -> bool {
     true
 }
@@ -667,6 +766,9 @@ impl ApplicationCommand {
     ///
     /// # Errors
     ///
+    /// Returns an [`Error::Model`] if the name of the command or of one of its
+    /// options is not 1 to 32 word characters or dashes.
+    ///
     /// May return an [`Error::Http`] if the [`ApplicationCommand`] is illformed,
     /// such as if more than 10 [`choices`] are set. See the [API Docs] for further details.
     ///
@@ -676,6 +778,7 @@ impl ApplicationCommand {
     /// [`ApplicationCommand`]: crate::model::interactions::application_command::ApplicationCommand
     /// [`InteractionCreate`]: crate::client::EventHandler::interaction_create
     /// [API Docs]: https://discord.com/developers/docs/interactions/slash-commands
+    /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
     /// [`choices`]: crate::model::interactions::application_command::ApplicationCommandOption::choices
@@ -686,7 +789,7 @@ impl ApplicationCommand {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command(f)?;
         http.as_ref().create_global_application_command(&Value::Object(map)).await
     }
 
@@ -704,6 +807,8 @@ impl ApplicationCommand {
 
         f(&mut array);
 
+        array.0.iter().filter_map(Value::as_object).try_for_each(Self::check_names)?;
+
         http.as_ref().create_global_application_commands(&Value::Array(array.0)).await
     }
 
@@ -716,7 +821,7 @@ impl ApplicationCommand {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command(f)?;
         http.as_ref().edit_global_application_command(command_id.into(), &Value::Object(map)).await
     }
 
@@ -743,14 +848,44 @@ impl ApplicationCommand {
         http.as_ref().delete_global_application_command(command_id.into()).await
     }
 
+    /// Builds an application command, checking its names.
+    ///
+    /// Refer to [`Self::check_names`] for more information.
     #[inline]
-    pub(crate) fn build_application_command<F>(f: F) -> Map<String, Value>
+    pub(crate) fn build_application_command<F>(f: F) -> Result<Map<String, Value>>
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
         let mut create_application_command = CreateApplicationCommand::default();
         f(&mut create_application_command);
-        utils::hashmap_to_json_map(create_application_command.0)
+        let map = utils::hashmap_to_json_map(create_application_command.0);
+
+        Self::check_names(&map)?;
+
+        Ok(map)
+    }
+
+    /// Checks that the names of a built application command and of its options
+    /// are 1 to 32 word characters or dashes, i.e. match `^[\w-]{1,32}$`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::InvalidCommandName`] with the first invalid
+    /// name.
+    pub(crate) fn check_names(map: &JsonMap) -> Result<()> {
+        if let Some(name) = map.get("name").and_then(Value::as_str) {
+            let len = name.chars().count();
+            let is_valid = (1..=32).contains(&len)
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+
+            if !is_valid {
+                return Err(Error::Model(ModelError::InvalidCommandName(name.to_string())));
+            }
+        }
+
+        let options = map.get("options").and_then(Value::as_array).into_iter().flatten();
+
+        options.filter_map(Value::as_object).try_for_each(Self::check_names)
     }
 }
 
@@ -816,6 +951,29 @@ pub struct ApplicationCommandPermissionData {
     pub permission: bool,
 }
 
+/// A permission override of an [`ApplicationCommand`] for a role or a user.
+pub type CommandPermission = ApplicationCommandPermissionData;
+
+impl ApplicationCommandPermissionData {
+    /// Creates an override allowing or denying the role to use the command.
+    pub fn role(role_id: impl Into<RoleId>, permission: bool) -> Self {
+        Self {
+            id: role_id.into().into(),
+            kind: ApplicationCommandPermissionType::Role,
+            permission,
+        }
+    }
+
+    /// Creates an override allowing or denying the user to use the command.
+    pub fn user(user_id: impl Into<UserId>, permission: bool) -> Self {
+        Self {
+            id: user_id.into().into(),
+            kind: ApplicationCommandPermissionType::User,
+            permission,
+        }
+    }
+}
+
 impl CommandPermissionId {
     /// Converts this [`CommandPermissionId`] to [`UserId`].
     pub fn to_user_id(self) -> UserId {
@@ -910,6 +1068,44 @@ enum_number!(ApplicationCommandPermissionType {
     User
 });
 
+/// A context in which an [`ApplicationCommand`] can be used.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum InteractionContextType {
+    /// In guilds.
+    Guild = 0,
+    /// In the direct messages with the bot.
+    BotDm = 1,
+    /// In group direct messages and direct messages other than with the bot.
+    PrivateChannel = 2,
+    Unknown = !0,
+}
+
+enum_number!(InteractionContextType {
+    Guild,
+    BotDm,
+    PrivateChannel
+});
+
+/// A way an application can be installed, for which an [`ApplicationCommand`]
+/// can be available.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum ApplicationIntegrationType {
+    /// Installed to a guild.
+    GuildInstall = 0,
+    /// Installed to a user.
+    UserInstall = 1,
+    Unknown = !0,
+}
+
+enum_number!(ApplicationIntegrationType {
+    GuildInstall,
+    UserInstall
+});
+
 /// The only valid values a user can pick in an [`ApplicationCommandOption`].
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -919,3 +1115,123 @@ pub struct ApplicationCommandOptionChoice {
     /// The choice value.
     pub value: Value,
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde_json::json;
+
+    use super::{
+        ApplicationCommand,
+        ApplicationCommandInteractionData,
+        ApplicationIntegrationType,
+        InteractionContextType,
+    };
+    use crate::model::channel::ChannelType;
+    use crate::model::interactions::application_command::ApplicationCommandOptionType;
+    use crate::model::id::UserId;
+    use crate::model::{ModelError, Permissions};
+    use crate::Error;
+
+    #[test]
+    fn test_get_option() {
+        let data: ApplicationCommandInteractionData = serde_json::from_value(json!({
+            "id": "1",
+            "name": "mod",
+            "resolved": {
+                "users": {
+                    "5": {"id": "5", "username": "spammer", "discriminator": "0001", "avatar": null}
+                }
+            },
+            "options": [{
+                "name": "ban",
+                "type": 1,
+                "options": [
+                    {"name": "user", "type": 6, "value": "5"},
+                    {"name": "days", "type": 4, "value": 7},
+                    {"name": "reason", "type": 3, "value": "spam"}
+                ]
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(data.get_option::<UserId>("user"), Some(UserId(5)));
+        assert_eq!(data.get_option::<i64>("days"), Some(7));
+        assert_eq!(data.get_option::<String>("reason").as_deref(), Some("spam"));
+        // The value is not of the requested type.
+        assert_eq!(data.get_option::<bool>("reason"), None);
+        assert_eq!(data.get_option::<String>("missing"), None);
+        // Subcommands have no value themselves.
+        assert_eq!(data.get_option::<String>("ban"), None);
+    }
+
+    #[test]
+    fn test_build_application_command() {
+        let map = ApplicationCommand::build_application_command(|c| {
+            c.name("ping")
+                .name_localized("de", "pingen")
+                .description("Pings a channel")
+                .description_localized("de", "Pingt einen Kanal")
+                .default_member_permissions(Permissions::MANAGE_CHANNELS)
+                .dm_permission(false)
+                .contexts(&[InteractionContextType::Guild])
+                .integration_types(&[ApplicationIntegrationType::GuildInstall])
+                .create_option(|o| {
+                    o.kind(ApplicationCommandOptionType::Channel)
+                        .name("the-channel")
+                        .description("The channel")
+                        .channel_types(&[ChannelType::Text, ChannelType::News])
+                })
+                .create_option(|o| {
+                    o.kind(ApplicationCommandOptionType::Integer)
+                        .name("times")
+                        .description("How many times")
+                        .min_int_value(1)
+                        .max_int_value(5)
+                        .add_int_choice_localized("once", 1, vec![("de", "einmal")])
+                })
+        })
+        .unwrap();
+
+        assert_eq!(map["name_localizations"], json!({"de": "pingen"}));
+        assert_eq!(map["description_localizations"], json!({"de": "Pingt einen Kanal"}));
+        assert_eq!(map["default_member_permissions"], json!("16"));
+        assert_eq!(map["dm_permission"], json!(false));
+        assert_eq!(map["contexts"], json!([0]));
+        assert_eq!(map["integration_types"], json!([0]));
+        assert_eq!(map["options"][0]["channel_types"], json!([0, 5]));
+        assert_eq!(map["options"][1]["min_value"], json!(1));
+        assert_eq!(map["options"][1]["max_value"], json!(5));
+        assert_eq!(
+            map["options"][1]["choices"][0],
+            json!({"name": "once", "value": 1, "name_localizations": {"de": "einmal"}})
+        );
+    }
+
+    #[test]
+    fn test_check_names() {
+        let check = |command| match ApplicationCommand::check_names(&command) {
+            Ok(()) => None,
+            Err(Error::Model(ModelError::InvalidCommandName(name))) => Some(name),
+            Err(why) => panic!("unexpected error: {:?}", why),
+        };
+        let command = |name: &str, option: &str| {
+            let value = json!({
+                "name": name,
+                "options": [{"name": "group", "options": [{"name": option}]}],
+            });
+
+            value.as_object().unwrap().clone()
+        };
+
+        assert_eq!(check(command("ping", "the_channel-2")), None);
+        assert_eq!(check(command("größe", "x")), None);
+        assert_eq!(check(command(&"a".repeat(32), "x")), None);
+
+        assert_eq!(check(command("", "x")), Some(String::new()));
+        assert_eq!(check(command(&"a".repeat(33), "x")), Some("a".repeat(33)));
+        assert_eq!(check(command("ping pong", "x")), Some("ping pong".to_string()));
+        assert_eq!(check(command("ping", "the channel")), Some("the channel".to_string()));
+        assert_eq!(check(command("ping", "#channel")), Some("#channel".to_string()));
+    }
+}
@@ -10,7 +10,9 @@ use crate::builder::{
     EditInteractionResponse,
 };
 use crate::http::Http;
+use crate::model::interactions::response_builder::InteractionResponseBuilder;
 use crate::model::interactions::InteractionType;
+use crate::model::utils::snowflake_of;
 use crate::utils;
 
 /// An interaction triggered by a message component.
@@ -48,6 +50,13 @@ pub struct MessageComponentInteraction {
 }
 
 impl MessageComponentInteraction {
+    /// Creates an [`InteractionResponseBuilder`] for responding to this
+    /// interaction over several steps, such as deferring the response and
+    /// following up on it later.
+    pub fn response_builder<'a>(&'a self, http: &'a Http) -> InteractionResponseBuilder<'a> {
+        InteractionResponseBuilder::new(http, self.id, &self.token)
+    }
+
     /// Gets the interaction response.
     ///
     /// # Errors
@@ -228,7 +237,7 @@ impl<'de> Deserialize<'de> for MessageComponentInteraction {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         let mut map = JsonMap::deserialize(deserializer)?;
 
-        let id = map.get("guild_id").and_then(|x| x.as_str()).and_then(|x| x.parse::<u64>().ok());
+        let id = map.get("guild_id").and_then(snowflake_of);
 
         if let Some(guild_id) = id {
             if let Some(member) = map.get_mut("member").and_then(|x| x.as_object_mut()) {
@@ -382,6 +391,36 @@ pub struct MessageComponentInteractionData {
     pub values: Vec<String>,
 }
 
+impl MessageComponentInteractionData {
+    /// The selected values parsed as [`UserId`]s, for select menus whose
+    /// options are users.
+    ///
+    /// Values that are not snowflakes are skipped.
+    pub fn selected_user_ids(&self) -> Vec<UserId> {
+        self.selected_ids()
+    }
+
+    /// The selected values parsed as [`RoleId`]s, for select menus whose
+    /// options are roles.
+    ///
+    /// Values that are not snowflakes are skipped.
+    pub fn selected_role_ids(&self) -> Vec<RoleId> {
+        self.selected_ids()
+    }
+
+    /// The selected values parsed as [`ChannelId`]s, for select menus whose
+    /// options are channels.
+    ///
+    /// Values that are not snowflakes are skipped.
+    pub fn selected_channel_ids(&self) -> Vec<ChannelId> {
+        self.selected_ids()
+    }
+
+    fn selected_ids<T: From<u64>>(&self) -> Vec<T> {
+        self.values.iter().filter_map(|value| value.parse::<u64>().ok()).map(T::from).collect()
+    }
+}
+
 // A component.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -687,3 +726,22 @@ pub struct EphemeralMessage {
     /// The message Id.
     pub id: MessageId,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ComponentType, MessageComponentInteractionData};
+    use crate::model::id::{ChannelId, RoleId, UserId};
+
+    #[test]
+    fn test_selected_ids() {
+        let data = MessageComponentInteractionData {
+            custom_id: "select".to_string(),
+            component_type: ComponentType::SelectMenu,
+            values: vec!["80351110224678912".to_string(), "not an id".to_string(), "7".to_string()],
+        };
+
+        assert_eq!(data.selected_user_ids(), vec![UserId(80351110224678912), UserId(7)]);
+        assert_eq!(data.selected_role_ids(), vec![RoleId(80351110224678912), RoleId(7)]);
+        assert_eq!(data.selected_channel_ids(), vec![ChannelId(80351110224678912), ChannelId(7)]);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod application_command;
 pub mod message_component;
 pub mod ping;
+pub mod response_builder;
 
 use application_command::ApplicationCommandInteraction;
 use bitflags::__impl_bitflags;
@@ -0,0 +1,281 @@
+//! A helper for responding to an interaction over several steps.
+
+use super::prelude::*;
+use crate::builder::{CreateInteractionResponse, CreateInteractionResponseData};
+use crate::http::Http;
+use crate::internal::prelude::{JsonMap, Value};
+use crate::model::interactions::InteractionResponseType;
+use crate::utils;
+
+/// Responds to an interaction, keeping track of whether it has been
+/// acknowledged yet.
+///
+/// Discord requires the first response to an interaction to go through the
+/// interaction callback, and every later one through the interaction's
+/// webhook. This type picks the right endpoint for each action, so a
+/// response can be deferred, followed by slow work, and completed afterwards
+/// without tracking which stage the interaction is in.
+///
+/// # Examples
+///
+/// Defer an ephemeral response, and send the actual content once it is
+/// ready:
+///
+/// ```rust,no_run
+/// # use serenity::http::Http;
+/// # use serenity::model::interactions::application_command::ApplicationCommandInteraction;
+/// # async fn run(http: &Http, command: ApplicationCommandInteraction) -> serenity::Result<()> {
+/// let mut response = command.response_builder(http);
+///
+/// response.defer_ephemeral().await?;
+///
+/// // Do some slow work.
+///
+/// response.respond_with(|d| d.content("Done!")).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct InteractionResponseBuilder<'a> {
+    http: &'a Http,
+    interaction_id: InteractionId,
+    token: &'a str,
+    acknowledged: bool,
+}
+
+impl<'a> InteractionResponseBuilder<'a> {
+    /// Creates a builder for the interaction with the given Id and token,
+    /// which has not been acknowledged yet.
+    pub fn new(http: &'a Http, interaction_id: InteractionId, token: &'a str) -> Self {
+        Self {
+            http,
+            interaction_id,
+            token,
+            acknowledged: false,
+        }
+    }
+
+    /// Whether an initial response has been sent for the interaction.
+    pub fn is_acknowledged(&self) -> bool {
+        self.acknowledged
+    }
+
+    /// Acknowledges a message component interaction, deferring an update of
+    /// the message it was triggered by.
+    ///
+    /// Does nothing if the interaction has already been acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the API returns an error.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    pub async fn defer_update(&mut self) -> Result<()> {
+        self.defer(|r| r.kind(InteractionResponseType::DeferredUpdateMessage)).await
+    }
+
+    /// Acknowledges the interaction, deferring a response that will only be
+    /// visible to the user who invoked it.
+    ///
+    /// Does nothing if the interaction has already been acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the API returns an error.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    pub async fn defer_ephemeral(&mut self) -> Result<()> {
+        self.defer(|r| {
+            r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|d| d.ephemeral(true))
+        })
+        .await
+    }
+
+    async fn defer<F>(&mut self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut CreateInteractionResponse) -> &mut CreateInteractionResponse,
+    {
+        if self.acknowledged {
+            return Ok(());
+        }
+
+        let mut response = CreateInteractionResponse::default();
+        f(&mut response);
+
+        self.create_response(response).await
+    }
+
+    /// Sends a message in response to the interaction.
+    ///
+    /// If the interaction has not been acknowledged yet, this is its initial
+    /// response. Otherwise, the message is sent as a followup, which is
+    /// returned.
+    ///
+    /// **Note**: Message contents must be under 2000 unicode code points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Model`] if the message content is too long.
+    /// May also return an [`Error::Http`] if the API returns an error,
+    /// or an [`Error::Json`] if there is an error in deserializing the
+    /// API response.
+    ///
+    /// [`Error::Model`]: crate::error::Error::Model
+    /// [`Error::Http`]: crate::error::Error::Http
+    /// [`Error::Json`]: crate::error::Error::Json
+    pub async fn respond_with<F>(&mut self, f: F) -> Result<Option<Message>>
+    where
+        F: FnOnce(&mut CreateInteractionResponseData) -> &mut CreateInteractionResponseData,
+    {
+        let mut data = CreateInteractionResponseData::default();
+        f(&mut data);
+
+        if self.acknowledged {
+            let map = checked_map(data)?;
+
+            return self
+                .http
+                .create_followup_message(self.token, &Value::Object(map))
+                .await
+                .map(Some);
+        }
+
+        let mut response = CreateInteractionResponse::default();
+        response.kind(InteractionResponseType::ChannelMessageWithSource);
+        response.0.insert("data", Value::Object(checked_map(data)?));
+
+        self.create_response(response).await.map(|_| None)
+    }
+
+    /// Updates the original message of the interaction.
+    ///
+    /// If the interaction has not been acknowledged yet, this is its initial
+    /// response, updating the message a component was triggered by; this is
+    /// only valid for message component interactions. Otherwise, the
+    /// original response is edited, and returned.
+    ///
+    /// **Note**: Message contents must be under 2000 unicode code points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Model`] if the message content is too long.
+    /// May also return an [`Error::Http`] if the API returns an error,
+    /// or an [`Error::Json`] if there is an error in deserializing the
+    /// API response.
+    ///
+    /// [`Error::Model`]: crate::error::Error::Model
+    /// [`Error::Http`]: crate::error::Error::Http
+    /// [`Error::Json`]: crate::error::Error::Json
+    pub async fn update_original<F>(&mut self, f: F) -> Result<Option<Message>>
+    where
+        F: FnOnce(&mut CreateInteractionResponseData) -> &mut CreateInteractionResponseData,
+    {
+        let mut data = CreateInteractionResponseData::default();
+        f(&mut data);
+
+        if self.acknowledged {
+            let map = checked_map(data)?;
+
+            return self
+                .http
+                .edit_original_interaction_response(self.token, &Value::Object(map))
+                .await
+                .map(Some);
+        }
+
+        let mut response = CreateInteractionResponse::default();
+        response.kind(InteractionResponseType::UpdateMessage);
+        response.0.insert("data", Value::Object(checked_map(data)?));
+
+        self.create_response(response).await.map(|_| None)
+    }
+
+    async fn create_response(&mut self, response: CreateInteractionResponse) -> Result<()> {
+        let map = utils::hashmap_to_json_map(response.0);
+
+        self.http
+            .create_interaction_response(self.interaction_id.0, self.token, &Value::Object(map))
+            .await?;
+        self.acknowledged = true;
+
+        Ok(())
+    }
+}
+
+fn checked_map(data: CreateInteractionResponseData) -> Result<JsonMap> {
+    let map = utils::hashmap_to_json_map(data.0);
+
+    Message::check_content_length(&map)?;
+    Message::check_embed_length(&map)?;
+
+    Ok(map)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::InteractionResponseBuilder;
+    use crate::http::mock::{response, MockServer};
+    use crate::model::id::InteractionId;
+
+    const MESSAGE: &str = r#"{
+        "id": "334385199974967042",
+        "channel_id": "290926798999357250",
+        "author": {
+            "id": "53908099506183680",
+            "username": "Mason",
+            "discriminator": "9999",
+            "avatar": null
+        },
+        "content": "Done!",
+        "timestamp": "2017-07-11T17:27:07.299000+00:00",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0
+    }"#;
+
+    #[tokio::test]
+    async fn test_deferred_then_followup() {
+        let server = MockServer::new(vec![
+            response("204 No Content", &[], ""),
+            response("200 OK", &[], MESSAGE),
+            response("200 OK", &[], MESSAGE),
+        ]);
+        let http = server.builder().application_id(7).await.unwrap();
+        let mut builder = InteractionResponseBuilder::new(&http, InteractionId(1), "token");
+
+        builder.defer_ephemeral().await.unwrap();
+        assert!(builder.is_acknowledged());
+        assert!(server.next_request().starts_with("POST /api/v9/interactions/1/token/callback"));
+
+        // Deferring again must not send another initial response.
+        builder.defer_update().await.unwrap();
+
+        let followup = builder.respond_with(|d| d.content("Done!")).await.unwrap();
+        assert_eq!(followup.unwrap().content, "Done!");
+        assert!(server.next_request().starts_with("POST /api/v9/webhooks/7/token"));
+
+        builder.update_original(|d| d.content("Done!")).await.unwrap();
+        assert!(server
+            .next_request()
+            .starts_with("PATCH /api/v9/webhooks/7/token/messages/@original"));
+    }
+
+    #[tokio::test]
+    async fn test_initial_response() {
+        let server = MockServer::new(vec![response("204 No Content", &[], "")]);
+        let http = server.http().await;
+        let mut builder = InteractionResponseBuilder::new(&http, InteractionId(1), "token");
+
+        assert!(builder.respond_with(|d| d.content("Hi")).await.unwrap().is_none());
+        assert!(builder.is_acknowledged());
+        assert!(server.next_request().starts_with("POST /api/v9/interactions/1/token/callback"));
+    }
+}
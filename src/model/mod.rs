@@ -38,6 +38,7 @@ pub mod misc;
 pub mod oauth2;
 pub mod permissions;
 pub mod prelude;
+pub mod timestamp;
 pub mod user;
 pub mod voice;
 pub mod webhook;
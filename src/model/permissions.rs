@@ -47,6 +47,10 @@ use bitflags::__impl_bitflags;
 use serde::de::{Deserialize, Deserializer, Error as DeError};
 use serde::ser::{Serialize, Serializer};
 
+#[cfg(all(feature = "cache", feature = "model"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "cache", feature = "model"))))]
+pub use super::utils::compute_and_check;
+
 /// This macro generates the [`Permissions::get_permission_names`] method.
 ///
 /// It is invoked by passing the names of all methods used to check for
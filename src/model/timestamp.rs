@@ -0,0 +1,796 @@
+//! Helpers for the timestamps of the models, which are [`DateTime<Utc>`]s.
+//!
+//! # Calendar fields
+//!
+//! Calendar fields, e.g. for daily or weekly reports, are available through
+//! chrono's [`Datelike`] trait, as chrono is the only time backend:
+//!
+//! ```rust
+//! use chrono::{DateTime, Datelike, Utc};
+//!
+//! let timestamp: DateTime<Utc> = "2021-01-03T12:00:00Z".parse().unwrap();
+//!
+//! assert_eq!(timestamp.year(), 2021);
+//! // The day of the year, starting at 1.
+//! assert_eq!(timestamp.ordinal(), 3);
+//! // The first days of a year may belong to the last ISO week of the previous
+//! // one.
+//! assert_eq!(timestamp.iso_week().week(), 53);
+//! assert_eq!(timestamp.iso_week().year(), 2020);
+//! ```
+//!
+//! [`Datelike`]: chrono::Datelike
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter, Result as FmtResult},
+    iter,
+    time::Duration as StdDuration,
+};
+
+use chrono::{
+    DateTime,
+    Duration,
+    FixedOffset,
+    NaiveDateTime,
+    ParseError,
+    TimeZone,
+    Timelike,
+    Utc,
+};
+
+use super::id::snowflake_timestamp;
+
+/// Whether the snowflake `id` was created before the `cutoff`, e.g. to ignore
+/// messages sent before the bot joined a guild.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::snowflake_is_before;
+///
+/// // Created at 2016-04-30T11:18:25.796Z.
+/// let id = 175928847299117063;
+///
+/// assert!(snowflake_is_before(id, Utc.ymd(2017, 1, 1).and_hms(0, 0, 0)));
+/// assert!(!snowflake_is_before(id, Utc.ymd(2016, 1, 1).and_hms(0, 0, 0)));
+/// ```
+#[inline]
+pub fn snowflake_is_before(id: u64, cutoff: DateTime<Utc>) -> bool {
+    snowflake_timestamp(id) < cutoff
+}
+
+/// Parses many RFC 3339 timestamps at once, e.g. a column of a CSV file.
+///
+/// Returns the timestamps that could be parsed, in order, and the errors of
+/// those that could not along with their index in `inputs`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::timestamp::parse_many;
+///
+/// let (timestamps, errors) = parse_many(vec!["2021-08-01T00:00:00Z", "yesterday"]);
+///
+/// assert_eq!(timestamps.len(), 1);
+/// assert_eq!(errors[0].0, 1);
+/// ```
+pub fn parse_many<'a>(
+    inputs: impl IntoIterator<Item = &'a str>,
+) -> (Vec<DateTime<Utc>>, Vec<(usize, ParseError)>) {
+    let mut timestamps = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, input) in inputs.into_iter().enumerate() {
+        match DateTime::parse_from_rfc3339(input) {
+            Ok(timestamp) => timestamps.push(timestamp.with_timezone(&Utc)),
+            Err(why) => errors.push((index, why)),
+        }
+    }
+
+    (timestamps, errors)
+}
+
+/// Parses a timestamp in the basic format of ISO 8601, which has no
+/// separators, e.g. `20160430T111825Z` as emitted by some logging systems.
+///
+/// Returns `None` if the input is not exactly of the form `YYYYMMDDTHHMMSSZ`,
+/// i.e. in UTC without fractional seconds, or is not a valid date and time.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::parse_iso_basic;
+///
+/// let timestamp = parse_iso_basic("20160430T111825Z");
+///
+/// assert_eq!(timestamp, Some(Utc.with_ymd_and_hms(2016, 4, 30, 11, 18, 25).unwrap()));
+/// assert_eq!(parse_iso_basic("2016-04-30T11:18:25Z"), None);
+/// ```
+pub fn parse_iso_basic(input: &str) -> Option<DateTime<Utc>> {
+    // chrono also accepts fewer digits and leading whitespace, so the exact
+    // form is checked beforehand.
+    let is_basic = input.len() == 16
+        && input.bytes().enumerate().all(|(index, byte)| match index {
+            8 => byte == b'T',
+            15 => byte == b'Z',
+            _ => byte.is_ascii_digit(),
+        });
+
+    if !is_basic {
+        return None;
+    }
+
+    let timestamp = NaiveDateTime::parse_from_str(input, "%Y%m%dT%H%M%SZ").ok()?;
+
+    Some(Utc.from_utc_datetime(&timestamp))
+}
+
+/// Parses an RFC 3339 timestamp, assuming it is `offset_seconds` east of UTC
+/// if it lacks an offset, e.g. for third-party feeds known to use a specific
+/// local time.
+///
+/// Timestamps with an offset are parsed as usual, ignoring `offset_seconds`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::timestamp::parse_with_default_offset;
+///
+/// let local = parse_with_default_offset("2016-04-30T13:18:25", 2 * 60 * 60).unwrap();
+/// let utc = parse_with_default_offset("2016-04-30T11:18:25Z", 2 * 60 * 60).unwrap();
+///
+/// assert_eq!(local, utc);
+/// ```
+///
+/// Returns [`None`] if the input is not a timestamp with or without an offset,
+/// or if `offset_seconds` is not less than a day in either direction.
+pub fn parse_with_default_offset(input: &str, offset_seconds: i32) -> Option<DateTime<Utc>> {
+    let offset = FixedOffset::east_opt(offset_seconds)?;
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(input) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+
+    let local = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+
+    offset.from_local_datetime(&local).single().map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Formats a timestamp as a key that sorts lexicographically in the same order
+/// as the timestamps themselves, e.g. for keys of an ordered key-value store.
+///
+/// RFC 3339 strings do not sort reliably once their fractional seconds differ
+/// in precision, so this is the milliseconds since the Unix epoch instead,
+/// zero-padded to 20 digits. Timestamps before the epoch, which Discord never
+/// sends, are not supported and are clamped to the epoch.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::to_sortable_key;
+///
+/// let key = to_sortable_key(&Utc.timestamp(1_420_070_400, 0));
+///
+/// assert_eq!(key, "00000001420070400000");
+/// ```
+pub fn to_sortable_key(timestamp: &DateTime<Utc>) -> String {
+    format!("{:020}", timestamp.timestamp_millis().max(0))
+}
+
+/// Returns the current time, for timestamps of deserialized structs that
+/// default to when they were deserialized, as serde's `default` attribute
+/// takes the path of a function.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{DateTime, Utc};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Reminder {
+///     text: String,
+///     #[serde(default = "serenity::model::timestamp::default_now")]
+///     created_at: DateTime<Utc>,
+/// }
+///
+/// let before = Utc::now();
+/// let reminder: Reminder = serde_json::from_str(r#"{"text": "Stand up"}"#).unwrap();
+///
+/// assert!(reminder.created_at >= before);
+/// ```
+#[inline]
+pub fn default_now() -> DateTime<Utc> {
+    Utc::now()
+}
+
+/// Restricts a timestamp to the window from `min` to `max`, both included,
+/// e.g. to validate a user-supplied time for a scheduled event.
+///
+/// Returns `min` if the timestamp is before it, `max` if it is after it, and
+/// the timestamp itself otherwise.
+///
+/// # Panics
+///
+/// Panics in debug builds if `min` is after `max`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::clamp;
+///
+/// let min = Utc.ymd(2021, 8, 1).and_hms(0, 0, 0);
+/// let max = Utc.ymd(2021, 8, 31).and_hms(0, 0, 0);
+///
+/// assert_eq!(clamp(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0), min, max), max);
+/// ```
+#[inline]
+pub fn clamp(timestamp: DateTime<Utc>, min: DateTime<Utc>, max: DateTime<Utc>) -> DateTime<Utc> {
+    debug_assert!(min <= max, "the minimum timestamp is after the maximum");
+
+    timestamp.max(min).min(max)
+}
+
+/// Returns the earlier of two timestamps, e.g. to schedule at whichever of
+/// two deadlines comes first.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::earliest;
+///
+/// let reminder = Utc.ymd(2021, 8, 2).and_hms(9, 0, 0);
+/// let deadline = Utc.ymd(2021, 8, 1).and_hms(18, 0, 0);
+///
+/// assert_eq!(earliest(reminder, deadline), deadline);
+/// ```
+#[inline]
+pub fn earliest(timestamp: DateTime<Utc>, other: DateTime<Utc>) -> DateTime<Utc> {
+    timestamp.min(other)
+}
+
+/// Returns the later of two timestamps, e.g. to wait until both of two
+/// cooldowns are over.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::latest;
+///
+/// let first = Utc.ymd(2021, 8, 1).and_hms(12, 0, 0);
+/// let second = Utc.ymd(2021, 8, 1).and_hms(12, 5, 0);
+///
+/// assert_eq!(latest(first, second), second);
+/// ```
+#[inline]
+pub fn latest(timestamp: DateTime<Utc>, other: DateTime<Utc>) -> DateTime<Utc> {
+    timestamp.max(other)
+}
+
+/// Returns the start of the UTC day of the timestamp, at `00:00:00`, e.g. as
+/// the key of a daily analytics bucket.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::start_of_day;
+///
+/// let timestamp = Utc.with_ymd_and_hms(2021, 8, 1, 13, 37, 42).unwrap();
+///
+/// assert_eq!(start_of_day(timestamp), Utc.with_ymd_and_hms(2021, 8, 1, 0, 0, 0).unwrap());
+/// ```
+#[inline]
+pub fn start_of_day(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    let start = timestamp.date_naive().and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+
+    Utc.from_utc_datetime(&start)
+}
+
+/// Returns the start of the hour of the timestamp, e.g. as the key of an
+/// hourly analytics bucket.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::start_of_hour;
+///
+/// let timestamp = Utc.with_ymd_and_hms(2021, 8, 1, 13, 37, 42).unwrap();
+///
+/// assert_eq!(start_of_hour(timestamp), Utc.with_ymd_and_hms(2021, 8, 1, 13, 0, 0).unwrap());
+/// ```
+#[inline]
+pub fn start_of_hour(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    let start = timestamp
+        .date_naive()
+        .and_hms_opt(timestamp.hour(), 0, 0)
+        .expect("the hour of a timestamp is a valid time");
+
+    Utc.from_utc_datetime(&start)
+}
+
+/// Returns the next time of day in UTC after the timestamp, e.g. when a daily
+/// reset next happens.
+///
+/// This is later the same day if that time of day is still to come, and the
+/// next day otherwise, including when the timestamp is exactly at that time.
+/// Returns `None` if the time of day is invalid, e.g. an hour of 24.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::next_daily;
+///
+/// let now = Utc.with_ymd_and_hms(2021, 8, 1, 18, 30, 0).unwrap();
+///
+/// assert_eq!(next_daily(now, 20, 0, 0), Utc.with_ymd_and_hms(2021, 8, 1, 20, 0, 0).single());
+/// assert_eq!(next_daily(now, 6, 0, 0), Utc.with_ymd_and_hms(2021, 8, 2, 6, 0, 0).single());
+/// ```
+pub fn next_daily(
+    timestamp: DateTime<Utc>,
+    hour: u8,
+    minute: u8,
+    second: u8,
+) -> Option<DateTime<Utc>> {
+    let today = timestamp.date_naive().and_hms_opt(
+        u32::from(hour),
+        u32::from(minute),
+        u32::from(second),
+    )?;
+    let today = Utc.from_utc_datetime(&today);
+
+    if today > timestamp {
+        Some(today)
+    } else {
+        today.checked_add_signed(Duration::days(1))
+    }
+}
+
+/// Counts the calendar days in UTC from `earlier` to `timestamp`, e.g. for
+/// "days since join" displays.
+///
+/// Unlike dividing the elapsed time by a day, this counts the midnights
+/// crossed, so two timestamps an hour apart on different days are a day
+/// apart. The result is negative if `earlier` is after `timestamp`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::calendar_days_since;
+///
+/// let joined = Utc.with_ymd_and_hms(2021, 8, 1, 23, 30, 0).unwrap();
+/// let now = Utc.with_ymd_and_hms(2021, 8, 3, 0, 30, 0).unwrap();
+///
+/// assert_eq!(calendar_days_since(now, joined), 2);
+/// ```
+pub fn calendar_days_since(timestamp: DateTime<Utc>, earlier: DateTime<Utc>) -> i64 {
+    timestamp.date_naive().signed_duration_since(earlier.date_naive()).num_days()
+}
+
+/// Keeps only the first event of every whole second, e.g. to thin out events
+/// before ingesting them for analytics.
+///
+/// The fractional seconds are ignored, so events at `12:00:00.000` and
+/// `12:00:00.999` fall into the same second. Events need not be sorted; an
+/// event is dropped if any earlier one in `events` had the same second.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::dedupe_by_second;
+///
+/// let events = vec![
+///     (Utc.timestamp(1_420_070_400, 0), "first"),
+///     (Utc.timestamp(1_420_070_400, 500_000_000), "duplicate"),
+///     (Utc.timestamp(1_420_070_401, 0), "second"),
+/// ];
+///
+/// let deduped = dedupe_by_second(events.into_iter());
+///
+/// assert_eq!(deduped.iter().map(|(_, event)| *event).collect::<Vec<_>>(), vec!["first", "second"]);
+/// ```
+pub fn dedupe_by_second<T>(
+    events: impl Iterator<Item = (DateTime<Utc>, T)>,
+) -> Vec<(DateTime<Utc>, T)> {
+    let mut seen = HashSet::new();
+
+    events.filter(|(timestamp, _)| seen.insert(timestamp.timestamp())).collect()
+}
+
+/// A timestamp along with its RFC 3339 representation, which is computed once
+/// on construction.
+///
+/// This is meant for formatting the same timestamp many times, e.g. a shared
+/// batch time in logs, without allocating a new string every time.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::FormattedTimestamp;
+///
+/// let timestamp = FormattedTimestamp::new(Utc.timestamp(1_420_070_400, 0));
+///
+/// assert_eq!(timestamp.as_str(), "2015-01-01T00:00:00+00:00");
+/// assert_eq!(format!("Batch of {}", timestamp), "Batch of 2015-01-01T00:00:00+00:00");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FormattedTimestamp {
+    timestamp: DateTime<Utc>,
+    formatted: String,
+}
+
+impl FormattedTimestamp {
+    /// Formats the `timestamp` and keeps it along with the result.
+    pub fn new(timestamp: DateTime<Utc>) -> Self {
+        Self {
+            formatted: timestamp.to_rfc3339(),
+            timestamp,
+        }
+    }
+
+    /// The timestamp that was formatted.
+    #[inline]
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// The RFC 3339 representation of the timestamp.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.formatted
+    }
+
+    /// Formats the timestamp as a key that sorts in chronological order.
+    ///
+    /// Refer to [`to_sortable_key`] for more information.
+    #[inline]
+    pub fn to_sortable_key(&self) -> String {
+        to_sortable_key(&self.timestamp)
+    }
+}
+
+impl From<DateTime<Utc>> for FormattedTimestamp {
+    fn from(timestamp: DateTime<Utc>) -> Self {
+        Self::new(timestamp)
+    }
+}
+
+impl AsRef<str> for FormattedTimestamp {
+    fn as_ref(&self) -> &str {
+        &self.formatted
+    }
+}
+
+impl Display for FormattedTimestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.formatted)
+    }
+}
+
+/// A range of timestamps, including both the start and the end, e.g. the
+/// period during which a recurring reminder is active.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use chrono::{TimeZone, Utc};
+/// use serenity::model::timestamp::TimestampRange;
+///
+/// let start = Utc.ymd(2021, 8, 1).and_hms(0, 0, 0);
+/// let range = TimestampRange::new(start, Utc.ymd(2021, 8, 2).and_hms(0, 0, 0));
+///
+/// assert!(range.contains(Utc.ymd(2021, 8, 1).and_hms(12, 0, 0)));
+///
+/// let reminders = range.step_by(Duration::from_secs(12 * 60 * 60)).collect::<Vec<_>>();
+///
+/// assert_eq!(reminders.len(), 3);
+/// assert_eq!(reminders[0], start);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TimestampRange {
+    /// The first timestamp of the range.
+    pub start: DateTime<Utc>,
+    /// The last timestamp of the range.
+    pub end: DateTime<Utc>,
+}
+
+impl TimestampRange {
+    /// Creates a range from `start` to `end`, both included.
+    ///
+    /// The range is empty if `end` is before `start`.
+    #[inline]
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            end,
+        }
+    }
+
+    /// Whether the timestamp is within the range, including its start and
+    /// end.
+    #[inline]
+    pub fn contains(&self, timestamp: DateTime<Utc>) -> bool {
+        self.start <= timestamp && timestamp <= self.end
+    }
+
+    /// Iterates over the timestamps from the start of the range up to its end,
+    /// `interval` apart.
+    ///
+    /// The iterator is empty if the range is, or if `interval` is zero or too
+    /// large to be added to a timestamp.
+    pub fn step_by(&self, interval: StdDuration) -> impl Iterator<Item = DateTime<Utc>> {
+        let end = self.end;
+        let step = Duration::from_std(interval).ok().filter(|step| *step > Duration::zero());
+        let start = step.and(Some(self.start)).filter(|start| *start <= end);
+
+        iter::successors(start, move |current| {
+            current.checked_add_signed(step?).filter(|next| *next <= end)
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::Duration as StdDuration;
+
+    use chrono::{DateTime, Utc};
+
+    use super::{
+        calendar_days_since,
+        clamp,
+        dedupe_by_second,
+        earliest,
+        latest,
+        next_daily,
+        parse_iso_basic,
+        parse_many,
+        parse_with_default_offset,
+        snowflake_is_before,
+        start_of_day,
+        start_of_hour,
+        to_sortable_key,
+        FormattedTimestamp,
+        TimestampRange,
+    };
+
+    #[test]
+    fn test_clamp() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let (min, max) = (at("2021-08-01T00:00:00Z"), at("2021-08-31T00:00:00Z"));
+
+        assert_eq!(clamp(at("2021-07-01T00:00:00Z"), min, max), min);
+        assert_eq!(clamp(at("2021-08-15T12:00:00Z"), min, max), at("2021-08-15T12:00:00Z"));
+        assert_eq!(clamp(max, min, max), max);
+        assert_eq!(clamp(at("2021-09-01T00:00:00Z"), min, max), max);
+    }
+
+    #[test]
+    fn test_earliest_and_latest() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let (early, late) = (at("2021-08-01T00:00:00Z"), at("2021-08-01T00:00:00.001Z"));
+
+        assert_eq!(earliest(early, late), early);
+        assert_eq!(earliest(late, early), early);
+        assert_eq!(latest(early, late), late);
+        assert_eq!(latest(late, early), late);
+        assert_eq!(earliest(early, early), early);
+    }
+
+    #[test]
+    fn test_start_of_day_and_hour() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let timestamp = at("2016-04-30T11:18:25.796Z");
+
+        assert_eq!(start_of_day(timestamp), at("2016-04-30T00:00:00Z"));
+        assert_eq!(start_of_hour(timestamp), at("2016-04-30T11:00:00Z"));
+
+        // Already aligned timestamps are kept.
+        let midnight = at("2021-01-01T00:00:00Z");
+        assert_eq!(start_of_day(midnight), midnight);
+        assert_eq!(start_of_hour(midnight), midnight);
+
+        // The last instant of a day stays in that day.
+        let last = at("2020-12-31T23:59:59.999999999Z");
+        assert_eq!(start_of_day(last), at("2020-12-31T00:00:00Z"));
+        assert_eq!(start_of_hour(last), at("2020-12-31T23:00:00Z"));
+    }
+
+    #[test]
+    fn test_next_daily() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let now = at("2021-08-31T12:00:00.5Z");
+
+        // Later today.
+        assert_eq!(next_daily(now, 12, 0, 1), Some(at("2021-08-31T12:00:01Z")));
+        // Tomorrow, across the end of the month.
+        assert_eq!(next_daily(now, 12, 0, 0), Some(at("2021-09-01T12:00:00Z")));
+        assert_eq!(next_daily(now, 0, 0, 0), Some(at("2021-09-01T00:00:00Z")));
+        // Exactly at the time of day.
+        let six = at("2021-08-31T06:00:00Z");
+        assert_eq!(next_daily(six, 6, 0, 0), Some(at("2021-09-01T06:00:00Z")));
+
+        assert_eq!(next_daily(now, 24, 0, 0), None);
+        assert_eq!(next_daily(now, 0, 60, 0), None);
+    }
+
+    #[test]
+    fn test_calendar_days_since() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let days = |timestamp: &str, earlier: &str| calendar_days_since(at(timestamp), at(earlier));
+
+        // 23 hours apart, across midnight.
+        assert_eq!(days("2021-08-02T22:00:00Z", "2021-08-01T23:00:00Z"), 1);
+        // Within the same day.
+        assert_eq!(days("2021-08-01T23:59:59Z", "2021-08-01T00:00:00Z"), 0);
+        assert_eq!(days("2021-03-01T00:00:00Z", "2020-02-29T12:00:00Z"), 366);
+        assert_eq!(days("2021-08-01T00:00:00Z", "2021-08-02T00:00:00Z"), -1);
+    }
+
+    #[test]
+    fn test_sortable_key_order() {
+        // These RFC 3339 strings sort the other way around.
+        let (earlier, later) = ("2021-08-01T00:00:00Z", "2021-08-01T00:00:00.1Z");
+        assert!(earlier > later);
+
+        let earlier: DateTime<Utc> = earlier.parse().unwrap();
+        let later: DateTime<Utc> = later.parse().unwrap();
+
+        assert!(earlier < later);
+        assert!(to_sortable_key(&earlier) < to_sortable_key(&later));
+        assert_eq!(to_sortable_key(&earlier), "00000001627776000000");
+        assert_eq!(FormattedTimestamp::new(later).to_sortable_key(), to_sortable_key(&later));
+
+        let mut keys = vec![to_sortable_key(&later), to_sortable_key(&earlier)];
+        keys.sort();
+        assert_eq!(keys, vec![to_sortable_key(&earlier), to_sortable_key(&later)]);
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let inputs = vec![
+            "2021-08-01T00:00:00+00:00",
+            "not a timestamp",
+            "2021-08-01T02:00:00.123+02:00",
+            "",
+        ];
+
+        let (timestamps, errors) = parse_many(inputs);
+
+        assert_eq!(timestamps.len(), 2);
+        assert_eq!(timestamps[0], timestamps[1] - chrono::Duration::milliseconds(123));
+        assert_eq!(errors.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_parse_iso_basic() {
+        let expected: DateTime<Utc> = "2016-04-30T11:18:25Z".parse().unwrap();
+
+        assert_eq!(parse_iso_basic("20160430T111825Z"), Some(expected));
+
+        let malformed = [
+            "",
+            "2016-04-30T11:18:25Z",
+            "20160430T111825",
+            "20160430 111825Z",
+            "20160430t111825z",
+            "2016043T111825Z",
+            " 2016043T111825Z",
+            "20160430T111825.796Z",
+            "20160430T111825+0000",
+            "+2016043T111825Z",
+            "20160431T111825Z",
+            "20160430T241825Z",
+        ];
+
+        for input in &malformed {
+            assert_eq!(parse_iso_basic(input), None, "{:?} was accepted", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_default_offset() {
+        let expected: DateTime<Utc> = "2016-04-30T11:18:25.796Z".parse().unwrap();
+        let east = 2 * 60 * 60;
+        let west = -5 * 60 * 60;
+
+        // The offset of the input takes precedence.
+        for input in &["2016-04-30T11:18:25.796Z", "2016-04-30T13:18:25.796+02:00"] {
+            assert_eq!(parse_with_default_offset(input, east).unwrap(), expected);
+            assert_eq!(parse_with_default_offset(input, west).unwrap(), expected);
+        }
+
+        // Inputs without an offset are in the default one.
+        assert_eq!(parse_with_default_offset("2016-04-30T13:18:25.796", east).unwrap(), expected);
+        assert_eq!(parse_with_default_offset("2016-04-30T06:18:25.796", west).unwrap(), expected);
+        assert_eq!(
+            parse_with_default_offset("2016-04-30T11:18:25", 0).unwrap(),
+            expected - chrono::Duration::milliseconds(796)
+        );
+
+        assert!(parse_with_default_offset("2016-04-30", east).is_none());
+        assert!(parse_with_default_offset("not a timestamp", east).is_none());
+        assert!(parse_with_default_offset("2016-04-30T13:18:25", 24 * 60 * 60).is_none());
+        assert!(parse_with_default_offset("2016-04-30T11:18:25Z", -24 * 60 * 60).is_none());
+    }
+
+    #[test]
+    fn test_snowflake_is_before() {
+        // Created at 2016-04-30T11:18:25.796Z.
+        let id = 175_928_847_299_117_063;
+        let created_at: DateTime<Utc> = "2016-04-30T11:18:25.796Z".parse().unwrap();
+
+        assert!(snowflake_is_before(id, created_at + chrono::Duration::milliseconds(1)));
+        assert!(!snowflake_is_before(id, created_at));
+        assert!(!snowflake_is_before(id, created_at - chrono::Duration::days(1)));
+    }
+
+    #[test]
+    fn test_dedupe_by_second() {
+        let second: DateTime<Utc> = "2021-08-01T00:00:00Z".parse().unwrap();
+        let events = vec![
+            (second, 1),
+            (second + chrono::Duration::milliseconds(400), 2),
+            (second + chrono::Duration::milliseconds(999), 3),
+        ];
+
+        assert_eq!(dedupe_by_second(events.into_iter()), vec![(second, 1)]);
+
+        let events = vec![
+            (second + chrono::Duration::seconds(1), 1),
+            (second, 2),
+            (second + chrono::Duration::milliseconds(1500), 3),
+        ];
+
+        let deduped = dedupe_by_second(events.into_iter());
+        assert_eq!(deduped.iter().map(|(_, event)| *event).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let start: DateTime<Utc> = "2021-08-01T00:00:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2021-08-02T00:00:00Z".parse().unwrap();
+        let range = TimestampRange::new(start, end);
+        let millisecond = chrono::Duration::milliseconds(1);
+
+        assert!(range.contains(start));
+        assert!(range.contains(end));
+        assert!(!range.contains(start - millisecond));
+        assert!(!range.contains(end + millisecond));
+
+        assert!(!TimestampRange::new(end, start).contains(start));
+    }
+
+    #[test]
+    fn test_range_step_by() {
+        let start: DateTime<Utc> = "2021-08-01T00:00:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2021-08-02T00:00:00Z".parse().unwrap();
+        let range = TimestampRange::new(start, end);
+
+        let steps = range.step_by(StdDuration::from_secs(6 * 60 * 60)).collect::<Vec<_>>();
+        let hours = steps.iter().map(|step| (*step - start).num_hours()).collect::<Vec<_>>();
+        assert_eq!(hours, vec![0, 6, 12, 18, 24]);
+
+        // The last step does not exceed the end.
+        let steps = range.step_by(StdDuration::from_secs(7 * 60 * 60)).collect::<Vec<_>>();
+        assert_eq!(steps.last().map(|step| (*step - start).num_hours()), Some(21));
+
+        assert_eq!(range.step_by(StdDuration::from_secs(0)).count(), 0);
+        assert_eq!(range.step_by(StdDuration::from_secs(u64::MAX)).count(), 0);
+        assert_eq!(TimestampRange::new(end, start).step_by(StdDuration::from_secs(1)).count(), 0);
+    }
+}
@@ -866,7 +866,7 @@ impl User {
     ///                 .push(Bold + msg.author.tag())
     ///                 .build();
     ///
-    ///             let _ = msg.channel_id.say(&context.http, &content).await;
+    ///             let _ = msg.channel_id.say(context.http(), &content).await;
     ///         }
     ///     }
     /// }
@@ -1134,6 +1134,27 @@ fn tag(name: &str, discriminator: u16) -> String {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod test {
+    use crate::model::prelude::*;
+    use crate::model::utils::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        let user: User = assert_roundtrip(
+            r#"{
+                "id": "80351110224678912",
+                "username": "Nelly",
+                "discriminator": "1337",
+                "avatar": "8342729096ea3675442027381ff50dfe",
+                "bot": false,
+                "public_flags": 64
+            }"#,
+        );
+
+        assert_eq!(user.id, UserId(80351110224678912));
+        assert_eq!(user.discriminator, 1337);
+        assert_eq!(user.public_flags, Some(UserPublicFlags::HOUSE_BRAVERY));
+    }
+
     #[cfg(feature = "model")]
     mod model {
         use crate::model::user::User;
@@ -1,7 +1,11 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+};
 
 use serde::de::Error as DeError;
-use serde::de::MapAccess;
+use serde::de::{MapAccess, SeqAccess};
 use serde::ser::{Serialize, SerializeSeq, Serializer};
 
 #[cfg(all(feature = "cache", feature = "model"))]
@@ -31,6 +35,25 @@ pub fn deserialize_emojis<'de, D: Deserializer<'de>>(
     Ok(emojis)
 }
 
+/// Deserializes a list of emojis into two maps by their Ids, one of the static
+/// and one of the animated emojis, e.g. to show them in separate sections of
+/// an emoji picker.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not a list of emojis.
+pub fn deserialize_emojis_split<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<(HashMap<EmojiId, Emoji>, HashMap<EmojiId, Emoji>), D::Error> {
+    let vec: Vec<Emoji> = Deserialize::deserialize(deserializer)?;
+    let (animated, still): (Vec<Emoji>, Vec<Emoji>) =
+        vec.into_iter().partition(|emoji| emoji.animated);
+
+    let into_map = |emojis: Vec<Emoji>| emojis.into_iter().map(|emoji| (emoji.id, emoji)).collect();
+
+    Ok((into_map(still), into_map(animated)))
+}
+
 pub fn serialize_emojis<S: Serializer>(
     emojis: &HashMap<EmojiId, Emoji>,
     serializer: S,
@@ -57,6 +80,55 @@ pub fn deserialize_guild_channels<'de, D: Deserializer<'de>>(
     Ok(map)
 }
 
+/// Deserializes the channels of a [`Guild`], but returns them in the order of
+/// a channel list instead of in a map: channels without a category first, then
+/// each category followed by its channels, with siblings ordered by their
+/// position.
+///
+/// Channels referencing a category that is not among the deserialized ones
+/// are treated as having no category.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not a list of guild
+/// channels.
+pub fn deserialize_guild_channels_sorted<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<Vec<GuildChannel>, D::Error> {
+    let vec: Vec<GuildChannel> = Deserialize::deserialize(deserializer)?;
+    let ids: HashSet<ChannelId> = vec.iter().map(|channel| channel.id).collect();
+    let mut children: HashMap<Option<ChannelId>, Vec<GuildChannel>> = HashMap::new();
+
+    for channel in vec {
+        let parent = channel.category_id.filter(|id| ids.contains(id) && *id != channel.id);
+
+        children.entry(parent).or_default().push(channel);
+    }
+
+    for siblings in children.values_mut() {
+        // Sorted in reverse, so that popping yields the first channel.
+        siblings.sort_by_key(|c| {
+            std::cmp::Reverse((c.kind == ChannelType::Category, c.position, c.id))
+        });
+    }
+
+    let mut sorted = Vec::with_capacity(ids.len());
+    let mut stack = children.remove(&None).unwrap_or_default();
+
+    while let Some(channel) = stack.pop() {
+        if let Some(mut siblings) = children.remove(&Some(channel.id)) {
+            stack.append(&mut siblings);
+        }
+
+        sorted.push(channel);
+    }
+
+    // Only channels in a cycle of parents are left, which can't be placed.
+    sorted.extend(children.into_iter().flat_map(|(_, siblings)| siblings.into_iter().rev()));
+
+    Ok(sorted)
+}
+
 pub fn deserialize_members<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> StdResult<HashMap<UserId, Member>, D::Error> {
@@ -72,6 +144,40 @@ pub fn deserialize_members<'de, D: Deserializer<'de>>(
     Ok(members)
 }
 
+/// Deserializes a list of members into a map by their user Ids, like the
+/// members of a [`Guild`], and also returns an index of the members by their
+/// lowercased display name, i.e. their nickname or else their username.
+///
+/// Names are not unique, so every name maps to the Ids of all members with it,
+/// in the order they were deserialized.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not a list of members.
+pub fn deserialize_members_with_name_index<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<(HashMap<UserId, Member>, HashMap<String, Vec<UserId>>), D::Error> {
+    let vec: Vec<Member> = Deserialize::deserialize(deserializer)?;
+    let mut members = HashMap::with_capacity(vec.len());
+    let mut names: HashMap<String, Vec<UserId>> = HashMap::new();
+
+    for member in vec {
+        let user_id = member.user.id;
+        let name = member.nick.as_ref().unwrap_or(&member.user.name).to_lowercase();
+
+        let ids = names.entry(name).or_default();
+
+        // A repeated member replaces the previous one, so is only indexed once.
+        if !ids.contains(&user_id) {
+            ids.push(user_id);
+        }
+
+        members.insert(user_id, member);
+    }
+
+    Ok((members, names))
+}
+
 #[cfg(all(feature = "unstable_discord_api", feature = "model"))]
 pub fn deserialize_partial_members_map<'de, D: Deserializer<'de>>(
     deserializer: D,
@@ -214,12 +320,100 @@ pub fn deserialize_presences<'de, D: Deserializer<'de>>(
     let mut presences = HashMap::new();
 
     for presence in vec {
-        presences.insert(presence.user_id, presence);
+        merge_presence(&mut presences, presence);
     }
 
     Ok(presences)
 }
 
+/// Deserializes a list of presences into an existing map of presences, reusing
+/// its allocation instead of building a new map, e.g. when the presences of a
+/// large guild are received again after a reconnect.
+///
+/// The presences are inserted as they are read, overwriting the presences of
+/// the same users. Presences of users absent from the list are **not**
+/// removed; it is up to the caller to decide whether they are stale.
+///
+/// Returns the Ids of the users that had no presence in the map before.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not a list of presences.
+pub fn deserialize_presences_into<'de, D: Deserializer<'de>>(
+    existing: &mut HashMap<UserId, Presence>,
+    deserializer: D,
+) -> StdResult<HashSet<UserId>, D::Error> {
+    struct PresencesVisitor<'a>(&'a mut HashMap<UserId, Presence>);
+
+    impl<'de> Visitor<'de> for PresencesVisitor<'_> {
+        type Value = HashSet<UserId>;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+            formatter.write_str("a sequence of presences")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> StdResult<Self::Value, A::Error> {
+            let mut added = HashSet::new();
+
+            while let Some(presence) = seq.next_element::<Presence>()? {
+                let user_id = presence.user_id;
+
+                if self.0.insert(user_id, presence).is_none() {
+                    added.insert(user_id);
+                }
+            }
+
+            Ok(added)
+        }
+    }
+
+    deserializer.deserialize_seq(PresencesVisitor(existing))
+}
+
+/// Inserts a presence into a map of presences, merging it into the presence of
+/// the same user if there is one.
+///
+/// Presence updates may lack the full user and the client status, so those of
+/// the existing presence are kept when the update lacks them. Updates always
+/// carry the full list of activities, so those of the update replace the
+/// existing ones, even if the list is empty.
+///
+/// Returns the previous presence, if there was one.
+pub fn merge_presence(
+    presences: &mut HashMap<UserId, Presence>,
+    mut presence: Presence,
+) -> Option<Presence> {
+    if let Some(old) = presences.get(&presence.user_id) {
+        if presence.user.is_none() {
+            presence.user = old.user.clone();
+        }
+
+        if presence.client_status.is_none() {
+            presence.client_status = old.client_status.clone();
+        }
+    }
+
+    presences.insert(presence.user_id, presence)
+}
+
+/// Merges an update of a voice state into the voice states of a guild.
+///
+/// The user is added if they joined a channel and updated if they moved to
+/// another one, or changed e.g. whether they are muted. The user is removed if
+/// they disconnected, i.e. the update has no channel.
+///
+/// Returns the previous voice state, if there was one.
+pub fn merge_voice_state(
+    voice_states: &mut HashMap<UserId, VoiceState>,
+    voice_state: VoiceState,
+) -> Option<VoiceState> {
+    if voice_state.channel_id.is_some() {
+        voice_states.insert(voice_state.user_id, voice_state)
+    } else {
+        voice_states.remove(&voice_state.user_id)
+    }
+}
+
 pub fn serialize_presences<S: Serializer>(
     presences: &HashMap<UserId, Presence>,
     serializer: S,
@@ -233,6 +427,21 @@ pub fn serialize_presences<S: Serializer>(
     seq.end()
 }
 
+/// Splits the Ids of members into those who are online and those who are
+/// offline, according to their presences.
+///
+/// Members without a presence are counted as offline, as the gateway does not
+/// send presences of offline members.
+pub fn partition_members_by_presence(
+    members: &HashMap<UserId, Member>,
+    presences: &HashMap<UserId, Presence>,
+) -> (Vec<UserId>, Vec<UserId>) {
+    members.keys().copied().partition(|user_id| match presences.get(user_id) {
+        Some(presence) => presence.status != OnlineStatus::Offline,
+        None => false,
+    })
+}
+
 pub fn deserialize_buttons<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> StdResult<Vec<ActivityButton>, D::Error> {
@@ -288,12 +497,75 @@ pub fn deserialize_roles<'de, D: Deserializer<'de>>(
     let mut roles = HashMap::new();
 
     for role in vec {
-        roles.insert(role.id, role);
+        merge_role(&mut roles, role);
     }
 
     Ok(roles)
 }
 
+/// Deserializes a list of roles into a map by their Ids, like the roles of a
+/// [`Guild`], and also returns a copy of the `@everyone` role, whose Id is the
+/// Id of its guild, for code that always needs the base permissions.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not a list of roles.
+pub fn deserialize_roles_with_everyone<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<(HashMap<RoleId, Role>, Option<Role>), D::Error> {
+    let roles = deserialize_roles(deserializer)?;
+    let everyone = roles.values().find(|role| role.id.0 == role.guild_id.0).cloned();
+
+    Ok((roles, everyone))
+}
+
+/// Inserts a role into a map of roles, replacing the role with the same Id.
+///
+/// Returns the replaced role, if there was one.
+pub fn merge_role(roles: &mut HashMap<RoleId, Role>, role: Role) -> Option<Role> {
+    roles.insert(role.id, role)
+}
+
+/// Removes the role with the given Id from a map of roles, returning it.
+pub fn remove_role(roles: &mut HashMap<RoleId, Role>, role_id: RoleId) -> Option<Role> {
+    roles.remove(&role_id)
+}
+
+/// The changes between two snapshots of a guild's roles.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RoleDiff {
+    /// The Ids of the roles only in the new snapshot.
+    pub added: Vec<RoleId>,
+    /// The Ids of the roles only in the old snapshot.
+    pub removed: Vec<RoleId>,
+    /// The Ids of the roles in both snapshots, but with different values.
+    pub modified: Vec<RoleId>,
+}
+
+/// Compares two snapshots of a guild's roles, e.g. to detect changes without
+/// fetching the audit log.
+///
+/// The Ids of each category are sorted.
+pub fn diff_roles(old: &HashMap<RoleId, Role>, new: &HashMap<RoleId, Role>) -> RoleDiff {
+    let mut diff = RoleDiff::default();
+
+    for (id, role) in new {
+        match old.get(id) {
+            Some(old_role) if old_role != role => diff.modified.push(*id),
+            Some(_) => {},
+            None => diff.added.push(*id),
+        }
+    }
+
+    diff.removed.extend(old.keys().filter(|id| !new.contains_key(id)));
+
+    diff.added.sort();
+    diff.removed.sort();
+    diff.modified.sort();
+
+    diff
+}
+
 pub fn serialize_roles<S: Serializer>(
     roles: &HashMap<RoleId, Role>,
     serializer: S,
@@ -339,6 +611,133 @@ pub fn deserialize_u64<'de, D: Deserializer<'de>>(deserializer: D) -> StdResult<
     deserializer.deserialize_any(U64Visitor)
 }
 
+/// Deserializes a snowflake id, which Discord never sets to `0`.
+///
+/// A `0` usually means that a missing field was defaulted upstream, so it is
+/// rejected rather than silently accepted. The models accept any id; use this
+/// with `#[serde(deserialize_with = "...")]` where a `0` should be an error.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not an id, or if the id
+/// is `0`.
+pub fn deserialize_nonzero_u64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<u64, D::Error> {
+    match deserializer.deserialize_any(U64Visitor)? {
+        0 => Err(DeError::custom("id must be non-zero")),
+        id => Ok(id),
+    }
+}
+
+/// Deserializes an optional snowflake id, treating an empty string the same
+/// as `null`, as Discord sends either for some absent ids.
+///
+/// Use it through [`optional_id`] with
+/// `#[serde(default, with = "serenity::model::id::optional_id")]`.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is neither an id, `null`
+/// nor an empty string.
+pub fn deserialize_optional_id<'de, D, T>(deserializer: D) -> StdResult<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<u64>,
+{
+    struct OptionalIdVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: From<u64>> Visitor<'de> for OptionalIdVisitor<T> {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+            formatter.write_str("an optional identifier")
+        }
+
+        fn visit_none<E: DeError>(self) -> StdResult<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: DeError>(self) -> StdResult<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> StdResult<Self::Value, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_str<E: DeError>(self, v: &str) -> StdResult<Self::Value, E> {
+            if v.is_empty() {
+                return Ok(None);
+            }
+
+            U64Visitor.visit_str(v).map(|id| Some(T::from(id)))
+        }
+
+        fn visit_i64<E: DeError>(self, v: i64) -> StdResult<Self::Value, E> {
+            U64Visitor.visit_i64(v).map(|id| Some(T::from(id)))
+        }
+
+        fn visit_u64<E: DeError>(self, v: u64) -> StdResult<Self::Value, E> {
+            Ok(Some(T::from(v)))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> StdResult<Self::Value, A::Error> {
+            U64Visitor.visit_map(map).map(|id| Some(T::from(id)))
+        }
+    }
+
+    deserializer.deserialize_option(OptionalIdVisitor(PhantomData))
+}
+
+/// A module for `#[serde(with = "...")]` on optional id fields, see
+/// [`deserialize_optional_id`].
+pub mod optional_id {
+    use serde::{Deserializer, Serialize, Serializer};
+
+    use crate::internal::prelude::StdResult;
+
+    /// Deserializes an optional id, see [`deserialize_optional_id`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the input is neither an id, `null`
+    /// nor an empty string.
+    ///
+    /// [`deserialize_optional_id`]: super::deserialize_optional_id
+    pub fn deserialize<'de, D, T>(deserializer: D) -> StdResult<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: From<u64>,
+    {
+        super::deserialize_optional_id(deserializer)
+    }
+
+    /// Serializes an optional id, as `null` if it is absent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if the id cannot be serialized.
+    pub fn serialize<S: Serializer, T: Serialize>(
+        id: &Option<T>,
+        serializer: S,
+    ) -> StdResult<S::Ok, S::Error> {
+        id.serialize(serializer)
+    }
+}
+
+/// Reads a snowflake id from a raw payload, where it is a string, or an integer
+/// if the payload was decoded from ETF.
+pub(crate) fn snowflake_of(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::String(id) => id.parse().ok(),
+        value => value.as_u64(),
+    }
+}
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 pub fn serialize_u64<S: Serializer>(data: &u64, ser: S) -> StdResult<S::Ok, S::Error> {
     ser.serialize_str(&data.to_string())
@@ -357,6 +756,53 @@ pub fn deserialize_voice_states<'de, D: Deserializer<'de>>(
     Ok(voice_states)
 }
 
+/// Deserializes a list of voice states into a map by their user Ids, like the
+/// voice states of a [`Guild`], but clears the channel-specific flags of users
+/// that are not connected to a channel.
+///
+/// Discord occasionally sends voice states without a `channel_id` that still
+/// carry the mute and deaf flags from the channel the user disconnected from.
+/// The models keep these flags as received.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the input is not a list of voice
+/// states.
+pub fn deserialize_voice_states_clean<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<HashMap<UserId, VoiceState>, D::Error> {
+    let mut voice_states = deserialize_voice_states(deserializer)?;
+
+    for voice_state in voice_states.values_mut() {
+        if voice_state.channel_id.is_none() {
+            clear_voice_channel_flags(voice_state);
+        }
+    }
+
+    Ok(voice_states)
+}
+
+fn clear_voice_channel_flags(voice_state: &mut VoiceState) {
+    voice_state.deaf = false;
+    voice_state.mute = false;
+    voice_state.self_deaf = false;
+    voice_state.self_mute = false;
+    voice_state.self_stream = None;
+    voice_state.self_video = false;
+    voice_state.suppress = false;
+    voice_state.request_to_speak_timestamp = None;
+}
+
+/// Returns the Ids of the users that were muted by the server, as opposed to
+/// having muted themselves.
+pub fn voice_states_server_muted(voice_states: &HashMap<UserId, VoiceState>) -> Vec<UserId> {
+    voice_states
+        .values()
+        .filter(|voice_state| voice_state.mute)
+        .map(|voice_state| voice_state.user_id)
+        .collect()
+}
+
 pub fn serialize_gen_map<K: Eq + Hash, S: Serializer, V: Serialize>(
     map: &HashMap<K, V>,
     serializer: S,
@@ -396,8 +842,37 @@ pub async fn user_has_perms(
     cache: impl AsRef<Cache>,
     channel_id: ChannelId,
     guild_id: Option<GuildId>,
-    mut permissions: Permissions,
+    permissions: Permissions,
 ) -> Result<bool> {
+    compute_and_check(cache, channel_id, guild_id, permissions).await.map(|(_, has)| has)
+}
+
+/// Computes the current user's permissions in a channel using the cache, and
+/// checks whether they contain the `required` ones.
+///
+/// Returns the computed permissions along with the result of the check, so
+/// callers that need both do not have to look them up twice. All permissions
+/// are assumed to be granted outside of guild channels, as they cannot be
+/// tracked reliably there.
+///
+/// # Errors
+///
+/// Returns a [`ModelError::ChannelNotFound`], [`ModelError::GuildNotFound`] or
+/// [`ModelError::MemberNotFound`] if the channel, its guild or the current
+/// user's member is not in the cache, and a [`ModelError::InvalidChannelType`]
+/// if a `guild_id` is given for a private channel.
+///
+/// [`ModelError::ChannelNotFound`]: crate::model::ModelError::ChannelNotFound
+/// [`ModelError::GuildNotFound`]: crate::model::ModelError::GuildNotFound
+/// [`ModelError::MemberNotFound`]: crate::model::ModelError::MemberNotFound
+/// [`ModelError::InvalidChannelType`]: crate::model::ModelError::InvalidChannelType
+#[cfg(all(feature = "cache", feature = "model"))]
+pub async fn compute_and_check(
+    cache: impl AsRef<Cache>,
+    channel_id: ChannelId,
+    guild_id: Option<GuildId>,
+    required: Permissions,
+) -> Result<(Permissions, bool)> {
     let cache = cache.as_ref();
 
     let channel = match cache.channel(channel_id).await {
@@ -416,10 +891,10 @@ pub async fn user_has_perms(
     // just assume that all permissions are granted and return `true`.
     let (guild_id, guild_channel) = match channel {
         Channel::Guild(channel) => (channel.guild_id, channel),
-        Channel::Category(_) => return Ok(true),
+        Channel::Category(_) => return Ok((Permissions::all(), true)),
         Channel::Private(_) => match guild_id {
             Some(_) => return Err(Error::Model(ModelError::InvalidChannelType)),
-            None => return Ok(true),
+            None => return Ok((Permissions::all(), true)),
         },
     };
 
@@ -435,9 +910,7 @@ pub async fn user_has_perms(
 
     let perms = guild.user_permissions_in(&guild_channel, member)?;
 
-    permissions.remove(perms);
-
-    Ok(permissions.is_empty())
+    Ok((perms, perms.contains(required)))
 }
 
 macro_rules! num_visitors {
@@ -502,3 +975,413 @@ macro_rules! num_visitors {
 }
 
 num_visitors!(U16Visitor: u16, U32Visitor: u32, U64Visitor: u64);
+
+/// Deserializes a model from a JSON fixture, and checks that serializing it
+/// and deserializing the result again yields the same JSON, i.e. that no field
+/// is lost or misread on the way.
+///
+/// Maps serialized as lists, like the members of a guild, must have at most
+/// one entry, as their order is not stable.
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub(crate) fn assert_roundtrip<T>(fixture: &str) -> T
+where
+    T: serde::de::DeserializeOwned + Serialize,
+{
+    let model: T = serde_json::from_str(fixture).unwrap();
+    let json = serde_json::to_value(&model).unwrap();
+    let roundtripped: T = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(serde_json::to_value(&roundtripped).unwrap(), json);
+
+    model
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_optional_id() {
+        #[derive(Deserialize)]
+        struct Model {
+            #[serde(default, with = "optional_id")]
+            channel_id: Option<ChannelId>,
+        }
+
+        let parse = |json: &str| serde_json::from_str::<Model>(json).unwrap().channel_id;
+
+        assert_eq!(parse(r#"{"channel_id": null}"#), None);
+        assert_eq!(parse(r#"{"channel_id": ""}"#), None);
+        assert_eq!(parse("{}"), None);
+        assert_eq!(parse(r#"{"channel_id": "81384788765"}"#), Some(ChannelId(81384788765)));
+        assert_eq!(parse(r#"{"channel_id": 7}"#), Some(ChannelId(7)));
+        assert!(serde_json::from_str::<Model>(r#"{"channel_id": "abc"}"#).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_nonzero_u64() {
+        assert_eq!(deserialize_nonzero_u64(json!("123")).unwrap(), 123);
+        assert_eq!(deserialize_nonzero_u64(json!(7)).unwrap(), 7);
+
+        for zero in &[json!("0"), json!(0)] {
+            let err = deserialize_nonzero_u64(zero.clone()).unwrap_err();
+            assert_eq!(err.to_string(), "id must be non-zero");
+        }
+    }
+
+    #[test]
+    fn test_merge_presence() {
+        let mut presences = HashMap::new();
+        let playing = serde_json::from_value::<Presence>(json!({
+            "user": {"id": "1", "username": "user", "discriminator": "0001", "avatar": null},
+            "status": "online",
+            "activities": [{"name": "a game", "type": 0}],
+            "client_status": {"desktop": "online"},
+        }))
+        .unwrap();
+        assert!(merge_presence(&mut presences, playing).is_none());
+
+        let idle = serde_json::from_value::<Presence>(json!({
+            "user": {"id": "1"},
+            "status": "idle",
+            "activities": [{"name": "a game", "type": 0}],
+        }))
+        .unwrap();
+        let old = merge_presence(&mut presences, idle).unwrap();
+        assert_eq!(old.status, OnlineStatus::Online);
+
+        let presence = &presences[&UserId(1)];
+        assert_eq!(presence.status, OnlineStatus::Idle);
+        assert_eq!(presence.activities.len(), 1);
+        assert!(presence.client_status.is_some());
+        assert_eq!(presence.user.as_ref().unwrap().name, "user");
+
+        // The status changed and the game was closed in the same update.
+        let dnd_stopped_playing = serde_json::from_value::<Presence>(json!({
+            "user": {"id": "1"},
+            "status": "dnd",
+            "activities": [],
+        }))
+        .unwrap();
+        merge_presence(&mut presences, dnd_stopped_playing);
+
+        let presence = &presences[&UserId(1)];
+        assert_eq!(presence.status, OnlineStatus::DoNotDisturb);
+        assert!(presence.activities.is_empty());
+        assert_eq!(presence.user.as_ref().unwrap().name, "user");
+    }
+
+    #[test]
+    fn test_merge_and_remove_role() {
+        let role = |name: &str| -> Role {
+            serde_json::from_value(json!({
+                "id": "1",
+                "guild_id": "2",
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "name": name,
+                "permissions": "0",
+                "position": 1,
+            }))
+            .unwrap()
+        };
+
+        let mut roles = HashMap::new();
+        assert!(merge_role(&mut roles, role("old")).is_none());
+        assert_eq!(merge_role(&mut roles, role("new")).unwrap().name, "old");
+        assert_eq!(roles[&RoleId(1)].name, "new");
+
+        assert_eq!(remove_role(&mut roles, RoleId(1)).unwrap().name, "new");
+        assert!(remove_role(&mut roles, RoleId(1)).is_none());
+    }
+
+    #[test]
+    fn test_diff_roles() {
+        let role = |id: u64, name: &str, hoist: bool| -> (RoleId, Role) {
+            let role: Role = serde_json::from_value(json!({
+                "id": id.to_string(),
+                "guild_id": "9",
+                "color": 0,
+                "hoist": hoist,
+                "managed": false,
+                "name": name,
+                "permissions": "0",
+                "position": id,
+            }))
+            .unwrap();
+
+            (role.id, role)
+        };
+
+        let old: HashMap<_, _> = vec![
+            role(1, "kept", false),
+            role(2, "renamed", false),
+            role(3, "hoisted", false),
+            role(4, "removed", false),
+        ]
+        .into_iter()
+        .collect();
+        let new: HashMap<_, _> = vec![
+            role(1, "kept", false),
+            role(2, "new name", false),
+            role(3, "hoisted", true),
+            role(6, "added", false),
+            role(5, "added", false),
+        ]
+        .into_iter()
+        .collect();
+
+        let diff = diff_roles(&old, &new);
+        assert_eq!(diff.added, vec![RoleId(5), RoleId(6)]);
+        assert_eq!(diff.removed, vec![RoleId(4)]);
+        assert_eq!(diff.modified, vec![RoleId(2), RoleId(3)]);
+
+        assert_eq!(diff_roles(&new, &new), RoleDiff::default());
+    }
+
+    #[test]
+    fn test_deserialize_roles_with_everyone() {
+        let role = |id: u64, name: &str| {
+            json!({
+                "id": id.to_string(),
+                "guild_id": "2",
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "name": name,
+                "permissions": "0",
+                "position": 0,
+            })
+        };
+
+        let (roles, everyone) =
+            deserialize_roles_with_everyone(json!([role(1, "mods"), role(2, "@everyone")]))
+                .unwrap();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(everyone.unwrap().name, "@everyone");
+
+        let (roles, everyone) = deserialize_roles_with_everyone(json!([role(1, "mods")])).unwrap();
+        assert_eq!(roles.len(), 1);
+        assert!(everyone.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_emojis_split() {
+        let emoji = |id: u64, animated: bool| {
+            json!({
+                "id": id.to_string(),
+                "name": "emoji",
+                "animated": animated,
+                "managed": false,
+                "require_colons": true,
+                "roles": [],
+            })
+        };
+
+        let value = json!([emoji(1, false), emoji(2, true)]);
+        let (still, animated) = deserialize_emojis_split(value).unwrap();
+
+        assert_eq!(still.keys().collect::<Vec<_>>(), vec![&EmojiId(1)]);
+        assert_eq!(animated.keys().collect::<Vec<_>>(), vec![&EmojiId(2)]);
+    }
+
+    #[test]
+    fn test_deserialize_presences_into() {
+        let presence = |id: u64, status: &str| {
+            json!({
+                "user": {"id": id.to_string()},
+                "status": status,
+            })
+        };
+
+        let mut presences =
+            deserialize_presences(json!([presence(1, "online"), presence(2, "online")])).unwrap();
+
+        let value = json!([presence(2, "idle"), presence(3, "online")]);
+        let added = deserialize_presences_into(&mut presences, value).unwrap();
+
+        assert_eq!(added.into_iter().collect::<Vec<_>>(), vec![UserId(3)]);
+        assert_eq!(presences.len(), 3);
+        assert_eq!(presences[&UserId(2)].status, OnlineStatus::Idle);
+    }
+
+    #[test]
+    fn test_deserialize_members_with_name_index() {
+        let member = |id: u64, username: &str, nick: Option<&str>| {
+            json!({
+                "user": {
+                    "id": id.to_string(),
+                    "username": username,
+                    "discriminator": "0001",
+                    "avatar": null,
+                },
+                "guild_id": "1",
+                "nick": nick,
+                "roles": [],
+                "joined_at": null,
+                "deaf": false,
+                "mute": false,
+            })
+        };
+
+        let value = json!([
+            member(1, "alice", Some("Cake")),
+            member(2, "bob", Some("cake")),
+            member(3, "Carol", None),
+        ]);
+        let (members, names) = deserialize_members_with_name_index(value).unwrap();
+
+        assert_eq!(members.len(), 3);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["cake"], vec![UserId(1), UserId(2)]);
+        assert_eq!(names["carol"], vec![UserId(3)]);
+    }
+
+    #[test]
+    fn test_partition_members_by_presence() {
+        let member = |id: u64| {
+            serde_json::from_value::<Member>(json!({
+                "user": {
+                    "id": id.to_string(),
+                    "username": "user",
+                    "discriminator": "0001",
+                    "avatar": null,
+                },
+                "guild_id": "1",
+                "roles": [],
+                "joined_at": null,
+                "deaf": false,
+                "mute": false,
+            }))
+            .unwrap()
+        };
+        let presence = serde_json::from_value::<Presence>(json!({
+            "user": {"id": "2"},
+            "status": "online",
+        }))
+        .unwrap();
+
+        let mut members = HashMap::new();
+        members.insert(UserId(2), member(2));
+        members.insert(UserId(3), member(3));
+        let mut presences = HashMap::new();
+        presences.insert(presence.user_id, presence);
+
+        let (online, offline) = partition_members_by_presence(&members, &presences);
+        assert_eq!(online, vec![UserId(2)]);
+        assert_eq!(offline, vec![UserId(3)]);
+    }
+
+    #[test]
+    fn test_deserialize_guild_channels_sorted() {
+        let channel = |id: u64, kind: u8, position: i64, parent_id: Option<u64>| {
+            json!({
+                "id": id.to_string(),
+                "guild_id": "1",
+                "type": kind,
+                "name": format!("channel {}", id),
+                "position": position,
+                "parent_id": parent_id.map(|id| id.to_string()),
+            })
+        };
+
+        let value = json!([
+            channel(12, 0, 1, Some(10)),
+            channel(11, 2, 0, Some(10)),
+            channel(10, 4, 0, None),
+            channel(20, 0, 5, None),
+        ]);
+        let channels = deserialize_guild_channels_sorted(value).unwrap();
+        let ids = channels.iter().map(|channel| channel.id.0).collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![20, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_deserialize_voice_states_clean() {
+        let voice_state = |user_id: u64, channel_id: Option<u64>| {
+            json!({
+                "channel_id": channel_id.map(|id| id.to_string()),
+                "deaf": true,
+                "mute": true,
+                "self_deaf": true,
+                "self_mute": true,
+                "self_video": false,
+                "session_id": "session",
+                "suppress": false,
+                "user_id": user_id.to_string(),
+            })
+        };
+
+        let value = json!([voice_state(1, Some(10)), voice_state(2, None)]);
+        let voice_states = deserialize_voice_states_clean(value).unwrap();
+
+        let connected = &voice_states[&UserId(1)];
+        assert!(connected.mute && connected.deaf && connected.self_mute && connected.self_deaf);
+
+        let disconnected = &voice_states[&UserId(2)];
+        assert!(!disconnected.mute && !disconnected.deaf);
+        assert!(!disconnected.self_mute && !disconnected.self_deaf);
+    }
+
+    #[test]
+    fn test_merge_voice_state() {
+        let voice_state = |channel_id: Option<u64>| -> VoiceState {
+            serde_json::from_value(json!({
+                "channel_id": channel_id.map(|id| id.to_string()),
+                "deaf": false,
+                "mute": false,
+                "self_deaf": false,
+                "self_mute": false,
+                "self_video": false,
+                "session_id": "session",
+                "suppress": false,
+                "user_id": "1",
+            }))
+            .unwrap()
+        };
+        let mut voice_states = HashMap::new();
+
+        // Joining a channel.
+        assert!(merge_voice_state(&mut voice_states, voice_state(Some(10))).is_none());
+        assert_eq!(voice_states[&UserId(1)].channel_id, Some(ChannelId(10)));
+
+        // Moving to another channel.
+        let old = merge_voice_state(&mut voice_states, voice_state(Some(20))).unwrap();
+        assert_eq!(old.channel_id, Some(ChannelId(10)));
+        assert_eq!(voice_states[&UserId(1)].channel_id, Some(ChannelId(20)));
+
+        // Disconnecting.
+        let old = merge_voice_state(&mut voice_states, voice_state(None)).unwrap();
+        assert_eq!(old.channel_id, Some(ChannelId(20)));
+        assert!(voice_states.is_empty());
+        assert!(merge_voice_state(&mut voice_states, voice_state(None)).is_none());
+    }
+
+    #[test]
+    fn test_voice_states_server_muted() {
+        let voice_state = |user_id: u64, mute: bool, self_mute: bool| {
+            json!({
+                "channel_id": "10",
+                "deaf": false,
+                "mute": mute,
+                "self_deaf": false,
+                "self_mute": self_mute,
+                "self_video": false,
+                "session_id": "session",
+                "suppress": false,
+                "user_id": user_id.to_string(),
+            })
+        };
+
+        let value = json!([voice_state(1, false, true), voice_state(2, true, false)]);
+        let voice_states = deserialize_voice_states(value).unwrap();
+
+        assert_eq!(voice_states_server_muted(&voice_states), vec![UserId(2)]);
+    }
+}
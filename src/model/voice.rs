@@ -10,6 +10,7 @@ use super::{
     id::{ChannelId, GuildId, RoleId, UserId},
     user::User,
 };
+pub use super::utils::{deserialize_voice_states_clean, voice_states_server_muted};
 #[cfg(feature = "unstable_discord_api")]
 use crate::model::permissions::Permissions;
 
@@ -38,15 +38,21 @@ fn channel_belongs_to_guild(channel: &Channel, guild: GuildId) -> bool {
     }
 }
 
+#[allow(deprecated)]
 async fn lookup_channel_global(ctx: &Context, s: &str) -> Result<Channel, ChannelParseError> {
     if let Some(channel_id) = s.parse::<u64>().ok().or_else(|| crate::utils::parse_channel(s)) {
         return ChannelId(channel_id).to_channel(ctx).await.map_err(ChannelParseError::Http);
     }
 
-    let channels = ctx.cache.channels.read().await;
-    if let Some(channel) =
-        channels.values().find(|channel| channel.name.eq_ignore_ascii_case(s)).cloned()
-    {
+    let lookup_by_name = |_: &ChannelId, channel: &GuildChannel| {
+        if channel.name.eq_ignore_ascii_case(s) {
+            Some(channel.clone())
+        } else {
+            None
+        }
+    };
+
+    if let Some(channel) = ctx.cache.channels.find_map(lookup_by_name).await {
         return Ok(Channel::Guild(channel));
     }
 
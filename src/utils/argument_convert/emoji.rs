@@ -33,29 +33,32 @@ impl std::fmt::Display for EmojiParseError {
 impl ArgumentConvert for Emoji {
     type Err = EmojiParseError;
 
+    #[allow(deprecated)]
     async fn convert(
         ctx: &Context,
         _guild_id: Option<GuildId>,
         _channel_id: Option<ChannelId>,
         s: &str,
     ) -> Result<Self, Self::Err> {
-        let guilds = ctx.cache.guilds.read().await;
+        let guilds = &ctx.cache.guilds;
 
         let direct_id = s.parse::<u64>().ok().map(EmojiId);
         let id_from_mention = crate::utils::parse_emoji(s).map(|e| e.id);
 
         if let Some(emoji_id) = direct_id.or(id_from_mention) {
-            if let Some(emoji) = guilds.values().find_map(|guild| guild.emojis.get(&emoji_id)) {
-                return Ok(emoji.clone());
+            let lookup_by_id = |_: &GuildId, guild: &Guild| guild.emojis.get(&emoji_id).cloned();
+
+            if let Some(emoji) = guilds.find_map(lookup_by_id).await {
+                return Ok(emoji);
             }
         }
 
-        if let Some(emoji) = guilds
-            .values()
-            .flat_map(|guild| guild.emojis.values())
-            .find(|emoji| emoji.name.eq_ignore_ascii_case(s))
-        {
-            return Ok(emoji.clone());
+        let lookup_by_name = |_: &GuildId, guild: &Guild| {
+            guild.emojis.values().find(|emoji| emoji.name.eq_ignore_ascii_case(s)).cloned()
+        };
+
+        if let Some(emoji) = guilds.find_map(lookup_by_name).await {
+            return Ok(emoji);
         }
 
         Err(EmojiParseError::NotFoundOrMalformed)
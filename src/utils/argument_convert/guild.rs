@@ -28,18 +28,27 @@ impl std::fmt::Display for GuildParseError {
 impl ArgumentConvert for Guild {
     type Err = GuildParseError;
 
+    #[allow(deprecated)]
     async fn convert(
         ctx: &Context,
         _guild_id: Option<GuildId>,
         _channel_id: Option<ChannelId>,
         s: &str,
     ) -> Result<Self, Self::Err> {
-        let guilds = ctx.cache.guilds.read().await;
-
-        let lookup_by_id = || guilds.get(&GuildId(s.parse().ok()?));
+        if let Some(guild_id) = s.parse().ok().map(GuildId) {
+            if let Some(guild) = ctx.cache.guild(guild_id).await {
+                return Ok(guild);
+            }
+        }
 
-        let lookup_by_name = || guilds.values().find(|guild| guild.name.eq_ignore_ascii_case(s));
+        let lookup_by_name = |_: &GuildId, guild: &Guild| {
+            if guild.name.eq_ignore_ascii_case(s) {
+                Some(guild.clone())
+            } else {
+                None
+            }
+        };
 
-        lookup_by_id().or_else(lookup_by_name).cloned().ok_or(GuildParseError::NotFoundOrMalformed)
+        ctx.cache.guilds.find_map(lookup_by_name).await.ok_or(GuildParseError::NotFoundOrMalformed)
     }
 }
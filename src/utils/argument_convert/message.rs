@@ -48,6 +48,7 @@ impl std::fmt::Display for MessageParseError {
 impl ArgumentConvert for Message {
     type Err = MessageParseError;
 
+    #[allow(deprecated)]
     async fn convert(
         ctx: &Context,
         _guild_id: Option<GuildId>,
@@ -72,7 +73,7 @@ impl ArgumentConvert for Message {
         }
 
         if cfg!(feature = "http") {
-            ctx.http.get_message(channel_id.0, message_id.0).await.map_err(MessageParseError::Http)
+            ctx.http().get_message(channel_id.0, message_id.0).await.map_err(MessageParseError::Http)
         } else {
             Err(MessageParseError::HttpNotAvailable)
         }
@@ -39,6 +39,7 @@ impl std::fmt::Display for RoleParseError {
 impl ArgumentConvert for Role {
     type Err = RoleParseError;
 
+    #[allow(deprecated)]
     async fn convert(
         ctx: &Context,
         guild_id: Option<GuildId>,
@@ -34,6 +34,7 @@ impl std::fmt::Display for UserParseError {
 impl ArgumentConvert for User {
     type Err = UserParseError;
 
+    #[allow(deprecated)]
     async fn convert(
         ctx: &Context,
         _guild_id: Option<GuildId>,
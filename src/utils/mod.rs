@@ -762,6 +762,18 @@ mod test {
     #[cfg(feature = "cache")]
     use crate::cache::Cache;
 
+    #[test]
+    fn test_shard_id() {
+        let guild_id = 81384788765712384_u64;
+
+        // A single shard receives all guilds.
+        assert_eq!(shard_id(guild_id, 1), 0);
+        assert_eq!(shard_id(guild_id, 3), 1);
+        assert_eq!(shard_id(guild_id, 17), 7);
+        assert_eq!(shard_id(guild_id, 100), 98);
+        assert_eq!(shard_id(41771983423143937_u64, 17), 4);
+    }
+
     #[test]
     fn test_invite_parser() {
         assert_eq!(parse_invite("https://discord.gg/abc"), "abc");
@@ -932,8 +944,8 @@ mod test {
         guild.members.insert(user.id, member.clone());
         guild.roles.insert(role.id, role.clone());
         cache.users.write().await.insert(user.id, user.clone());
-        cache.guilds.write().await.insert(guild.id, guild.clone());
-        cache.channels.write().await.insert(channel.id, channel.clone());
+        cache.guilds.insert(guild.id, guild.clone()).await;
+        cache.channels.insert(channel.id, channel.clone()).await;
 
         let with_user_mentions = "<@!100000000000000000> <@!000000000000000000> <@123> <@!123> \
         <@!123123123123123123123> <@123> <@123123123123123123> <@!invalid> \